@@ -9,7 +9,20 @@ use syn::{
   ReturnType, Token, Type,
 };
 
-pub fn generate_command(attrs: Vec<NestedMeta>, function: ItemFn) -> TokenStream {
+/// Whether the path is `tauri::<name>` or `::tauri::<name>`, with any generic arguments.
+///
+/// The macro can't resolve the imported names, so a `Window` or `State` type imported with
+/// `use` must be marked with the `#[window]` or `#[state]` argument attribute instead.
+fn is_tauri_type(path: &Path, name: &str) -> bool {
+  let segments: Vec<String> = path
+    .segments
+    .iter()
+    .map(|seg| seg.ident.to_string())
+    .collect();
+  segments == ["tauri", name]
+}
+
+pub fn generate_command(attrs: Vec<NestedMeta>, mut function: ItemFn) -> TokenStream {
   // Check if "with_window" attr was passed to macro
   let with_window = attrs.iter().any(|a| {
     if let NestedMeta::Meta(Meta::Path(path)) = a {
@@ -28,73 +41,93 @@ pub fn generate_command(attrs: Vec<NestedMeta>, function: ItemFn) -> TokenStream
   let returns_result = match function.sig.output {
     ReturnType::Type(_, ref ty) => match &**ty {
       Type::Path(type_path) => {
+        // match `Result`, `std::result::Result` and aliases like `io::Result`
         type_path
           .path
           .segments
-          .first()
-          .map(|seg| seg.ident.to_string())
-          == Some("Result".to_string())
+          .last()
+          .map(|seg| seg.ident == "Result")
+          .unwrap_or(false)
       }
       _ => false,
     },
     ReturnType::Default => false,
   };
 
-  // Split function args into names and types
-  let (mut names, mut types): (Vec<Ident>, Vec<Path>) = function
-    .sig
-    .inputs
-    .iter()
-    .map(|param| {
-      let mut arg_name = None;
-      let mut arg_type = None;
-      if let FnArg::Typed(arg) = param {
-        if let Pat::Ident(ident) = arg.pat.as_ref() {
-          arg_name = Some(ident.ident.clone());
-        }
-        if let Type::Path(path) = arg.ty.as_ref() {
-          arg_type = Some(path.path.clone());
+  // Split function args into the ones injected by Tauri and the ones deserialized from the payload
+  let mut names: Vec<Ident> = Vec::new();
+  let mut types: Vec<Path> = Vec::new();
+  let mut call_args = Vec::new();
+  let mut states = Vec::new();
+  for (index, param) in function.sig.inputs.iter_mut().enumerate() {
+    let mut arg_name = None;
+    let mut arg_type = None;
+    let mut window_attr = false;
+    let mut state_attr = false;
+    if let FnArg::Typed(arg) = param {
+      // the `#[window]` and `#[state]` attributes aren't real attributes,
+      // so they're removed from the function
+      arg.attrs.retain(|attr| {
+        if attr.path.is_ident("window") {
+          window_attr = true;
+          false
+        } else if attr.path.is_ident("state") {
+          state_attr = true;
+          false
+        } else {
+          true
         }
+      });
+      if let Pat::Ident(ident) = arg.pat.as_ref() {
+        arg_name = Some(ident.ident.clone());
+      }
+      if let Type::Path(path) = arg.ty.as_ref() {
+        arg_type = Some(path.path.clone());
       }
-      (
-        arg_name.clone().unwrap(),
-        arg_type.unwrap_or_else(|| panic!("Invalid type for arg \"{}\"", arg_name.unwrap())),
-      )
-    })
-    .unzip();
+    }
+    let arg_name = arg_name.unwrap();
+    let arg_type = arg_type.unwrap_or_else(|| panic!("Invalid type for arg \"{}\"", arg_name));
 
-  let window_arg_maybe = match types.first() {
-    Some(_) if with_window => {
-      // Remove window arg from list so it isn't expected as arg from JS
-      types.drain(0..1);
-      names.drain(0..1);
-      // Tell wrapper to pass `window` to original function
-      quote!(_window,)
+    // the window that invoked the command is injected on `tauri::Window` arguments
+    let is_window = window_attr || is_tauri_type(&arg_type, "Window");
+    // and the managed value on `tauri::State` arguments
+    let is_state = state_attr || is_tauri_type(&arg_type, "State");
+    if (with_window && index == 0) || is_window {
+      call_args.push(quote!(_window.clone()));
+    } else if is_state {
+      let state = format_ident!("__tauri_state_{}", index);
+      call_args.push(quote!(#state));
+      states.push((state, arg_type));
+    } else {
+      call_args.push(quote!(parsed_args.#arg_name));
+      names.push(arg_name);
+      types.push(arg_type);
     }
-    // Tell wrapper not to pass `window` to original function
-    _ => quote!(),
-  };
+  }
+
   let await_maybe = if function.sig.asyncness.is_some() {
     quote!(.await)
   } else {
     quote!()
   };
 
-  // if the command handler returns a Result,
-  // we just map the values to the ones expected by Tauri
-  // otherwise we wrap it with an `Ok()`, converting the return value to tauri::InvokeResponse
+  // if the command handler returns a Result, its variants resolve and reject the JS promise,
+  // otherwise we wrap it with an `Ok()` so the return value resolves the promise.
   // note that all types must implement `serde::Serialize`.
   let return_value = if returns_result {
     quote! {
-      match #fn_name(#window_arg_maybe #(parsed_args.#names),*)#await_maybe {
-        Ok(value) => ::core::result::Result::Ok(value),
-        Err(e) => ::core::result::Result::Err(e),
+      match #fn_name(#(#call_args),*)#await_maybe {
+        ::core::result::Result::Ok(value) => ::core::result::Result::Ok(value),
+        ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
       }
     }
   } else {
-    quote! { ::core::result::Result::<_, ()>::Ok(#fn_name(#window_arg_maybe #(parsed_args.#names),*)#await_maybe) }
+    quote! { ::core::result::Result::<_, ()>::Ok(#fn_name(#(#call_args),*)#await_maybe) }
   };
 
+  // an unmanaged state rejects the invoke instead of panicking in the command task
+  let (state_names, state_types): (Vec<_>, Vec<_>) = states.into_iter().unzip();
+
   quote! {
     #function
     pub fn #fn_wrapper<P: ::tauri::Params>(message: ::tauri::InvokeMessage<P>) {
@@ -104,6 +137,17 @@ pub fn generate_command(attrs: Vec<NestedMeta>, function: ItemFn) -> TokenStream
         #(#names: #types),*
       }
       let _window = message.window();
+      #(
+        let #state_names: #state_types = match ::tauri::Manager::try_state(&_window) {
+          Some(state) => state,
+          None => {
+            return message.reject(format!(
+              "state for type `{}` is not managed",
+              ::std::any::type_name::<#state_types>()
+            ));
+          }
+        };
+      )*
       match ::serde_json::from_value::<ParsedArgs>(message.payload()) {
         Ok(parsed_args) => message.respond_async(async move {
           #return_value
@@ -137,7 +181,10 @@ pub fn generate_handler(item: proc_macro::TokenStream) -> TokenStream {
     move |message| {
       match message.command() {
         #(stringify!(#fn_names) => #fn_wrappers(message),)*
-        _ => {},
+        command => {
+          let error = format!("command `{}` not found", command);
+          message.reject(error)
+        }
       }
     }
   }
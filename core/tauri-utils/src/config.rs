@@ -76,6 +76,60 @@ pub struct WindowConfig {
   /// Whether the window should always be on top of other windows.
   #[serde(default)]
   pub always_on_top: bool,
+  /// The proxy the webview sends its network requests through, e.g. `http://proxy.local:3128`.
+  pub proxy_url: Option<String>,
+  /// Whether the webview renders with the GPU. Disabling it forces software rendering,
+  /// working around graphics driver bugs, e.g. on Linux virtual machines.
+  #[serde(default = "default_hardware_acceleration")]
+  pub hardware_acceleration: bool,
+  /// Whether the window can receive focus. Combined with `visible: false`, the window is a worker:
+  /// a background page used only to run JavaScript.
+  #[serde(default = "default_focusable")]
+  pub focusable: bool,
+  /// Whether the window is a splashscreen, shown while the other windows load.
+  ///
+  /// The other windows are created hidden, then shown when their page has loaded,
+  /// closing the splashscreen.
+  #[serde(default)]
+  pub splashscreen: bool,
+  /// Whether the splashscreen closes once the other windows load. Disable it to close the
+  /// splashscreen with the `close_splashscreen` API instead, e.g. once the app setup finished.
+  #[serde(default = "default_splashscreen_auto_close")]
+  pub splashscreen_auto_close: bool,
+  /// The API modules and the commands the window can use, on top of the `event` module,
+  /// e.g. `["fs", "dialog", "command:greet", "plugin:sql"]`.
+  ///
+  /// Restricts the modules enabled on the app allowlist and the app and plugin commands;
+  /// all of them are available by default. The app commands are listed as `command:<name>`,
+  /// and the plugins as `plugin:<name>`, or `plugin:<name>|<command>` for a single command.
+  pub allowlist: Option<Vec<String>>,
+  /// JavaScript sources evaluated on each page load, after the Tauri API is injected.
+  #[serde(default)]
+  pub initialization_scripts: Vec<String>,
+  /// What happens when the user closes the window, unless a `CloseRequested` handler prevents it.
+  #[serde(default)]
+  pub close_behavior: CloseBehavior,
+  /// Whether the webview dev tools are opened when the window is created. Ignored on release builds.
+  #[serde(default)]
+  pub devtools: bool,
+}
+
+/// What happens when the user closes a window.
+#[derive(PartialEq, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseBehavior {
+  /// The window is destroyed.
+  Destroy,
+  /// The window is hidden, e.g. for an app living in the system tray.
+  Hide,
+  /// The app exits, like `AppHandle::exit`: the other windows are closed first.
+  Quit,
+}
+
+impl Default for CloseBehavior {
+  fn default() -> Self {
+    Self::Destroy
+  }
 }
 
 fn default_window_label() -> String {
@@ -102,6 +156,18 @@ fn default_decorations() -> bool {
   true
 }
 
+fn default_hardware_acceleration() -> bool {
+  true
+}
+
+fn default_focusable() -> bool {
+  true
+}
+
+fn default_splashscreen_auto_close() -> bool {
+  true
+}
+
 fn default_title() -> String {
   "Tauri App".to_string()
 }
@@ -127,6 +193,15 @@ impl Default for WindowConfig {
       visible: default_visible(),
       decorations: default_decorations(),
       always_on_top: false,
+      proxy_url: None,
+      hardware_acceleration: default_hardware_acceleration(),
+      focusable: default_focusable(),
+      splashscreen: false,
+      splashscreen_auto_close: default_splashscreen_auto_close(),
+      allowlist: None,
+      initialization_scripts: Vec::new(),
+      close_behavior: CloseBehavior::default(),
+      devtools: false,
     }
   }
 }
@@ -164,6 +239,18 @@ impl Default for UpdaterConfig {
   }
 }
 
+/// The license configuration, used by the `license` API module.
+#[derive(PartialEq, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseConfig {
+  /// The public key verifying the license keys, as generated by `tauri signer generate`.
+  #[serde(default)]
+  pub pubkey: Option<String>,
+  /// The trial duration, in days, starting on the first license check.
+  #[serde(default)]
+  pub trial_days: Option<u32>,
+}
+
 /// A CLI argument definition
 #[derive(PartialEq, Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -316,6 +403,148 @@ impl Default for BundleConfig {
   }
 }
 
+/// The file system allowlist options read at runtime.
+#[derive(PartialEq, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FsAllowlistConfig {
+  /// The glob patterns of the paths the file system APIs can access, e.g. `$APP/**`.
+  #[serde(default)]
+  pub scope: Vec<String>,
+}
+
+/// An argument of a command on the shell scope.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ShellAllowedArg {
+  /// An argument that can't be changed.
+  Fixed(String),
+  /// An argument set by the frontend.
+  Var {
+    /// The regex the whole argument must match.
+    validator: String,
+  },
+}
+
+/// The arguments the frontend can pass to a command on the shell scope.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ShellAllowedArgs {
+  /// Whether any argument is allowed.
+  Flag(bool),
+  /// The exact list of arguments.
+  List(Vec<ShellAllowedArg>),
+}
+
+impl Default for ShellAllowedArgs {
+  fn default() -> Self {
+    Self::Flag(false)
+  }
+}
+
+/// A command the shell `execute` API can run.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellAllowedCommand {
+  /// The name the frontend uses to run the command.
+  pub name: String,
+  /// The program to run, or the sidecar name when `sidecar` is `true`.
+  pub cmd: String,
+  /// The arguments the frontend can pass. No arguments are allowed by default.
+  #[serde(default)]
+  pub args: ShellAllowedArgs,
+  /// Whether the command is a sidecar.
+  #[serde(default)]
+  pub sidecar: bool,
+}
+
+/// The shell allowlist options read at runtime.
+#[derive(PartialEq, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellAllowlistConfig {
+  /// The commands the `execute` API can run.
+  #[serde(default)]
+  pub scope: Vec<ShellAllowedCommand>,
+  /// The regex the paths and URLs opened with the `open` API must match.
+  /// Only `mailto:`, `tel:`, `http://` and `https://` URLs are allowed by default.
+  #[serde(default)]
+  pub open_scope: Option<String>,
+  /// The programs the `open` API can open the paths and URLs with, e.g. `firefox`.
+  /// The paths and URLs can only be opened with the default app when it's empty.
+  #[serde(default)]
+  pub open_with: Vec<String>,
+}
+
+/// The custom protocols allowlist options read at runtime.
+#[derive(PartialEq, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolAllowlistConfig {
+  /// The glob patterns of the files served by the `asset` protocol, e.g. `$HOME/Pictures/**`.
+  #[serde(default)]
+  pub asset_scope: Vec<String>,
+}
+
+/// The allowlist options read at runtime. The APIs themselves are enabled with Cargo features.
+#[derive(PartialEq, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowlistConfig {
+  /// The file system API options.
+  #[serde(default)]
+  pub fs: FsAllowlistConfig,
+  /// The shell API options.
+  #[serde(default)]
+  pub shell: ShellAllowlistConfig,
+  /// The custom protocols options.
+  #[serde(default)]
+  pub protocol: ProtocolAllowlistConfig,
+}
+
+/// A command asking the user for consent with a native dialog before running.
+#[derive(PartialEq, Eq, Hash, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionPrompt {
+  /// The file system APIs reading a path outside of the `tauri > allowlist > fs > scope`.
+  FsReadOutsideScope,
+  /// The file system APIs writing a path outside of the `tauri > allowlist > fs > scope`.
+  FsWriteOutsideScope,
+  /// The shell `execute` API.
+  ShellExecute,
+}
+
+/// The security configuration read at runtime.
+#[derive(PartialEq, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityConfig {
+  /// The commands asking the user for consent.
+  /// The answer is remembered for the window until the app exits.
+  #[serde(default)]
+  pub permission_prompts: Vec<PermissionPrompt>,
+  /// The scheme of the protocol serving the app assets, e.g. `app` for `app://myapp`.
+  #[serde(default = "default_scheme")]
+  pub scheme: String,
+  /// The host of the app URL, e.g. `myapp` for `app://myapp`. Defaults to the bundle identifier.
+  #[serde(default)]
+  pub host: Option<String>,
+  /// Whether the app pages can register service workers and use the Cache API, for offline support.
+  /// The webview may still disable them, e.g. WebKitGTK only allows them on `https`.
+  #[serde(default)]
+  pub service_workers: bool,
+}
+
+fn default_scheme() -> String {
+  "tauri".to_string()
+}
+
+impl Default for SecurityConfig {
+  fn default() -> Self {
+    Self {
+      permission_prompts: Vec::new(),
+      scheme: default_scheme(),
+      host: None,
+      service_workers: false,
+    }
+  }
+}
+
 fn default_window_config() -> Vec<WindowConfig> {
   vec![Default::default()]
 }
@@ -336,6 +565,15 @@ pub struct TauriConfig {
   /// The updater configuration.
   #[serde(default)]
   pub updater: UpdaterConfig,
+  /// The license configuration.
+  #[serde(default)]
+  pub license: LicenseConfig,
+  /// The allowlist configuration.
+  #[serde(default)]
+  pub allowlist: AllowlistConfig,
+  /// The security configuration.
+  #[serde(default)]
+  pub security: SecurityConfig,
 }
 
 impl Default for TauriConfig {
@@ -345,6 +583,9 @@ impl Default for TauriConfig {
       cli: None,
       bundle: BundleConfig::default(),
       updater: UpdaterConfig::default(),
+      license: LicenseConfig::default(),
+      allowlist: AllowlistConfig::default(),
+      security: SecurityConfig::default(),
     }
   }
 }
@@ -362,12 +603,20 @@ pub struct BuildConfig {
   /// Whether we should inject the Tauri API on `window.__TAURI__` or not.
   #[serde(default)]
   pub with_global_tauri: bool,
+  /// Whether the `console` messages of the windows are printed to the terminal
+  /// during `tauri dev`, prefixed with the window label.
+  #[serde(default = "default_dev_console")]
+  pub dev_console: bool,
 }
 
 fn default_dev_path() -> String {
   "http://localhost:8080".to_string()
 }
 
+fn default_dev_console() -> bool {
+  true
+}
+
 fn default_dist_path() -> String {
   "../dist".to_string()
 }
@@ -378,6 +627,7 @@ impl Default for BuildConfig {
       dev_path: default_dev_path(),
       dist_dir: default_dist_path(),
       with_global_tauri: false,
+      dev_console: default_dev_console(),
     }
   }
 }
@@ -586,6 +836,15 @@ mod build {
       let visible = self.visible;
       let decorations = self.decorations;
       let always_on_top = self.always_on_top;
+      let proxy_url = opt_str_lit(self.proxy_url.as_ref());
+      let hardware_acceleration = self.hardware_acceleration;
+      let focusable = self.focusable;
+      let splashscreen = self.splashscreen;
+      let splashscreen_auto_close = self.splashscreen_auto_close;
+      let allowlist = opt_vec_str_lit(self.allowlist.as_ref());
+      let initialization_scripts = vec_lit(&self.initialization_scripts, str_lit);
+      let close_behavior = &self.close_behavior;
+      let devtools = self.devtools;
 
       literal_struct!(
         tokens,
@@ -607,7 +866,16 @@ mod build {
         maximized,
         visible,
         decorations,
-        always_on_top
+        always_on_top,
+        proxy_url,
+        hardware_acceleration,
+        focusable,
+        splashscreen,
+        splashscreen_auto_close,
+        allowlist,
+        initialization_scripts,
+        close_behavior,
+        devtools
       );
     }
   }
@@ -722,8 +990,16 @@ mod build {
       let dev_path = str_lit(&self.dev_path);
       let dist_dir = str_lit(&self.dist_dir);
       let with_global_tauri = self.with_global_tauri;
+      let dev_console = self.dev_console;
 
-      literal_struct!(tokens, BuildConfig, dev_path, dist_dir, with_global_tauri);
+      literal_struct!(
+        tokens,
+        BuildConfig,
+        dev_path,
+        dist_dir,
+        with_global_tauri,
+        dev_console
+      );
     }
   }
 
@@ -738,14 +1014,156 @@ mod build {
     }
   }
 
+  impl ToTokens for LicenseConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let pubkey = opt_str_lit(self.pubkey.as_ref());
+      let trial_days = opt_lit(self.trial_days.as_ref());
+
+      literal_struct!(tokens, LicenseConfig, pubkey, trial_days);
+    }
+  }
+
+  impl ToTokens for ProtocolAllowlistConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let asset_scope = vec_lit(&self.asset_scope, str_lit);
+
+      literal_struct!(tokens, ProtocolAllowlistConfig, asset_scope);
+    }
+  }
+
+  impl ToTokens for FsAllowlistConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let scope = vec_lit(&self.scope, str_lit);
+
+      literal_struct!(tokens, FsAllowlistConfig, scope);
+    }
+  }
+
+  impl ToTokens for ShellAllowedArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::api::config::ShellAllowedArg };
+
+      tokens.append_all(match self {
+        Self::Fixed(arg) => {
+          let arg = str_lit(arg);
+          quote! { #prefix::Fixed(#arg) }
+        }
+        Self::Var { validator } => {
+          let validator = str_lit(validator);
+          quote! { #prefix::Var { validator: #validator } }
+        }
+      })
+    }
+  }
+
+  impl ToTokens for ShellAllowedArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::api::config::ShellAllowedArgs };
+
+      tokens.append_all(match self {
+        Self::Flag(flag) => quote! { #prefix::Flag(#flag) },
+        Self::List(args) => {
+          let args = vec_lit(args, identity);
+          quote! { #prefix::List(#args) }
+        }
+      })
+    }
+  }
+
+  impl ToTokens for ShellAllowedCommand {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let name = str_lit(&self.name);
+      let cmd = str_lit(&self.cmd);
+      let args = &self.args;
+      let sidecar = self.sidecar;
+
+      literal_struct!(tokens, ShellAllowedCommand, name, cmd, args, sidecar);
+    }
+  }
+
+  impl ToTokens for ShellAllowlistConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let scope = vec_lit(&self.scope, identity);
+      let open_scope = opt_str_lit(self.open_scope.as_ref());
+      let open_with = vec_lit(&self.open_with, str_lit);
+
+      literal_struct!(tokens, ShellAllowlistConfig, scope, open_scope, open_with);
+    }
+  }
+
+  impl ToTokens for AllowlistConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let fs = &self.fs;
+      let shell = &self.shell;
+      let protocol = &self.protocol;
+
+      literal_struct!(tokens, AllowlistConfig, fs, shell, protocol);
+    }
+  }
+
+  impl ToTokens for CloseBehavior {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::api::config::CloseBehavior };
+
+      tokens.append_all(match self {
+        Self::Destroy => quote! { #prefix::Destroy },
+        Self::Hide => quote! { #prefix::Hide },
+        Self::Quit => quote! { #prefix::Quit },
+      })
+    }
+  }
+
+  impl ToTokens for PermissionPrompt {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::api::config::PermissionPrompt };
+
+      tokens.append_all(match self {
+        Self::FsReadOutsideScope => quote! { #prefix::FsReadOutsideScope },
+        Self::FsWriteOutsideScope => quote! { #prefix::FsWriteOutsideScope },
+        Self::ShellExecute => quote! { #prefix::ShellExecute },
+      })
+    }
+  }
+
+  impl ToTokens for SecurityConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let permission_prompts = vec_lit(&self.permission_prompts, identity);
+      let scheme = str_lit(&self.scheme);
+      let host = opt_str_lit(self.host.as_ref());
+      let service_workers = self.service_workers;
+
+      literal_struct!(
+        tokens,
+        SecurityConfig,
+        permission_prompts,
+        scheme,
+        host,
+        service_workers
+      );
+    }
+  }
+
   impl ToTokens for TauriConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let windows = vec_lit(&self.windows, identity);
       let cli = opt_lit(self.cli.as_ref());
       let bundle = &self.bundle;
       let updater = &self.updater;
+      let license = &self.license;
+      let allowlist = &self.allowlist;
+      let security = &self.security;
 
-      literal_struct!(tokens, TauriConfig, windows, cli, bundle, updater);
+      literal_struct!(
+        tokens,
+        TauriConfig,
+        windows,
+        cli,
+        bundle,
+        updater,
+        license,
+        allowlist,
+        security
+      );
     }
   }
 
@@ -817,6 +1235,15 @@ mod test {
         visible: true,
         decorations: true,
         always_on_top: false,
+        proxy_url: None,
+        hardware_acceleration: true,
+        focusable: true,
+        splashscreen: false,
+        splashscreen_auto_close: true,
+        allowlist: None,
+        initialization_scripts: Vec::new(),
+        close_behavior: CloseBehavior::Destroy,
+        devtools: false,
       }],
       bundle: BundleConfig {
         identifier: String::from(""),
@@ -828,6 +1255,9 @@ mod test {
         pubkey: None,
         endpoints: None,
       },
+      license: LicenseConfig::default(),
+      allowlist: AllowlistConfig::default(),
+      security: SecurityConfig::default(),
     };
 
     // create a build config
@@ -835,6 +1265,7 @@ mod test {
       dev_path: String::from("http://localhost:8080"),
       dist_dir: String::from("../dist"),
       with_global_tauri: false,
+      dev_console: true,
     };
 
     // test the configs
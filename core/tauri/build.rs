@@ -24,6 +24,8 @@ fn main() {
     fs_remove_file: { any(fs_all, feature = "fs-remove-file") },
     fs_rename_file: { any(fs_all, feature = "fs-rename-file") },
     fs_path: { any(fs_all, feature = "fs-path") },
+    fs_metadata: { any(fs_all, feature = "fs-metadata") },
+    fs_symlink: { any(fs_all, feature = "fs-symlink") },
 
     // window
     window_all: { any(api_all, feature = "window-all") },
@@ -38,10 +40,14 @@ fn main() {
     dialog_all: { any(api_all, feature = "dialog-all") },
     dialog_open: { any(dialog_all, feature = "dialog-open") },
     dialog_save: { any(dialog_all, feature = "dialog-save") },
+    dialog_message: { any(dialog_all, feature = "dialog-message") },
+    dialog_ask: { any(dialog_all, feature = "dialog-ask") },
+    dialog_confirm: { any(dialog_all, feature = "dialog-confirm") },
 
     // http
     http_all: { any(api_all, feature = "http-all") },
     http_request: { any(http_all, feature = "http-request") },
+    http_download: { any(http_all, feature = "http-download") },
 
     // cli
     cli: { feature = "cli" },
@@ -49,7 +55,34 @@ fn main() {
     // notification
     notification_all: { any(api_all, feature = "notification-all") },
 
+    // path
+    path_all: { any(api_all, feature = "path-all") },
+
     // global shortcut
     global_shortcut_all: { any(api_all, feature = "global_shortcut-all") },
+
+    // clipboard, not enabled by `api-all` since it adds the `arboard` dependency
+    clipboard_all: { feature = "clipboard-all" },
+    clipboard_write_text: { any(clipboard_all, feature = "clipboard-write-text") },
+    clipboard_read_text: { any(clipboard_all, feature = "clipboard-read-text") },
+
+    // font
+    font_all: { any(api_all, feature = "font-all") },
+
+    // geolocation
+    geolocation_all: { any(api_all, feature = "geolocation-all") },
+
+    // printer
+    printer_all: { any(api_all, feature = "printer-all") },
+
+    // share
+    share_all: { any(api_all, feature = "share-all") },
+
+    // system, not enabled by `api-all` since it adds the `sysinfo` dependency
+    system_all: { feature = "system-all" },
+
+    // protocol
+    protocol_all: { any(api_all, feature = "protocol-all") },
+    protocol_asset: { any(protocol_all, feature = "protocol-asset") },
   }
 }
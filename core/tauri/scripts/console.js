@@ -0,0 +1,32 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  function format(value) {
+    if (typeof value === 'string') {
+      return value
+    }
+    if (value instanceof Error) {
+      return value.stack || String(value)
+    }
+    try {
+      var json = JSON.stringify(value)
+      return json === undefined ? String(value) : json
+    } catch (e) {
+      return String(value)
+    }
+  }
+  ['log', 'info', 'debug', 'warn', 'error'].forEach(function (level) {
+    var original = console[level]
+    console[level] = function () {
+      try {
+        var message = Array.prototype.map.call(arguments, format).join(' ')
+        window.__TAURI__.invoke('__console', { level: level, message: message })
+      } catch (e) {
+        // mirroring must not break the page logging
+      }
+      return original.apply(console, arguments)
+    }
+  })
+})()
@@ -11,21 +11,43 @@ if (!String.prototype.startsWith) {
 }
 
 (function () {
+  // captured before the page scripts run, so overriding these globals doesn't affect the bridge
+  var _Promise = window.Promise;
+  var _crypto = window.crypto;
+  var _getRandomValues = _crypto.getRandomValues;
+  var _Int8Array = window.Int8Array;
+  var _Uint8Array = window.Uint8Array;
+  var _objectKeys = Object.keys;
+  var _getOwnPropertySymbols = Object.getOwnPropertySymbols;
+  var _getOwnPropertyDescriptor = Object.getOwnPropertyDescriptor;
+  var _getOwnPropertyDescriptors = Object.getOwnPropertyDescriptors;
+  var _objectDefineProperty = Object.defineProperty;
+  var _objectDefineProperties = Object.defineProperties;
+  var _rpc = window.rpc;
+
+  // the IPC object may be defined after this script, so it's captured on first use
+  function rpc() {
+    if (!_rpc) {
+      _rpc = window.rpc;
+    }
+    return _rpc;
+  }
+
   function uid() {
-    const length = new Int8Array(1)
-    window.crypto.getRandomValues(length)
-    const array = new Uint8Array(Math.max(16, Math.abs(length[0])))
-    window.crypto.getRandomValues(array)
+    const length = new _Int8Array(1)
+    _getRandomValues.call(_crypto, length)
+    const array = new _Uint8Array(Math.max(16, Math.abs(length[0])))
+    _getRandomValues.call(_crypto, array)
     return array.join('')
   }
 
   function ownKeys(object, enumerableOnly) {
-    var keys = Object.keys(object);
-    if (Object.getOwnPropertySymbols) {
-      var symbols = Object.getOwnPropertySymbols(object);
+    var keys = _objectKeys(object);
+    if (_getOwnPropertySymbols) {
+      var symbols = _getOwnPropertySymbols(object);
       if (enumerableOnly)
         symbols = symbols.filter(function (sym) {
-          return Object.getOwnPropertyDescriptor(object, sym).enumerable;
+          return _getOwnPropertyDescriptor(object, sym).enumerable;
         });
       keys.push.apply(keys, symbols);
     }
@@ -39,17 +61,17 @@ if (!String.prototype.startsWith) {
         ownKeys(source, true).forEach(function (key) {
           _defineProperty(target, key, source[key]);
         });
-      } else if (Object.getOwnPropertyDescriptors) {
-        Object.defineProperties(
+      } else if (_getOwnPropertyDescriptors) {
+        _objectDefineProperties(
           target,
-          Object.getOwnPropertyDescriptors(source)
+          _getOwnPropertyDescriptors(source)
         );
       } else {
         ownKeys(source).forEach(function (key) {
-          Object.defineProperty(
+          _objectDefineProperty(
             target,
             key,
-            Object.getOwnPropertyDescriptor(source, key)
+            _getOwnPropertyDescriptor(source, key)
           );
         });
       }
@@ -59,7 +81,7 @@ if (!String.prototype.startsWith) {
 
   function _defineProperty(obj, key, value) {
     if (key in obj) {
-      Object.defineProperty(obj, key, {
+      _objectDefineProperty(obj, key, {
         value: value,
         enumerable: true,
         configurable: true,
@@ -71,6 +93,40 @@ if (!String.prototype.startsWith) {
     return obj;
   }
 
+  // finds the `{ __tauriBuffer: id }` values of an invoke response, sent by `tauri::Binary`
+  function collectBuffers(value, parent, key, found) {
+    if (value === null || typeof value !== "object") {
+      return;
+    }
+    if (typeof value.__tauriBuffer === "string") {
+      found.push({ parent: parent, key: key, id: value.__tauriBuffer });
+      return;
+    }
+    _objectKeys(value).forEach(function (k) {
+      collectBuffers(value[k], value, k, found);
+    });
+  }
+
+  // replaces the buffer ids of an invoke response with the bytes read from the `tauri-buffer` protocol
+  function resolveBuffers(response) {
+    var root = { value: response };
+    var found = [];
+    collectBuffers(response, root, "value", found);
+    return _Promise.all(
+      found.map(function (buffer) {
+        return fetch("tauri-buffer://" + buffer.id)
+          .then(function (r) {
+            return r.arrayBuffer();
+          })
+          .then(function (bytes) {
+            buffer.parent[buffer.key] = new _Uint8Array(bytes);
+          });
+      })
+    ).then(function () {
+      return root.value;
+    });
+  }
+
   if (!window.__TAURI__) {
     window.__TAURI__ = {};
   }
@@ -95,9 +151,9 @@ if (!String.prototype.startsWith) {
   window.__TAURI__.invoke = function invoke(cmd, args = {}) {
     var _this = this;
 
-    return new Promise(function (resolve, reject) {
+    return new _Promise(function (resolve, reject) {
       var callback = _this.transformCallback(function (r) {
-        resolve(r);
+        resolveBuffers(r).then(resolve, reject);
         delete window[error];
       }, true);
       var error = _this.transformCallback(function (e) {
@@ -113,8 +169,8 @@ if (!String.prototype.startsWith) {
         return reject(new Error("Invalid argument type."));
       }
 
-      if (window.rpc) {
-        window.rpc.notify(
+      if (rpc()) {
+        rpc().notify(
           cmd,
           _objectSpread(
             {
@@ -126,7 +182,7 @@ if (!String.prototype.startsWith) {
         );
       } else {
         window.addEventListener("DOMContentLoaded", function () {
-          window.rpc.notify(
+          rpc().notify(
             cmd,
             _objectSpread(
               {
@@ -297,10 +353,9 @@ if (!String.prototype.startsWith) {
   window.alert = function (message) {
     window.__TAURI__.invoke('tauri', {
       __tauriModule: "Dialog",
-      mainThread: true,
       message: {
         cmd: "messageDialog",
-        message: message,
+        message: String(message),
       },
     });
   };
@@ -308,10 +363,9 @@ if (!String.prototype.startsWith) {
   window.confirm = function (message) {
     return window.__TAURI__.invoke('tauri', {
       __tauriModule: "Dialog",
-      mainThread: true,
       message: {
-        cmd: "askDialog",
-        message: message,
+        cmd: "confirmDialog",
+        message: String(message),
       },
     });
   };
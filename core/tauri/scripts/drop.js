@@ -0,0 +1,31 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  function isDataDrop(event) {
+    var target = event.target
+    var editable = target && (target.isContentEditable || /^(INPUT|TEXTAREA)$/.test(target.tagName))
+    return !event.defaultPrevented && !editable && event.dataTransfer &&
+      Array.prototype.indexOf.call(event.dataTransfer.types, 'Files') === -1
+  }
+  window.addEventListener('dragover', function (event) {
+    if (isDataDrop(event)) {
+      event.preventDefault()
+    }
+  })
+  window.addEventListener('drop', function (event) {
+    if (!isDataDrop(event)) {
+      return
+    }
+    event.preventDefault()
+    var items = {}
+    Array.prototype.forEach.call(event.dataTransfer.types, function (type) {
+      items[type] = event.dataTransfer.getData(type)
+    })
+    window.__TAURI__.invoke('__drop', {
+      items: items,
+      position: { x: event.clientX, y: event.clientY }
+    })
+  })
+})()
@@ -0,0 +1,19 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  function reply(payload) {
+    payload.id = __ID__
+    window.__TAURI__.invoke('__evalResult', payload)
+  }
+  try {
+    Promise.resolve((0, eval)(__SCRIPT__)).then(function (result) {
+      reply({ result: result === undefined ? null : result })
+    }, function (error) {
+      reply({ error: String(error) })
+    })
+  } catch (error) {
+    reply({ error: String(error) })
+  }
+})()
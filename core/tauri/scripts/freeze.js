@@ -0,0 +1,117 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  if (!window.__TAURI__) {
+    return
+  }
+  var api = window.__TAURI__
+  var invoke = api.invoke
+  var getOwnPropertyNames = Object.getOwnPropertyNames
+  var getOwnPropertyDescriptor = Object.getOwnPropertyDescriptor
+  var defineProperty = Object.defineProperty
+  var reported = Object.create(null)
+
+  function report(reason) {
+    if (!reported[reason]) {
+      reported[reason] = true
+      invoke.call(api, '__tampering', { reason: reason })
+    }
+  }
+
+  function snapshot(name, prototype) {
+    var keys = getOwnPropertyNames(prototype)
+    var values = Object.create(null)
+    for (var i = 0; i < keys.length; i++) {
+      values[keys[i]] = getOwnPropertyDescriptor(prototype, keys[i]).value
+    }
+    return { name: name, prototype: prototype, count: keys.length, values: values }
+  }
+  var prototypes = [
+    snapshot('Object', Object.prototype),
+    snapshot('Array', Array.prototype),
+    snapshot('Function', Function.prototype)
+  ]
+
+  function checkPrototypes() {
+    for (var i = 0; i < prototypes.length; i++) {
+      var entry = prototypes[i]
+      var keys = getOwnPropertyNames(entry.prototype)
+      var polluted = keys.length !== entry.count
+      for (var j = 0; !polluted && j < keys.length; j++) {
+        var descriptor = getOwnPropertyDescriptor(entry.prototype, keys[j])
+        polluted = descriptor.value !== entry.values[keys[j]]
+      }
+      if (polluted) {
+        report('polluted ' + entry.name + '.prototype')
+      }
+    }
+  }
+
+  function guard(object, key, name) {
+    var descriptor = getOwnPropertyDescriptor(object, key)
+    if (!descriptor || !descriptor.configurable) {
+      return
+    }
+    var value = object[key]
+    defineProperty(object, key, {
+      get: function () {
+        return value
+      },
+      set: function () {
+        report('set ' + name)
+      },
+      enumerable: true,
+      configurable: false
+    })
+  }
+
+  api.invoke = function (cmd, args) {
+    checkPrototypes()
+    return invoke.call(api, cmd, args)
+  }
+  // the API modules are frozen too, the window list is updated at runtime
+  for (var key in api) {
+    var module = api[key]
+    if (key.indexOf('__') !== 0 && module && typeof module === 'object') {
+      Object.freeze(module)
+    }
+  }
+  Object.freeze(api)
+  var frozen = new Proxy(api, {
+    set: function (target, key) {
+      report('set window.__TAURI__.' + String(key))
+      return false
+    },
+    defineProperty: function (target, key) {
+      report('define window.__TAURI__.' + String(key))
+      return false
+    },
+    deleteProperty: function (target, key) {
+      report('delete window.__TAURI__.' + String(key))
+      return false
+    },
+    setPrototypeOf: function () {
+      report('set the window.__TAURI__ prototype')
+      return false
+    }
+  })
+  defineProperty(window, '__TAURI__', {
+    get: function () {
+      return frozen
+    },
+    set: function () {
+      report('set window.__TAURI__')
+    },
+    enumerable: true,
+    configurable: false
+  })
+  guard(window, '__HANDLER__', 'the event handler')
+  guard(window, 'rpc', 'window.rpc')
+  if (!window.rpc) {
+    window.addEventListener('DOMContentLoaded', function () {
+      guard(window, 'rpc', 'window.rpc')
+    })
+  }
+})()
@@ -0,0 +1,38 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  function report(payload) {
+    try {
+      window.__TAURI__.invoke('__jsError', payload)
+    } catch (e) {
+      // reporting must not raise another error
+    }
+  }
+  function stackOf(error) {
+    return error && error.stack ? String(error.stack) : null
+  }
+  window.addEventListener('error', function (event) {
+    // failing resources also dispatch error events, but they aren't exceptions
+    if (!(event instanceof ErrorEvent)) {
+      return
+    }
+    report({
+      kind: 'error',
+      message: String(event.message),
+      stack: stackOf(event.error),
+      source: event.filename || null,
+      line: event.lineno || null,
+      column: event.colno || null
+    })
+  })
+  window.addEventListener('unhandledrejection', function (event) {
+    var reason = event.reason
+    report({
+      kind: 'unhandledRejection',
+      message: reason && reason.message ? String(reason.message) : String(reason),
+      stack: stackOf(reason)
+    })
+  })
+})()
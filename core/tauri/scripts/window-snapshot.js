@@ -0,0 +1,13 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+(function () {
+  var provider = window.__TAURI__.__windowSnapshotProvider
+  if (typeof provider !== 'function') {
+    return null
+  }
+  return Promise.resolve(provider()).then(function (snapshot) {
+    return { snapshot: snapshot === undefined ? null : snapshot }
+  })
+})()
@@ -0,0 +1,339 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::system_settings::{self, Watcher};
+
+/// How often a watcher waiting for the change notifications checks whether it was dropped.
+#[cfg(any(target_os = "linux", windows))]
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often the settings are read on macOS, which doesn't notify their changes to other threads.
+#[cfg(target_os = "macos")]
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The accessibility settings of the system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityState {
+  /// Whether a screen reader (VoiceOver, Narrator, Orca...) is running.
+  pub screen_reader: bool,
+  /// Whether the high contrast mode is active.
+  pub high_contrast: bool,
+}
+
+/// Reads the current accessibility settings.
+///
+/// Settings that can't be read are reported as disabled.
+pub fn state() -> AccessibilityState {
+  AccessibilityState {
+    screen_reader: screen_reader_active(),
+    high_contrast: high_contrast_active(),
+  }
+}
+
+/// Watches the accessibility settings.
+///
+/// The current state is sent right away, then a new state is sent each time it changes.
+/// The settings are read again when the system notifies a change on Linux and Windows,
+/// and every two seconds on macOS.
+/// The watcher stops when it's dropped.
+pub fn watch() -> Watcher<AccessibilityState> {
+  #[cfg(target_os = "linux")]
+  return linux::watch();
+  #[cfg(windows)]
+  return windows::watch();
+  #[cfg(target_os = "macos")]
+  return system_settings::watch(POLL_INTERVAL, state);
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  system_settings::watch_with(state, |cancel| {
+    // nothing can change
+    while cancel.sleep(Duration::from_secs(60)) {}
+    false
+  })
+}
+
+/// Forces the webview to expose its accessibility tree to assistive technologies,
+/// even if no screen reader was detected when it started.
+///
+/// Must be called before the webview runtime is created.
+pub fn force_accessibility_tree() {
+  #[cfg(windows)]
+  {
+    let key = "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS";
+    let flag = "--force-renderer-accessibility";
+    let args = std::env::var(key).unwrap_or_default();
+    if !args.contains(flag) {
+      std::env::set_var(key, format!("{} {}", args, flag).trim());
+    }
+  }
+  #[cfg(target_os = "linux")]
+  {
+    // GTK disables the AT-SPI bridge when this is set
+    std::env::remove_var("NO_AT_BRIDGE");
+  }
+  // the macOS WKWebView always exposes its accessibility tree
+}
+
+fn screen_reader_active() -> bool {
+  #[cfg(target_os = "linux")]
+  return system_settings::gsettings(
+    "org.gnome.desktop.a11y.applications",
+    "screen-reader-enabled",
+  )
+  .map(|value| value == "true")
+  .unwrap_or(false);
+  #[cfg(target_os = "macos")]
+  return macos::voice_over_enabled();
+  #[cfg(windows)]
+  return system_settings::registry_value(r"HKCU\Control Panel\Accessibility\Blind Access", "On")
+    .map(|value| value == "1")
+    .unwrap_or(false);
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  false
+}
+
+pub(crate) fn high_contrast_active() -> bool {
+  #[cfg(target_os = "linux")]
+  return system_settings::gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+    .map(|value| value == "true")
+    .unwrap_or(false)
+    || system_settings::gsettings("org.gnome.desktop.interface", "gtk-theme")
+      .map(|theme| theme.contains("HighContrast"))
+      .unwrap_or(false);
+  #[cfg(target_os = "macos")]
+  return macos::increase_contrast();
+  #[cfg(windows)]
+  return system_settings::registry_value(
+    r"HKCU\Control Panel\Accessibility\HighContrast",
+    "Flags",
+  )
+  .and_then(|flags| flags.parse::<u32>().ok())
+  // HCF_HIGHCONTRASTON
+  .map(|flags| flags & 1 != 0)
+  .unwrap_or(false);
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  false
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{channel, RecvTimeoutError},
+    thread,
+    time::Duration,
+  };
+
+  use super::{state, AccessibilityState, CANCEL_CHECK_INTERVAL};
+  use crate::api::system_settings::{self, Watcher};
+
+  /// The schemas of the settings read by [`state`].
+  const SCHEMAS: &[&str] = &[
+    "org.gnome.desktop.a11y.applications",
+    "org.gnome.desktop.a11y.interface",
+    "org.gnome.desktop.interface",
+  ];
+
+  /// The `gsettings monitor` processes, killed when the watcher stops.
+  struct Monitors(Vec<Child>);
+
+  impl Drop for Monitors {
+    fn drop(&mut self) {
+      for child in &mut self.0 {
+        let _ = child.kill();
+        let _ = child.wait();
+      }
+    }
+  }
+
+  pub fn watch() -> Watcher<AccessibilityState> {
+    let (tx, rx) = channel();
+    let mut monitors = Monitors(Vec::new());
+    for schema in SCHEMAS {
+      let child = Command::new("gsettings")
+        .args(&["monitor", schema])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+      if let Ok(mut child) = child {
+        if let Some(stdout) = child.stdout.take() {
+          let tx = tx.clone();
+          // each line is a changed key, and the output ends when the process is killed
+          thread::spawn(move || {
+            for _ in BufReader::new(stdout).lines() {
+              if tx.send(()).is_err() {
+                break;
+              }
+            }
+          });
+        }
+        monitors.0.push(child);
+      }
+    }
+    // the changes are disconnected once every monitor exits
+    drop(tx);
+
+    system_settings::watch_with(state, move |cancel| {
+      let _monitors = &monitors;
+      loop {
+        match rx.recv_timeout(CANCEL_CHECK_INTERVAL) {
+          Ok(()) => {
+            // a burst of changes is read once
+            while rx.try_recv().is_ok() {}
+            return true;
+          }
+          Err(RecvTimeoutError::Timeout) => {
+            if cancel.is_cancelled() {
+              return false;
+            }
+          }
+          // gsettings isn't available, so the settings can't be read either
+          Err(RecvTimeoutError::Disconnected) => {
+            while cancel.sleep(Duration::from_secs(60)) {}
+            return false;
+          }
+        }
+      }
+    })
+  }
+}
+
+#[cfg(windows)]
+mod windows {
+  use std::{iter::once, ptr::null_mut, time::Duration};
+
+  use winapi::{
+    shared::{
+      minwindef::{FALSE, HKEY, TRUE},
+      winerror::ERROR_SUCCESS,
+    },
+    um::{
+      handleapi::CloseHandle,
+      synchapi::{CreateEventW, WaitForSingleObject},
+      winbase::WAIT_OBJECT_0,
+      winnt::{HANDLE, KEY_NOTIFY, REG_NOTIFY_CHANGE_LAST_SET},
+      winreg::{RegCloseKey, RegNotifyChangeKeyValue, RegOpenKeyExW, HKEY_CURRENT_USER},
+    },
+  };
+
+  use super::{state, AccessibilityState, CANCEL_CHECK_INTERVAL};
+  use crate::api::system_settings::{self, Watcher};
+
+  /// The `Control Panel\Accessibility` registry key and the event signaled when it changes.
+  struct Notifier {
+    key: HKEY,
+    event: HANDLE,
+  }
+
+  // the handles are only used by the watcher thread
+  unsafe impl Send for Notifier {}
+
+  impl Notifier {
+    fn open() -> Option<Self> {
+      let path: Vec<u16> = r"Control Panel\Accessibility"
+        .encode_utf16()
+        .chain(once(0))
+        .collect();
+      let mut key = null_mut();
+      let status =
+        unsafe { RegOpenKeyExW(HKEY_CURRENT_USER, path.as_ptr(), 0, KEY_NOTIFY, &mut key) };
+      if status != ERROR_SUCCESS as i32 {
+        return None;
+      }
+      // auto reset, so each wait consumes its notification
+      let event = unsafe { CreateEventW(null_mut(), FALSE, FALSE, null_mut()) };
+      let notifier = Self { key, event };
+      if event.is_null() || !notifier.register() {
+        return None;
+      }
+      Some(notifier)
+    }
+
+    /// Asks for the next change of the key or its subkeys to signal the event.
+    fn register(&self) -> bool {
+      let status = unsafe {
+        RegNotifyChangeKeyValue(self.key, TRUE, REG_NOTIFY_CHANGE_LAST_SET, self.event, TRUE)
+      };
+      status == ERROR_SUCCESS as i32
+    }
+
+    /// Waits up to the timeout for a change, registering for the next one when it happens.
+    fn wait(&self, timeout: Duration) -> bool {
+      let signaled = unsafe { WaitForSingleObject(self.event, timeout.as_millis() as u32) };
+      if signaled != WAIT_OBJECT_0 {
+        return false;
+      }
+      // without a new registration, the next waits only time out
+      self.register();
+      true
+    }
+  }
+
+  impl Drop for Notifier {
+    fn drop(&mut self) {
+      unsafe {
+        RegCloseKey(self.key);
+        if !self.event.is_null() {
+          CloseHandle(self.event);
+        }
+      }
+    }
+  }
+
+  pub fn watch() -> Watcher<AccessibilityState> {
+    let notifier = Notifier::open();
+    system_settings::watch_with(state, move |cancel| match &notifier {
+      Some(notifier) => loop {
+        if notifier.wait(CANCEL_CHECK_INTERVAL) {
+          return true;
+        }
+        if cancel.is_cancelled() {
+          return false;
+        }
+      },
+      // the settings can't be read either without the registry key
+      None => {
+        while cancel.sleep(Duration::from_secs(60)) {}
+        false
+      }
+    })
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+  //! The settings are read from the shared `NSWorkspace`, without spawning a process.
+
+  use objc::{
+    class, msg_send,
+    runtime::{Object, BOOL, YES},
+    sel, sel_impl,
+  };
+
+  pub fn voice_over_enabled() -> bool {
+    unsafe {
+      let workspace: *mut Object = msg_send![class!(NSWorkspace), sharedWorkspace];
+      // available since macOS 10.13
+      let available: BOOL = msg_send![workspace, respondsToSelector: sel!(isVoiceOverEnabled)];
+      if available != YES {
+        return false;
+      }
+      let enabled: BOOL = msg_send![workspace, isVoiceOverEnabled];
+      enabled == YES
+    }
+  }
+
+  pub fn increase_contrast() -> bool {
+    unsafe {
+      let workspace: *mut Object = msg_send![class!(NSWorkspace), sharedWorkspace];
+      let enabled: BOOL = msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+      enabled == YES
+    }
+  }
+}
@@ -4,8 +4,17 @@
 
 use std::{
   env,
-  path::PathBuf,
+  path::{Path, PathBuf},
   process::{exit, Command},
+  sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+use crate::api::{
+  config::Config,
+  path::{resolve_path, BaseDirectory},
 };
 
 /// Get the current binary
@@ -29,6 +38,38 @@ pub fn current_binary() -> Option<PathBuf> {
   current_binary
 }
 
+/// Gets the random identifier of this install of the app, generated on the first call
+/// and persisted in the app data directory.
+///
+/// It isn't derived from the hardware, so it changes when the app data is removed,
+/// and it can't be used to track the user across apps.
+pub fn install_id(config: &Config) -> crate::api::Result<Uuid> {
+  // serializes the first calls, so they don't generate different identifiers
+  static LOCK: Lazy<Mutex<()>> = Lazy::new(Default::default);
+  let _guard = LOCK.lock().unwrap();
+  read_or_create_install_id(&resolve_path(
+    config,
+    ".install-id",
+    Some(BaseDirectory::App),
+  )?)
+}
+
+/// Reads the install identifier of the file,
+/// replacing it with a new one if it's missing or invalid.
+fn read_or_create_install_id(path: &Path) -> crate::api::Result<Uuid> {
+  if let Ok(id) = std::fs::read_to_string(path) {
+    if let Ok(id) = Uuid::parse_str(id.trim()) {
+      return Ok(id);
+    }
+  }
+  let id = Uuid::new_v4();
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, id.to_string())?;
+  Ok(id)
+}
+
 /// Restart application
 pub fn restart_application(binary_to_start: Option<PathBuf>) {
   let mut binary_path = binary_to_start;
@@ -45,3 +86,19 @@ pub fn restart_application(binary_to_start: Option<PathBuf>) {
 
   exit(0);
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn persists_install_id() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let path = dir.path().join("app").join(".install-id");
+    let id = read_or_create_install_id(&path).expect("failed to create install id");
+    assert_eq!(read_or_create_install_id(&path).unwrap(), id);
+
+    std::fs::write(&path, "invalid").unwrap();
+    assert_ne!(read_or_create_install_id(&path).unwrap(), id);
+  }
+}
@@ -0,0 +1,137 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::system_settings::{self, Watcher};
+
+/// The appearance preferences of the desktop.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppearancePreferences {
+  /// The system accent color, in the `#rrggbb` format, if it can be read.
+  pub accent_color: Option<String>,
+  /// Whether the user asked to minimize animations.
+  pub reduced_motion: bool,
+  /// Whether the user asked to reduce the transparency and blur effects.
+  pub reduced_transparency: bool,
+  /// Whether the high (or increased) contrast mode is active.
+  pub high_contrast: bool,
+}
+
+/// Reads the current appearance preferences.
+///
+/// Preferences that can't be read are reported as disabled.
+pub fn preferences() -> AppearancePreferences {
+  AppearancePreferences {
+    accent_color: accent_color(),
+    reduced_motion: reduced_motion(),
+    reduced_transparency: reduced_transparency(),
+    high_contrast: super::accessibility::high_contrast_active(),
+  }
+}
+
+/// Polls the appearance preferences on the given interval.
+///
+/// The current preferences are sent right away, then new preferences are sent each time they change.
+/// Polling stops when the watcher is dropped.
+pub fn watch(interval: Duration) -> Watcher<AppearancePreferences> {
+  system_settings::watch(interval, preferences)
+}
+
+#[allow(dead_code)]
+fn rgb_to_hex(red: u8, green: u8, blue: u8) -> String {
+  format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+fn accent_color() -> Option<String> {
+  #[cfg(target_os = "linux")]
+  // available since GNOME 47
+  return system_settings::gsettings("org.gnome.desktop.interface", "accent-color").and_then(
+    |color| {
+      let rgb = match color.as_str() {
+        "blue" => (0x35, 0x84, 0xe4),
+        "teal" => (0x21, 0x90, 0xa4),
+        "green" => (0x3a, 0x94, 0x4a),
+        "yellow" => (0xc8, 0x88, 0x00),
+        "orange" => (0xed, 0x5b, 0x00),
+        "red" => (0xe6, 0x2d, 0x42),
+        "pink" => (0xd5, 0x61, 0x99),
+        "purple" => (0x91, 0x41, 0xac),
+        "slate" => (0x6f, 0x83, 0x96),
+        _ => return None,
+      };
+      Some(rgb_to_hex(rgb.0, rgb.1, rgb.2))
+    },
+  );
+  #[cfg(target_os = "macos")]
+  {
+    // the key is missing when the default (blue) accent color is used
+    let color = system_settings::defaults("-g", "AppleAccentColor")
+      .and_then(|color| color.parse::<i32>().ok())
+      .unwrap_or(4);
+    let rgb = match color {
+      -1 => (0x8c, 0x8c, 0x8c),
+      0 => (0xff, 0x52, 0x57),
+      1 => (0xf7, 0x82, 0x1b),
+      2 => (0xff, 0xc6, 0x00),
+      3 => (0x62, 0xba, 0x46),
+      5 => (0xa5, 0x50, 0xa7),
+      6 => (0xf7, 0x4f, 0x9e),
+      _ => (0x00, 0x7a, 0xff),
+    };
+    return Some(rgb_to_hex(rgb.0, rgb.1, rgb.2));
+  }
+  #[cfg(windows)]
+  // stored as 0xAABBGGRR
+  return system_settings::registry_dword(r"HKCU\Software\Microsoft\Windows\DWM", "AccentColor")
+    .map(|color| rgb_to_hex(color as u8, (color >> 8) as u8, (color >> 16) as u8));
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  None
+}
+
+fn reduced_motion() -> bool {
+  #[cfg(target_os = "linux")]
+  return system_settings::gsettings("org.gnome.desktop.interface", "enable-animations")
+    .map(|value| value == "false")
+    .unwrap_or(false);
+  #[cfg(target_os = "macos")]
+  return system_settings::defaults_bool("com.apple.universalaccess", "reduceMotion");
+  #[cfg(windows)]
+  return system_settings::registry_value(
+    r"HKCU\Control Panel\Desktop\WindowMetrics",
+    "MinAnimate",
+  )
+  .map(|value| value == "0")
+  .unwrap_or(false);
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  false
+}
+
+fn reduced_transparency() -> bool {
+  #[cfg(target_os = "macos")]
+  return system_settings::defaults_bool("com.apple.universalaccess", "reduceTransparency");
+  #[cfg(windows)]
+  return system_settings::registry_dword(
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+    "EnableTransparency",
+  )
+  .map(|value| value == 0)
+  .unwrap_or(false);
+  // GNOME has no transparency preference
+  #[cfg(not(any(target_os = "macos", windows)))]
+  false
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn formats_hex_color() {
+    assert_eq!(rgb_to_hex(0, 122, 255), "#007aff");
+  }
+}
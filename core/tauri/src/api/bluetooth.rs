@@ -0,0 +1,254 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use btleplug::{
+  api::{Central, CharPropFlags, Manager as _, Peripheral as _, ScanFilter, WriteType},
+  platform::{Adapter, Manager, Peripheral},
+};
+use futures::StreamExt;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api::private::async_runtime::{channel, spawn, Receiver};
+
+fn bluetooth_error(error: btleplug::Error) -> crate::api::Error {
+  crate::api::Error::Bluetooth(error.to_string())
+}
+
+/// A Bluetooth LE device found by [`scan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Device {
+  /// The device identifier, used to [`connect`] to it.
+  pub id: String,
+  /// The advertised device name.
+  pub name: Option<String>,
+  /// The signal strength of the last advertisement, in dBm.
+  pub rssi: Option<i16>,
+}
+
+/// A GATT characteristic of a connected device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Characteristic {
+  /// The characteristic UUID.
+  pub uuid: Uuid,
+  /// The UUID of the service the characteristic belongs to.
+  pub service: Uuid,
+  /// Whether the characteristic can be read.
+  pub read: bool,
+  /// Whether the characteristic can be written, with or without response.
+  pub write: bool,
+  /// Whether the characteristic sends notifications or indications.
+  pub notify: bool,
+}
+
+impl From<btleplug::api::Characteristic> for Characteristic {
+  fn from(characteristic: btleplug::api::Characteristic) -> Self {
+    let properties = characteristic.properties;
+    Self {
+      uuid: characteristic.uuid,
+      service: characteristic.service_uuid,
+      read: properties.contains(CharPropFlags::READ),
+      write: properties.intersects(CharPropFlags::WRITE | CharPropFlags::WRITE_WITHOUT_RESPONSE),
+      notify: properties.intersects(CharPropFlags::NOTIFY | CharPropFlags::INDICATE),
+    }
+  }
+}
+
+/// A value notified by a characteristic the [`Connection`] is subscribed to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+  /// The characteristic UUID.
+  pub characteristic: Uuid,
+  /// The notified value.
+  pub value: Vec<u8>,
+}
+
+/// The first Bluetooth adapter of the system.
+async fn adapter() -> crate::api::Result<Adapter> {
+  Manager::new()
+    .await
+    .map_err(bluetooth_error)?
+    .adapters()
+    .await
+    .map_err(bluetooth_error)?
+    .into_iter()
+    .next()
+    .ok_or_else(|| crate::api::Error::Bluetooth("no Bluetooth adapter found".into()))
+}
+
+/// Scans for Bluetooth LE devices for the given duration.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: uses BlueZ through D-Bus.
+/// - **macOS**: the device identifiers are random UUIDs assigned by the system, not MAC addresses.
+pub async fn scan(duration: Duration) -> crate::api::Result<Vec<Device>> {
+  let adapter = adapter().await?;
+  adapter
+    .start_scan(ScanFilter::default())
+    .await
+    .map_err(bluetooth_error)?;
+  tokio::time::sleep(duration).await;
+  let _ = adapter.stop_scan().await;
+
+  let mut devices = Vec::new();
+  for peripheral in adapter.peripherals().await.map_err(bluetooth_error)? {
+    let properties = peripheral.properties().await.map_err(bluetooth_error)?;
+    devices.push(Device {
+      id: peripheral.id().to_string(),
+      name: properties.as_ref().and_then(|p| p.local_name.clone()),
+      rssi: properties.and_then(|p| p.rssi),
+    });
+  }
+  Ok(devices)
+}
+
+/// Connects to a device found by [`scan`], discovering its services.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::bluetooth::{connect, scan};
+/// use std::time::Duration;
+///
+/// async fn read_thermometer() -> tauri::api::Result<()> {
+///   let device = scan(Duration::from_secs(5))
+///     .await?
+///     .into_iter()
+///     .find(|device| device.name.as_deref() == Some("Thermometer"))
+///     .expect("thermometer not found");
+///   let connection = connect(&device.id).await?;
+///   for characteristic in connection.characteristics() {
+///     if characteristic.read {
+///       let value = connection.read(characteristic.uuid).await?;
+///       println!("{}: {:?}", characteristic.uuid, value);
+///     }
+///   }
+///   connection.disconnect().await
+/// }
+/// ```
+pub async fn connect(id: &str) -> crate::api::Result<Connection> {
+  let adapter = adapter().await?;
+  let peripheral = adapter
+    .peripherals()
+    .await
+    .map_err(bluetooth_error)?
+    .into_iter()
+    .find(|peripheral| peripheral.id().to_string() == id)
+    .ok_or_else(|| crate::api::Error::Bluetooth(format!("device {} not found, scan first", id)))?;
+  peripheral.connect().await.map_err(bluetooth_error)?;
+  peripheral
+    .discover_services()
+    .await
+    .map_err(bluetooth_error)?;
+  Ok(Connection { peripheral })
+}
+
+/// A connection to a Bluetooth LE device.
+pub struct Connection {
+  peripheral: Peripheral,
+}
+
+impl Connection {
+  fn characteristic(&self, uuid: Uuid) -> crate::api::Result<btleplug::api::Characteristic> {
+    self
+      .peripheral
+      .characteristics()
+      .into_iter()
+      .find(|characteristic| characteristic.uuid == uuid)
+      .ok_or_else(|| crate::api::Error::Bluetooth(format!("characteristic {} not found", uuid)))
+  }
+
+  /// The characteristics of the device.
+  pub fn characteristics(&self) -> Vec<Characteristic> {
+    self
+      .peripheral
+      .characteristics()
+      .into_iter()
+      .map(Into::into)
+      .collect()
+  }
+
+  /// Reads the value of a characteristic.
+  pub async fn read(&self, characteristic: Uuid) -> crate::api::Result<Vec<u8>> {
+    let characteristic = self.characteristic(characteristic)?;
+    self
+      .peripheral
+      .read(&characteristic)
+      .await
+      .map_err(bluetooth_error)
+  }
+
+  /// Writes the value of a characteristic, waiting for the device to acknowledge it if `with_response` is set.
+  pub async fn write(
+    &self,
+    characteristic: Uuid,
+    value: &[u8],
+    with_response: bool,
+  ) -> crate::api::Result<()> {
+    let characteristic = self.characteristic(characteristic)?;
+    let write_type = if with_response {
+      WriteType::WithResponse
+    } else {
+      WriteType::WithoutResponse
+    };
+    self
+      .peripheral
+      .write(&characteristic, value, write_type)
+      .await
+      .map_err(bluetooth_error)
+  }
+
+  /// Enables the notifications of a characteristic, received on [`Self::notifications`].
+  pub async fn subscribe(&self, characteristic: Uuid) -> crate::api::Result<()> {
+    let characteristic = self.characteristic(characteristic)?;
+    self
+      .peripheral
+      .subscribe(&characteristic)
+      .await
+      .map_err(bluetooth_error)
+  }
+
+  /// Disables the notifications of a characteristic.
+  pub async fn unsubscribe(&self, characteristic: Uuid) -> crate::api::Result<()> {
+    let characteristic = self.characteristic(characteristic)?;
+    self
+      .peripheral
+      .unsubscribe(&characteristic)
+      .await
+      .map_err(bluetooth_error)
+  }
+
+  /// Returns a receiver of the values notified by the subscribed characteristics,
+  /// which ends when the device disconnects.
+  pub async fn notifications(&self) -> crate::api::Result<Receiver<Notification>> {
+    let mut stream = self
+      .peripheral
+      .notifications()
+      .await
+      .map_err(bluetooth_error)?;
+    let (tx, rx) = channel(32);
+    spawn(async move {
+      while let Some(notification) = stream.next().await {
+        let notification = Notification {
+          characteristic: notification.uuid,
+          value: notification.value,
+        };
+        if tx.send(notification).await.is_err() {
+          break;
+        }
+      }
+    });
+    Ok(rx)
+  }
+
+  /// Disconnects from the device.
+  pub async fn disconnect(&self) -> crate::api::Result<()> {
+    self.peripheral.disconnect().await.map_err(bluetooth_error)
+  }
+}
@@ -0,0 +1,277 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// The largest side of the thumbnails, in pixels.
+#[allow(dead_code)]
+const THUMBNAIL_SIZE: &str = "320";
+
+/// The kind of a capturable source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureSourceKind {
+  /// A whole screen.
+  Screen,
+  /// A single window.
+  Window,
+}
+
+/// A screen or a window that can be shared with `getDisplayMedia`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureSource {
+  /// The source identifier, e.g. `screen:HDMI-1` or `window:0x04000007`.
+  pub id: String,
+  /// Whether the source is a screen or a window.
+  pub kind: CaptureSourceKind,
+  /// The screen name or the window title.
+  pub title: String,
+  /// A PNG preview of the source, as a `data:` URL, if requested and available.
+  pub thumbnail: Option<String>,
+}
+
+impl CaptureSource {
+  #[allow(dead_code)]
+  fn new(kind: CaptureSourceKind, handle: &str, title: String) -> Self {
+    let prefix = match kind {
+      CaptureSourceKind::Screen => "screen",
+      CaptureSourceKind::Window => "window",
+    };
+    Self {
+      id: format!("{}:{}", prefix, handle),
+      kind,
+      title,
+      thumbnail: None,
+    }
+  }
+
+  /// The platform identifier of the screen or window.
+  #[allow(dead_code)]
+  fn handle(&self) -> &str {
+    self.id.splitn(2, ':').nth(1).unwrap_or_default()
+  }
+}
+
+/// Lists the screens and the windows that can be captured, with their thumbnails if requested.
+///
+/// Taking the thumbnails is slow, so only request them to show a picker.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: X11 only, read with `xrandr` and `wmctrl`.
+///   The thumbnails are taken with ImageMagick's `import`.
+/// - **macOS**: only the screens are listed.
+/// - **Windows**: the windows are the main window of each process. The thumbnails aren't available.
+pub fn sources(thumbnails: bool) -> Vec<CaptureSource> {
+  let mut sources = screens();
+  sources.extend(windows());
+  if thumbnails {
+    for source in &mut sources {
+      source.thumbnail =
+        thumbnail(source).map(|png| format!("data:image/png;base64,{}", base64::encode(png)));
+    }
+  }
+  sources
+}
+
+fn screens() -> Vec<CaptureSource> {
+  #[cfg(target_os = "linux")]
+  return system_settings::command_output("xrandr", &["--listmonitors"])
+    .map(|output| parse_xrandr_monitors(&output))
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(name, _)| CaptureSource::new(CaptureSourceKind::Screen, &name, name.clone()))
+    .collect();
+  #[cfg(target_os = "macos")]
+  return system_settings::command_output("system_profiler", &["SPDisplaysDataType", "-json"])
+    .and_then(|output| serde_json::from_str::<serde_json::Value>(&output).ok())
+    .map(|profile| parse_macos_displays(&profile))
+    .unwrap_or_default()
+    .into_iter()
+    .enumerate()
+    // `screencapture` numbers the displays from 1
+    .map(|(i, name)| CaptureSource::new(CaptureSourceKind::Screen, &(i + 1).to_string(), name))
+    .collect();
+  #[cfg(windows)]
+  return system_settings::powershell(
+    "Add-Type -AssemblyName System.Windows.Forms; \
+      [System.Windows.Forms.Screen]::AllScreens | ForEach-Object { $_.DeviceName }",
+  )
+  .unwrap_or_default()
+  .lines()
+  .map(|name| CaptureSource::new(CaptureSourceKind::Screen, name, name.to_string()))
+  .collect();
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Vec::new()
+}
+
+fn windows() -> Vec<CaptureSource> {
+  #[cfg(target_os = "linux")]
+  return system_settings::command_output("wmctrl", &["-l"])
+    .map(|output| parse_wmctrl_windows(&output))
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(id, title)| CaptureSource::new(CaptureSourceKind::Window, &id, title))
+    .collect();
+  #[cfg(windows)]
+  return system_settings::powershell(
+    "Get-Process | Where-Object { $_.MainWindowTitle } | \
+      ForEach-Object { \"$($_.MainWindowHandle)`t$($_.MainWindowTitle)\" }",
+  )
+  .unwrap_or_default()
+  .lines()
+  .filter_map(|line| {
+    let mut parts = line.splitn(2, '\t');
+    let handle = parts.next()?;
+    let title = parts.next()?;
+    Some(CaptureSource::new(
+      CaptureSourceKind::Window,
+      handle,
+      title.to_string(),
+    ))
+  })
+  .collect();
+  // the macOS window identifiers can't be read with the system tools
+  #[cfg(not(any(target_os = "linux", windows)))]
+  Vec::new()
+}
+
+/// Takes a PNG screenshot of the source, scaled down to the thumbnail size.
+#[allow(unused_variables)]
+fn thumbnail(source: &CaptureSource) -> Option<Vec<u8>> {
+  #[cfg(target_os = "linux")]
+  {
+    let size = format!("{}x{}", THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    return match source.kind {
+      CaptureSourceKind::Screen => {
+        let geometry = system_settings::command_output("xrandr", &["--listmonitors"])
+          .map(|output| parse_xrandr_monitors(&output))
+          .unwrap_or_default()
+          .into_iter()
+          .find(|(name, _)| name == source.handle())
+          .map(|(_, geometry)| geometry)?;
+        system_settings::command_bytes(
+          "import",
+          &[
+            "-silent", "-window", "root", "-crop", &geometry, "-resize", &size, "png:-",
+          ],
+        )
+      }
+      CaptureSourceKind::Window => system_settings::command_bytes(
+        "import",
+        &[
+          "-silent",
+          "-window",
+          source.handle(),
+          "-resize",
+          &size,
+          "png:-",
+        ],
+      ),
+    };
+  }
+  #[cfg(target_os = "macos")]
+  {
+    let file = tempfile::Builder::new().suffix(".png").tempfile().ok()?;
+    let path = file.path().to_string_lossy().into_owned();
+    let display = format!("-D{}", source.handle());
+    system_settings::command_output("screencapture", &["-x", &display, "-t", "png", &path])?;
+    system_settings::command_output("sips", &["-Z", THUMBNAIL_SIZE, &path])?;
+    return std::fs::read(&path).ok();
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+  None
+}
+
+/// Parses the `xrandr --listmonitors` output into the monitor names and their `WxH+X+Y` geometry.
+#[allow(dead_code)]
+fn parse_xrandr_monitors(output: &str) -> Vec<(String, String)> {
+  // ` 0: +*eDP-1 1920/344x1080/194+0+0  eDP-1`, the sizes are followed by their millimeters
+  output
+    .lines()
+    .skip(1)
+    .filter_map(|line| {
+      let parts = line.split_whitespace().collect::<Vec<_>>();
+      let name = parts.last()?;
+      let mut geometry = parts.get(2)?.split('x');
+      let width = geometry.next()?.split('/').next()?;
+      let mut height = geometry.next()?.split('+');
+      let height_mm = height.next()?;
+      let x = height.next()?;
+      let y = height.next()?;
+      let height = height_mm.split('/').next()?;
+      Some((
+        name.to_string(),
+        format!("{}x{}+{}+{}", width, height, x, y),
+      ))
+    })
+    .collect()
+}
+
+/// Parses the `wmctrl -l` output into the window identifiers and titles.
+#[allow(dead_code)]
+fn parse_wmctrl_windows(output: &str) -> Vec<(String, String)> {
+  // `0x04000007  0 hostname The window title`
+  output
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.split_whitespace();
+      let id = parts.next()?;
+      let _desktop = parts.next()?;
+      let _host = parts.next()?;
+      Some((id.to_string(), parts.collect::<Vec<_>>().join(" ")))
+    })
+    .collect()
+}
+
+/// Reads the display names of the `system_profiler SPDisplaysDataType -json` output.
+#[allow(dead_code)]
+fn parse_macos_displays(profile: &serde_json::Value) -> Vec<String> {
+  profile["SPDisplaysDataType"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .flat_map(|gpu| gpu["spdisplays_ndrvs"].as_array().into_iter().flatten())
+    .filter_map(|display| display["_name"].as_str().map(ToString::to_string))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_sources() {
+    let monitors = "Monitors: 2\n 0: +*eDP-1 1920/344x1080/194+0+0  eDP-1\n 1: +HDMI-1 2560/597x1440/336+1920+0  HDMI-1";
+    assert_eq!(
+      parse_xrandr_monitors(monitors),
+      vec![
+        ("eDP-1".to_string(), "1920x1080+0+0".to_string()),
+        ("HDMI-1".to_string(), "2560x1440+1920+0".to_string())
+      ]
+    );
+    assert_eq!(
+      parse_wmctrl_windows("0x04000007  0 laptop Inbox - Mail\n0x05a00003 -1 laptop Panel"),
+      vec![
+        ("0x04000007".to_string(), "Inbox - Mail".to_string()),
+        ("0x05a00003".to_string(), "Panel".to_string())
+      ]
+    );
+    let profile = serde_json::json!({
+      "SPDisplaysDataType": [{ "spdisplays_ndrvs": [{ "_name": "Color LCD" }, { "_name": "LG UltraFine" }] }]
+    });
+    assert_eq!(
+      parse_macos_displays(&profile),
+      vec!["Color LCD".to_string(), "LG UltraFine".to_string()]
+    );
+    assert_eq!(
+      CaptureSource::new(CaptureSourceKind::Window, "0x04000007", "Mail".into()).handle(),
+      "0x04000007"
+    );
+  }
+}
@@ -0,0 +1,48 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Reads and writes text on the system clipboard.
+
+use arboard::Clipboard;
+
+fn clipboard() -> crate::api::Result<Clipboard> {
+  Clipboard::new().map_err(clipboard_error)
+}
+
+fn clipboard_error(error: arboard::Error) -> crate::api::Error {
+  crate::api::Error::Clipboard(error.to_string())
+}
+
+/// Writes the text to the clipboard, replacing its content.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::clipboard;
+///
+/// clipboard::write_text("Hello from Tauri").expect("failed to write to the clipboard");
+/// ```
+pub fn write_text(text: impl AsRef<str>) -> crate::api::Result<()> {
+  clipboard()?
+    .set_text(text.as_ref().to_string())
+    .map_err(clipboard_error)
+}
+
+/// Reads the text on the clipboard. Returns `None` if the clipboard is empty or doesn't contain text.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::clipboard;
+///
+/// if let Some(text) = clipboard::read_text().expect("failed to read the clipboard") {
+///   println!("the clipboard contains {}", text);
+/// }
+/// ```
+pub fn read_text() -> crate::api::Result<Option<String>> {
+  match clipboard()?.get_text() {
+    Ok(text) if text.is_empty() => Ok(None),
+    Ok(text) => Ok(Some(text)),
+    Err(arboard::Error::ContentNotAvailable) => Ok(None),
+    Err(e) => Err(clipboard_error(e)),
+  }
+}
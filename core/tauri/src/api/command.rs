@@ -3,9 +3,12 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-  io::{BufRead, BufReader, Write},
+  env::current_exe,
+  io::{BufRead, BufReader, Read, Write},
+  path::Path,
   process::{Command as StdCommand, Stdio},
-  sync::Arc,
+  sync::{Arc, Mutex},
+  thread,
 };
 
 #[cfg(unix)]
@@ -16,11 +19,10 @@ use std::os::windows::process::CommandExt;
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
-use crate::api::private::async_runtime::{channel, spawn, Receiver, RwLock};
+use crate::api::private::async_runtime::{channel, Receiver, Sender};
 use os_pipe::{pipe, PipeWriter};
 use serde::Serialize;
 use shared_child::SharedChild;
-use tauri_utils::platform;
 
 /// Payload for the `Terminated` command event.
 #[derive(Serialize)]
@@ -65,19 +67,26 @@ pub struct Command {
 }
 
 /// Child spawned.
+///
+/// Cloning it shares the process, so it can be written to and killed from anywhere.
+#[derive(Clone)]
 pub struct CommandChild {
   inner: Arc<SharedChild>,
-  stdin_writer: PipeWriter,
+  stdin_writer: Arc<Mutex<PipeWriter>>,
 }
 
 impl CommandChild {
   /// Write to process stdin.
-  pub fn write(&mut self, buf: &[u8]) -> crate::api::Result<()> {
-    self.stdin_writer.write_all(buf)?;
+  pub fn write(&self, buf: &[u8]) -> crate::api::Result<()> {
+    self
+      .stdin_writer
+      .lock()
+      .expect("poisoned stdin writer")
+      .write_all(buf)?;
     Ok(())
   }
   /// Send a kill signal to the child.
-  pub fn kill(self) -> crate::api::Result<()> {
+  pub fn kill(&self) -> crate::api::Result<()> {
     self.inner.kill()?;
     Ok(())
   }
@@ -98,12 +107,23 @@ impl Command {
   }
 
   /// Creates a new Command for launching the given sidecar program.
+  ///
+  /// The sidecar is a binary declared on `tauri > bundle > externalBin`, e.g. `binaries/my-bin`.
+  /// The CLI copies it next to the app executable without its target triple suffix,
+  /// so `my-bin-x86_64-pc-windows-msvc.exe` runs from the executable directory as `my-bin.exe`.
   pub fn new_sidecar<S: Into<String>>(program: S) -> Self {
-    Self::new(format!(
-      "{}-{}",
-      program.into(),
-      platform::target_triple().expect("unsupported platform")
-    ))
+    let program = program.into();
+    let name = Path::new(&program)
+      .file_name()
+      .map(|name| name.to_string_lossy().into_owned())
+      .unwrap_or(program);
+    #[cfg(windows)]
+    let name = format!("{}.exe", name);
+    let path = match current_exe() {
+      Ok(exe) => exe.with_file_name(&name).to_string_lossy().into_owned(),
+      Err(_) => name,
+    };
+    Self::new(path)
   }
 
   /// Append args to the command.
@@ -129,68 +149,63 @@ impl Command {
     command.stdin(stdin_reader);
 
     let shared_child = SharedChild::spawn(&mut command)?;
+    // drops the pipe ends given to the child, so the readers stop when it exits
+    drop(command);
     let child = Arc::new(shared_child);
     let child_ = child.clone();
-    let guard = Arc::new(RwLock::new(()));
 
     let (tx, rx) = channel(1);
 
-    let tx_ = tx.clone();
-    let guard_ = guard.clone();
-    spawn(async move {
-      let _lock = guard_.read().await;
-      let reader = BufReader::new(stdout_reader);
-      for line in reader.lines() {
-        let _ = match line {
-          Ok(line) => tx_.send(CommandEvent::Stdout(line)).await,
-          Err(e) => tx_.send(CommandEvent::Error(e.to_string())).await,
-        };
-      }
-    });
-
-    let tx_ = tx.clone();
-    let guard_ = guard.clone();
-    spawn(async move {
-      let _lock = guard_.read().await;
-      let reader = BufReader::new(stderr_reader);
-      for line in reader.lines() {
-        let _ = match line {
-          Ok(line) => tx_.send(CommandEvent::Stderr(line)).await,
-          Err(e) => tx_.send(CommandEvent::Error(e.to_string())).await,
-        };
-      }
-    });
+    // the pipes are read on their own threads, so long-running processes don't block the async runtime
+    let stdout_thread = read_lines(stdout_reader, tx.clone(), CommandEvent::Stdout);
+    let stderr_thread = read_lines(stderr_reader, tx.clone(), CommandEvent::Stderr);
 
-    spawn(async move {
-      let _ = match child_.wait() {
-        Ok(status) => {
-          guard.write().await;
-          tx.send(CommandEvent::Terminated(TerminatedPayload {
-            code: status.code(),
-            #[cfg(windows)]
-            signal: None,
-            #[cfg(unix)]
-            signal: status.signal(),
-          }))
-          .await
-        }
-        Err(e) => {
-          guard.write().await;
-          tx.send(CommandEvent::Error(e.to_string())).await
-        }
+    thread::spawn(move || {
+      let event = match child_.wait() {
+        Ok(status) => CommandEvent::Terminated(TerminatedPayload {
+          code: status.code(),
+          #[cfg(windows)]
+          signal: None,
+          #[cfg(unix)]
+          signal: status.signal(),
+        }),
+        Err(e) => CommandEvent::Error(e.to_string()),
       };
+      // the output lines are sent before the termination
+      let _ = stdout_thread.join();
+      let _ = stderr_thread.join();
+      let _ = tx.blocking_send(event);
     });
 
     Ok((
       rx,
       CommandChild {
         inner: child,
-        stdin_writer,
+        stdin_writer: Arc::new(Mutex::new(stdin_writer)),
       },
     ))
   }
 }
 
+/// Sends the lines read from the pipe to the channel, on a new thread.
+///
+/// The pipe is drained even when the receiver is dropped, so the process never blocks on a full pipe.
+fn read_lines<R: Read + Send + 'static>(
+  reader: R,
+  tx: Sender<CommandEvent>,
+  wrapper: fn(String) -> CommandEvent,
+) -> thread::JoinHandle<()> {
+  thread::spawn(move || {
+    for line in BufReader::new(reader).lines() {
+      let event = match line {
+        Ok(line) => wrapper(line),
+        Err(e) => CommandEvent::Error(e.to_string()),
+      };
+      let _ = tx.blocking_send(event);
+    }
+  })
+}
+
 // tests for the commands functions.
 #[cfg(test)]
 mod test {
@@ -0,0 +1,279 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// What the app can be the default handler of.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum HandlerTarget {
+  /// A URL scheme, e.g. `myapp` for the `myapp://` links.
+  Scheme(String),
+  /// A file extension without the dot, e.g. `md`.
+  Extension(String),
+}
+
+/// Whether the app is the default handler of the URL scheme or file extension.
+///
+/// The `identifier` is the app bundle identifier.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: compares the handler of `xdg-mime` with the desktop file of the app,
+///   named after its executable. The MIME type of extensions is read from the shared MIME database.
+/// - **macOS**: compares the handler of Launch Services with the bundle identifier.
+/// - **Windows**: compares the `open` command of the handler in the registry with the executable.
+#[allow(unused_variables)]
+pub fn is_default(identifier: &str, target: &HandlerTarget) -> crate::api::Result<bool> {
+  #[cfg(target_os = "linux")]
+  {
+    let mime_type = linux_mime_type(target)?;
+    return Ok(
+      system_settings::command_output("xdg-mime", &["query", "default", &mime_type])
+        .map(|handler| handler == desktop_file_name())
+        .unwrap_or(false),
+    );
+  }
+  #[cfg(target_os = "macos")]
+  {
+    let script = format!(
+      "ObjC.import('CoreServices');
+      {}
+      var handler = ObjC.castRefToObject({});
+      handler.isNil() ? '' : handler.js",
+      macos_content_type(target),
+      match target {
+        HandlerTarget::Scheme(_) => "$.LSCopyDefaultHandlerForURLScheme($(target))",
+        // 0xFFFFFFFF is `kLSRolesAll`
+        HandlerTarget::Extension(_) => {
+          "$.LSCopyDefaultRoleHandlerForContentType($(target), 0xFFFFFFFF)"
+        }
+      }
+    );
+    return Ok(
+      system_settings::command_output("osascript", &["-l", "JavaScript", "-e", &script])
+        .map(|handler| handler.eq_ignore_ascii_case(identifier))
+        .unwrap_or(false),
+    );
+  }
+  #[cfg(windows)]
+  {
+    let executable = std::env::current_exe()?;
+    let script = format!(
+      "{}
+      $key = \"Registry::HKEY_CLASSES_ROOT\\$progId\\shell\\open\\command\"
+      $command = (Get-ItemProperty -LiteralPath $key -ErrorAction SilentlyContinue).'(default)'
+      if ($command) {{ $command }}",
+      windows_prog_id(target)
+    );
+    return Ok(
+      system_settings::powershell(&script)
+        .map(|command| {
+          command
+            .to_lowercase()
+            .contains(&executable.to_string_lossy().to_lowercase())
+        })
+        .unwrap_or(false),
+    );
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Err(crate::api::Error::DefaultHandler(
+    "default handlers aren't supported on this platform".into(),
+  ))
+}
+
+/// Asks the system to make the app the default handler of the URL scheme or file extension.
+///
+/// The app must already be registered as a handler, e.g. by its bundle.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: sets the default handler with `xdg-mime` right away.
+/// - **macOS**: sets the default handler with Launch Services, which may ask the user to confirm.
+/// - **Windows**: apps can't set the default handlers themselves,
+///   so the default apps page of the Settings opens for the user to choose.
+#[allow(unused_variables)]
+pub fn request_default(identifier: &str, target: &HandlerTarget) -> crate::api::Result<()> {
+  #[cfg(target_os = "linux")]
+  {
+    let mime_type = linux_mime_type(target)?;
+    return system_settings::command_output(
+      "xdg-mime",
+      &["default", &desktop_file_name(), &mime_type],
+    )
+    .map(|_| ())
+    .ok_or_else(|| {
+      crate::api::Error::DefaultHandler("`xdg-mime` failed to set the default handler".into())
+    });
+  }
+  #[cfg(target_os = "macos")]
+  {
+    let script = format!(
+      "ObjC.import('CoreServices');
+      {}
+      {}",
+      macos_content_type(target),
+      match target {
+        HandlerTarget::Scheme(_) => format!(
+          "$.LSSetDefaultHandlerForURLScheme($(target), $({:?}))",
+          identifier
+        ),
+        HandlerTarget::Extension(_) => format!(
+          "$.LSSetDefaultRoleHandlerForContentType($(target), 0xFFFFFFFF, $({:?}))",
+          identifier
+        ),
+      }
+    );
+    return match system_settings::command_output("osascript", &["-l", "JavaScript", "-e", &script])
+    {
+      Some(status) if status == "0" => Ok(()),
+      _ => Err(crate::api::Error::DefaultHandler(
+        "Launch Services refused to set the default handler".into(),
+      )),
+    };
+  }
+  #[cfg(windows)]
+  {
+    return open::that("ms-settings:defaultapps")
+      .map(|_| ())
+      .map_err(Into::into);
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Err(crate::api::Error::DefaultHandler(
+    "default handlers aren't supported on this platform".into(),
+  ))
+}
+
+/// The desktop file of the app, named after its executable by the bundler.
+#[cfg(target_os = "linux")]
+fn desktop_file_name() -> String {
+  let name = std::env::current_exe()
+    .ok()
+    .and_then(|path| {
+      path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+    })
+    .unwrap_or_default();
+  format!("{}.desktop", name)
+}
+
+/// The MIME type of the target, `x-scheme-handler/<scheme>` for URL schemes.
+#[cfg(target_os = "linux")]
+fn linux_mime_type(target: &HandlerTarget) -> crate::api::Result<String> {
+  match target {
+    HandlerTarget::Scheme(scheme) => Ok(format!("x-scheme-handler/{}", scheme)),
+    HandlerTarget::Extension(extension) => {
+      let mut directories = vec![dirs_next::data_dir()];
+      directories.extend(
+        std::env::var("XDG_DATA_DIRS")
+          .unwrap_or_else(|_| "/usr/local/share:/usr/share".into())
+          .split(':')
+          .map(|dir| Some(dir.into())),
+      );
+      directories
+        .into_iter()
+        .flatten()
+        .filter_map(|dir| std::fs::read_to_string(dir.join("mime/globs2")).ok())
+        .find_map(|globs| mime_type_from_globs(&globs, extension))
+        .ok_or_else(|| {
+          crate::api::Error::DefaultHandler(format!("unknown file extension `{}`", extension))
+        })
+    }
+  }
+}
+
+/// Finds the MIME type of the extension on a `globs2` file of the shared MIME database,
+/// whose lines have the `<weight>:<MIME type>:<glob>` format and are sorted by weight.
+#[allow(dead_code)]
+fn mime_type_from_globs(globs: &str, extension: &str) -> Option<String> {
+  let glob = format!("*.{}", extension.to_lowercase());
+  globs
+    .lines()
+    .filter(|line| !line.starts_with('#'))
+    .find_map(|line| {
+      let mut parts = line.splitn(4, ':');
+      let _weight = parts.next()?;
+      let mime_type = parts.next()?;
+      if parts.next()? == glob {
+        Some(mime_type.to_string())
+      } else {
+        None
+      }
+    })
+}
+
+/// Declares the `target` variable of the scripts, the URL scheme or the uniform type identifier
+/// of the extension.
+#[cfg(target_os = "macos")]
+fn macos_content_type(target: &HandlerTarget) -> String {
+  match target {
+    HandlerTarget::Scheme(scheme) => format!("var target = {:?};", scheme),
+    HandlerTarget::Extension(extension) => format!(
+      "var target = ObjC.castRefToObject($.UTTypeCreatePreferredIdentifierForTag(
+        $.kUTTagClassFilenameExtension, $({:?}), null)).js;",
+      extension
+    ),
+  }
+}
+
+/// Declares the `$progId` variable of the scripts, the program identifier chosen by the user
+/// or the one registered for the target.
+#[cfg(windows)]
+fn windows_prog_id(target: &HandlerTarget) -> String {
+  let (user_choice, default) = match target {
+    HandlerTarget::Scheme(scheme) => (
+      format!(
+        "HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\Shell\\Associations\\\
+          UrlAssociations\\{}\\UserChoice",
+        scheme
+      ),
+      format!("'{}'", scheme.replace('\'', "''")),
+    ),
+    HandlerTarget::Extension(extension) => (
+      format!(
+        "HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\\
+          FileExts\\.{}\\UserChoice",
+        extension
+      ),
+      format!(
+        "(Get-ItemProperty -LiteralPath 'Registry::HKEY_CLASSES_ROOT\\.{}' \
+          -ErrorAction SilentlyContinue).'(default)'",
+        extension.replace('\'', "''")
+      ),
+    ),
+  };
+  format!(
+    "$progId = (Get-ItemProperty -LiteralPath 'Registry::{}' -ErrorAction SilentlyContinue).ProgId
+    if (-not $progId) {{ $progId = {} }}",
+    user_choice.replace('\'', "''"),
+    default
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn reads_mime_type_from_globs() {
+    let globs = "# This file was automatically generated by the\n\
+      # update-mime-database command. DO NOT EDIT!\n\
+      50:text/markdown:*.md\n\
+      50:text/x-markdown:*.md\n\
+      50:application/pdf:*.pdf\n";
+    assert_eq!(
+      mime_type_from_globs(globs, "md"),
+      Some("text/markdown".into())
+    );
+    assert_eq!(
+      mime_type_from_globs(globs, "PDF"),
+      Some("application/pdf".into())
+    );
+    assert_eq!(mime_type_from_globs(globs, "txt"), None);
+  }
+}
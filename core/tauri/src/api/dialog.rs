@@ -5,10 +5,60 @@
 use std::path::{Path, PathBuf};
 
 use rfd::FileDialog;
-use tinyfiledialogs::{message_box_ok, message_box_yes_no, MessageBoxIcon, YesNo};
+use serde::Deserialize;
+use tinyfiledialogs::{message_box_ok_cancel, message_box_yes_no, MessageBoxIcon, OkCancel, YesNo};
+
+use crate::{Params, Window};
+
+/// Hides the page of the parent window behind an overlay catching the input while a dialog shows.
+const BLOCK_INPUT_SCRIPT: &str = r#"(function () {
+  if (window.__TAURI_DIALOG_OVERLAY__) return;
+  var overlay = document.createElement('div');
+  overlay.style.cssText = 'position:fixed;inset:0;z-index:2147483647;background:rgba(0,0,0,0.15)';
+  overlay.blockKeys = function (e) {
+    e.preventDefault();
+    e.stopImmediatePropagation();
+  };
+  window.addEventListener('keydown', overlay.blockKeys, true);
+  document.documentElement.appendChild(overlay);
+  window.__TAURI_DIALOG_OVERLAY__ = overlay;
+})()"#;
+const UNBLOCK_INPUT_SCRIPT: &str = r#"(function () {
+  var overlay = window.__TAURI_DIALOG_OVERLAY__;
+  if (!overlay) return;
+  window.removeEventListener('keydown', overlay.blockKeys, true);
+  overlay.remove();
+  delete window.__TAURI_DIALOG_OVERLAY__;
+})()"#;
+
+/// Shows a blocking dialog on a separate thread and passes its response to the callback,
+/// so the calling thread, e.g. the event loop or an async task, keeps running.
+fn run_dialog<T, D, F>(dialog: D, callback: F)
+where
+  D: FnOnce() -> T + Send + 'static,
+  F: FnOnce(T) + Send + 'static,
+{
+  std::thread::spawn(move || callback(dialog()));
+}
 
 /// The file dialog builder.
 /// Constructs file picker dialogs that can select single/multiple files or directories.
+///
+/// The dialogs don't block the calling thread: the picked paths are passed to a callback once
+/// the user closes the dialog.
+///
+/// # Example
+/// ```no_run
+/// use tauri::api::dialog::FileDialogBuilder;
+///
+/// FileDialogBuilder::new()
+///   .add_filter("Markdown", &["md"])
+///   .pick_file(|path| {
+///     if let Some(path) = path {
+///       println!("picked {}", path.display());
+///     }
+///   });
+/// ```
 #[derive(Default)]
 pub struct FileDialogBuilder(FileDialog);
 
@@ -30,49 +80,292 @@ impl FileDialogBuilder {
     self
   }
 
-  /// Pick one file.
-  pub fn pick_file(self) -> Option<PathBuf> {
-    self.0.pick_file()
+  /// Set the file name preselected on the dialog, e.g. the suggested name of a save dialog.
+  pub fn set_file_name(mut self, file_name: impl AsRef<str>) -> Self {
+    self.0 = self.0.set_file_name(file_name.as_ref());
+    self
+  }
+
+  /// Set the starting directory of the dialog, or the preselected file if the path isn't
+  /// an existing directory, e.g. `~/Documents/report.pdf`.
+  pub fn set_default_path<P: AsRef<Path>>(self, default_path: P) -> Self {
+    let default_path = default_path.as_ref();
+    if default_path.is_dir() {
+      return self.set_directory(default_path);
+    }
+    match (default_path.parent(), default_path.file_name()) {
+      (Some(parent), Some(file_name)) => {
+        let builder = self.set_file_name(file_name.to_string_lossy());
+        if parent.as_os_str().is_empty() {
+          builder
+        } else {
+          builder.set_directory(parent)
+        }
+      }
+      _ => self.set_directory(default_path),
+    }
+  }
+
+  /// Pick one file. The callback receives `None` if the dialog was cancelled.
+  pub fn pick_file<F: FnOnce(Option<PathBuf>) + Send + 'static>(self, f: F) {
+    let dialog = self.0;
+    run_dialog(move || dialog.pick_file(), f)
+  }
+
+  /// Pick multiple files. The callback receives `None` if the dialog was cancelled.
+  pub fn pick_files<F: FnOnce(Option<Vec<PathBuf>>) + Send + 'static>(self, f: F) {
+    let dialog = self.0;
+    run_dialog(move || dialog.pick_files(), f)
   }
 
-  /// Pick multiple files.
-  pub fn pick_files(self) -> Option<Vec<PathBuf>> {
-    self.0.pick_files()
+  /// Pick one folder. The callback receives `None` if the dialog was cancelled.
+  pub fn pick_folder<F: FnOnce(Option<PathBuf>) + Send + 'static>(self, f: F) {
+    let dialog = self.0;
+    run_dialog(move || dialog.pick_folder(), f)
   }
 
-  /// Pick one folder.
-  pub fn pick_folder(self) -> Option<PathBuf> {
-    self.0.pick_folder()
+  /// Opens save file dialog. The callback receives `None` if the dialog was cancelled.
+  pub fn save_file<F: FnOnce(Option<PathBuf>) + Send + 'static>(self, f: F) {
+    let dialog = self.0;
+    run_dialog(move || dialog.save_file(), f)
   }
+}
+
+/// The button selected when a question dialog opens, answered by pressing enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DefaultButton {
+  /// The "yes" or "ok" button.
+  Accept,
+  /// The "no" or "cancel" button.
+  Cancel,
+}
 
-  /// Opens save file dialog.
-  pub fn save_file(self) -> Option<PathBuf> {
-    self.0.save_file()
+impl Default for DefaultButton {
+  fn default() -> Self {
+    Self::Cancel
   }
 }
 
-/// Response for the ask dialog
-pub enum AskResponse {
-  /// User confirmed.
-  Yes,
-  /// User denied.
-  No,
+/// Blocks (`true`) or unblocks (`false`) the parent window of a dialog.
+type ParentHook = Box<dyn Fn(bool) + Send>;
+
+/// The message dialog builder, showing a message or a question.
+///
+/// Like the file dialogs, they don't block the calling thread: the answer is passed to a callback
+/// once the user closes the dialog. See [`blocking`] for the dialogs waiting for the answer.
+///
+/// # Example
+/// ```no_run
+/// use tauri::api::dialog::{DefaultButton, MessageDialogBuilder};
+///
+/// MessageDialogBuilder::new("Tauri", "Delete the file?")
+///   .default_button(DefaultButton::Cancel)
+///   .ask(|yes| {
+///     if yes {
+///       println!("deleting the file");
+///     }
+///   });
+/// ```
+pub struct MessageDialogBuilder {
+  title: String,
+  message: String,
+  default_button: DefaultButton,
+  parent: Option<ParentHook>,
+}
+
+impl MessageDialogBuilder {
+  /// Creates a dialog with the given title and message.
+  pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      message: message.into(),
+      default_button: Default::default(),
+      parent: None,
+    }
+  }
+
+  /// Makes the dialog modal to the parent window: the window is restored if minimized
+  /// and its page doesn't receive input until the dialog closes.
+  ///
+  /// wry doesn't expose the native window handle yet, so the dialog itself isn't attached to the
+  /// window and the window can still be moved or closed.
+  pub fn parent<M: Params>(mut self, parent: &Window<M>) -> Self {
+    let parent = parent.clone();
+    self.parent = Some(Box::new(move |block| {
+      // the dialog shows even if the page can't be blocked, e.g. while it loads
+      if block {
+        let _ = parent.unminimize();
+        let _ = parent.eval(BLOCK_INPUT_SCRIPT);
+      } else {
+        let _ = parent.eval(UNBLOCK_INPUT_SCRIPT);
+      }
+    }));
+    self
+  }
+
+  /// Sets the button selected when a question dialog opens. Defaults to [`DefaultButton::Cancel`].
+  pub fn default_button(mut self, default_button: DefaultButton) -> Self {
+    self.default_button = default_button;
+    self
+  }
+
+  /// Shows the message with an "ok" button. The callback runs once the dialog is closed.
+  pub fn show<F: FnOnce() + Send + 'static>(self, f: F) {
+    self.run(|title, message| blocking::message(title, message), |_| f())
+  }
+
+  /// Shows the question with a "yes" and a "no" button,
+  /// passing whether the user answered yes to the callback.
+  pub fn ask<F: FnOnce(bool) + Send + 'static>(self, f: F) {
+    let default_button = self.default_button;
+    self.run(
+      move |title, message| {
+        let default = match default_button {
+          DefaultButton::Accept => YesNo::Yes,
+          DefaultButton::Cancel => YesNo::No,
+        };
+        matches!(
+          message_box_yes_no(title, message, MessageBoxIcon::Question, default),
+          YesNo::Yes
+        )
+      },
+      f,
+    )
+  }
+
+  /// Shows the message with an "ok" and a "cancel" button,
+  /// passing whether the user confirmed to the callback.
+  pub fn confirm<F: FnOnce(bool) + Send + 'static>(self, f: F) {
+    let default_button = self.default_button;
+    self.run(
+      move |title, message| {
+        let default = match default_button {
+          DefaultButton::Accept => OkCancel::Ok,
+          DefaultButton::Cancel => OkCancel::Cancel,
+        };
+        matches!(
+          message_box_ok_cancel(title, message, MessageBoxIcon::Question, default),
+          OkCancel::Ok
+        )
+      },
+      f,
+    )
+  }
+
+  fn run<T, D, F>(self, dialog: D, callback: F)
+  where
+    D: FnOnce(&str, &str) -> T + Send + 'static,
+    F: FnOnce(T) + Send + 'static,
+  {
+    let Self {
+      title,
+      message,
+      parent,
+      ..
+    } = self;
+    run_dialog(
+      move || {
+        if let Some(parent) = &parent {
+          parent(true);
+        }
+        let answer = dialog(&title, &message);
+        if let Some(parent) = &parent {
+          parent(false);
+        }
+        answer
+      },
+      callback,
+    )
+  }
 }
 
-/// Displays a dialog with a message and an optional title with a "yes" and a "no" button
-pub fn ask(title: impl AsRef<str>, message: impl AsRef<str>) -> AskResponse {
-  match message_box_yes_no(
-    title.as_ref(),
-    message.as_ref(),
-    MessageBoxIcon::Question,
-    YesNo::No,
-  ) {
-    YesNo::Yes => AskResponse::Yes,
-    YesNo::No => AskResponse::No,
+fn message_dialog<M: Params>(
+  parent_window: Option<&Window<M>>,
+  title: impl Into<String>,
+  message: impl Into<String>,
+) -> MessageDialogBuilder {
+  let builder = MessageDialogBuilder::new(title, message);
+  match parent_window {
+    Some(parent) => builder.parent(parent),
+    None => builder,
   }
 }
 
-/// Displays a message dialog
-pub fn message(title: impl AsRef<str>, message: impl AsRef<str>) {
-  message_box_ok(title.as_ref(), message.as_ref(), MessageBoxIcon::Info);
+/// Shows a question dialog with a "yes" and a "no" button, modal to the parent window if any,
+/// passing whether the user answered yes to the callback.
+///
+/// Use [`MessageDialogBuilder`] to select the default button.
+pub fn ask<M: Params, F: FnOnce(bool) + Send + 'static>(
+  parent_window: Option<&Window<M>>,
+  title: impl Into<String>,
+  message: impl Into<String>,
+  f: F,
+) {
+  message_dialog(parent_window, title, message).ask(f)
+}
+
+/// Shows a dialog with an "ok" and a "cancel" button, modal to the parent window if any,
+/// passing whether the user confirmed to the callback.
+///
+/// Use [`MessageDialogBuilder`] to select the default button.
+pub fn confirm<M: Params, F: FnOnce(bool) + Send + 'static>(
+  parent_window: Option<&Window<M>>,
+  title: impl Into<String>,
+  message: impl Into<String>,
+  f: F,
+) {
+  message_dialog(parent_window, title, message).confirm(f)
+}
+
+/// Shows a message dialog, modal to the parent window if any.
+/// The callback runs once the dialog is closed.
+pub fn message<M: Params, F: FnOnce() + Send + 'static>(
+  parent_window: Option<&Window<M>>,
+  title: impl Into<String>,
+  message: impl Into<String>,
+  f: F,
+) {
+  message_dialog(parent_window, title, message).show(f)
+}
+
+/// The message dialogs blocking the calling thread until the user closes them,
+/// e.g. before the event loop starts.
+pub mod blocking {
+  use tinyfiledialogs::{
+    message_box_ok, message_box_ok_cancel, message_box_yes_no, MessageBoxIcon, OkCancel, YesNo,
+  };
+
+  /// Displays a dialog with a message and a title with a "yes" and a "no" button,
+  /// returning whether the user answered yes.
+  pub fn ask(title: impl AsRef<str>, message: impl AsRef<str>) -> bool {
+    matches!(
+      message_box_yes_no(
+        title.as_ref(),
+        message.as_ref(),
+        MessageBoxIcon::Question,
+        YesNo::No,
+      ),
+      YesNo::Yes
+    )
+  }
+
+  /// Displays a dialog with a message and a title with an "ok" and a "cancel" button,
+  /// returning whether the user confirmed.
+  pub fn confirm(title: impl AsRef<str>, message: impl AsRef<str>) -> bool {
+    matches!(
+      message_box_ok_cancel(
+        title.as_ref(),
+        message.as_ref(),
+        MessageBoxIcon::Question,
+        OkCancel::Cancel,
+      ),
+      OkCancel::Ok
+    )
+  }
+
+  /// Displays a message dialog.
+  pub fn message(title: impl AsRef<str>, message: impl AsRef<str>) {
+    message_box_ok(title.as_ref(), message.as_ref(), MessageBoxIcon::Info);
+  }
 }
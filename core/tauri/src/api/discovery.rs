@@ -0,0 +1,774 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+mod dns;
+
+use std::{
+  collections::{HashMap, VecDeque},
+  net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  thread::{self, JoinHandle},
+  time::{Duration, Instant},
+};
+
+use dns::{Message, Question, Record, RecordData};
+use serde::Serialize;
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::api::private::async_runtime::{channel, Receiver, Sender};
+
+const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+/// The name queried to enumerate every service type on the network (RFC 6763 9).
+const SERVICES_ENUMERATION: &str = "_services._dns-sd._udp.local";
+/// TTL of the advertised records, in seconds.
+const DEFAULT_TTL: u32 = 120;
+/// How often the browser re-sends its query.
+const QUERY_INTERVAL: Duration = Duration::from_secs(10);
+/// How long a socket read blocks before the worker checks if it was stopped.
+const READ_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// A service to advertise on the local network.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::discovery::{advertise, ServiceInfo};
+///
+/// let service = ServiceInfo::new("_tauri._tcp", "My App", 4242).property("version", "1.0.0");
+/// let advertisement = advertise(service).expect("failed to advertise service");
+/// // the service is withdrawn when `advertisement` is dropped
+/// ```
+#[derive(Debug, Clone)]
+pub struct ServiceInfo {
+  service_type: String,
+  instance_name: String,
+  port: u16,
+  host_name: Option<String>,
+  addresses: Vec<IpAddr>,
+  properties: HashMap<String, String>,
+}
+
+impl ServiceInfo {
+  /// Creates the description of a service.
+  ///
+  /// The service type has the `_name._tcp` or `_name._udp` form, e.g. `_tauri._tcp`.
+  /// The instance name is the human readable name of this service instance.
+  pub fn new(service_type: impl Into<String>, instance_name: impl Into<String>, port: u16) -> Self {
+    Self {
+      service_type: service_type.into(),
+      instance_name: instance_name.into(),
+      port,
+      host_name: None,
+      addresses: Vec::new(),
+      properties: HashMap::new(),
+    }
+  }
+
+  /// Sets the host name announced for this service, without the `.local` suffix.
+  /// Defaults to a name derived from the instance name.
+  pub fn host_name(mut self, host_name: impl Into<String>) -> Self {
+    self.host_name.replace(host_name.into());
+    self
+  }
+
+  /// Adds an address to announce for this service.
+  /// Defaults to the primary IPv4 address of the machine if no address is set.
+  pub fn address(mut self, address: IpAddr) -> Self {
+    self.addresses.push(address);
+    self
+  }
+
+  /// Adds a key-value property to the service's TXT record.
+  pub fn property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+    self.properties.insert(key.into(), value.into());
+    self
+  }
+}
+
+/// A service instance discovered on the local network.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Peer {
+  /// The instance name.
+  pub name: String,
+  /// The service type, e.g. `_tauri._tcp`.
+  pub service_type: String,
+  /// The host name, e.g. `my-app.local`.
+  pub host_name: String,
+  /// The port the service listens on.
+  pub port: u16,
+  /// The addresses of the host.
+  pub addresses: Vec<IpAddr>,
+  /// The properties from the service's TXT record.
+  pub properties: HashMap<String, String>,
+}
+
+/// Event emitted while browsing for services.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "payload")]
+pub enum DiscoveryEvent {
+  /// A service instance was found (or its information changed).
+  Found(Peer),
+  /// A service instance left the network or its records expired.
+  Lost(Peer),
+  /// A network error happened. Browsing stops after this event.
+  Error(String),
+}
+
+/// A running service advertisement.
+///
+/// The service is withdrawn from the network when this value is dropped.
+pub struct Advertisement {
+  stop: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl Advertisement {
+  /// Withdraws the service from the network.
+  pub fn stop(self) {}
+}
+
+impl Drop for Advertisement {
+  fn drop(&mut self) {
+    stop_worker(&self.stop, &mut self.handle);
+  }
+}
+
+/// A running service browser.
+///
+/// Browsing stops when this value is dropped.
+pub struct Browser {
+  stop: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl Browser {
+  /// Stops browsing.
+  pub fn stop(self) {}
+}
+
+impl Drop for Browser {
+  fn drop(&mut self) {
+    stop_worker(&self.stop, &mut self.handle);
+  }
+}
+
+fn stop_worker(stop: &AtomicBool, handle: &mut Option<JoinHandle<()>>) {
+  stop.store(true, Ordering::SeqCst);
+  if let Some(handle) = handle.take() {
+    let _ = handle.join();
+  }
+}
+
+/// Advertises a service on the local network using multicast DNS.
+///
+/// The service answers queries until the returned [`Advertisement`] is dropped.
+pub fn advertise(service: ServiceInfo) -> crate::api::Result<Advertisement> {
+  let records = ServiceRecords::new(service)?;
+  let socket = multicast_socket()?;
+  let destination = SocketAddr::from((MDNS_ADDR, MDNS_PORT));
+
+  // announce twice, one second apart (RFC 6762 8.3)
+  let announcement = records.announcement(DEFAULT_TTL).encode();
+  socket.send_to(&announcement, destination)?;
+
+  let stop = Arc::new(AtomicBool::new(false));
+  let stop_ = stop.clone();
+  let handle = thread::spawn(move || {
+    let mut buf = [0u8; 9000];
+    let mut announce_at = Some(Instant::now() + Duration::from_secs(1));
+    while !stop_.load(Ordering::SeqCst) {
+      if announce_at.map(|at| at <= Instant::now()).unwrap_or(false) {
+        announce_at = None;
+        let _ = socket.send_to(&announcement, destination);
+      }
+
+      let (len, source) = match socket.recv_from(&mut buf) {
+        Ok(received) => received,
+        Err(_) => continue,
+      };
+      let query = match Message::decode(&buf[..len]) {
+        Some(message) if !message.response => message,
+        _ => continue,
+      };
+      if let Some(response) = records.respond(&query) {
+        // legacy resolvers (not bound to 5353) and unicast questions expect a direct reply
+        let unicast = source.port() != MDNS_PORT
+          || query
+            .questions
+            .iter()
+            .all(|question| question.unicast_response);
+        let target = if unicast { source } else { destination };
+        let _ = socket.send_to(&response.encode(), target);
+      }
+    }
+
+    // goodbye packet (RFC 6762 10.1)
+    let _ = socket.send_to(&records.announcement(0).encode(), destination);
+  });
+
+  Ok(Advertisement {
+    stop,
+    handle: Some(handle),
+  })
+}
+
+/// Browses the local network for instances of the given service type, e.g. `_tauri._tcp`.
+///
+/// Returns a receiver of [`DiscoveryEvent`]s and the [`Browser`] handle that stops browsing when dropped.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::discovery::{browse, DiscoveryEvent};
+///
+/// let (mut rx, _browser) = browse("_tauri._tcp").expect("failed to browse services");
+/// std::thread::spawn(move || {
+///   while let Some(event) = rx.blocking_recv() {
+///     if let DiscoveryEvent::Found(peer) = event {
+///       println!("found {} at {:?}:{}", peer.name, peer.addresses, peer.port);
+///     }
+///   }
+/// });
+/// ```
+pub fn browse(
+  service_type: impl AsRef<str>,
+) -> crate::api::Result<(Receiver<DiscoveryEvent>, Browser)> {
+  let service_type = service_type_fqdn(service_type.as_ref())?;
+  let socket = multicast_socket()?;
+  let (tx, rx) = channel(32);
+
+  let stop = Arc::new(AtomicBool::new(false));
+  let stop_ = stop.clone();
+  let handle = thread::spawn(move || {
+    let mut state = BrowserState::new(service_type, tx);
+    let mut buf = [0u8; 9000];
+    let mut next_query = Instant::now();
+    while !stop_.load(Ordering::SeqCst) {
+      if next_query <= Instant::now() {
+        next_query = Instant::now() + QUERY_INTERVAL;
+        let query = state.query().encode();
+        if let Err(e) = socket.send_to(&query, (MDNS_ADDR, MDNS_PORT)) {
+          let _ = state.tx.try_send(DiscoveryEvent::Error(e.to_string()));
+          break;
+        }
+      }
+
+      let received = match socket.recv_from(&mut buf) {
+        Ok((len, _)) => Message::decode(&buf[..len]),
+        Err(_) => None,
+      };
+      if let Some(message) = received.filter(|message| message.response) {
+        state.process(message);
+      }
+      if state.flush().is_err() {
+        // the receiver was dropped
+        break;
+      }
+    }
+  });
+
+  Ok((
+    rx,
+    Browser {
+      stop,
+      handle: Some(handle),
+    },
+  ))
+}
+
+/// Binds a socket to the mDNS port, sharing it with other responders on this machine.
+fn multicast_socket() -> crate::api::Result<UdpSocket> {
+  let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+  socket.set_reuse_address(true)?;
+  #[cfg(unix)]
+  socket.set_reuse_port(true)?;
+  socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, MDNS_PORT)).into())?;
+  socket.join_multicast_v4(&MDNS_ADDR, &Ipv4Addr::UNSPECIFIED)?;
+  socket.set_multicast_loop_v4(true)?;
+  socket.set_multicast_ttl_v4(255)?;
+  let socket: UdpSocket = socket.into();
+  socket.set_read_timeout(Some(READ_TIMEOUT))?;
+  Ok(socket)
+}
+
+/// Gets the address of the interface used to reach the network.
+///
+/// Connecting a UDP socket doesn't send anything, it only selects the outgoing interface.
+fn primary_ipv4() -> Option<IpAddr> {
+  let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+  socket.connect((Ipv4Addr::new(10, 254, 254, 254), 1)).ok()?;
+  socket
+    .local_addr()
+    .ok()
+    .map(|addr| addr.ip())
+    .filter(|ip| !ip.is_unspecified())
+}
+
+/// Validates a `_name._tcp` service type and returns its fully qualified name.
+fn service_type_fqdn(service_type: &str) -> crate::api::Result<String> {
+  let service_type = service_type.trim_end_matches('.');
+  let service_type = service_type.strip_suffix(".local").unwrap_or(service_type);
+  let mut labels = service_type.split('.');
+  match (labels.next(), labels.next(), labels.next()) {
+    (Some(name), Some(protocol), None)
+      if name.len() > 1 && name.starts_with('_') && (protocol == "_tcp" || protocol == "_udp") =>
+    {
+      Ok(format!("{}.local", service_type))
+    }
+    _ => Err(crate::api::Error::Discovery(format!(
+      "invalid service type `{}`, expected `_name._tcp` or `_name._udp`",
+      service_type
+    ))),
+  }
+}
+
+/// Removes the `.<service type>` suffix of a fully qualified instance name, ignoring the case.
+fn instance_label<'a>(instance: &'a str, suffix: &str) -> &'a str {
+  let split = instance.len().saturating_sub(suffix.len());
+  match (instance.get(..split), instance.get(split..)) {
+    (Some(label), Some(rest)) if rest.eq_ignore_ascii_case(suffix) => label,
+    _ => instance,
+  }
+}
+
+/// Turns an instance name into a valid host name label.
+fn host_label(name: &str) -> String {
+  let label: String = name
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+    .collect();
+  let label = label.trim_matches('-');
+  if label.is_empty() {
+    "tauri".into()
+  } else {
+    label.to_lowercase()
+  }
+}
+
+/// The DNS records describing an advertised service.
+struct ServiceRecords {
+  service_type: String,
+  instance: String,
+  host: String,
+  port: u16,
+  txt: Vec<String>,
+  addresses: Vec<IpAddr>,
+}
+
+impl ServiceRecords {
+  fn new(service: ServiceInfo) -> crate::api::Result<Self> {
+    let service_type = service_type_fqdn(&service.service_type)?;
+    if service.instance_name.is_empty() || service.instance_name.contains('.') {
+      return Err(crate::api::Error::Discovery(format!(
+        "invalid instance name `{}`, it must be non-empty and cannot contain `.`",
+        service.instance_name
+      )));
+    }
+    let host = format!(
+      "{}.local",
+      host_label(
+        service
+          .host_name
+          .as_deref()
+          .unwrap_or(&service.instance_name)
+      )
+    );
+    let addresses = if service.addresses.is_empty() {
+      primary_ipv4().into_iter().collect()
+    } else {
+      service.addresses
+    };
+    let mut txt: Vec<String> = service
+      .properties
+      .into_iter()
+      .map(|(key, value)| format!("{}={}", key, value))
+      .collect();
+    txt.sort();
+
+    Ok(Self {
+      instance: format!("{}.{}", service.instance_name, service_type),
+      service_type,
+      host,
+      port: service.port,
+      txt,
+      addresses,
+    })
+  }
+
+  fn ptr(&self, ttl: u32) -> Record {
+    Record {
+      name: self.service_type.clone(),
+      ttl,
+      data: RecordData::Ptr(self.instance.clone()),
+    }
+  }
+
+  fn srv(&self, ttl: u32) -> Record {
+    Record {
+      name: self.instance.clone(),
+      ttl,
+      data: RecordData::Srv {
+        priority: 0,
+        weight: 0,
+        port: self.port,
+        target: self.host.clone(),
+      },
+    }
+  }
+
+  fn txt(&self, ttl: u32) -> Record {
+    Record {
+      name: self.instance.clone(),
+      ttl,
+      data: RecordData::Txt(self.txt.clone()),
+    }
+  }
+
+  fn addresses(&self, ttl: u32) -> Vec<Record> {
+    self
+      .addresses
+      .iter()
+      .map(|address| Record {
+        name: self.host.clone(),
+        ttl,
+        data: match address {
+          IpAddr::V4(ip) => RecordData::A(*ip),
+          IpAddr::V6(ip) => RecordData::Aaaa(*ip),
+        },
+      })
+      .collect()
+  }
+
+  /// The unsolicited response announcing (or withdrawing, with a zero TTL) the service.
+  fn announcement(&self, ttl: u32) -> Message {
+    let mut answers = vec![self.ptr(ttl), self.srv(ttl), self.txt(ttl)];
+    answers.extend(self.addresses(ttl));
+    Message {
+      response: true,
+      answers,
+      ..Default::default()
+    }
+  }
+
+  /// Builds the response to a query, if any of its questions is about this service.
+  fn respond(&self, query: &Message) -> Option<Message> {
+    let mut answers = Vec::new();
+    let mut additionals = Vec::new();
+    for Question { name, qtype, .. } in &query.questions {
+      let name = name.to_lowercase();
+      let qtype = *qtype;
+      let any = qtype == dns::TYPE_ANY;
+      if name == SERVICES_ENUMERATION && (any || qtype == dns::TYPE_PTR) {
+        answers.push(Record {
+          name: SERVICES_ENUMERATION.into(),
+          ttl: DEFAULT_TTL,
+          data: RecordData::Ptr(self.service_type.clone()),
+        });
+      } else if name == self.service_type.to_lowercase() && (any || qtype == dns::TYPE_PTR) {
+        answers.push(self.ptr(DEFAULT_TTL));
+        additionals.push(self.srv(DEFAULT_TTL));
+        additionals.push(self.txt(DEFAULT_TTL));
+        additionals.extend(self.addresses(DEFAULT_TTL));
+      } else if name == self.instance.to_lowercase() {
+        if any || qtype == dns::TYPE_SRV {
+          answers.push(self.srv(DEFAULT_TTL));
+          additionals.extend(self.addresses(DEFAULT_TTL));
+        }
+        if any || qtype == dns::TYPE_TXT {
+          answers.push(self.txt(DEFAULT_TTL));
+        }
+      } else if name == self.host && (any || qtype == dns::TYPE_A || qtype == dns::TYPE_AAAA) {
+        answers.extend(self.addresses(DEFAULT_TTL));
+      }
+    }
+
+    if answers.is_empty() {
+      None
+    } else {
+      Some(Message {
+        id: query.id,
+        response: true,
+        questions: Vec::new(),
+        answers,
+        additionals,
+      })
+    }
+  }
+}
+
+/// What the browser knows about a service instance so far.
+#[derive(Default)]
+struct InstanceState {
+  /// The instance name as announced, since lookups are case insensitive.
+  name: String,
+  host: Option<String>,
+  port: Option<u16>,
+  properties: HashMap<String, String>,
+  expires_at: Option<Instant>,
+  /// The peer last sent on the channel, used to detect changes.
+  announced: Option<Peer>,
+}
+
+struct BrowserState {
+  service_type: String,
+  instances: HashMap<String, InstanceState>,
+  /// The addresses of each host, with the instant their record expires.
+  hosts: HashMap<String, Vec<(IpAddr, Instant)>>,
+  /// The events the receiver didn't take yet, since the worker never blocks on a full channel.
+  pending: VecDeque<DiscoveryEvent>,
+  tx: Sender<DiscoveryEvent>,
+}
+
+impl BrowserState {
+  fn new(service_type: String, tx: Sender<DiscoveryEvent>) -> Self {
+    Self {
+      service_type,
+      instances: HashMap::new(),
+      hosts: HashMap::new(),
+      pending: VecDeque::new(),
+      tx,
+    }
+  }
+
+  /// The periodic query: the service PTR plus the addresses of hosts we couldn't resolve yet.
+  fn query(&self) -> Message {
+    let mut query = Message::query(self.service_type.clone(), dns::TYPE_PTR);
+    for host in self.instances.values().filter_map(|i| i.host.as_ref()) {
+      if self.hosts.get(host).map(Vec::is_empty).unwrap_or(true) {
+        query.questions.push(Question {
+          name: host.clone(),
+          qtype: dns::TYPE_A,
+          unicast_response: false,
+        });
+      }
+    }
+    query
+  }
+
+  fn process(&mut self, message: Message) {
+    let service_type = self.service_type.to_lowercase();
+    for record in message.answers.into_iter().chain(message.additionals) {
+      let name = record.name.to_lowercase();
+      let expires_at = Instant::now() + Duration::from_secs(record.ttl as u64);
+      match record.data {
+        RecordData::Ptr(instance) if name == service_type => {
+          if record.ttl == 0 {
+            if let Some(state) = self.instances.get_mut(&instance.to_lowercase()) {
+              state.expires_at.replace(Instant::now());
+            }
+          } else {
+            self
+              .instances
+              .entry(instance.to_lowercase())
+              .or_insert_with(|| InstanceState {
+                name: instance.clone(),
+                ..Default::default()
+              })
+              .expires_at
+              .replace(expires_at);
+          }
+        }
+        RecordData::Srv { port, target, .. } => {
+          if let Some(state) = self.instances.get_mut(&name) {
+            state.port.replace(port);
+            state.host.replace(target.to_lowercase());
+          }
+        }
+        RecordData::Txt(entries) => {
+          if let Some(state) = self.instances.get_mut(&name) {
+            state.properties = entries
+              .into_iter()
+              .map(|entry| {
+                let mut parts = entry.splitn(2, '=');
+                let key = parts.next().unwrap_or_default().to_string();
+                let value = parts.next().unwrap_or_default().to_string();
+                (key, value)
+              })
+              .collect();
+          }
+        }
+        RecordData::A(ip) => self.add_address(name, IpAddr::V4(ip), record.ttl),
+        RecordData::Aaaa(ip) => self.add_address(name, IpAddr::V6(ip), record.ttl),
+        _ => {}
+      }
+    }
+  }
+
+  fn add_address(&mut self, host: String, address: IpAddr, ttl: u32) {
+    let addresses = self.hosts.entry(host).or_default();
+    addresses.retain(|(a, _)| a != &address);
+    if ttl > 0 {
+      addresses.push((address, Instant::now() + Duration::from_secs(ttl as u64)));
+    }
+  }
+
+  /// Sends `Found` events for resolved instances and `Lost` events for expired ones.
+  ///
+  /// Fails if the receiver was dropped.
+  /// The events that don't fit in the channel are sent on the next call.
+  fn flush(&mut self) -> Result<(), ()> {
+    let now = Instant::now();
+    let events = &mut self.pending;
+    let suffix = format!(".{}", self.service_type);
+    let service_type = self
+      .service_type
+      .strip_suffix(".local")
+      .unwrap_or(&self.service_type)
+      .to_string();
+    self.hosts.retain(|_, addresses| {
+      addresses.retain(|(_, expires_at)| *expires_at > now);
+      !addresses.is_empty()
+    });
+    let hosts = &self.hosts;
+
+    self.instances.retain(|_, state| {
+      if state.expires_at.map(|at| at <= now).unwrap_or(false) {
+        if let Some(peer) = state.announced.take() {
+          events.push_back(DiscoveryEvent::Lost(peer));
+        }
+        return false;
+      }
+
+      if let (Some(host), Some(port)) = (&state.host, state.port) {
+        let addresses: Vec<IpAddr> = hosts
+          .get(host)
+          .map(|addresses| addresses.iter().map(|(address, _)| *address).collect())
+          .unwrap_or_default();
+        if !addresses.is_empty() {
+          let peer = Peer {
+            name: instance_label(&state.name, &suffix).to_string(),
+            service_type: service_type.clone(),
+            host_name: host.clone(),
+            port,
+            addresses,
+            properties: state.properties.clone(),
+          };
+          if state.announced.as_ref() != Some(&peer) {
+            state.announced.replace(peer.clone());
+            events.push_back(DiscoveryEvent::Found(peer));
+          }
+        }
+      }
+      true
+    });
+
+    while let Some(event) = self.pending.pop_front() {
+      match self.tx.try_send(event) {
+        Ok(()) => {}
+        Err(TrySendError::Full(event)) => {
+          self.pending.push_front(event);
+          break;
+        }
+        Err(TrySendError::Closed(_)) => return Err(()),
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn service_records() -> ServiceRecords {
+    ServiceRecords::new(
+      ServiceInfo::new("_tauri._tcp", "My App", 4242)
+        .address(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)))
+        .property("version", "1.0.0"),
+    )
+    .expect("failed to create service records")
+  }
+
+  #[test]
+  fn validates_service_type() {
+    assert_eq!(
+      service_type_fqdn("_tauri._tcp").unwrap(),
+      "_tauri._tcp.local"
+    );
+    assert_eq!(
+      service_type_fqdn("_tauri._udp.local.").unwrap(),
+      "_tauri._udp.local"
+    );
+    assert!(service_type_fqdn("tauri._tcp").is_err());
+    assert!(service_type_fqdn("_tauri._http").is_err());
+    assert!(service_type_fqdn("_tauri").is_err());
+  }
+
+  #[test]
+  fn strips_instance_suffix_ignoring_case() {
+    assert_eq!(
+      instance_label("My App._Tauri._TCP.local", "._tauri._tcp.local"),
+      "My App"
+    );
+    assert_eq!(
+      instance_label("My App._http._tcp.local", "._tauri._tcp.local"),
+      "My App._http._tcp.local"
+    );
+  }
+
+  #[test]
+  fn rejects_dotted_instance_names() {
+    assert!(ServiceRecords::new(ServiceInfo::new("_tauri._tcp", "my.app", 4242)).is_err());
+  }
+
+  #[test]
+  fn responds_to_ptr_query() {
+    let records = service_records();
+    let query = Message::query("_tauri._tcp.local", dns::TYPE_PTR);
+    let response = records.respond(&query).expect("missing response");
+    assert!(response.response);
+    assert_eq!(
+      response.answers[0].data,
+      RecordData::Ptr("My App._tauri._tcp.local".into())
+    );
+    assert!(response.additionals.iter().any(|record| record.data
+      == RecordData::Srv {
+        priority: 0,
+        weight: 0,
+        port: 4242,
+        target: "my-app.local".into(),
+      }));
+  }
+
+  #[test]
+  fn ignores_other_services() {
+    let records = service_records();
+    let query = Message::query("_http._tcp.local", dns::TYPE_PTR);
+    assert!(records.respond(&query).is_none());
+  }
+
+  #[test]
+  fn browser_resolves_and_loses_peers() {
+    let (tx, mut rx) = channel(8);
+    let mut state = BrowserState::new("_tauri._tcp.local".into(), tx);
+    let records = service_records();
+
+    state.process(records.announcement(DEFAULT_TTL));
+    state.flush().unwrap();
+    match rx.blocking_recv().expect("missing found event") {
+      DiscoveryEvent::Found(peer) => {
+        assert_eq!(peer.name, "My App");
+        assert_eq!(peer.service_type, "_tauri._tcp");
+        assert_eq!(peer.port, 4242);
+        assert_eq!(
+          peer.addresses,
+          vec![IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20))]
+        );
+        assert_eq!(peer.properties.get("version"), Some(&"1.0.0".to_string()));
+      }
+      _ => panic!("expected a found event"),
+    }
+
+    // the same records again must not emit a duplicated event, so the next one is the goodbye
+    state.process(records.announcement(DEFAULT_TTL));
+    state.flush().unwrap();
+    state.process(records.announcement(0));
+    state.flush().unwrap();
+    assert!(matches!(rx.blocking_recv(), Some(DiscoveryEvent::Lost(_))));
+  }
+}
@@ -0,0 +1,406 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! A minimal DNS message codec covering the records used by DNS-SD over mDNS.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_PTR: u16 = 12;
+pub const TYPE_TXT: u16 = 16;
+pub const TYPE_AAAA: u16 = 28;
+pub const TYPE_SRV: u16 = 33;
+pub const TYPE_ANY: u16 = 255;
+
+const CLASS_IN: u16 = 1;
+/// The top bit of the class on a record marks it as the unique owner of its name (RFC 6762 10.2).
+const CACHE_FLUSH: u16 = 0x8000;
+/// The top bit of the class on a question requests a unicast response (RFC 6762 5.4).
+const UNICAST_RESPONSE: u16 = 0x8000;
+/// Flags of an authoritative response.
+const RESPONSE_FLAGS: u16 = 0x8400;
+/// Maximum number of compression pointers followed while reading a single name.
+const MAX_POINTER_JUMPS: usize = 16;
+
+/// A DNS question.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Question {
+  pub name: String,
+  pub qtype: u16,
+  pub unicast_response: bool,
+}
+
+/// The data of a resource record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordData {
+  A(Ipv4Addr),
+  Aaaa(Ipv6Addr),
+  Ptr(String),
+  Srv {
+    priority: u16,
+    weight: u16,
+    port: u16,
+    target: String,
+  },
+  Txt(Vec<String>),
+  /// A record type we do not need to understand.
+  Other(u16),
+}
+
+impl RecordData {
+  fn record_type(&self) -> u16 {
+    match self {
+      Self::A(_) => TYPE_A,
+      Self::Aaaa(_) => TYPE_AAAA,
+      Self::Ptr(_) => TYPE_PTR,
+      Self::Srv { .. } => TYPE_SRV,
+      Self::Txt(_) => TYPE_TXT,
+      Self::Other(rtype) => *rtype,
+    }
+  }
+}
+
+/// A DNS resource record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+  pub name: String,
+  pub ttl: u32,
+  pub data: RecordData,
+}
+
+/// A DNS message. Authority records are read but discarded since mDNS only uses them for probing.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Message {
+  pub id: u16,
+  pub response: bool,
+  pub questions: Vec<Question>,
+  pub answers: Vec<Record>,
+  pub additionals: Vec<Record>,
+}
+
+impl Message {
+  /// Creates a multicast query message for the given name and record type.
+  pub fn query(name: impl Into<String>, qtype: u16) -> Self {
+    Self {
+      questions: vec![Question {
+        name: name.into(),
+        qtype,
+        unicast_response: false,
+      }],
+      ..Default::default()
+    }
+  }
+
+  /// Serializes the message into its wire format.
+  ///
+  /// Names are written uncompressed, which is always valid and keeps the writer simple.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(512);
+    write_u16(&mut buf, self.id);
+    write_u16(&mut buf, if self.response { RESPONSE_FLAGS } else { 0 });
+    write_u16(&mut buf, self.questions.len() as u16);
+    write_u16(&mut buf, self.answers.len() as u16);
+    write_u16(&mut buf, 0);
+    write_u16(&mut buf, self.additionals.len() as u16);
+
+    for question in &self.questions {
+      write_name(&mut buf, &question.name);
+      write_u16(&mut buf, question.qtype);
+      write_u16(
+        &mut buf,
+        if question.unicast_response {
+          CLASS_IN | UNICAST_RESPONSE
+        } else {
+          CLASS_IN
+        },
+      );
+    }
+
+    for record in self.answers.iter().chain(self.additionals.iter()) {
+      write_record(&mut buf, record, self.response);
+    }
+
+    buf
+  }
+
+  /// Parses a message from its wire format, returning `None` if it is malformed.
+  pub fn decode(buf: &[u8]) -> Option<Self> {
+    let id = read_u16(buf, 0)?;
+    let flags = read_u16(buf, 2)?;
+    let question_count = read_u16(buf, 4)?;
+    let answer_count = read_u16(buf, 6)?;
+    let authority_count = read_u16(buf, 8)?;
+    let additional_count = read_u16(buf, 10)?;
+    let mut pos = 12;
+
+    let mut questions = Vec::new();
+    for _ in 0..question_count {
+      let (name, next) = read_name(buf, pos)?;
+      let qtype = read_u16(buf, next)?;
+      let qclass = read_u16(buf, next + 2)?;
+      questions.push(Question {
+        name,
+        qtype,
+        unicast_response: qclass & UNICAST_RESPONSE != 0,
+      });
+      pos = next + 4;
+    }
+
+    let mut answers = Vec::new();
+    for _ in 0..answer_count {
+      let (record, next) = read_record(buf, pos)?;
+      answers.push(record);
+      pos = next;
+    }
+
+    for _ in 0..authority_count {
+      let (_, next) = read_record(buf, pos)?;
+      pos = next;
+    }
+
+    let mut additionals = Vec::new();
+    for _ in 0..additional_count {
+      let (record, next) = read_record(buf, pos)?;
+      additionals.push(record);
+      pos = next;
+    }
+
+    Some(Self {
+      id,
+      response: flags & 0x8000 != 0,
+      questions,
+      answers,
+      additionals,
+    })
+  }
+}
+
+fn write_u16(buf: &mut Vec<u8>, value: u16) {
+  buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_name(buf: &mut Vec<u8>, name: &str) {
+  for label in name.split('.').filter(|label| !label.is_empty()) {
+    let bytes = label.as_bytes();
+    let len = bytes.len().min(63);
+    buf.push(len as u8);
+    buf.extend_from_slice(&bytes[..len]);
+  }
+  buf.push(0);
+}
+
+fn write_record(buf: &mut Vec<u8>, record: &Record, response: bool) {
+  write_name(buf, &record.name);
+  write_u16(buf, record.data.record_type());
+  // shared records (PTR) must not set the cache-flush bit
+  let unique = !matches!(record.data, RecordData::Ptr(_));
+  write_u16(
+    buf,
+    if response && unique {
+      CLASS_IN | CACHE_FLUSH
+    } else {
+      CLASS_IN
+    },
+  );
+  buf.extend_from_slice(&record.ttl.to_be_bytes());
+
+  let mut rdata = Vec::new();
+  match &record.data {
+    RecordData::A(ip) => rdata.extend_from_slice(&ip.octets()),
+    RecordData::Aaaa(ip) => rdata.extend_from_slice(&ip.octets()),
+    RecordData::Ptr(name) => write_name(&mut rdata, name),
+    RecordData::Srv {
+      priority,
+      weight,
+      port,
+      target,
+    } => {
+      write_u16(&mut rdata, *priority);
+      write_u16(&mut rdata, *weight);
+      write_u16(&mut rdata, *port);
+      write_name(&mut rdata, target);
+    }
+    RecordData::Txt(entries) => {
+      if entries.is_empty() {
+        // a TXT record must contain at least one (empty) string
+        rdata.push(0);
+      }
+      for entry in entries {
+        let bytes = entry.as_bytes();
+        let len = bytes.len().min(255);
+        rdata.push(len as u8);
+        rdata.extend_from_slice(&bytes[..len]);
+      }
+    }
+    RecordData::Other(_) => {}
+  }
+  write_u16(buf, rdata.len() as u16);
+  buf.extend_from_slice(&rdata);
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Option<u16> {
+  let bytes = buf.get(pos..pos + 2)?;
+  Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+  let bytes = buf.get(pos..pos + 4)?;
+  Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Reads a (possibly compressed) name, returning it with the position right after it.
+fn read_name(buf: &[u8], mut pos: usize) -> Option<(String, usize)> {
+  let mut labels = Vec::new();
+  let mut end = None;
+  let mut jumps = 0;
+  loop {
+    let len = *buf.get(pos)? as usize;
+    if len & 0xC0 == 0xC0 {
+      let offset = ((len & 0x3F) << 8) | *buf.get(pos + 1)? as usize;
+      if end.is_none() {
+        end = Some(pos + 2);
+      }
+      jumps += 1;
+      if jumps > MAX_POINTER_JUMPS {
+        return None;
+      }
+      pos = offset;
+    } else if len == 0 {
+      pos += 1;
+      break;
+    } else {
+      let label = buf.get(pos + 1..pos + 1 + len)?;
+      labels.push(String::from_utf8_lossy(label).into_owned());
+      pos += 1 + len;
+    }
+  }
+  Some((labels.join("."), end.unwrap_or(pos)))
+}
+
+fn read_record(buf: &[u8], pos: usize) -> Option<(Record, usize)> {
+  let (name, next) = read_name(buf, pos)?;
+  let rtype = read_u16(buf, next)?;
+  let ttl = read_u32(buf, next + 4)?;
+  let len = read_u16(buf, next + 8)? as usize;
+  let start = next + 10;
+  let rdata = buf.get(start..start + len)?;
+
+  let data = match rtype {
+    TYPE_A if len == 4 => RecordData::A(Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3])),
+    TYPE_AAAA if len == 16 => {
+      let mut octets = [0u8; 16];
+      octets.copy_from_slice(rdata);
+      RecordData::Aaaa(Ipv6Addr::from(octets))
+    }
+    TYPE_PTR => RecordData::Ptr(read_name(buf, start)?.0),
+    TYPE_SRV => RecordData::Srv {
+      priority: read_u16(buf, start)?,
+      weight: read_u16(buf, start + 2)?,
+      port: read_u16(buf, start + 4)?,
+      target: read_name(buf, start + 6)?.0,
+    },
+    TYPE_TXT => {
+      let mut entries = Vec::new();
+      let mut cursor = 0;
+      while cursor < rdata.len() {
+        let entry_len = rdata[cursor] as usize;
+        let entry = rdata.get(cursor + 1..cursor + 1 + entry_len)?;
+        if !entry.is_empty() {
+          entries.push(String::from_utf8_lossy(entry).into_owned());
+        }
+        cursor += 1 + entry_len;
+      }
+      RecordData::Txt(entries)
+    }
+    other => RecordData::Other(other),
+  };
+
+  Some((Record { name, ttl, data }, start + len))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn message_roundtrip() {
+    let message = Message {
+      id: 0,
+      response: true,
+      questions: vec![],
+      answers: vec![Record {
+        name: "_tauri._tcp.local".into(),
+        ttl: 120,
+        data: RecordData::Ptr("my app._tauri._tcp.local".into()),
+      }],
+      additionals: vec![
+        Record {
+          name: "my app._tauri._tcp.local".into(),
+          ttl: 120,
+          data: RecordData::Srv {
+            priority: 0,
+            weight: 0,
+            port: 4242,
+            target: "my-app.local".into(),
+          },
+        },
+        Record {
+          name: "my app._tauri._tcp.local".into(),
+          ttl: 120,
+          data: RecordData::Txt(vec!["version=1.0.0".into()]),
+        },
+        Record {
+          name: "my-app.local".into(),
+          ttl: 120,
+          data: RecordData::A(Ipv4Addr::new(192, 168, 1, 20)),
+        },
+      ],
+    };
+
+    assert_eq!(Message::decode(&message.encode()), Some(message));
+  }
+
+  #[test]
+  fn query_sets_unicast_bit() {
+    let mut query = Message::query("_tauri._tcp.local", TYPE_PTR);
+    query.questions[0].unicast_response = true;
+    let decoded = Message::decode(&query.encode()).expect("failed to decode query");
+    assert!(!decoded.response);
+    assert!(decoded.questions[0].unicast_response);
+    assert_eq!(decoded.questions[0].name, "_tauri._tcp.local");
+  }
+
+  #[test]
+  fn reads_compressed_names() {
+    // header with a single PTR answer whose target points back to the question name
+    let mut buf = vec![0, 0, 0x84, 0, 0, 1, 0, 1, 0, 0, 0, 0];
+    write_name(&mut buf, "_tauri._tcp.local");
+    write_u16(&mut buf, TYPE_PTR);
+    write_u16(&mut buf, CLASS_IN);
+    // answer name: pointer to offset 12
+    buf.extend_from_slice(&[0xC0, 12]);
+    write_u16(&mut buf, TYPE_PTR);
+    write_u16(&mut buf, CLASS_IN);
+    buf.extend_from_slice(&120u32.to_be_bytes());
+    // rdata: "peer" label followed by a pointer to offset 12
+    write_u16(&mut buf, 7);
+    buf.push(4);
+    buf.extend_from_slice(b"peer");
+    buf.extend_from_slice(&[0xC0, 12]);
+
+    let message = Message::decode(&buf).expect("failed to decode message");
+    assert_eq!(message.answers[0].name, "_tauri._tcp.local");
+    assert_eq!(
+      message.answers[0].data,
+      RecordData::Ptr("peer._tauri._tcp.local".into())
+    );
+  }
+
+  #[test]
+  fn rejects_pointer_loops() {
+    let mut buf = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    buf.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1]);
+    assert_eq!(Message::decode(&buf), None);
+  }
+}
@@ -68,4 +68,45 @@ pub enum Error {
   /// Shell error.
   #[error("shell error: {0}")]
   Shell(String),
+  /// The response body is larger than the client allows.
+  #[error("response body larger than {0} bytes")]
+  ResponseTooLarge(u64),
+  /// The download was cancelled.
+  #[error("download cancelled")]
+  DownloadCancelled,
+  /// Clipboard error.
+  #[error("clipboard error: {0}")]
+  Clipboard(String),
+  /// Geolocation error.
+  #[error("geolocation error: {0}")]
+  Geolocation(String),
+  /// Printing error.
+  #[error("print error: {0}")]
+  Print(String),
+  /// Default handler error.
+  #[error("default handler error: {0}")]
+  DefaultHandler(String),
+  /// Sharing error.
+  #[error("share error: {0}")]
+  Share(String),
+  /// Bluetooth error.
+  #[cfg(feature = "bluetooth")]
+  #[error("bluetooth error: {0}")]
+  Bluetooth(String),
+  /// License error.
+  #[cfg(feature = "license")]
+  #[error("license error: {0}")]
+  License(String),
+  /// Serial port error.
+  #[cfg(feature = "serial")]
+  #[error("serial port error: {0}")]
+  Serial(String),
+  /// Embedded HTTP server error.
+  #[cfg(feature = "server")]
+  #[error("server error: {0}")]
+  Server(String),
+  /// Service discovery error.
+  #[cfg(feature = "discovery")]
+  #[error("discovery error: {0}")]
+  Discovery(String),
 }
@@ -5,11 +5,41 @@
 mod extract;
 mod file_move;
 
-use std::{fs, path::Path};
+use serde::Serialize;
+use std::{
+  fs,
+  io::{ErrorKind, Read},
+  path::{Path, PathBuf},
+  time::{SystemTime, UNIX_EPOCH},
+};
 
 pub use extract::*;
 pub use file_move::*;
 
+/// The metadata of a file or directory, returned by [`metadata`].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+  /// The size in bytes.
+  pub size: u64,
+  /// The last modification time, in milliseconds since the Unix epoch.
+  pub modified_at: Option<u64>,
+  /// The last access time, in milliseconds since the Unix epoch.
+  pub accessed_at: Option<u64>,
+  /// The creation time, in milliseconds since the Unix epoch. Not available on every file system.
+  pub created_at: Option<u64>,
+  /// Whether the path is a directory.
+  pub is_dir: bool,
+  /// Whether the path is a regular file.
+  pub is_file: bool,
+  /// Whether the path is a symbolic link. The other fields describe the link target.
+  pub is_symlink: bool,
+  /// Whether the file is read-only.
+  pub readonly: bool,
+  /// The Unix permission bits, e.g. `0o644`. Always `None` on Windows.
+  pub mode: Option<u32>,
+}
+
 /// Reads a string file.
 pub fn read_string<P: AsRef<Path>>(file: P) -> crate::api::Result<String> {
   fs::read_to_string(file).map_err(Into::into)
@@ -20,6 +50,100 @@ pub fn read_binary<P: AsRef<Path>>(file: P) -> crate::api::Result<Vec<u8>> {
   fs::read(file).map_err(Into::into)
 }
 
+/// Reads a binary file in chunks of `chunk_size` bytes, the last one being shorter,
+/// so the file is never fully loaded in memory. Returns the number of bytes read.
+pub fn read_chunks<P: AsRef<Path>, F: FnMut(&[u8])>(
+  file: P,
+  chunk_size: usize,
+  mut on_chunk: F,
+) -> crate::api::Result<u64> {
+  let mut file = fs::File::open(file)?;
+  let mut buffer = vec![0; chunk_size.max(1)];
+  let mut read = 0;
+  loop {
+    let mut filled = 0;
+    while filled < buffer.len() {
+      match file.read(&mut buffer[filled..]) {
+        Ok(0) => break,
+        Ok(n) => filled += n,
+        Err(e) if e.kind() == ErrorKind::Interrupted => {}
+        Err(e) => return Err(e.into()),
+      }
+    }
+    if filled > 0 {
+      on_chunk(&buffer[..filled]);
+      read += filled as u64;
+    }
+    if filled < buffer.len() {
+      return Ok(read);
+    }
+  }
+}
+
+/// Reads the metadata of a file or directory, following symbolic links.
+pub fn metadata<P: AsRef<Path>>(path: P) -> crate::api::Result<Metadata> {
+  let path = path.as_ref();
+  let is_symlink = fs::symlink_metadata(path)?.file_type().is_symlink();
+  let metadata = fs::metadata(path)?;
+
+  #[cfg(unix)]
+  let mode = {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+  };
+  #[cfg(not(unix))]
+  let mode = None;
+
+  Ok(Metadata {
+    size: metadata.len(),
+    modified_at: metadata.modified().ok().and_then(epoch_millis),
+    accessed_at: metadata.accessed().ok().and_then(epoch_millis),
+    created_at: metadata.created().ok().and_then(epoch_millis),
+    is_dir: metadata.is_dir(),
+    is_file: metadata.is_file(),
+    is_symlink,
+    readonly: metadata.permissions().readonly(),
+    mode,
+  })
+}
+
+fn epoch_millis(time: SystemTime) -> Option<u64> {
+  time
+    .duration_since(UNIX_EPOCH)
+    .ok()
+    .map(|duration| duration.as_millis() as u64)
+}
+
+/// Creates a symbolic link at `link` pointing to `target`.
+///
+/// # Platform-specific
+///
+/// - **Windows:** the link is a directory link if the target is a directory.
+///   Creating links requires the developer mode or administrator privileges.
+pub fn create_symlink<P: AsRef<Path>, Q: AsRef<Path>>(
+  target: P,
+  link: Q,
+) -> crate::api::Result<()> {
+  #[cfg(unix)]
+  std::os::unix::fs::symlink(target, link)?;
+
+  #[cfg(windows)]
+  {
+    if target.as_ref().is_dir() {
+      std::os::windows::fs::symlink_dir(target, link)?;
+    } else {
+      std::os::windows::fs::symlink_file(target, link)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Reads the target of a symbolic link.
+pub fn read_link<P: AsRef<Path>>(link: P) -> crate::api::Result<PathBuf> {
+  fs::read_link(link).map_err(Into::into)
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -78,6 +202,47 @@ mod test {
     }
   }
 
+  #[test]
+  fn check_read_chunks() {
+    let mut chunks = Vec::new();
+    let read = read_chunks("test/api/test.txt", 8, |chunk| chunks.push(chunk.to_vec()))
+      .expect("failed to read chunks");
+
+    assert_eq!(read, 19);
+    assert_eq!(
+      chunks,
+      vec![b"This is ".to_vec(), b"a test d".to_vec(), b"oc!".to_vec()]
+    );
+  }
+
+  // creating links on Windows requires the developer mode
+  #[cfg(unix)]
+  #[test]
+  fn check_metadata_and_symlink() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let target = dir.path().join("target.txt");
+    fs::write(&target, "tauri").expect("failed to write file");
+    let link = dir.path().join("link.txt");
+    create_symlink(&target, &link).expect("failed to create symlink");
+
+    let file_metadata = metadata(&target).expect("failed to read metadata");
+    assert_eq!(file_metadata.size, 5);
+    assert!(file_metadata.is_file);
+    assert!(!file_metadata.is_symlink);
+    assert!(file_metadata.modified_at.is_some());
+
+    let link_metadata = metadata(&link).expect("failed to read link metadata");
+    assert!(link_metadata.is_symlink);
+    assert_eq!(link_metadata.size, 5);
+    assert_eq!(read_link(&link).expect("failed to read link"), target);
+
+    assert!(
+      metadata(dir.path())
+        .expect("failed to read dir metadata")
+        .is_dir
+    );
+  }
+
   #[test]
   fn check_read_binary_fail() {
     let file = String::from("test/api/");
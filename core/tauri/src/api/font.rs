@@ -0,0 +1,204 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+use std::path::PathBuf;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// A font installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontFace {
+  /// The font family name, e.g. `DejaVu Sans`.
+  pub family: String,
+  /// The style of the face in its family, e.g. `Bold Oblique`, if available.
+  pub style: Option<String>,
+  /// The font file, if available.
+  pub path: Option<PathBuf>,
+}
+
+/// Lists the font families installed on the system, sorted by name.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: read with `fc-list`, so only the fonts known to Fontconfig are listed.
+pub fn list() -> Vec<String> {
+  #[cfg(windows)]
+  let mut families = system_settings::powershell(
+    "Add-Type -AssemblyName System.Drawing; \
+      (New-Object System.Drawing.Text.InstalledFontCollection).Families | ForEach-Object { $_.Name }",
+  )
+  .unwrap_or_default()
+  .lines()
+  .map(ToString::to_string)
+  .collect::<Vec<_>>();
+  #[cfg(not(windows))]
+  let mut families = faces()
+    .into_iter()
+    .map(|face| face.family)
+    .collect::<Vec<_>>();
+  families.sort();
+  families.dedup();
+  families
+}
+
+/// Lists the font faces installed on the system, with their style and file.
+///
+/// Reading the faces is slower than [`list`], so prefer it when only the family names are needed.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: read with `fc-list`, so only the fonts known to Fontconfig are listed.
+/// - **macOS**: read with `system_profiler`, which can take a few seconds.
+/// - **Windows**: read from the registry, which doesn't split the family and the style,
+///   so the family is the full face name and the style is `None`.
+pub fn faces() -> Vec<FontFace> {
+  #[cfg(target_os = "linux")]
+  return system_settings::command_output(
+    "fc-list",
+    &["--format", "%{family[0]}\\t%{style[0]}\\t%{file}\\n"],
+  )
+  .map(|output| parse_fc_list(&output))
+  .unwrap_or_default();
+  #[cfg(target_os = "macos")]
+  return system_settings::command_output("system_profiler", &["SPFontsDataType", "-json"])
+    .and_then(|output| serde_json::from_str::<serde_json::Value>(&output).ok())
+    .map(|profile| parse_macos_fonts(&profile))
+    .unwrap_or_default();
+  #[cfg(windows)]
+  {
+    let fonts_dir = std::env::var_os("WINDIR")
+      .map(|dir| PathBuf::from(dir).join("Fonts"))
+      .unwrap_or_default();
+    return [
+      r"HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Fonts",
+      // fonts installed for the current user only
+      r"HKCU\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Fonts",
+    ]
+    .iter()
+    .filter_map(|key| system_settings::command_output("reg", &["query", key]))
+    .flat_map(|output| parse_registry_fonts(&output, &fonts_dir))
+    .collect();
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Vec::new()
+}
+
+/// Parses the `fc-list --format "%{family[0]}\t%{style[0]}\t%{file}\n"` output.
+#[allow(dead_code)]
+fn parse_fc_list(output: &str) -> Vec<FontFace> {
+  output
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.splitn(3, '\t');
+      let family = parts.next().filter(|family| !family.is_empty())?;
+      let style = parts.next().filter(|style| !style.is_empty());
+      let path = parts.next().filter(|path| !path.is_empty());
+      Some(FontFace {
+        family: family.to_string(),
+        style: style.map(ToString::to_string),
+        path: path.map(PathBuf::from),
+      })
+    })
+    .collect()
+}
+
+/// Reads the typefaces of the `system_profiler SPFontsDataType -json` output.
+#[allow(dead_code)]
+fn parse_macos_fonts(profile: &serde_json::Value) -> Vec<FontFace> {
+  profile["SPFontsDataType"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .flat_map(|file| {
+      let path = file["path"].as_str().map(PathBuf::from);
+      file["typefaces"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(move |typeface| {
+          Some(FontFace {
+            family: typeface["family"].as_str()?.to_string(),
+            style: typeface["style"].as_str().map(ToString::to_string),
+            path: path.clone(),
+          })
+        })
+    })
+    .collect()
+}
+
+/// Parses the `reg query` output of a fonts key, resolving the file names in the fonts directory.
+#[allow(dead_code)]
+fn parse_registry_fonts(output: &str, fonts_dir: &std::path::Path) -> Vec<FontFace> {
+  // `    Arial Bold (TrueType)    REG_SZ    arialbd.ttf`
+  output
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.trim().splitn(2, "    REG_SZ    ");
+      let name = parts.next()?;
+      let file = parts.next()?.trim();
+      let family = match name.rfind(" (") {
+        Some(index) if name.ends_with(')') => &name[..index],
+        _ => name,
+      };
+      Some(FontFace {
+        family: family.to_string(),
+        style: None,
+        path: Some(fonts_dir.join(file)),
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_fonts() {
+    assert_eq!(
+      parse_fc_list(
+        "DejaVu Sans\tBold\t/usr/share/fonts/DejaVuSans-Bold.ttf\nNoto Sans\t\t\n\tRegular\t/x.ttf"
+      ),
+      vec![
+        FontFace {
+          family: "DejaVu Sans".into(),
+          style: Some("Bold".into()),
+          path: Some(PathBuf::from("/usr/share/fonts/DejaVuSans-Bold.ttf")),
+        },
+        FontFace {
+          family: "Noto Sans".into(),
+          style: None,
+          path: None,
+        }
+      ]
+    );
+    let profile = serde_json::json!({
+      "SPFontsDataType": [{
+        "path": "/Library/Fonts/Arial.ttf",
+        "typefaces": [{ "family": "Arial", "style": "Regular" }]
+      }]
+    });
+    assert_eq!(
+      parse_macos_fonts(&profile),
+      vec![FontFace {
+        family: "Arial".into(),
+        style: Some("Regular".into()),
+        path: Some(PathBuf::from("/Library/Fonts/Arial.ttf")),
+      }]
+    );
+    let registry = "\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Fonts\n    Arial Bold (TrueType)    REG_SZ    arialbd.ttf\n";
+    assert_eq!(
+      parse_registry_fonts(registry, std::path::Path::new("Fonts")),
+      vec![FontFace {
+        family: "Arial Bold".into(),
+        style: None,
+        path: Some(PathBuf::from("Fonts").join("arialbd.ttf")),
+      }]
+    );
+  }
+}
@@ -0,0 +1,167 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// How long the location service is given to find the position, in seconds.
+#[allow(dead_code)]
+const TIMEOUT_SECS: u32 = 30;
+
+/// A geographic position.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Coordinates {
+  /// The latitude, in decimal degrees.
+  pub latitude: f64,
+  /// The longitude, in decimal degrees.
+  pub longitude: f64,
+  /// The accuracy of the latitude and longitude, in meters.
+  pub accuracy: f64,
+  /// The altitude above the sea level, in meters, if available.
+  pub altitude: Option<f64>,
+}
+
+/// Gets the current position of the device from the location service of the system.
+///
+/// Blocks until the position is found, which can take a few seconds.
+/// The system may ask the user to allow the app to read the location, or fail if it's disabled.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: read from GeoClue with its `where-am-i` demo agent, so GeoClue must be installed.
+/// - **macOS**: read from CoreLocation through JavaScript for Automation.
+/// - **Windows**: read from the Windows location service through `System.Device.Location`.
+pub fn current_position() -> crate::api::Result<Coordinates> {
+  #[cfg(target_os = "linux")]
+  {
+    let timeout = TIMEOUT_SECS.to_string();
+    return [
+      "/usr/libexec/geoclue-2.0/demos/where-am-i",
+      "/usr/lib/geoclue-2.0/demos/where-am-i",
+    ]
+    .iter()
+    .find_map(|program| system_settings::command_output(program, &["-t", &timeout]))
+    .and_then(|output| parse_where_am_i(&output))
+    .ok_or_else(|| crate::api::Error::Geolocation("GeoClue couldn't find the position".into()));
+  }
+  #[cfg(target_os = "macos")]
+  {
+    let script = format!(
+      "ObjC.import('CoreLocation');
+      var manager = $.CLLocationManager.alloc.init;
+      manager.startUpdatingLocation;
+      for (var i = 0; i < {} * 10 && manager.location.isNil(); i++) delay(0.1);
+      var location = manager.location;
+      location.isNil() ? '' : [location.coordinate.latitude, location.coordinate.longitude,
+        location.horizontalAccuracy, location.verticalAccuracy >= 0 ? location.altitude : ''].join('\\t')",
+      TIMEOUT_SECS
+    );
+    return system_settings::command_output("osascript", &["-l", "JavaScript", "-e", &script])
+      .and_then(|output| parse_tab_separated(&output))
+      .ok_or_else(|| {
+        crate::api::Error::Geolocation("CoreLocation couldn't find the position".into())
+      });
+  }
+  #[cfg(windows)]
+  return system_settings::powershell(&format!(
+    "Add-Type -AssemblyName System.Device; \
+      $watcher = New-Object System.Device.Location.GeoCoordinateWatcher; \
+      $null = $watcher.TryStart($false, [TimeSpan]::FromSeconds({})); \
+      $location = $watcher.Position.Location; \
+      if (-not $location.IsUnknown) {{ \
+        $altitude = if ([double]::IsNaN($location.Altitude)) {{ '' }} else {{ $location.Altitude }}; \
+        \"$($location.Latitude)`t$($location.Longitude)`t$($location.HorizontalAccuracy)`t$altitude\" \
+      }}",
+    TIMEOUT_SECS
+  ))
+  .and_then(|output| parse_tab_separated(&output))
+  .ok_or_else(|| {
+    crate::api::Error::Geolocation(
+      "the location service is disabled or couldn't find the position".into(),
+    )
+  });
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Err(crate::api::Error::Geolocation(
+    "geolocation isn't supported on this platform".into(),
+  ))
+}
+
+/// Parses the first location of the GeoClue `where-am-i` output.
+#[allow(dead_code)]
+fn parse_where_am_i(output: &str) -> Option<Coordinates> {
+  // `Latitude:    48.858400°`, `Accuracy:    100.000000 meters`
+  let value = |name: &str| {
+    output
+      .lines()
+      .find_map(|line| line.trim().strip_prefix(name))
+      .and_then(|value| {
+        value
+          .trim()
+          .trim_end_matches('°')
+          .split_whitespace()
+          .next()?
+          .parse::<f64>()
+          .ok()
+      })
+  };
+  Some(Coordinates {
+    latitude: value("Latitude:")?,
+    longitude: value("Longitude:")?,
+    accuracy: value("Accuracy:")?,
+    altitude: value("Altitude:"),
+  })
+}
+
+/// Parses the `latitude\tlongitude\taccuracy\taltitude` line, where the altitude may be empty.
+#[allow(dead_code)]
+fn parse_tab_separated(output: &str) -> Option<Coordinates> {
+  let mut values = output.trim().split('\t');
+  let mut next = || {
+    values
+      .next()
+      .and_then(|value| value.trim().parse::<f64>().ok())
+  };
+  Some(Coordinates {
+    latitude: next()?,
+    longitude: next()?,
+    accuracy: next()?,
+    altitude: next(),
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_positions() {
+    let output = "Client object: /org/freedesktop/GeoClue2/Client/1\n\nNew location:\nLatitude:    48.858400°\nLongitude:   2.294500°\nAccuracy:    100.000000 meters\nAltitude:    35.000000 meters\nDescription: GeoIP";
+    assert_eq!(
+      parse_where_am_i(output),
+      Some(Coordinates {
+        latitude: 48.8584,
+        longitude: 2.2945,
+        accuracy: 100.,
+        altitude: Some(35.),
+      })
+    );
+    assert_eq!(
+      parse_where_am_i("Client object: /org/freedesktop/GeoClue2/Client/1"),
+      None
+    );
+    assert_eq!(
+      parse_tab_separated("-33.8568\t151.2153\t65\t\n"),
+      Some(Coordinates {
+        latitude: -33.8568,
+        longitude: 151.2153,
+        accuracy: 65.,
+        altitude: None,
+      })
+    );
+    assert_eq!(parse_tab_separated(""), None);
+  }
+}
@@ -3,12 +3,19 @@
 // SPDX-License-Identifier: MIT
 
 use bytes::Bytes;
+use futures::Stream;
 use reqwest::{header::HeaderName, redirect::Policy, Method};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use std::{collections::HashMap, path::PathBuf, time::Duration};
+use std::{
+  collections::HashMap,
+  fs::OpenOptions,
+  io::Write,
+  path::{Path, PathBuf},
+  time::Duration,
+};
 
 /// Client builder.
 #[derive(Default, Deserialize)]
@@ -16,8 +23,19 @@ use std::{collections::HashMap, path::PathBuf, time::Duration};
 pub struct ClientBuilder {
   /// Max number of redirections to follow
   pub max_redirections: Option<usize>,
+  /// Whether redirections are followed. Defaults to `true`.
+  /// When `false`, the redirection response is returned.
+  pub follow_redirects: Option<bool>,
   /// Connect timeout in seconds for the request
   pub connect_timeout: Option<u64>,
+  /// Timeout in seconds for the whole request, from connecting to reading the body.
+  pub timeout: Option<u64>,
+  /// The proxy URL all requests are sent through, e.g. `http://proxy.local:3128`.
+  pub proxy: Option<String>,
+  /// Max number of idle connections kept open for each host.
+  pub pool_max_idle_per_host: Option<usize>,
+  /// Max size in bytes of the response bodies.
+  pub max_response_size: Option<u64>,
 }
 
 impl ClientBuilder {
@@ -32,17 +50,51 @@ impl ClientBuilder {
     self
   }
 
+  /// Sets whether redirections are followed.
+  pub fn follow_redirects(mut self, follow_redirects: bool) -> Self {
+    self.follow_redirects = Some(follow_redirects);
+    self
+  }
+
   /// Sets the connection timeout.
   pub fn connect_timeout(mut self, connect_timeout: u64) -> Self {
     self.connect_timeout = Some(connect_timeout);
     self
   }
 
+  /// Sets the request timeout.
+  pub fn timeout(mut self, timeout: u64) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Sets the proxy URL.
+  pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+    self.proxy = Some(proxy.into());
+    self
+  }
+
+  /// Sets the maximum number of idle connections kept open for each host.
+  pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+    self.pool_max_idle_per_host = Some(max_idle);
+    self
+  }
+
+  /// Sets the maximum size of the response bodies.
+  pub fn max_response_size(mut self, max_response_size: u64) -> Self {
+    self.max_response_size = Some(max_response_size);
+    self
+  }
+
   /// Builds the ClientOptions.
+  ///
+  /// The client keeps its connections open, so reuse it for requests to the same hosts.
   pub fn build(self) -> crate::api::Result<Client> {
     let mut client_builder = reqwest::Client::builder();
 
-    if let Some(max_redirections) = self.max_redirections {
+    if self.follow_redirects == Some(false) {
+      client_builder = client_builder.redirect(Policy::none())
+    } else if let Some(max_redirections) = self.max_redirections {
       client_builder = client_builder.redirect(Policy::limited(max_redirections))
     }
 
@@ -50,14 +102,32 @@ impl ClientBuilder {
       client_builder = client_builder.connect_timeout(Duration::from_secs(connect_timeout));
     }
 
+    if let Some(timeout) = self.timeout {
+      client_builder = client_builder.timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(proxy) = self.proxy {
+      client_builder = client_builder.proxy(reqwest::Proxy::all(&proxy)?);
+    }
+
+    if let Some(max_idle) = self.pool_max_idle_per_host {
+      client_builder = client_builder.pool_max_idle_per_host(max_idle);
+    }
+
     let client = client_builder.build()?;
-    Ok(Client(client))
+    Ok(Client {
+      inner: client,
+      max_response_size: self.max_response_size,
+    })
   }
 }
 
 /// The HTTP client.
 #[derive(Clone)]
-pub struct Client(reqwest::Client);
+pub struct Client {
+  inner: reqwest::Client,
+  max_response_size: Option<u64>,
+}
 
 impl Client {
   /// Executes an HTTP request
@@ -66,7 +136,7 @@ impl Client {
   /// If reading the response as binary, the byte array will be serialized using serde_json
   pub async fn send(&self, request: HttpRequestBuilder) -> crate::api::Result<Response> {
     let method = Method::from_bytes(request.method.to_uppercase().as_bytes())?;
-    let mut request_builder = self.0.request(method, &request.url);
+    let mut request_builder = self.inner.request(method, &request.url);
 
     if let Some(query) = request.query {
       request_builder = request_builder.query(&query);
@@ -105,11 +175,82 @@ impl Client {
     };
 
     let response = response.error_for_status()?;
+    if let (Some(max), Some(length)) = (self.max_response_size, response.content_length()) {
+      if length > max {
+        return Err(crate::api::Error::ResponseTooLarge(max));
+      }
+    }
     Ok(Response(
       request.response_type.unwrap_or(ResponseType::Json),
       response,
+      self.max_response_size,
     ))
   }
+
+  /// Downloads the URL to the file, calling `on_progress` with the number of bytes written so far
+  /// and the file size, if known.
+  ///
+  /// If the file already exists, the download resumes from its current size with a range request.
+  /// The file is written again from the start if the server doesn't support range requests.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// use tauri::api::http::ClientBuilder;
+  ///
+  /// async fn download() -> tauri::api::Result<()> {
+  ///   let client = ClientBuilder::new().build()?;
+  ///   client
+  ///     .download("https://example.com/installer.msi", "installer.msi", |received, total| {
+  ///       println!("{}/{:?}", received, total);
+  ///     })
+  ///     .await
+  /// }
+  /// ```
+  pub async fn download<F: FnMut(u64, Option<u64>)>(
+    &self,
+    url: &str,
+    path: impl AsRef<Path>,
+    mut on_progress: F,
+  ) -> crate::api::Result<()> {
+    let path = path.as_ref();
+    let offset = path.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+    let mut request = self.inner.get(url);
+    if offset > 0 {
+      request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let response = request.send().await?;
+
+    // the range starts at the end of the file: it's already complete
+    if offset > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+      on_progress(offset, Some(offset));
+      return Ok(());
+    }
+    let mut response = response.error_for_status()?;
+
+    let resumed = offset > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = if resumed {
+      OpenOptions::new().append(true).open(path)?
+    } else {
+      OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?
+    };
+    let mut received = if resumed { offset } else { 0 };
+    let total = response.content_length().map(|length| length + received);
+
+    on_progress(received, total);
+    while let Some(chunk) = response.chunk().await? {
+      file.write_all(&chunk)?;
+      received += chunk.len() as u64;
+      check_size(received, self.max_response_size)?;
+      on_progress(received, total);
+    }
+    file.flush()?;
+    Ok(())
+  }
 }
 
 #[derive(Serialize_repr, Deserialize_repr, Clone, Debug)]
@@ -122,6 +263,9 @@ pub enum ResponseType {
   Text,
   /// Read the response as binary
   Binary,
+  /// Read the response in chunks, see [`Response::bytes_stream`].
+  /// The chunks are emitted to the window with the `tauri://download-progress` event.
+  Stream,
 }
 
 /// FormBody data types.
@@ -246,11 +390,83 @@ impl HttpRequestBuilder {
 }
 
 /// The HTTP response.
-pub struct Response(ResponseType, reqwest::Response);
+pub struct Response(ResponseType, reqwest::Response, Option<u64>);
 
 impl Response {
+  /// The type the response was requested with.
+  pub fn response_type(&self) -> &ResponseType {
+    &self.0
+  }
+
+  /// The size of the body, if the server sent it.
+  pub fn content_length(&self) -> Option<u64> {
+    self.1.content_length()
+  }
+
+  /// Reads the body as it's received, without buffering it.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// use futures::StreamExt;
+  /// use tauri::api::http::{ClientBuilder, HttpRequestBuilder, ResponseType};
+  ///
+  /// async fn download() -> tauri::api::Result<()> {
+  ///   let client = ClientBuilder::new().build()?;
+  ///   let request = HttpRequestBuilder::new("GET", "https://example.com/large.zip")
+  ///     .response_type(ResponseType::Stream);
+  ///   let mut stream = Box::pin(client.send(request).await?.bytes_stream());
+  ///   while let Some(chunk) = stream.next().await {
+  ///     println!("received {} bytes", chunk?.len());
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn bytes_stream(self) -> impl Stream<Item = crate::api::Result<Bytes>> {
+    let max_size = self.2;
+    futures::stream::try_unfold((self.1, 0), move |(mut response, received)| async move {
+      match response.chunk().await? {
+        Some(chunk) => {
+          let received = received + chunk.len() as u64;
+          check_size(received, max_size)?;
+          Ok(Some((chunk, (response, received))))
+        }
+        None => Ok::<_, crate::api::Error>(None),
+      }
+    })
+  }
+
+  /// Reads the response info, calling `on_chunk` with each chunk of the body as it's received,
+  /// along with the number of bytes received so far and the body size, if known.
+  /// The body isn't kept, so the data of the returned [`ResponseData`] is `null`.
+  pub async fn read_chunks<F: FnMut(Bytes, u64, Option<u64>)>(
+    mut self,
+    mut on_chunk: F,
+  ) -> crate::api::Result<ResponseData> {
+    let url = self.1.url().to_string();
+    let mut headers = HashMap::new();
+    for (name, value) in self.1.headers() {
+      headers.insert(name.as_str().to_string(), value.to_str()?.to_string());
+    }
+    let status = self.1.status().as_u16();
+    let total = self.1.content_length();
+
+    let mut received = 0;
+    while let Some(chunk) = self.1.chunk().await? {
+      received += chunk.len() as u64;
+      check_size(received, self.2)?;
+      on_chunk(chunk, received, total);
+    }
+
+    Ok(ResponseData {
+      url,
+      status,
+      headers,
+      data: Value::Null,
+    })
+  }
+
   /// Reads the response and returns its info.
-  pub async fn read(self) -> crate::api::Result<ResponseData> {
+  pub async fn read(mut self) -> crate::api::Result<ResponseData> {
     let url = self.1.url().to_string();
     let mut headers = HashMap::new();
     for (name, value) in self.1.headers() {
@@ -258,10 +474,16 @@ impl Response {
     }
     let status = self.1.status().as_u16();
 
+    let mut body = Vec::new();
+    while let Some(chunk) = self.1.chunk().await? {
+      body.extend_from_slice(&chunk);
+      check_size(body.len() as u64, self.2)?;
+    }
+
     let data = match self.0 {
-      ResponseType::Json => self.1.json().await?,
-      ResponseType::Text => Value::String(self.1.text().await?),
-      ResponseType::Binary => Value::String(serde_json::to_string(&self.1.bytes().await?)?),
+      ResponseType::Json => serde_json::from_slice(&body)?,
+      ResponseType::Text => Value::String(String::from_utf8_lossy(&body).into_owned()),
+      ResponseType::Binary | ResponseType::Stream => Value::String(serde_json::to_string(&body)?),
     };
 
     Ok(ResponseData {
@@ -273,6 +495,14 @@ impl Response {
   }
 }
 
+/// Fails if the received body is larger than the client's `max_response_size`.
+fn check_size(received: u64, max_size: Option<u64>) -> crate::api::Result<()> {
+  match max_size {
+    Some(max) if received > max => Err(crate::api::Error::ResponseTooLarge(max)),
+    _ => Ok(()),
+  }
+}
+
 /// The response type.
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,123 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::system_settings::{self, Watcher};
+
+/// Whether the user is using the computer, as reported by [`watch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdleState {
+  /// The keyboard or the mouse was used before the idle threshold.
+  Active,
+  /// The keyboard and the mouse weren't used for the idle threshold.
+  Idle,
+}
+
+/// The time since the user last used the keyboard or the mouse, if it can be read.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: read from the GNOME idle monitor, or with `xprintidle` on other X11 sessions.
+/// - **Windows**: read with PowerShell, so it takes a few hundred milliseconds.
+pub fn idle_time() -> Option<Duration> {
+  #[cfg(target_os = "linux")]
+  return system_settings::command_output(
+    "gdbus",
+    &[
+      "call",
+      "--session",
+      "--dest",
+      "org.gnome.Mutter.IdleMonitor",
+      "--object-path",
+      "/org/gnome/Mutter/IdleMonitor/Core",
+      "--method",
+      "org.gnome.Mutter.IdleMonitor.GetIdletime",
+    ],
+  )
+  .and_then(|output| parse_gdbus_uint64(&output))
+  .or_else(|| {
+    system_settings::command_output("xprintidle", &[]).and_then(|millis| millis.parse().ok())
+  })
+  .map(Duration::from_millis);
+  #[cfg(target_os = "macos")]
+  return system_settings::command_output("ioreg", &["-c", "IOHIDSystem", "-d", "4"])
+    .and_then(|output| parse_ioreg_idle_time(&output))
+    .map(Duration::from_nanos);
+  #[cfg(windows)]
+  {
+    // `GetLastInputInfo` reports the tick count of the last input, which wraps around every 49 days
+    let script = "Add-Type 'using System.Runtime.InteropServices; \
+      public struct LastInput { public uint size; public uint time; } \
+      public static class Input { [DllImport(\"user32.dll\")] \
+      public static extern bool GetLastInputInfo(ref LastInput info); }'; \
+      $info = New-Object LastInput; $info.size = 8; \
+      [void][Input]::GetLastInputInfo([ref]$info); \
+      $now = [long][Environment]::TickCount -band 4294967295; \
+      ($now - $info.time + 4294967296) % 4294967296";
+    return system_settings::powershell(script)
+      .and_then(|millis| millis.parse().ok())
+      .map(Duration::from_millis);
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  None
+}
+
+/// Whether the user has been idle for at least the threshold.
+///
+/// The user is considered active when the idle time can't be read.
+pub fn state(threshold: Duration) -> IdleState {
+  match idle_time() {
+    Some(idle_time) if idle_time >= threshold => IdleState::Idle,
+    _ => IdleState::Active,
+  }
+}
+
+/// Polls the idle time on the given interval.
+///
+/// The current state is sent right away, then `Idle` is sent once the user has been idle
+/// for the threshold, and `Active` when they use the computer again.
+/// Polling stops when the watcher is dropped.
+pub fn watch(threshold: Duration, interval: Duration) -> Watcher<IdleState> {
+  system_settings::watch(interval, move || state(threshold))
+}
+
+/// Parses the `(uint64 1234,)` reply of `gdbus call`.
+#[allow(dead_code)]
+fn parse_gdbus_uint64(output: &str) -> Option<u64> {
+  output
+    .trim_matches(|c| c == '(' || c == ')' || c == ',')
+    .trim_start_matches("uint64")
+    .trim()
+    .parse()
+    .ok()
+}
+
+/// Parses the `"HIDIdleTime" = 1234` line of `ioreg`, in nanoseconds.
+#[allow(dead_code)]
+fn parse_ioreg_idle_time(output: &str) -> Option<u64> {
+  output
+    .lines()
+    .find(|line| line.contains("\"HIDIdleTime\""))
+    .and_then(|line| line.split('=').nth(1))
+    .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_idle_times() {
+    assert_eq!(parse_gdbus_uint64("(uint64 15230,)"), Some(15230));
+    assert_eq!(parse_gdbus_uint64("Error: no such name"), None);
+    assert_eq!(
+      parse_ioreg_idle_time("  |   \"HIDIdleTime\" = 2045918333\n  |   \"HIDParameters\" = {}"),
+      Some(2045918333)
+    );
+  }
+}
@@ -0,0 +1,234 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use keyring::{Keyring, KeyringError};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+/// The keyring entry of the activated license key.
+const LICENSE_ENTRY: &str = "license";
+/// The keyring entry of the trial start time.
+const TRIAL_ENTRY: &str = "trial";
+
+fn license_error(message: impl std::fmt::Display) -> crate::api::Error {
+  crate::api::Error::License(message.to_string())
+}
+
+/// A license, signed by the app vendor.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct License {
+  /// The license identifier.
+  pub id: String,
+  /// The name of the license owner.
+  pub licensee: String,
+  /// When the license expires, in seconds since the Unix epoch.
+  #[serde(default)]
+  pub expires_at: Option<u64>,
+  /// The app features the license unlocks.
+  #[serde(default)]
+  pub features: Vec<String>,
+}
+
+/// The result of a license [`check`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum LicenseStatus {
+  /// A valid license is activated.
+  Licensed {
+    /// The activated license.
+    license: License,
+  },
+  /// The activated license expired.
+  Expired {
+    /// The activated license.
+    license: License,
+  },
+  /// No license is activated, and the trial is running.
+  #[serde(rename_all = "camelCase")]
+  Trial {
+    /// When the trial ends, in seconds since the Unix epoch.
+    expires_at: u64,
+  },
+  /// No license is activated, and the trial ended.
+  #[serde(rename_all = "camelCase")]
+  TrialExpired {
+    /// When the trial ended, in seconds since the Unix epoch.
+    expires_at: u64,
+  },
+  /// No license is activated, and there's no trial.
+  Unlicensed,
+}
+
+/// Verifies a license key and returns its license.
+///
+/// The license key is the base64 encoded license JSON, a dot, and the signature of the JSON
+/// created with `tauri signer sign`, so it can be pasted by the user.
+/// The public key is the one generated by `tauri signer generate`.
+/// The expiration isn't checked, see [`check`].
+///
+/// # Example
+/// ```sh
+/// tauri signer sign -k ~/.tauri/license.key license.json
+/// echo "$(base64 -w0 license.json).$(cat license.json.sig)"
+/// ```
+pub fn verify(key: &str, public_key: &str) -> crate::api::Result<License> {
+  let mut parts = key.trim().splitn(2, '.');
+  let license = base64::decode(parts.next().unwrap_or_default()).map_err(license_error)?;
+  let signature = parts
+    .next()
+    .ok_or_else(|| license_error("missing signature"))
+    .and_then(|signature| base64::decode(signature).map_err(license_error))?;
+  let public_key = base64::decode(public_key).map_err(license_error)?;
+
+  let public_key =
+    PublicKey::decode(&String::from_utf8_lossy(&public_key)).map_err(license_error)?;
+  let signature = Signature::decode(&String::from_utf8_lossy(&signature)).map_err(license_error)?;
+  public_key
+    .verify(&license, &signature)
+    .map_err(|_| license_error("invalid signature"))?;
+
+  serde_json::from_slice(&license).map_err(Into::into)
+}
+
+/// Verifies a license key and stores it in the keyring of the system, so [`check`] finds it.
+///
+/// The `identifier` is the app bundle identifier, naming the keyring entries.
+pub fn activate(identifier: &str, key: &str, public_key: &str) -> crate::api::Result<License> {
+  let license = verify(key, public_key)?;
+  Keyring::new(identifier, LICENSE_ENTRY)
+    .set_password(key.trim())
+    .map_err(license_error)?;
+  Ok(license)
+}
+
+/// Removes the activated license key from the keyring.
+pub fn deactivate(identifier: &str) -> crate::api::Result<()> {
+  match Keyring::new(identifier, LICENSE_ENTRY).delete_password() {
+    Ok(()) | Err(KeyringError::NoPasswordFound) => Ok(()),
+    Err(e) => Err(license_error(e)),
+  }
+}
+
+/// Checks the activated license, or the trial if no valid license is activated.
+///
+/// The trial starts on the first check and is stored in the keyring, so reinstalling the app
+/// doesn't restart it. A license key that fails to verify, e.g. because it was edited, is ignored.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: the keyring is the Secret Service, e.g. GNOME Keyring or KWallet.
+/// - **macOS**: the keyring is the Keychain.
+/// - **Windows**: the keyring is the Credential Manager.
+pub fn check(
+  identifier: &str,
+  public_key: &str,
+  trial: Option<Duration>,
+) -> crate::api::Result<LicenseStatus> {
+  let now = unix_time(SystemTime::now());
+  let license = match Keyring::new(identifier, LICENSE_ENTRY).get_password() {
+    Ok(key) => verify(&key, public_key).ok(),
+    Err(KeyringError::NoPasswordFound) => None,
+    Err(e) => return Err(license_error(e)),
+  };
+  let trial_start = match (&license, trial) {
+    (None, Some(_)) => Some(trial_start(identifier, now)?),
+    _ => None,
+  };
+  Ok(status(license, trial_start, trial, now))
+}
+
+/// Reads the trial start time of the keyring, starting the trial now if it's missing.
+fn trial_start(identifier: &str, now: u64) -> crate::api::Result<u64> {
+  let keyring = Keyring::new(identifier, TRIAL_ENTRY);
+  match keyring.get_password() {
+    Ok(start) => start
+      .parse()
+      .map_err(|_| license_error("invalid trial start time")),
+    Err(KeyringError::NoPasswordFound) => {
+      keyring
+        .set_password(&now.to_string())
+        .map_err(license_error)?;
+      Ok(now)
+    }
+    Err(e) => Err(license_error(e)),
+  }
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+  time
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default()
+}
+
+/// Computes the license status at the `now` Unix time.
+fn status(
+  license: Option<License>,
+  trial_start: Option<u64>,
+  trial: Option<Duration>,
+  now: u64,
+) -> LicenseStatus {
+  match (license, trial_start.zip(trial)) {
+    (Some(license), _) => match license.expires_at {
+      Some(expires_at) if expires_at <= now => LicenseStatus::Expired { license },
+      _ => LicenseStatus::Licensed { license },
+    },
+    (None, Some((start, trial))) => {
+      let expires_at = start + trial.as_secs();
+      if expires_at <= now {
+        LicenseStatus::TrialExpired { expires_at }
+      } else {
+        LicenseStatus::Trial { expires_at }
+      }
+    }
+    (None, None) => LicenseStatus::Unlicensed,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const PUBLIC_KEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDhBMDREMjU4MDRCNzE5M0EKUldRNkdiY0VXTklFaW9JTEk2Z09iRDNtUHBHc0hxTUlqQkFtVTJ4cE1HRE9ma29wN0plSE1tWXIK";
+  const SIGNATURE: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIHRhdXJpIHNlY3JldCBrZXkKUldRNkdiY0VXTklFaW5pRW40bzVuSEtaL0xjRUcxTlJhZFFIK0JQUFUyeWRsVXJzS3NNcjV6ajl3NEh3djVKWUtaV2dDVlRma3dXN3JJbHpwTmNYQ1UvQmNRTDNzQm9veXdzPQp0cnVzdGVkIGNvbW1lbnQ6IHRpbWVzdGFtcDoxNjIyNTA1NjAwCWZpbGU6bGljZW5zZS5qc29uCjNlU01UMVZiQmRudCtOZ0FKeGhXN3IrY1dxUTRRRHpxYUlBY2JrSnhLcWh6MFFHOXJOV0VrUGthR2RveFhBZFlyR3RnMENRVG5TSDB3Yldsa3lHekFBPT0K";
+
+  #[test]
+  fn verifies_licenses() {
+    let license_json =
+      r#"{"id":"1f0c2b7e","licensee":"Jane Doe","expiresAt":4102444800,"features":["pro"]}"#;
+    let key = format!("{}.{}", base64::encode(license_json), SIGNATURE);
+    let license = verify(&key, PUBLIC_KEY).expect("failed to verify license");
+    assert_eq!(
+      license,
+      License {
+        id: "1f0c2b7e".into(),
+        licensee: "Jane Doe".into(),
+        expires_at: Some(4102444800),
+        features: vec!["pro".into()],
+      }
+    );
+
+    let tampered_json =
+      r#"{"id":"1f0c2b7e","licensee":"Jane Doe","expiresAt":null,"features":["pro"]}"#;
+    let tampered = format!("{}.{}", base64::encode(tampered_json), SIGNATURE);
+    assert!(verify(&tampered, PUBLIC_KEY).is_err());
+    assert!(verify(&base64::encode(license_json), PUBLIC_KEY).is_err());
+
+    assert_eq!(
+      status(Some(license.clone()), None, None, 4102444800),
+      LicenseStatus::Expired { license }
+    );
+    let trial = Some(Duration::from_secs(30 * 24 * 60 * 60));
+    assert_eq!(
+      status(None, Some(1000), trial, 2000),
+      LicenseStatus::Trial {
+        expires_at: 1000 + 30 * 24 * 60 * 60
+      }
+    );
+    assert_eq!(status(None, None, None, 2000), LicenseStatus::Unlicensed);
+  }
+}
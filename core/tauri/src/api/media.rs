@@ -0,0 +1,267 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// The kind of a media capture device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaDeviceKind {
+  /// A camera.
+  VideoInput,
+  /// A microphone.
+  AudioInput,
+}
+
+/// A camera or a microphone that can be used with `getUserMedia`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaDevice {
+  /// The device identifier of the system, e.g. `/dev/video0` or a PulseAudio source name.
+  pub id: String,
+  /// Whether the device is a camera or a microphone.
+  pub kind: MediaDeviceKind,
+  /// The device name, which the webviews only expose after the user allows the capture.
+  pub label: String,
+  /// Whether it's the default device of its kind.
+  pub is_default: bool,
+}
+
+/// Lists the cameras and the microphones connected to the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: the cameras are read from Video4Linux
+///   and the microphones from PulseAudio with `pactl`.
+///   Only the microphones have a default device.
+/// - **macOS**: read with `system_profiler`, which can take a few seconds.
+/// - **Windows**: read from the Plug and Play devices. Only the first camera is marked as default.
+pub fn devices() -> Vec<MediaDevice> {
+  let mut devices = cameras();
+  devices.extend(microphones());
+  devices
+}
+
+fn cameras() -> Vec<MediaDevice> {
+  #[cfg(target_os = "linux")]
+  {
+    let mut cameras = std::fs::read_dir("/sys/class/video4linux")
+      .map(|entries| {
+        entries
+          .filter_map(|entry| {
+            let entry = entry.ok()?;
+            // each camera also exposes metadata nodes, which can't capture
+            let index = std::fs::read_to_string(entry.path().join("index")).ok()?;
+            if index.trim() != "0" {
+              return None;
+            }
+            let label = std::fs::read_to_string(entry.path().join("name")).ok()?;
+            Some(MediaDevice {
+              id: format!("/dev/{}", entry.file_name().to_string_lossy()),
+              kind: MediaDeviceKind::VideoInput,
+              label: label.trim().to_string(),
+              is_default: false,
+            })
+          })
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+    cameras.sort_by(|a, b| a.id.cmp(&b.id));
+    return cameras;
+  }
+  #[cfg(target_os = "macos")]
+  return system_settings::command_output("system_profiler", &["SPCameraDataType", "-json"])
+    .and_then(|output| serde_json::from_str::<serde_json::Value>(&output).ok())
+    .map(|profile| parse_macos_cameras(&profile))
+    .unwrap_or_default();
+  #[cfg(windows)]
+  return system_settings::powershell(
+    "Get-CimInstance Win32_PnPEntity -Filter \"PNPClass='Camera' OR PNPClass='Image'\" | \
+      ForEach-Object { \"$($_.DeviceID)`t$($_.Name)\" }",
+  )
+  .map(|output| parse_windows_devices(&output, MediaDeviceKind::VideoInput))
+  .unwrap_or_default()
+  .into_iter()
+  .enumerate()
+  .map(|(i, device)| MediaDevice {
+    is_default: i == 0,
+    ..device
+  })
+  .collect();
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Vec::new()
+}
+
+fn microphones() -> Vec<MediaDevice> {
+  #[cfg(target_os = "linux")]
+  {
+    let default = system_settings::command_output("pactl", &["info"])
+      .and_then(|output| parse_pactl_default_source(&output));
+    return system_settings::command_output("pactl", &["list", "short", "sources"])
+      .map(|output| parse_pactl_sources(&output, default.as_deref()))
+      .unwrap_or_default();
+  }
+  #[cfg(target_os = "macos")]
+  return system_settings::command_output("system_profiler", &["SPAudioDataType", "-json"])
+    .and_then(|output| serde_json::from_str::<serde_json::Value>(&output).ok())
+    .map(|profile| parse_macos_microphones(&profile))
+    .unwrap_or_default();
+  #[cfg(windows)]
+  return system_settings::powershell(
+    "Get-CimInstance Win32_PnPEntity -Filter \"PNPClass='AudioEndpoint'\" | \
+      ForEach-Object { \"$($_.DeviceID)`t$($_.Name)\" }",
+  )
+  .map(|output| parse_windows_devices(&output, MediaDeviceKind::AudioInput))
+  .unwrap_or_default();
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Vec::new()
+}
+
+/// Reads the default source name of the `pactl info` output.
+#[allow(dead_code)]
+fn parse_pactl_default_source(output: &str) -> Option<String> {
+  output
+    .lines()
+    .find_map(|line| line.strip_prefix("Default Source: "))
+    .map(|name| name.trim().to_string())
+}
+
+/// Parses the `pactl list short sources` output, skipping the monitors of the outputs.
+#[allow(dead_code)]
+fn parse_pactl_sources(output: &str, default: Option<&str>) -> Vec<MediaDevice> {
+  // `1\talsa_input.usb-Blue_Yeti-00.analog-stereo\tmodule-alsa-card.c\ts16le 2ch 48000Hz\tSUSPENDED`
+  output
+    .lines()
+    .filter_map(|line| line.split('\t').nth(1))
+    .filter(|name| !name.ends_with(".monitor"))
+    .map(|name| MediaDevice {
+      id: name.to_string(),
+      kind: MediaDeviceKind::AudioInput,
+      label: name.to_string(),
+      is_default: default == Some(name),
+    })
+    .collect()
+}
+
+/// Reads the cameras of the `system_profiler SPCameraDataType -json` output.
+#[allow(dead_code)]
+fn parse_macos_cameras(profile: &serde_json::Value) -> Vec<MediaDevice> {
+  profile["SPCameraDataType"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .enumerate()
+    .filter_map(|(i, camera)| {
+      let label = camera["_name"].as_str()?;
+      Some(MediaDevice {
+        id: camera["spcamera_unique-id"]
+          .as_str()
+          .unwrap_or(label)
+          .to_string(),
+        kind: MediaDeviceKind::VideoInput,
+        label: label.to_string(),
+        // the built-in camera is listed first
+        is_default: i == 0,
+      })
+    })
+    .collect()
+}
+
+/// Reads the input devices of the `system_profiler SPAudioDataType -json` output.
+#[allow(dead_code)]
+fn parse_macos_microphones(profile: &serde_json::Value) -> Vec<MediaDevice> {
+  profile["SPAudioDataType"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .flat_map(|group| group["_items"].as_array().into_iter().flatten())
+    .filter(|device| device["coreaudio_device_input"].is_number())
+    .filter_map(|device| {
+      let label = device["_name"].as_str()?;
+      Some(MediaDevice {
+        id: label.to_string(),
+        kind: MediaDeviceKind::AudioInput,
+        label: label.to_string(),
+        is_default: device["coreaudio_default_audio_input_device"] == "spaudio_yes",
+      })
+    })
+    .collect()
+}
+
+/// Parses the `DeviceID\tName` lines of the Plug and Play devices.
+///
+/// The audio endpoints include the outputs,
+/// so only the capture endpoints are kept for [`MediaDeviceKind::AudioInput`].
+#[allow(dead_code)]
+fn parse_windows_devices(output: &str, kind: MediaDeviceKind) -> Vec<MediaDevice> {
+  output
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.splitn(2, '\t');
+      let id = parts.next()?;
+      let label = parts.next()?;
+      // `SWD\MMDEVAPI\{0.0.1.00000000}.{...}`, where `0.0.1` are the capture endpoints
+      if kind == MediaDeviceKind::AudioInput && !id.contains(r"\{0.0.1.") {
+        return None;
+      }
+      Some(MediaDevice {
+        id: id.to_string(),
+        kind,
+        label: label.to_string(),
+        is_default: false,
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_devices() {
+    let info = "Server Name: pulseaudio\nDefault Sink: alsa_output.pci-0000_00_1f.3.analog-stereo\nDefault Source: alsa_input.usb-Blue_Yeti-00.analog-stereo";
+    let default = parse_pactl_default_source(info);
+    let sources = "0\talsa_output.pci-0000_00_1f.3.analog-stereo.monitor\tmodule-alsa-card.c\ts16le 2ch 44100Hz\tSUSPENDED\n1\talsa_input.usb-Blue_Yeti-00.analog-stereo\tmodule-alsa-card.c\ts16le 2ch 48000Hz\tRUNNING";
+    assert_eq!(
+      parse_pactl_sources(sources, default.as_deref()),
+      vec![MediaDevice {
+        id: "alsa_input.usb-Blue_Yeti-00.analog-stereo".into(),
+        kind: MediaDeviceKind::AudioInput,
+        label: "alsa_input.usb-Blue_Yeti-00.analog-stereo".into(),
+        is_default: true,
+      }]
+    );
+
+    let profile = serde_json::json!({
+      "SPAudioDataType": [{ "_items": [
+        { "_name": "MacBook Pro Microphone", "coreaudio_device_input": 1, "coreaudio_default_audio_input_device": "spaudio_yes" },
+        { "_name": "MacBook Pro Speakers", "coreaudio_device_output": 2 }
+      ]}]
+    });
+    assert_eq!(
+      parse_macos_microphones(&profile),
+      vec![MediaDevice {
+        id: "MacBook Pro Microphone".into(),
+        kind: MediaDeviceKind::AudioInput,
+        label: "MacBook Pro Microphone".into(),
+        is_default: true,
+      }]
+    );
+
+    let endpoints = "SWD\\MMDEVAPI\\{0.0.0.00000000}.{A1}\tSpeakers (Realtek Audio)\nSWD\\MMDEVAPI\\{0.0.1.00000000}.{B2}\tMicrophone (Realtek Audio)";
+    assert_eq!(
+      parse_windows_devices(endpoints, MediaDeviceKind::AudioInput),
+      vec![MediaDevice {
+        id: "SWD\\MMDEVAPI\\{0.0.1.00000000}.{B2}".into(),
+        kind: MediaDeviceKind::AudioInput,
+        label: "Microphone (Realtek Audio)".into(),
+        is_default: false,
+      }]
+    );
+  }
+}
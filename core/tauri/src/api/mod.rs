@@ -6,10 +6,18 @@
 #![warn(missing_docs)]
 // #![feature(const_int_pow)]
 
+/// The accessibility API module allows you to read the system accessibility settings.
+pub mod accessibility;
 /// The App API module allows you to manage application processes.
 pub mod app;
+/// The appearance API module allows you to read the desktop appearance preferences.
+pub mod appearance;
+/// The capture API module lists the screens and windows that can be shared.
+pub mod capture;
 /// The Command API module allows you to manage child processes.
 pub mod command;
+/// The default handler API module allows you to make the app open URL schemes and file types.
+pub mod default_handler;
 /// The Dialog API module allows you to show messages and prompt for file paths.
 pub mod dialog;
 /// The Dir module is a helper for file system directory management.
@@ -18,17 +26,30 @@ pub mod dir;
 pub mod file;
 /// The HTTP request API.
 pub mod http;
+/// The idle API module allows you to know when the user stops using the computer.
+pub mod idle;
+/// The media API module lists the cameras and microphones that can be used with `getUserMedia`.
+pub mod media;
 /// The file system path operations API.
 pub mod path;
+/// The process API module allows you to relaunch the app.
+pub mod process;
 /// The RPC module includes utilities to send messages to the JS layer of the webview.
 pub mod rpc;
 /// The shell api.
 pub mod shell;
+/// The sound API module allows you to play the desktop sounds and audio files natively.
+pub mod sound;
 /// TCP ports access API.
 pub mod tcp;
 /// The semver API.
 pub mod version;
 
+pub(crate) mod system_settings;
+
+/// The receiver of the system settings watchers, e.g. [`accessibility::watch`].
+pub use system_settings::Watcher;
+
 /// The Tauri config definition.
 pub use tauri_utils::config;
 
@@ -43,10 +64,54 @@ pub use clap;
 #[cfg(global_shortcut_all)]
 pub mod shortcuts;
 
+/// The clipboard API module.
+#[cfg(any(clipboard_write_text, clipboard_read_text))]
+pub mod clipboard;
+
 /// The desktop notifications API module.
 #[cfg(notification_all)]
 pub mod notification;
 
+/// The font API module allows you to list the fonts installed on the system.
+#[cfg(font_all)]
+pub mod font;
+
+/// The geolocation API module allows you to read the position of the device.
+#[cfg(geolocation_all)]
+pub mod geolocation;
+
+/// The printer API module allows you to list the printers and print files silently.
+#[cfg(printer_all)]
+pub mod printer;
+
+/// The share API module allows you to show the share sheet of the system.
+#[cfg(share_all)]
+pub mod share;
+
+/// The system API module allows you to read the CPU and memory usage of the system and processes.
+#[cfg(system_all)]
+pub mod system;
+
+/// The localhost-only embedded HTTP server API.
+#[cfg(feature = "server")]
+pub mod server;
+
+/// The local network service discovery API (mDNS / DNS-SD).
+#[cfg(feature = "discovery")]
+pub mod discovery;
+
+/// The serial port API, to communicate with hardware such as microcontroller boards.
+#[cfg(feature = "serial")]
+pub mod serial;
+
+/// The Bluetooth LE API, to scan for devices and use their GATT characteristics.
+#[cfg(feature = "bluetooth")]
+pub mod bluetooth;
+
+/// The license API, to verify signed license keys and keep the activated license in the keyring.
+#[cfg(feature = "license")]
+pub mod license;
+
 pub use tauri_utils::*;
 
 mod error;
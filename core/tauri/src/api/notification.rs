@@ -4,6 +4,26 @@
 
 #[cfg(windows)]
 use std::path::MAIN_SEPARATOR;
+use std::{
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  time::{Duration, SystemTime},
+};
+
+/// The longest sleep of the scheduler between two checks of the delivery time,
+/// so it isn't late after the computer wakes up from a suspend.
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How the user interacted with a notification, see [`Notification::show_with_handler`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+  /// The notification was clicked.
+  Clicked,
+  /// An action button was clicked, with the id given to [`Notification::action`].
+  Action(String),
+}
 
 /// The Notification definition.
 /// Allows you to construct a Notification data and send it.
@@ -26,8 +46,12 @@ pub struct Notification {
   title: Option<String>,
   /// The notification icon.
   icon: Option<String>,
+  /// The notification sound.
+  sound: Option<String>,
   /// The notification identifier
   identifier: String,
+  /// The action buttons, as (id, title) pairs.
+  actions: Vec<(String, String)>,
 }
 
 impl Notification {
@@ -51,14 +75,136 @@ impl Notification {
     self
   }
 
-  /// Sets the notification icon.
+  /// Sets the notification icon: a path to an image, or the name of an icon of the desktop theme.
   pub fn icon(mut self, icon: impl Into<String>) -> Self {
     self.icon = Some(icon.into());
     self
   }
 
+  /// Sets the notification sound, the name of a system sound.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: a sound of the freedesktop sound theme, e.g. `message-new-instant`.
+  /// - **macOS**: a sound of `/System/Library/Sounds`, e.g. `Ping`, or `default`.
+  /// - **Windows**: a Windows notification sound, e.g. `Reminder` or `IM`.
+  pub fn sound(mut self, sound: impl Into<String>) -> Self {
+    self.sound = Some(sound.into());
+    self
+  }
+
+  /// Adds an action button, reported to [`Notification::show_with_handler`] with its id.
+  pub fn action(mut self, id: impl Into<String>, title: impl Into<String>) -> Self {
+    self.actions.push((id.into(), title.into()));
+    self
+  }
+
   /// Shows the notification.
   pub fn show(self) -> crate::api::Result<()> {
+    self.build()?.show()?;
+    Ok(())
+  }
+
+  /// Shows the notification, running the handler on a separate thread
+  /// when the user clicks it or one of its action buttons.
+  ///
+  /// # Example
+  /// ```no_run
+  /// use tauri::api::notification::{Notification, NotificationEvent};
+  ///
+  /// Notification::new("studio.tauri.example")
+  ///   .title("Download finished")
+  ///   .action("open", "Open")
+  ///   .show_with_handler(|event| {
+  ///     if event == NotificationEvent::Action("open".into()) {
+  ///       println!("opening the download");
+  ///     }
+  ///   })
+  ///   .expect("failed to show the notification");
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS / Windows**: the notification library doesn't report the interactions yet,
+  ///   so the action buttons aren't shown and the handler never runs.
+  pub fn show_with_handler<F: FnOnce(NotificationEvent) + Send + 'static>(
+    self,
+    handler: F,
+  ) -> crate::api::Result<()> {
+    let notification = self.build()?;
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+      let mut notification = notification;
+      // the notification server invokes the default action when the notification is clicked
+      notification.action("default", "");
+      let (tx, rx) = std::sync::mpsc::channel();
+      // the handle waits for the action on the thread it was created on
+      std::thread::spawn(move || match notification.show() {
+        Ok(handle) => {
+          let _ = tx.send(Ok(()));
+          handle.wait_for_action(|action| match action {
+            "default" => handler(NotificationEvent::Clicked),
+            // the notification was dismissed or expired
+            "__closed" => {}
+            action => handler(NotificationEvent::Action(action.to_string())),
+          });
+        }
+        Err(e) => {
+          let _ = tx.send(Err(e));
+        }
+      });
+      return rx.recv().unwrap_or(Ok(())).map_err(Into::into);
+    }
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+      drop(handler);
+      notification.show()?;
+      Ok(())
+    }
+  }
+
+  /// Shows the notification at the given time from a task of the async runtime,
+  /// running the handler like [`Notification::show_with_handler`].
+  ///
+  /// The notification is shown right away if the time has passed,
+  /// and isn't shown if the app exits before.
+  ///
+  /// # Example
+  /// ```no_run
+  /// use std::time::{Duration, SystemTime};
+  /// use tauri::api::notification::Notification;
+  ///
+  /// let reminder = Notification::new("studio.tauri.example")
+  ///   .title("Stand up")
+  ///   .schedule(SystemTime::now() + Duration::from_secs(3600), |_| {});
+  /// // the user stood up already
+  /// reminder.cancel();
+  /// ```
+  pub fn schedule<F: FnOnce(NotificationEvent) + Send + 'static>(
+    self,
+    at: SystemTime,
+    handler: F,
+  ) -> ScheduledNotification {
+    let scheduled = ScheduledNotification::default();
+    let cancelled = scheduled.0.clone();
+    crate::async_runtime::spawn(async move {
+      while let Ok(remaining) = at.duration_since(SystemTime::now()) {
+        if remaining.as_millis() == 0 || cancelled.load(Ordering::SeqCst) {
+          break;
+        }
+        tokio::time::sleep(remaining.min(SCHEDULE_CHECK_INTERVAL)).await;
+      }
+      if !cancelled.load(Ordering::SeqCst) {
+        if let Err(e) = self.show_with_handler(handler) {
+          eprintln!("failed to show the scheduled notification: {}", e); // TODO log::error!
+        }
+      }
+    });
+    scheduled
+  }
+
+  /// Converts the notification to the one of the notification library.
+  fn build(self) -> crate::api::Result<notify_rust::Notification> {
     let mut notification = notify_rust::Notification::new();
     if let Some(body) = self.body {
       notification.body(&body);
@@ -69,6 +215,13 @@ impl Notification {
     if let Some(icon) = self.icon {
       notification.icon(&icon);
     }
+    if let Some(sound) = self.sound {
+      notification.sound_name(&sound);
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    for (id, title) in &self.actions {
+      notification.action(id, title);
+    }
     #[cfg(windows)]
     {
       let exe = std::env::current_exe()?;
@@ -81,7 +234,22 @@ impl Notification {
         notification.app_id(&self.identifier);
       }
     }
-    notification.show()?;
-    Ok(())
+    Ok(notification)
+  }
+}
+
+/// A notification waiting for its delivery time, see [`Notification::schedule`].
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledNotification(Arc<AtomicBool>);
+
+impl ScheduledNotification {
+  /// Cancels the notification, unless it was shown already.
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::SeqCst);
+  }
+
+  /// Whether the notification was cancelled.
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(Ordering::SeqCst)
   }
 }
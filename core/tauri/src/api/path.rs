@@ -7,6 +7,7 @@ use std::{
   path::{Path, PathBuf},
 };
 
+use crate::api::config::Config;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 /// A Base Directory to use.
@@ -52,22 +53,30 @@ pub enum BaseDirectory {
   /// The Resource directory.
   Resource,
   /// The default App config directory.
-  /// Resolves to ${CONFIG_DIR}/${APP_NAME}
+  /// Resolves to ${CONFIG_DIR}/${BUNDLE_IDENTIFIER}
   App,
   /// The current working directory.
   Current,
+  /// The default App log directory.
+  /// Resolves to ${HOME}/Library/Logs/${BUNDLE_IDENTIFIER} on macOS
+  /// and ${CONFIG_DIR}/${BUNDLE_IDENTIFIER}/logs on Linux and Windows.
+  Log,
 }
 
 /// Resolves the path with the optional base directory.
 ///
+/// The app directories are resolved with the bundle identifier of the `config`.
+///
 /// # Example
-/// ```
+/// ```rust,no_run
 /// use tauri::api::path::{resolve_path, BaseDirectory};
-/// let path = resolve_path("path/to/something", Some(BaseDirectory::Config))
+/// let context = tauri::generate_context!();
+/// let path = resolve_path(&context.config, "path/to/something", Some(BaseDirectory::Config))
 ///   .expect("failed to resolve path");
 /// // path is equal to "/home/${whoami}/.config/path/to/something" on Linux
 /// ```
 pub fn resolve_path<P: AsRef<Path>>(
+  config: &Config,
   path: P,
   dir: Option<BaseDirectory>,
 ) -> crate::api::Result<PathBuf> {
@@ -90,8 +99,9 @@ pub fn resolve_path<P: AsRef<Path>>(
       BaseDirectory::Template => template_dir(),
       BaseDirectory::Video => video_dir(),
       BaseDirectory::Resource => resource_dir(),
-      BaseDirectory::App => app_dir(),
+      BaseDirectory::App => app_dir(config),
       BaseDirectory::Current => Some(env::current_dir()?),
+      BaseDirectory::Log => log_dir(config),
     };
     if let Some(mut base_dir_path_value) = base_dir_path {
       base_dir_path_value.push(path);
@@ -193,24 +203,52 @@ pub fn resource_dir() -> Option<PathBuf> {
   crate::api::platform::resource_dir().ok()
 }
 
-fn app_name() -> crate::api::Result<String> {
-  let exe = std::env::current_exe()?;
-  let app_name = exe
-    .file_stem()
-    .expect("failed to get exe filename")
-    .to_string_lossy();
-
-  Ok(app_name.to_string())
+/// Returns the path to the suggested directory for your app config files,
+/// named after the bundle identifier of the `config`.
+pub fn app_dir(config: &Config) -> Option<PathBuf> {
+  // the unit tests write their files to a temporary directory instead
+  #[cfg(test)]
+  let config_dir = Some(std::env::temp_dir().join("tauri-tests"));
+  #[cfg(not(test))]
+  let config_dir = dirs_next::config_dir();
+  config_dir.map(|dir| dir.join(&config.tauri.bundle.identifier))
 }
 
-/// Returns the path to the suggested directory for your app config files.
-pub fn app_dir() -> Option<PathBuf> {
-  dirs_next::config_dir().and_then(|mut dir| {
-    if let Ok(app_name) = app_name() {
-      dir.push(app_name);
-      Some(dir)
-    } else {
-      None
-    }
-  })
+/// Returns the path to the suggested directory for your app log files,
+/// named after the bundle identifier of the `config`.
+///
+/// # Platform-specific
+///
+/// - **macOS:** `~/Library/Logs/${BUNDLE_IDENTIFIER}`.
+/// - **Linux and Windows:** `${CONFIG_DIR}/${BUNDLE_IDENTIFIER}/logs`.
+pub fn log_dir(config: &Config) -> Option<PathBuf> {
+  #[cfg(target_os = "macos")]
+  let path = dirs_next::home_dir().map(|dir| {
+    dir
+      .join("Library/Logs")
+      .join(&config.tauri.bundle.identifier)
+  });
+
+  #[cfg(not(target_os = "macos"))]
+  let path = app_dir(config).map(|dir| dir.join("logs"));
+
+  path
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn resolves_app_dirs_with_identifier() {
+    let mut config = crate::testing::mock_context().config;
+    config.tauri.bundle.identifier = "com.tauri.test".into();
+    let app_dir = resolve_path(&config, "", Some(BaseDirectory::App)).unwrap();
+    assert!(app_dir.ends_with("com.tauri.test"));
+    let log_dir = resolve_path(&config, "", Some(BaseDirectory::Log)).unwrap();
+    assert!(log_dir.starts_with(dirs_next::home_dir().unwrap()));
+    assert!(log_dir
+      .iter()
+      .any(|component| component == "com.tauri.test"));
+  }
 }
@@ -0,0 +1,222 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+use std::path::{Path, PathBuf};
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// A printer configured on the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Printer {
+  /// The printer name, used to choose it on [`PrintJob::printer`].
+  pub name: String,
+  /// Whether it's the default printer of the system.
+  pub is_default: bool,
+}
+
+/// Lists the printers configured on the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux / macOS**: read from CUPS with `lpstat`.
+pub fn list() -> Vec<Printer> {
+  #[cfg(any(target_os = "linux", target_os = "macos"))]
+  {
+    let default = system_settings::command_output("lpstat", &["-d"])
+      .and_then(|output| parse_lpstat_default(&output));
+    return system_settings::command_output("lpstat", &["-e"])
+      .unwrap_or_default()
+      .lines()
+      .map(|name| Printer {
+        name: name.to_string(),
+        is_default: default.as_deref() == Some(name),
+      })
+      .collect();
+  }
+  #[cfg(windows)]
+  return system_settings::powershell(
+    "Get-CimInstance Win32_Printer | ForEach-Object { \"$($_.Default)`t$($_.Name)\" }",
+  )
+  .unwrap_or_default()
+  .lines()
+  .filter_map(|line| {
+    let mut parts = line.splitn(2, '\t');
+    let is_default = parts.next()? == "True";
+    let name = parts.next()?;
+    Some(Printer {
+      name: name.to_string(),
+      is_default,
+    })
+  })
+  .collect();
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Vec::new()
+}
+
+/// Prints a file silently, without showing a print dialog.
+///
+/// # Example
+/// ```no_run
+/// tauri::api::printer::PrintJob::new("/tmp/receipt.pdf")
+///   .printer("Receipt_Printer")
+///   .copies(2)
+///   .print()
+///   .expect("failed to print the receipt");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrintJob {
+  path: PathBuf,
+  printer: Option<String>,
+  copies: u32,
+  pages: Option<String>,
+}
+
+impl PrintJob {
+  /// Creates a job printing one copy of the file on the default printer.
+  pub fn new<P: AsRef<Path>>(path: P) -> Self {
+    Self {
+      path: path.as_ref().to_path_buf(),
+      printer: None,
+      copies: 1,
+      pages: None,
+    }
+  }
+
+  /// Sets the printer, see [`list`].
+  pub fn printer(mut self, name: impl Into<String>) -> Self {
+    self.printer.replace(name.into());
+    self
+  }
+
+  /// Sets the number of copies.
+  pub fn copies(mut self, copies: u32) -> Self {
+    self.copies = copies;
+    self
+  }
+
+  /// Sets the pages to print, e.g. `1-3,5`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: unsupported, the whole file is printed.
+  pub fn pages(mut self, pages: impl Into<String>) -> Self {
+    self.pages.replace(pages.into());
+    self
+  }
+
+  /// Sends the file to the printer, returning once the job is queued.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS**: printed with CUPS' `lp`, which handles PDF, PostScript, images and text files.
+  /// - **Windows**: printed with the `PrintTo` action of the application associated with the file type,
+  ///   e.g. the PDF reader, so it must be installed.
+  pub fn print(self) -> crate::api::Result<()> {
+    if !self.path.is_file() {
+      return Err(crate::api::Error::Print(format!(
+        "{} isn't a file",
+        self.path.display()
+      )));
+    }
+    if self.copies == 0 {
+      return Err(crate::api::Error::Print(
+        "the copies must be at least 1".into(),
+      ));
+    }
+    if let Some(pages) = &self.pages {
+      if !is_valid_page_range(pages) {
+        return Err(crate::api::Error::Print(format!(
+          "invalid page range `{}`",
+          pages
+        )));
+      }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+      let copies = self.copies.to_string();
+      let path = self.path.to_string_lossy();
+      let mut args = vec!["-s", "-n", copies.as_str()];
+      if let Some(printer) = &self.printer {
+        args.extend(&["-d", printer.as_str()]);
+      }
+      if let Some(pages) = &self.pages {
+        args.extend(&["-P", pages.as_str()]);
+      }
+      // the file name can't be mistaken for an option after `--`
+      args.extend(&["--", &*path]);
+      return system_settings::command_output("lp", &args)
+        .map(|_| ())
+        .ok_or_else(|| crate::api::Error::Print("`lp` failed to queue the job".into()));
+    }
+    #[cfg(windows)]
+    {
+      let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+      let printer = match &self.printer {
+        Some(printer) => format!(
+          "-Verb PrintTo -ArgumentList {}",
+          quote(&format!("\"{}\"", printer))
+        ),
+        None => "-Verb Print".into(),
+      };
+      let script = format!(
+        "1..{} | ForEach-Object {{ Start-Process -FilePath {} {} -WindowStyle Hidden -Wait }}",
+        self.copies,
+        quote(&self.path.to_string_lossy()),
+        printer
+      );
+      return system_settings::powershell(&script)
+        .map(|_| ())
+        .ok_or_else(|| crate::api::Error::Print("the file couldn't be printed".into()));
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    Err(crate::api::Error::Print(
+      "printing isn't supported on this platform".into(),
+    ))
+  }
+}
+
+/// Whether the page range has the `1-3,5` format.
+fn is_valid_page_range(pages: &str) -> bool {
+  let is_page = |page: &str| !page.is_empty() && page.chars().all(|c| c.is_ascii_digit());
+  pages.split(',').all(|range| {
+    let mut bounds = range.splitn(2, '-');
+    let start = bounds.next().unwrap_or_default();
+    is_page(start) && bounds.next().map(is_page).unwrap_or(true)
+  })
+}
+
+/// Reads the printer name of the `lpstat -d` output.
+#[allow(dead_code)]
+fn parse_lpstat_default(output: &str) -> Option<String> {
+  // `system default destination: Receipt_Printer`, or `no system default destination`
+  output
+    .splitn(2, ": ")
+    .nth(1)
+    .map(|name| name.trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn validates_page_ranges() {
+    assert!(is_valid_page_range("1"));
+    assert!(is_valid_page_range("1-3,5"));
+    assert!(!is_valid_page_range("2, 4-6"));
+    assert!(!is_valid_page_range(""));
+    assert!(!is_valid_page_range("1-"));
+    assert!(!is_valid_page_range("1;rm"));
+    assert_eq!(
+      parse_lpstat_default("system default destination: Receipt_Printer"),
+      Some("Receipt_Printer".into())
+    );
+    assert_eq!(parse_lpstat_default("no system default destination"), None);
+  }
+}
@@ -0,0 +1,30 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{env, process::Command};
+
+use crate::api::app::current_binary;
+
+/// Starts a new instance of the app with the arguments of the current process.
+///
+/// The current process keeps running; use [`restart`] to exit it too.
+pub fn relaunch() -> crate::api::Result<()> {
+  let binary = current_binary().ok_or_else(|| {
+    std::io::Error::new(
+      std::io::ErrorKind::NotFound,
+      "failed to find the current executable",
+    )
+  })?;
+  Command::new(binary).args(env::args_os().skip(1)).spawn()?;
+  Ok(())
+}
+
+/// Relaunches the app with the arguments of the current process and exits the current process.
+///
+/// It only returns if the new instance failed to start. The windows aren't closed first,
+/// use [`crate::AppHandle::restart`] to tear the app down cleanly.
+pub fn restart() -> crate::api::Result<()> {
+  relaunch()?;
+  std::process::exit(0)
+}
@@ -0,0 +1,204 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  io::{ErrorKind, Read, Write},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+  },
+  thread::{self, JoinHandle},
+  time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::api::private::async_runtime::{channel, Receiver};
+
+/// How long a read blocks before the reader checks if the port was closed.
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The kind of a serial port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PortKind {
+  /// A USB device, e.g. a USB to serial adapter or a microcontroller board.
+  Usb,
+  /// A PCI device.
+  Pci,
+  /// A Bluetooth device.
+  Bluetooth,
+  /// A port of unknown kind, e.g. a built-in or virtual port.
+  Unknown,
+}
+
+/// A serial port available on the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortInfo {
+  /// The port name, used to [`open`] it, e.g. `/dev/ttyUSB0` or `COM3`.
+  pub name: String,
+  /// The kind of device.
+  pub kind: PortKind,
+  /// The USB vendor identifier.
+  pub vendor_id: Option<u16>,
+  /// The USB product identifier.
+  pub product_id: Option<u16>,
+  /// The USB serial number.
+  pub serial_number: Option<String>,
+  /// The USB manufacturer name.
+  pub manufacturer: Option<String>,
+  /// The USB product name.
+  pub product: Option<String>,
+}
+
+impl From<serialport::SerialPortInfo> for PortInfo {
+  fn from(info: serialport::SerialPortInfo) -> Self {
+    let mut port = Self {
+      name: info.port_name,
+      kind: PortKind::Unknown,
+      vendor_id: None,
+      product_id: None,
+      serial_number: None,
+      manufacturer: None,
+      product: None,
+    };
+    match info.port_type {
+      serialport::SerialPortType::UsbPort(usb) => {
+        port.kind = PortKind::Usb;
+        port.vendor_id = Some(usb.vid);
+        port.product_id = Some(usb.pid);
+        port.serial_number = usb.serial_number;
+        port.manufacturer = usb.manufacturer;
+        port.product = usb.product;
+      }
+      serialport::SerialPortType::PciPort => port.kind = PortKind::Pci,
+      serialport::SerialPortType::BluetoothPort => port.kind = PortKind::Bluetooth,
+      serialport::SerialPortType::Unknown => {}
+    }
+    port
+  }
+}
+
+/// Event emitted while a serial port is open.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "payload")]
+pub enum SerialEvent {
+  /// Bytes were read from the port.
+  Data(Vec<u8>),
+  /// Reading failed, e.g. because the device was unplugged. Reading stops after this event.
+  Error(String),
+}
+
+/// Lists the serial ports available on the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: the USB information is read with `libudev`.
+pub fn available_ports() -> crate::api::Result<Vec<PortInfo>> {
+  serialport::available_ports()
+    .map(|ports| ports.into_iter().map(Into::into).collect())
+    .map_err(|e| crate::api::Error::Serial(e.to_string()))
+}
+
+/// An open serial port.
+///
+/// The port is closed when this value is dropped.
+pub struct SerialPort {
+  name: String,
+  port: Mutex<Box<dyn serialport::SerialPort>>,
+  stop: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl SerialPort {
+  /// The port name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Writes the bytes to the port.
+  pub fn write(&self, data: &[u8]) -> crate::api::Result<()> {
+    let mut port = self.port.lock().expect("poisoned serial port");
+    port
+      .write_all(data)
+      .and_then(|_| port.flush())
+      .map_err(|e| crate::api::Error::Serial(e.to_string()))
+  }
+
+  /// Closes the port.
+  pub fn close(self) {}
+}
+
+impl Drop for SerialPort {
+  fn drop(&mut self) {
+    self.stop.store(true, Ordering::SeqCst);
+    if let Some(handle) = self.handle.take() {
+      let _ = handle.join();
+    }
+  }
+}
+
+/// Opens a serial port, e.g. one listed by [`available_ports`], with the given baud rate.
+///
+/// Returns a receiver of the [`SerialEvent`]s read from the port and the [`SerialPort`] to write to it.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::serial::{open, SerialEvent};
+///
+/// let (mut rx, port) = open("/dev/ttyUSB0", 115_200).expect("failed to open the port");
+/// port.write(b"PING\n").expect("failed to write to the port");
+/// std::thread::spawn(move || {
+///   while let Some(event) = rx.blocking_recv() {
+///     if let SerialEvent::Data(data) = event {
+///       println!("received {}", String::from_utf8_lossy(&data));
+///     }
+///   }
+/// });
+/// ```
+pub fn open(
+  name: impl Into<String>,
+  baud_rate: u32,
+) -> crate::api::Result<(Receiver<SerialEvent>, SerialPort)> {
+  let name = name.into();
+  let port = serialport::new(&name, baud_rate)
+    .timeout(READ_TIMEOUT)
+    .open()
+    .map_err(|e| crate::api::Error::Serial(e.to_string()))?;
+  let mut reader = port
+    .try_clone()
+    .map_err(|e| crate::api::Error::Serial(e.to_string()))?;
+  let (tx, rx) = channel(32);
+
+  let stop = Arc::new(AtomicBool::new(false));
+  let stop_ = stop.clone();
+  let handle = thread::spawn(move || {
+    let mut buf = [0u8; 1024];
+    while !stop_.load(Ordering::SeqCst) {
+      let event = match reader.read(&mut buf) {
+        Ok(0) => continue,
+        Ok(len) => SerialEvent::Data(buf[..len].to_vec()),
+        Err(e) if e.kind() == ErrorKind::TimedOut || e.kind() == ErrorKind::Interrupted => continue,
+        Err(e) => {
+          let _ = tx.blocking_send(SerialEvent::Error(e.to_string()));
+          break;
+        }
+      };
+      if tx.blocking_send(event).is_err() {
+        break;
+      }
+    }
+  });
+
+  Ok((
+    rx,
+    SerialPort {
+      name,
+      port: Mutex::new(port),
+      stop,
+      handle: Some(handle),
+    },
+  ))
+}
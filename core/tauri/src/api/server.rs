@@ -0,0 +1,535 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  io::Read,
+  net::Ipv4Addr,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  thread::{self, JoinHandle},
+  time::Duration,
+};
+
+use rand::{distributions::Alphanumeric, Rng};
+use serde::Serialize;
+
+/// The HTTP method type used on routes.
+pub use http::Method;
+
+/// How long the server blocks waiting for a request before checking if it was stopped.
+const RECV_TIMEOUT: Duration = Duration::from_millis(250);
+/// Maximum size of a request body, larger requests get a `413 Payload Too Large` response.
+const MAX_BODY_SIZE: u64 = 10 * 1024 * 1024;
+/// Length of the generated authentication token.
+const TOKEN_LENGTH: usize = 32;
+/// How long browsers cache a preflight response, in seconds.
+const PREFLIGHT_MAX_AGE: &str = "600";
+
+/// A request received by the server.
+#[derive(Debug)]
+pub struct Request {
+  method: Method,
+  path: String,
+  query: Option<String>,
+  headers: HashMap<String, String>,
+  body: Vec<u8>,
+}
+
+impl Request {
+  /// The request method.
+  pub fn method(&self) -> &Method {
+    &self.method
+  }
+
+  /// The request path, without the query string.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// The raw query string, if any.
+  pub fn query(&self) -> Option<&str> {
+    self.query.as_deref()
+  }
+
+  /// Gets a header value. The lookup is case insensitive.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .get(&name.to_lowercase())
+      .map(|value| value.as_str())
+  }
+
+  /// The request body.
+  pub fn body(&self) -> &[u8] {
+    &self.body
+  }
+
+  /// Deserializes the request body as JSON.
+  pub fn json<T: serde::de::DeserializeOwned>(&self) -> crate::api::Result<T> {
+    serde_json::from_slice(&self.body).map_err(Into::into)
+  }
+}
+
+/// A response returned by a route handler.
+#[derive(Debug, Clone)]
+pub struct Response {
+  status: u16,
+  headers: Vec<(String, String)>,
+  body: Vec<u8>,
+}
+
+impl Response {
+  /// Creates a response with the given status code and body.
+  pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+    Self {
+      status,
+      headers: Vec::new(),
+      body: body.into(),
+    }
+  }
+
+  /// Creates a `200 OK` response with a JSON body.
+  pub fn json<T: Serialize>(value: &T) -> crate::api::Result<Self> {
+    Ok(Self::new(200, serde_json::to_vec(value)?).header("Content-Type", "application/json"))
+  }
+
+  /// Adds a header to the response.
+  pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+    self.headers.push((name.into(), value.into()));
+    self
+  }
+
+  fn status(status: u16) -> Self {
+    Self::new(status, Vec::new())
+  }
+}
+
+type Handler = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+
+struct Route {
+  method: Method,
+  path: String,
+  handler: Handler,
+}
+
+/// Builds the embedded HTTP server.
+///
+/// The server only listens on the loopback interface and requires every request
+/// to send the server token as a bearer token on the `Authorization` header.
+/// The token is checked before the request body is read.
+///
+/// Browser pages, e.g. of an extension, can only call the server from the origins
+/// allowed with [`ServerBuilder::allowed_origin`]: the server answers their CORS preflight
+/// requests, which can't send the token, and adds the CORS headers to their responses.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::server::{Method, Response, ServerBuilder};
+///
+/// let server = ServerBuilder::new()
+///   .route(Method::GET, "/status", |_request| Response::new(200, "running"))
+///   .allowed_origin("chrome-extension://abcdefghijklmnopabcdefghijklmnop")
+///   .start()
+///   .expect("failed to start server");
+/// // share `server.port()` and `server.token()` with the companion tool
+/// println!("listening on {} with token {}", server.port(), server.token());
+/// ```
+#[derive(Default)]
+pub struct ServerBuilder {
+  port: Option<u16>,
+  token: Option<String>,
+  allowed_origins: Vec<String>,
+  routes: Vec<Route>,
+}
+
+impl ServerBuilder {
+  /// Creates a new server builder.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the port to listen on. Defaults to a free port chosen by the system.
+  pub fn port(mut self, port: u16) -> Self {
+    self.port.replace(port);
+    self
+  }
+
+  /// Sets the token clients must send. Defaults to a random token generated on start.
+  pub fn token(mut self, token: impl Into<String>) -> Self {
+    self.token.replace(token.into());
+    self
+  }
+
+  /// Allows the browser pages of the given origin, e.g. `chrome-extension://<id>`,
+  /// to call the server. Requests from other origins are still served, but browsers
+  /// don't let the pages read the responses.
+  pub fn allowed_origin(mut self, origin: impl Into<String>) -> Self {
+    self.allowed_origins.push(origin.into());
+    self
+  }
+
+  /// Adds a route. Paths are matched exactly, ignoring the query string.
+  pub fn route<F: Fn(&Request) -> Response + Send + Sync + 'static>(
+    mut self,
+    method: Method,
+    path: impl Into<String>,
+    handler: F,
+  ) -> Self {
+    self.routes.push(Route {
+      method,
+      path: path.into(),
+      handler: Box::new(handler),
+    });
+    self
+  }
+
+  /// Starts the server on a background thread.
+  pub fn start(self) -> crate::api::Result<Server> {
+    let token = self.token.unwrap_or_else(|| {
+      rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+    });
+    // binding the port 0 lets the system pick a free port without racing other processes
+    let server = tiny_http::Server::http((Ipv4Addr::LOCALHOST, self.port.unwrap_or(0)))
+      .map_err(|e| crate::api::Error::Server(e.to_string()))?;
+    let port = server.server_addr().port();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_ = stop.clone();
+    let routes = self.routes;
+    let allowed_origins = self.allowed_origins;
+    let token_ = token.clone();
+    let handle = thread::spawn(move || {
+      while !stop_.load(Ordering::SeqCst) {
+        let mut request = match server.recv_timeout(RECV_TIMEOUT) {
+          Ok(Some(request)) => request,
+          Ok(None) => continue,
+          Err(_) => break,
+        };
+        let response = match read_head(&request) {
+          Ok(req) => {
+            let origin = req
+              .header("origin")
+              .filter(|origin| allowed_origins.iter().any(|allowed| allowed == origin))
+              .map(ToString::to_string);
+            if req.method == Method::OPTIONS && req.header("origin").is_some() {
+              preflight(&routes, origin.as_deref(), &req)
+            } else {
+              let response = dispatch(&routes, &token_, req, || read_body(&mut request));
+              match origin {
+                Some(origin) => with_cors(response, &origin),
+                None => response,
+              }
+            }
+          }
+          Err(response) => response,
+        };
+        let _ = request.respond(into_http_response(response));
+      }
+    });
+
+    Ok(Server {
+      port,
+      token,
+      stop,
+      handle: Some(handle),
+    })
+  }
+}
+
+/// A running embedded HTTP server. It is stopped when dropped.
+pub struct Server {
+  port: u16,
+  token: String,
+  stop: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl Server {
+  /// The port the server listens on.
+  pub fn port(&self) -> u16 {
+    self.port
+  }
+
+  /// The token clients must send as `Authorization: Bearer <token>`.
+  pub fn token(&self) -> &str {
+    &self.token
+  }
+
+  /// Stops the server, see the [`Drop`] implementation.
+  pub fn stop(self) {
+    drop(self)
+  }
+}
+
+impl Drop for Server {
+  fn drop(&mut self) {
+    self.stop.store(true, Ordering::SeqCst);
+    if let Some(handle) = self.handle.take() {
+      let _ = handle.join();
+    }
+  }
+}
+
+/// Reads the request line and headers, or returns the error response if they're invalid.
+/// The body is left unread, see [`read_body`].
+fn read_head(request: &tiny_http::Request) -> Result<Request, Response> {
+  let method = Method::from_bytes(request.method().to_string().as_bytes())
+    .map_err(|_| Response::status(400))?;
+  let url = request.url().to_string();
+  let mut parts = url.splitn(2, '?');
+  let path = parts.next().unwrap_or_default().to_string();
+  let query = parts.next().map(|query| query.to_string());
+  let headers = request
+    .headers()
+    .iter()
+    .map(|header| {
+      (
+        header.field.as_str().as_str().to_lowercase(),
+        header.value.as_str().to_string(),
+      )
+    })
+    .collect();
+
+  Ok(Request {
+    method,
+    path,
+    query,
+    headers,
+    body: Vec::new(),
+  })
+}
+
+/// Reads the request body, or returns the error response if it's too large.
+fn read_body(request: &mut tiny_http::Request) -> Result<Vec<u8>, Response> {
+  if request.body_length().unwrap_or_default() as u64 > MAX_BODY_SIZE {
+    return Err(Response::status(413));
+  }
+  // chunked bodies don't announce their length, so one more byte is read to detect larger bodies
+  let mut body = Vec::new();
+  request
+    .as_reader()
+    .take(MAX_BODY_SIZE + 1)
+    .read_to_end(&mut body)
+    .map_err(|_| Response::status(400))?;
+  if body.len() as u64 > MAX_BODY_SIZE {
+    return Err(Response::status(413));
+  }
+  Ok(body)
+}
+
+fn into_http_response(response: Response) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+  let mut http_response =
+    tiny_http::Response::from_data(response.body).with_status_code(response.status);
+  for (name, value) in response.headers {
+    if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+      http_response.add_header(header);
+    }
+  }
+  http_response
+}
+
+/// Checks the token, then reads the body with `read_body` and runs the matching route.
+fn dispatch<F: FnOnce() -> Result<Vec<u8>, Response>>(
+  routes: &[Route],
+  token: &str,
+  mut request: Request,
+  read_body: F,
+) -> Response {
+  if !is_authorized(token, &request) {
+    return Response::status(401).header("WWW-Authenticate", "Bearer");
+  }
+
+  let mut path_matched = false;
+  for route in routes.iter().filter(|route| route.path == request.path) {
+    path_matched = true;
+    if route.method == request.method {
+      request.body = match read_body() {
+        Ok(body) => body,
+        Err(response) => return response,
+      };
+      return (route.handler)(&request);
+    }
+  }
+
+  Response::status(if path_matched { 405 } else { 404 })
+}
+
+/// Answers a CORS preflight request of an allowed `origin` with the methods of its path.
+fn preflight(routes: &[Route], origin: Option<&str>, request: &Request) -> Response {
+  let origin = match origin {
+    Some(origin) => origin,
+    None => return Response::status(403),
+  };
+  let methods: Vec<&str> = routes
+    .iter()
+    .filter(|route| route.path == request.path)
+    .map(|route| route.method.as_str())
+    .collect();
+  if methods.is_empty() {
+    return Response::status(404);
+  }
+  with_cors(Response::status(204), origin)
+    .header("Access-Control-Allow-Methods", methods.join(", "))
+    .header(
+      "Access-Control-Allow-Headers",
+      "Authorization, Content-Type",
+    )
+    .header("Access-Control-Max-Age", PREFLIGHT_MAX_AGE)
+}
+
+/// Lets the browser pages of the allowed `origin` read the response.
+fn with_cors(response: Response, origin: &str) -> Response {
+  response
+    .header("Access-Control-Allow-Origin", origin)
+    .header("Vary", "Origin")
+}
+
+fn is_authorized(token: &str, request: &Request) -> bool {
+  match request
+    .header("authorization")
+    .and_then(|value| value.strip_prefix("Bearer "))
+  {
+    Some(provided) => constant_time_eq(provided.as_bytes(), token.as_bytes()),
+    None => false,
+  }
+}
+
+/// Compares the token without short-circuiting so its value can't be guessed from response times.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  if a.len() != b.len() {
+    return false;
+  }
+  a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn request(method: Method, path: &str, authorization: Option<&str>) -> Request {
+    let mut headers = HashMap::new();
+    if let Some(authorization) = authorization {
+      headers.insert("authorization".to_string(), authorization.to_string());
+    }
+    Request {
+      method,
+      path: path.into(),
+      query: None,
+      headers,
+      body: Vec::new(),
+    }
+  }
+
+  fn routes() -> Vec<Route> {
+    ServerBuilder::new()
+      .route(Method::GET, "/status", |_| Response::new(200, "ok"))
+      .routes
+  }
+
+  fn empty_body() -> Result<Vec<u8>, Response> {
+    Ok(Vec::new())
+  }
+
+  #[test]
+  fn rejects_missing_or_invalid_token() {
+    let routes = routes();
+    let unread_body = || -> Result<Vec<u8>, Response> { panic!("the body was read") };
+    let unauthenticated = dispatch(
+      &routes,
+      "secret",
+      request(Method::GET, "/status", None),
+      unread_body,
+    );
+    assert_eq!(unauthenticated.status, 401);
+    let invalid = dispatch(
+      &routes,
+      "secret",
+      request(Method::GET, "/status", Some("Bearer wrong")),
+      unread_body,
+    );
+    assert_eq!(invalid.status, 401);
+  }
+
+  #[test]
+  fn dispatches_to_route() {
+    let routes = routes();
+    let auth = Some("Bearer secret");
+    let response = dispatch(
+      &routes,
+      "secret",
+      request(Method::GET, "/status", auth),
+      empty_body,
+    );
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"ok");
+    let response = dispatch(
+      &routes,
+      "secret",
+      request(Method::POST, "/status", auth),
+      empty_body,
+    );
+    assert_eq!(response.status, 405);
+    let response = dispatch(
+      &routes,
+      "secret",
+      request(Method::GET, "/missing", auth),
+      empty_body,
+    );
+    assert_eq!(response.status, 404);
+  }
+
+  #[test]
+  fn answers_preflight_of_allowed_origins() {
+    let routes = routes();
+    let response = preflight(
+      &routes,
+      Some("chrome-extension://id"),
+      &request(Method::OPTIONS, "/status", None),
+    );
+    assert_eq!(response.status, 204);
+    assert!(response.headers.contains(&(
+      "Access-Control-Allow-Origin".to_string(),
+      "chrome-extension://id".to_string()
+    )));
+    assert!(response.headers.contains(&(
+      "Access-Control-Allow-Methods".to_string(),
+      "GET".to_string()
+    )));
+    let response = preflight(&routes, None, &request(Method::OPTIONS, "/status", None));
+    assert_eq!(response.status, 403);
+  }
+
+  #[test]
+  fn rejects_large_bodies() {
+    use std::{io::Write, net::TcpStream};
+
+    let server = ServerBuilder::new()
+      .token("secret")
+      .route(Method::POST, "/upload", |_| Response::new(200, "ok"))
+      .start()
+      .unwrap();
+    assert_ne!(server.port(), 0);
+
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port())).unwrap();
+    write!(
+      stream,
+      concat!(
+        "POST /upload HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer secret\r\n",
+        "Content-Length: {}\r\nConnection: close\r\n\r\n"
+      ),
+      MAX_BODY_SIZE + 1
+    )
+    .unwrap();
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    assert!(response.starts_with("HTTP/1.1 413"));
+  }
+}
@@ -0,0 +1,250 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+
+use std::path::PathBuf;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// An item to share.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum ShareItem {
+  /// A text.
+  Text(String),
+  /// A web link.
+  Url(String),
+  /// A file, e.g. an export.
+  File(PathBuf),
+}
+
+/// Shows the share sheet of the system with the items, blocking until the user closes it.
+///
+/// # Example
+/// ```no_run
+/// use tauri::api::share::{share, ShareItem};
+///
+/// share(&[
+///   ShareItem::Text("The report of this week".into()),
+///   ShareItem::File("/tmp/report.pdf".into()),
+/// ])
+/// .expect("failed to share the report");
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **Linux**: there's no share sheet, so an email is composed with `xdg-email` instead,
+///   the texts and links being its body and the files its attachments.
+///   It returns once the email client opens.
+/// - **macOS**: shown with `NSSharingServicePicker` through JavaScript for Automation.
+/// - **Windows**: shown with the Share UI of Windows 10, through PowerShell.
+///   Only the first link is shared as a link, the others are appended to the text.
+pub fn share(items: &[ShareItem]) -> crate::api::Result<()> {
+  if items.is_empty() {
+    return Err(crate::api::Error::Share("there's nothing to share".into()));
+  }
+  for item in items {
+    if let ShareItem::File(path) = item {
+      if !path.is_file() {
+        return Err(crate::api::Error::Share(format!(
+          "{} isn't a file",
+          path.display()
+        )));
+      }
+    }
+  }
+
+  #[cfg(target_os = "linux")]
+  {
+    let body = items
+      .iter()
+      .filter_map(|item| match item {
+        ShareItem::Text(text) | ShareItem::Url(text) => Some(text.as_str()),
+        ShareItem::File(_) => None,
+      })
+      .collect::<Vec<_>>()
+      .join("\n");
+    let files = items
+      .iter()
+      .filter_map(|item| match item {
+        ShareItem::File(path) => Some(path.to_string_lossy().into_owned()),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    let mut args = vec!["--utf8"];
+    if !body.is_empty() {
+      args.extend(&["--body", body.as_str()]);
+    }
+    for file in &files {
+      args.extend(&["--attach", file.as_str()]);
+    }
+    return system_settings::command_output("xdg-email", &args)
+      .map(|_| ())
+      .ok_or_else(|| crate::api::Error::Share("`xdg-email` failed to compose the email".into()));
+  }
+  #[cfg(target_os = "macos")]
+  {
+    // JSON is a valid JavaScript literal
+    let script = MACOS_SCRIPT.replace("__ITEMS__", &serde_json::to_string(items)?);
+    return system_settings::command_output("osascript", &["-l", "JavaScript", "-e", &script])
+      .map(|_| ())
+      .ok_or_else(|| crate::api::Error::Share("the share sheet couldn't be shown".into()));
+  }
+  #[cfg(windows)]
+  {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let mut texts = Vec::new();
+    let mut url = None;
+    let mut files = Vec::new();
+    for item in items {
+      match item {
+        ShareItem::Url(link) if url.is_none() => url = Some(quote(link)),
+        ShareItem::Text(text) | ShareItem::Url(text) => texts.push(text.as_str()),
+        ShareItem::File(path) => files.push(quote(&path.to_string_lossy())),
+      }
+    }
+    let script = format!(
+      "{}\n[TauriShare]::Share({}, {}, {}, @({}))",
+      WINDOWS_SCRIPT,
+      quote(&share_title()),
+      quote(&texts.join("\n")),
+      url.unwrap_or_else(|| "''".into()),
+      files.join(", ")
+    );
+    return system_settings::powershell(&script)
+      .map(|_| ())
+      .ok_or_else(|| crate::api::Error::Share("the Share UI couldn't be shown".into()));
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+  Err(crate::api::Error::Share(
+    "sharing isn't supported on this platform".into(),
+  ))
+}
+
+/// The title of the Windows Share UI, which requires one.
+#[cfg(windows)]
+fn share_title() -> String {
+  std::env::current_exe()
+    .ok()
+    .and_then(|path| {
+      path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+    })
+    .unwrap_or_else(|| "Share".into())
+}
+
+/// Shows the picker next to the mouse cursor from a 1x1 window,
+/// pumping the events until a service finishes sharing or the picker is dismissed.
+#[cfg(target_os = "macos")]
+const MACOS_SCRIPT: &str = r#"ObjC.import('AppKit');
+var items = __ITEMS__;
+var objects = $.NSMutableArray.array;
+items.forEach(function (item) {
+  if (item.type === 'text') objects.addObject($(item.value));
+  else if (item.type === 'url') objects.addObject($.NSURL.URLWithString(item.value));
+  else objects.addObject($.NSURL.fileURLWithPath(item.value));
+});
+var done = false;
+var delegate;
+ObjC.registerSubclass({
+  name: 'TauriShareDelegate',
+  protocols: ['NSSharingServicePickerDelegate', 'NSSharingServiceDelegate'],
+  methods: {
+    'sharingServicePicker:didChooseSharingService:': {
+      types: ['void', ['id', 'id']],
+      implementation: function (picker, service) { if (service.isNil()) done = true; }
+    },
+    'sharingServicePicker:delegateForSharingService:': {
+      types: ['id', ['id', 'id']],
+      implementation: function (picker, service) { return delegate; }
+    },
+    'sharingService:didShareItems:': {
+      types: ['void', ['id', 'id']],
+      implementation: function (service, items) { done = true; }
+    },
+    'sharingService:didFailToShareItems:error:': {
+      types: ['void', ['id', 'id', 'id']],
+      implementation: function (service, items, error) { done = true; }
+    }
+  }
+});
+delegate = $.TauriShareDelegate.alloc.init;
+var app = $.NSApplication.sharedApplication;
+app.setActivationPolicy($.NSApplicationActivationPolicyAccessory);
+var mouse = $.NSEvent.mouseLocation;
+var window = $.NSWindow.alloc.initWithContentRectStyleMaskBackingDefer(
+  $.NSMakeRect(mouse.x, mouse.y, 1, 1), $.NSWindowStyleMaskBorderless, $.NSBackingStoreBuffered,
+  false);
+window.makeKeyAndOrderFront(null);
+app.activateIgnoringOtherApps(true);
+var picker = $.NSSharingServicePicker.alloc.initWithItems(objects);
+picker.delegate = delegate;
+picker.showRelativeToRectOfViewPreferredEdge(
+  window.contentView.bounds, window.contentView, $.NSMinYEdge);
+while (!done) {
+  var event = app.nextEventMatchingMaskUntilDateInModeDequeue(
+    $.NSEventMaskAny, $.NSDate.dateWithTimeIntervalSinceNow(0.1), $.NSDefaultRunLoopMode, true);
+  if (!event.isNil()) app.sendEvent(event);
+}
+window.close;"#;
+
+/// Shows the Share UI for a hidden form through `IDataTransferManagerInterop`,
+/// running the form until the share completes or is cancelled.
+#[cfg(windows)]
+const WINDOWS_SCRIPT: &str = r#"$winmd = "$env:windir\System32\WinMetadata"
+Add-Type -ReferencedAssemblies @(
+  'System.Windows.Forms', 'System.Runtime', 'System.Runtime.WindowsRuntime',
+  "$winmd\Windows.Foundation.winmd", "$winmd\Windows.ApplicationModel.winmd",
+  "$winmd\Windows.Storage.winmd"
+) -TypeDefinition @'
+using System;
+using System.Collections.Generic;
+using System.Runtime.InteropServices;
+using System.Runtime.InteropServices.WindowsRuntime;
+using System.Windows.Forms;
+using Windows.ApplicationModel.DataTransfer;
+using Windows.Storage;
+
+public static class TauriShare {
+  [ComImport, Guid("3A3DCD6C-3EAB-43DC-BCDE-45671CE800C8")]
+  [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+  interface IDataTransferManagerInterop {
+    IntPtr GetForWindow(IntPtr appWindow, ref Guid riid);
+    void ShowShareUIForWindow(IntPtr appWindow);
+  }
+
+  public static void Share(string title, string text, string url, string[] files) {
+    var form = new Form { ShowInTaskbar = false, Opacity = 0, Width = 1, Height = 1 };
+    form.Load += (sender, e) => {
+      var interop = (IDataTransferManagerInterop)WindowsRuntimeMarshal
+        .GetActivationFactory(typeof(DataTransferManager));
+      var iid = new Guid("A5CAEE9B-8708-49D1-8D36-67D25A8DA00C");
+      var manager = (DataTransferManager)Marshal
+        .GetObjectForIUnknown(interop.GetForWindow(form.Handle, ref iid));
+      manager.DataRequested += async (source, args) => {
+        var deferral = args.Request.GetDeferral();
+        var data = args.Request.Data;
+        data.Properties.Title = title;
+        data.ShareCompleted += (package, completed) => form.BeginInvoke(new Action(form.Close));
+        data.ShareCanceled += (package, canceled) => form.BeginInvoke(new Action(form.Close));
+        if (text.Length > 0) data.SetText(text);
+        if (url.Length > 0) data.SetWebLink(new Uri(url));
+        if (files.Length > 0) {
+          var storageItems = new List<IStorageItem>();
+          foreach (var file in files) {
+            storageItems.Add(await StorageFile.GetFileFromPathAsync(file));
+          }
+          data.SetStorageItems(storageItems);
+        }
+        deferral.Complete();
+      };
+      interop.ShowShareUIForWindow(form.Handle);
+    };
+    Application.Run(form);
+  }
+}
+'@"#;
@@ -0,0 +1,113 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{path::Path, thread};
+
+use serde::Deserialize;
+
+#[allow(unused_imports)]
+use super::system_settings;
+
+/// A sound of the desktop theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemSound {
+  /// The alert sound, also played by [`beep`].
+  Beep,
+  /// The sound of an error message.
+  Error,
+  /// The sound of a warning message.
+  Warning,
+  /// The sound of an information message.
+  Information,
+  /// The sound of a question.
+  Question,
+}
+
+/// Plays the alert sound of the desktop.
+pub fn beep() {
+  play(SystemSound::Beep)
+}
+
+/// Plays a sound of the desktop theme, without waiting for it to end.
+///
+/// The sound is played natively, so it isn't subject to the webview autoplay policies.
+/// Errors are ignored, e.g. when the desktop has no sound theme.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: played with `canberra-gtk-play`.
+#[allow(unused_variables)]
+pub fn play(sound: SystemSound) {
+  thread::spawn(move || {
+    #[cfg(target_os = "linux")]
+    {
+      let name = match sound {
+        SystemSound::Beep => "bell",
+        SystemSound::Error => "dialog-error",
+        SystemSound::Warning => "dialog-warning",
+        SystemSound::Information => "dialog-information",
+        SystemSound::Question => "dialog-question",
+      };
+      system_settings::command_output("canberra-gtk-play", &["-i", name]);
+    }
+    #[cfg(target_os = "macos")]
+    {
+      let name = match sound {
+        SystemSound::Beep => "Tink",
+        SystemSound::Error => "Basso",
+        SystemSound::Warning => "Sosumi",
+        SystemSound::Information => "Glass",
+        SystemSound::Question => "Purr",
+      };
+      system_settings::command_output(
+        "afplay",
+        &[&format!("/System/Library/Sounds/{}.aiff", name)],
+      );
+    }
+    #[cfg(windows)]
+    {
+      let name = match sound {
+        SystemSound::Beep => "Beep",
+        SystemSound::Error => "Hand",
+        SystemSound::Warning => "Exclamation",
+        SystemSound::Information => "Asterisk",
+        SystemSound::Question => "Question",
+      };
+      // the sound is played asynchronously, so PowerShell must not exit before it ends
+      system_settings::powershell(&format!(
+        "[System.Media.SystemSounds]::{}.Play(); Start-Sleep -Seconds 2",
+        name
+      ));
+    }
+  });
+}
+
+/// Plays an audio file, e.g. a sound bundled as a resource, without waiting for it to end.
+///
+/// Errors are ignored, e.g. when the file doesn't exist.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: played with `paplay`, or `aplay` without PulseAudio.
+/// - **Windows**: only WAV files are supported.
+#[allow(unused_variables)]
+pub fn play_file<P: AsRef<Path>>(path: P) {
+  let path = path.as_ref().to_string_lossy().into_owned();
+  thread::spawn(move || {
+    #[cfg(target_os = "linux")]
+    {
+      if system_settings::command_output("paplay", &[&path]).is_none() {
+        system_settings::command_output("aplay", &["-q", &path]);
+      }
+    }
+    #[cfg(target_os = "macos")]
+    system_settings::command_output("afplay", &[&path]);
+    #[cfg(windows)]
+    system_settings::powershell(&format!(
+      "(New-Object System.Media.SoundPlayer '{}').PlaySync()",
+      path.replace('\'', "''")
+    ));
+  });
+}
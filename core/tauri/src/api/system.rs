@@ -0,0 +1,163 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use sysinfo::{Pid, Process, ProcessExt, ProcessorExt, System, SystemExt};
+
+/// The CPU and memory usage of a process.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessMetrics {
+  /// The process identifier.
+  pub pid: u32,
+  /// The process name.
+  pub name: String,
+  /// The CPU usage since the previous refresh, in percent of one core,
+  /// so it exceeds 100 when the process uses several cores.
+  pub cpu_usage: f32,
+  /// The resident memory, in bytes.
+  pub memory: u64,
+}
+
+impl From<&Process> for ProcessMetrics {
+  fn from(process: &Process) -> Self {
+    Self {
+      pid: process.pid() as u32,
+      name: process.name().to_string(),
+      cpu_usage: process.cpu_usage(),
+      memory: process.memory() * 1024,
+    }
+  }
+}
+
+/// The average number of processes waiting to run, see `uptime(1)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadAverage {
+  /// The average over the last minute.
+  pub one: f64,
+  /// The average over the last five minutes.
+  pub five: f64,
+  /// The average over the last fifteen minutes.
+  pub fifteen: f64,
+}
+
+/// The CPU and memory usage of the whole system.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemMetrics {
+  /// The CPU usage since the previous refresh, in percent of all the cores.
+  pub cpu_usage: f32,
+  /// The CPU usage of each core since the previous refresh, in percent.
+  pub cores: Vec<f32>,
+  /// The total memory, in bytes.
+  pub total_memory: u64,
+  /// The used memory, in bytes.
+  pub used_memory: u64,
+  /// The total swap space, in bytes.
+  pub total_swap: u64,
+  /// The used swap space, in bytes.
+  pub used_swap: u64,
+  /// The load average, which is always zero on Windows.
+  pub load_average: LoadAverage,
+}
+
+/// Reads the system and process metrics.
+///
+/// The CPU usages are measured between two refreshes,
+/// so they're zero until the monitor is refreshed after a while, e.g. every second.
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::api::system::Monitor;
+/// use std::{thread::sleep, time::Duration};
+///
+/// let mut monitor = Monitor::new();
+/// loop {
+///   sleep(Duration::from_secs(1));
+///   monitor.refresh();
+///   let system = monitor.system();
+///   println!("CPU {:.1}%, {} bytes used", system.cpu_usage, system.used_memory);
+/// }
+/// ```
+pub struct Monitor {
+  system: System,
+  pid: Option<Pid>,
+}
+
+impl Default for Monitor {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Monitor {
+  /// Creates a monitor, refreshing it a first time.
+  pub fn new() -> Self {
+    let mut monitor = Self {
+      system: System::new(),
+      pid: sysinfo::get_current_pid().ok(),
+    };
+    monitor.refresh();
+    monitor
+  }
+
+  /// Refreshes the CPU and memory usage of the system and the current process.
+  pub fn refresh(&mut self) {
+    self.system.refresh_cpu();
+    self.system.refresh_memory();
+    if let Some(pid) = self.pid {
+      self.system.refresh_process(pid);
+    }
+  }
+
+  /// Refreshes the list of processes and their CPU and memory usage,
+  /// which is slower than [`Self::refresh`].
+  pub fn refresh_processes(&mut self) {
+    self.system.refresh_processes();
+  }
+
+  /// The system metrics of the last refresh.
+  pub fn system(&self) -> SystemMetrics {
+    let load_average = self.system.get_load_average();
+    SystemMetrics {
+      cpu_usage: self.system.get_global_processor_info().get_cpu_usage(),
+      cores: self
+        .system
+        .get_processors()
+        .iter()
+        .map(|processor| processor.get_cpu_usage())
+        .collect(),
+      total_memory: self.system.get_total_memory() * 1024,
+      used_memory: self.system.get_used_memory() * 1024,
+      total_swap: self.system.get_total_swap() * 1024,
+      used_swap: self.system.get_used_swap() * 1024,
+      load_average: LoadAverage {
+        one: load_average.one,
+        five: load_average.five,
+        fifteen: load_average.fifteen,
+      },
+    }
+  }
+
+  /// The metrics of the current process at the last refresh.
+  pub fn process(&self) -> Option<ProcessMetrics> {
+    self
+      .pid
+      .and_then(|pid| self.system.get_process(pid))
+      .map(Into::into)
+  }
+
+  /// The processes of the last [`Self::refresh_processes`], sorted by identifier.
+  pub fn processes(&self) -> Vec<ProcessMetrics> {
+    let mut processes = self
+      .system
+      .get_processes()
+      .values()
+      .map(ProcessMetrics::from)
+      .collect::<Vec<_>>();
+    processes.sort_by_key(|process| process.pid);
+    processes
+  }
+}
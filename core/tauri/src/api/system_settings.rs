@@ -0,0 +1,173 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Helpers to read the desktop settings with the platform command line tools.
+
+use std::{
+  process::Command,
+  sync::mpsc::{self as std_mpsc, RecvTimeoutError, TryRecvError},
+  thread,
+  time::Duration,
+};
+
+use crate::api::private::async_runtime::{channel, Receiver};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Runs a command without a console window, returning its trimmed stdout if it succeeds.
+pub fn command_output(program: &str, args: &[&str]) -> Option<String> {
+  command_bytes(program, args).map(|stdout| String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+/// Runs a command without a console window, returning its raw stdout if it succeeds, e.g. an image.
+pub fn command_bytes(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+  let mut command = Command::new(program);
+  command.args(args);
+  #[cfg(windows)]
+  command.creation_flags(CREATE_NO_WINDOW);
+  let output = command.output().ok()?;
+  if output.status.success() {
+    Some(output.stdout)
+  } else {
+    None
+  }
+}
+
+/// Runs a PowerShell script, returning its trimmed output if it succeeds.
+#[cfg(windows)]
+pub fn powershell(script: &str) -> Option<String> {
+  // the encoded command avoids escaping the quotes of the script
+  let encoded = base64::encode(
+    script
+      .encode_utf16()
+      .flat_map(|unit| unit.to_le_bytes().to_vec())
+      .collect::<Vec<u8>>(),
+  );
+  command_output(
+    "powershell",
+    &["-NoProfile", "-NonInteractive", "-EncodedCommand", &encoded],
+  )
+}
+
+/// Reads a GNOME setting, removing the quotes around string values.
+#[cfg(target_os = "linux")]
+pub fn gsettings(schema: &str, key: &str) -> Option<String> {
+  command_output("gsettings", &["get", schema, key])
+    .map(|value| value.trim_matches('\'').to_string())
+}
+
+/// Reads a macOS user default.
+#[cfg(target_os = "macos")]
+pub fn defaults(domain: &str, key: &str) -> Option<String> {
+  command_output("defaults", &["read", domain, key])
+}
+
+/// Reads a boolean macOS user default, which is stored as `0` or `1`.
+#[cfg(target_os = "macos")]
+pub fn defaults_bool(domain: &str, key: &str) -> bool {
+  defaults(domain, key)
+    .map(|value| value == "1")
+    .unwrap_or(false)
+}
+
+/// Reads a value from the registry with `reg query`.
+#[cfg(windows)]
+pub fn registry_value(key: &str, name: &str) -> Option<String> {
+  let output = command_output("reg", &["query", key, "/v", name])?;
+  // the value line has the `<name>    <type>    <data>` format
+  output
+    .lines()
+    .find(|line| line.trim_start().starts_with(name))
+    .and_then(|line| line.split_whitespace().nth(2))
+    .map(|data| data.to_string())
+}
+
+/// Reads a `REG_DWORD` value from the registry, which `reg query` prints in hexadecimal.
+#[cfg(windows)]
+pub fn registry_dword(key: &str, name: &str) -> Option<u32> {
+  registry_value(key, name)
+    .and_then(|value| u32::from_str_radix(value.trim_start_matches("0x"), 16).ok())
+}
+
+/// The settings sent by a watcher thread, which stops as soon as this is dropped.
+pub struct Watcher<T> {
+  receiver: Receiver<T>,
+  // dropping the sender disconnects the thread's receiver, which ends its wait
+  _cancel: std_mpsc::Sender<()>,
+}
+
+impl<T> Watcher<T> {
+  /// Receives the next settings, or `None` if the watcher thread stopped.
+  pub async fn recv(&mut self) -> Option<T> {
+    self.receiver.recv().await
+  }
+}
+
+/// Tells a watcher thread that its [`Watcher`] was dropped.
+pub struct Cancel(std_mpsc::Receiver<()>);
+
+impl Cancel {
+  /// Sleeps for the given duration, returning `false` right away if the watcher was dropped.
+  pub fn sleep(&self, duration: Duration) -> bool {
+    matches!(
+      self.0.recv_timeout(duration),
+      Err(RecvTimeoutError::Timeout)
+    )
+  }
+
+  /// Whether the watcher was dropped.
+  #[allow(dead_code)]
+  pub fn is_cancelled(&self) -> bool {
+    matches!(self.0.try_recv(), Err(TryRecvError::Disconnected))
+  }
+}
+
+/// Calls `read` on the given interval, sending its result right away and then each time it changes.
+/// Polling stops when the watcher is dropped.
+pub fn watch<T, F>(interval: Duration, read: F) -> Watcher<T>
+where
+  T: PartialEq + Clone + Send + 'static,
+  F: Fn() -> T + Send + 'static,
+{
+  watch_with(read, move |cancel| cancel.sleep(interval))
+}
+
+/// Calls `read` right away and then each time `wait` returns `true`,
+/// sending its result when it changes.
+///
+/// `wait` blocks until the settings may have changed, and returns `false` once the watcher
+/// is dropped, which it can check with the given [`Cancel`].
+pub fn watch_with<T, F, W>(read: F, mut wait: W) -> Watcher<T>
+where
+  T: PartialEq + Clone + Send + 'static,
+  F: Fn() -> T + Send + 'static,
+  W: FnMut(&Cancel) -> bool + Send + 'static,
+{
+  let (tx, rx) = channel(1);
+  let (cancel_tx, cancel_rx) = std_mpsc::channel();
+  let cancel = Cancel(cancel_rx);
+  thread::spawn(move || {
+    let mut last = None;
+    loop {
+      let current = read();
+      if last.as_ref() != Some(&current) {
+        last.replace(current.clone());
+        if tx.blocking_send(current).is_err() {
+          break;
+        }
+      }
+      if !wait(&cancel) {
+        break;
+      }
+    }
+  });
+  Watcher {
+    receiver: rx,
+    _cancel: cancel_tx,
+  }
+}
@@ -0,0 +1,98 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::cell::RefCell;
+use uuid::Uuid;
+
+/// The buffers of a serialized invoke response, identified by their `tauri-buffer` protocol id.
+pub(crate) type Buffers = Vec<(String, Vec<u8>)>;
+
+thread_local! {
+  /// The buffers collected by the running [`collect_buffers`] call, if any.
+  static BUFFERS: RefCell<Option<Buffers>> = RefCell::new(None);
+}
+
+/// Binary data sent to the webview without JSON serialization.
+///
+/// When a command response contains it, as the returned value or nested in it,
+/// the webview reads the bytes from the `tauri-buffer` protocol
+/// and the invoke promise resolves it to an `Uint8Array`.
+/// Anywhere else, e.g. in an event payload, it's serialized as an array of numbers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// #[tauri::command]
+/// fn read_image(path: String) -> Result<tauri::Binary, String> {
+///   std::fs::read(path)
+///     .map(Into::into)
+///     .map_err(|e| e.to_string())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Binary(pub Vec<u8>);
+
+impl From<Vec<u8>> for Binary {
+  fn from(bytes: Vec<u8>) -> Self {
+    Self(bytes)
+  }
+}
+
+impl From<Binary> for Vec<u8> {
+  fn from(binary: Binary) -> Self {
+    binary.0
+  }
+}
+
+impl Serialize for Binary {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let id = BUFFERS.with(|buffers| {
+      buffers.borrow_mut().as_mut().map(|buffers| {
+        let id = Uuid::new_v4().to_simple().to_string();
+        buffers.push((id.clone(), self.0.clone()));
+        id
+      })
+    });
+    match id {
+      Some(id) => {
+        let mut state = serializer.serialize_struct("Binary", 1)?;
+        state.serialize_field("__tauriBuffer", &id)?;
+        state.end()
+      }
+      None => self.0.serialize(serializer),
+    }
+  }
+}
+
+/// Runs the closure serializing an invoke response,
+/// replacing its [`Binary`] values with their buffer id and returning the buffers.
+pub(crate) fn collect_buffers<T, F: FnOnce() -> T>(f: F) -> (T, Buffers) {
+  let previous = BUFFERS.with(|buffers| buffers.replace(Some(Vec::new())));
+  let result = f();
+  let buffers = BUFFERS
+    .with(|buffers| buffers.replace(previous))
+    .unwrap_or_default();
+  (result, buffers)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn collects_response_buffers() {
+    let response = json!({ "image": Binary(vec![1, 2, 3]), "name": "icon.png" });
+    assert_eq!(response["image"], json!([1, 2, 3]));
+
+    let (response, buffers) =
+      collect_buffers(|| serde_json::to_value(vec![Binary(vec![1, 2]), Binary(vec![3])]).unwrap());
+    assert_eq!(buffers.len(), 2);
+    assert_eq!(response[0]["__tauriBuffer"], json!(buffers[0].0));
+    assert_eq!(buffers[0].1, vec![1, 2]);
+    assert_eq!(response[1]["__tauriBuffer"], json!(buffers[1].0));
+    assert_eq!(buffers[1].1, vec![3]);
+  }
+}
@@ -4,22 +4,33 @@
 
 use crate::{
   api::{config::Config, PackageInfo},
-  hooks::InvokeMessage,
+  hooks::{BuiltinEndpoint, InvokeMessage},
+  sealed::ManagerBase,
   Params,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 mod app;
+mod bluetooth;
 mod cli;
+mod clipboard;
 mod dialog;
 mod event;
 #[allow(unused_imports)]
 mod file_system;
+mod font;
+mod geolocation;
 mod global_shortcut;
 mod http;
 mod internal;
+mod license;
 mod notification;
+mod path;
+mod printer;
+mod serial;
+mod share;
 mod shell;
+mod system;
 mod window;
 
 /// The response for a JS `invoke` call.
@@ -47,22 +58,39 @@ enum Module {
   Dialog(dialog::Cmd),
   Cli(cli::Cmd),
   Notification(notification::Cmd),
+  Path(path::Cmd),
   Http(http::Cmd),
   GlobalShortcut(global_shortcut::Cmd),
+  Clipboard(clipboard::Cmd),
+  Font(font::Cmd),
+  Printer(printer::Cmd),
+  Share(share::Cmd),
+  Serial(serial::Cmd),
+  Bluetooth(bluetooth::Cmd),
+  Geolocation(geolocation::Cmd),
+  System(system::Cmd),
+  License(license::Cmd),
 }
 
 impl Module {
   fn run<M: Params>(self, message: InvokeMessage<M>, config: &Config, package_info: PackageInfo) {
     let window = message.window();
     match self {
-      Self::App(cmd) => message.respond_async(async move {
+      Self::App(cmd) => {
+        let identifier = config.tauri.bundle.identifier.clone();
+        message.respond_async(async move {
+          cmd
+            .run(window, package_info, identifier)
+            .and_then(|r| r.json)
+            .map_err(|e| e.to_string())
+        })
+      }
+      Self::Fs(cmd) => message.respond_async(async move {
         cmd
-          .run(package_info)
+          .run(window)
           .and_then(|r| r.json)
           .map_err(|e| e.to_string())
       }),
-      Self::Fs(cmd) => message
-        .respond_async(async move { cmd.run().and_then(|r| r.json).map_err(|e| e.to_string()) }),
       Self::Window(cmd) => message.respond_async(async move {
         cmd
           .run(window)
@@ -88,8 +116,13 @@ impl Module {
           .and_then(|r| r.json)
           .map_err(|e| e.to_string())
       }),
-      Self::Dialog(cmd) => message
-        .respond_async(async move { cmd.run().and_then(|r| r.json).map_err(|e| e.to_string()) }),
+      Self::Dialog(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .await
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
       Self::Cli(cmd) => {
         if let Some(cli_config) = config.tauri.cli.clone() {
           message.respond_async(async move {
@@ -104,14 +137,20 @@ impl Module {
         let identifier = config.tauri.bundle.identifier.clone();
         message.respond_async(async move {
           cmd
-            .run(identifier)
+            .run(window, identifier)
             .and_then(|r| r.json)
             .map_err(|e| e.to_string())
         })
       }
+      Self::Path(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
       Self::Http(cmd) => message.respond_async(async move {
         cmd
-          .run()
+          .run(window)
           .await
           .and_then(|r| r.json)
           .map_err(|e| e.to_string())
@@ -122,15 +161,89 @@ impl Module {
           .and_then(|r| r.json)
           .map_err(|e| e.to_string())
       }),
+      Self::Clipboard(cmd) => message
+        .respond_async(async move { cmd.run().and_then(|r| r.json).map_err(|e| e.to_string()) }),
+      Self::Font(cmd) => message
+        .respond_async(async move { cmd.run().and_then(|r| r.json).map_err(|e| e.to_string()) }),
+      Self::Geolocation(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::Printer(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::Share(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::Serial(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::Bluetooth(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .await
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::System(cmd) => message.respond_async(async move {
+        cmd
+          .run(window)
+          .and_then(|r| r.json)
+          .map_err(|e| e.to_string())
+      }),
+      Self::License(cmd) => {
+        let identifier = config.tauri.bundle.identifier.clone();
+        let license_config = config.tauri.license.clone();
+        message.respond_async(async move {
+          cmd
+            .run(identifier, license_config)
+            .and_then(|r| r.json)
+            .map_err(|e| e.to_string())
+        })
+      }
     }
   }
 }
 
+/// Handles an invoke to a Tauri API module, using the handler registered with
+/// [`crate::Manager::register_endpoint`] if any.
 pub(crate) fn handle<M: Params>(
   module: String,
   message: InvokeMessage<M>,
   config: &Config,
   package_info: &PackageInfo,
+) {
+  let window = message.window();
+  if !window.manager().is_module_allowed(window.label(), &module) {
+    message.reject(
+      crate::Error::WindowApiNotAllowlisted(module, window.label().to_string()).to_string(),
+    );
+    return;
+  }
+  let handler = window.manager().endpoint_handler(&module);
+  match handler {
+    Some(handler) => handler(message, BuiltinEndpoint::new(module)),
+    None => handle_builtin(module, message, config, package_info),
+  }
+}
+
+/// Handles an invoke to a Tauri API module with the built-in handler.
+pub(crate) fn handle_builtin<M: Params>(
+  module: String,
+  message: InvokeMessage<M>,
+  config: &Config,
+  package_info: &PackageInfo,
 ) {
   let mut payload = message.payload();
   if let JsonValue::Object(ref mut obj) = payload {
@@ -5,7 +5,12 @@
 use std::process::exit;
 
 use super::InvokeResponse;
-use crate::api::{app::restart_application, PackageInfo};
+use crate::{
+  api::{
+    app::restart_application, default_handler::HandlerTarget, sound::SystemSound, PackageInfo,
+  },
+  Manager, Params, Window,
+};
 use serde::Deserialize;
 
 /// The API descriptor.
@@ -18,6 +23,18 @@ pub enum Cmd {
   GetAppName,
   /// Get Tauri Version
   GetTauriVersion,
+  /// Get the random identifier of this install
+  GetInstallId,
+  /// Get the desktop appearance preferences
+  GetAppearance,
+  /// Get the time since the user last used the keyboard or the mouse
+  GetIdleTime,
+  /// Play a sound of the desktop theme
+  PlaySound { sound: SystemSound },
+  /// Check whether the app is the default handler of a URL scheme or file extension
+  IsDefaultHandler { target: HandlerTarget },
+  /// Ask the system to make the app the default handler of a URL scheme or file extension
+  RequestDefaultHandler { target: HandlerTarget },
   /// Relaunch application
   Relaunch,
   /// Close application with provided exit_code
@@ -26,11 +43,39 @@ pub enum Cmd {
 }
 
 impl Cmd {
-  pub fn run(self, package_info: PackageInfo) -> crate::Result<InvokeResponse> {
+  pub fn run<M: Params>(
+    self,
+    window: Window<M>,
+    package_info: PackageInfo,
+    identifier: String,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::GetAppVersion => Ok(package_info.version.into()),
       Self::GetAppName => Ok(package_info.name.into()),
       Self::GetTauriVersion => Ok(env!("CARGO_PKG_VERSION").into()),
+      Self::GetInstallId => crate::api::app::install_id(window.config())
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::GetAppearance => Ok(crate::api::appearance::preferences().into()),
+      Self::GetIdleTime => Ok(
+        crate::api::idle::idle_time()
+          .map(|idle_time| idle_time.as_millis() as u64)
+          .into(),
+      ),
+      Self::PlaySound { sound } => {
+        crate::api::sound::play(sound);
+        Ok(().into())
+      }
+      Self::IsDefaultHandler { target } => {
+        crate::api::default_handler::is_default(&identifier, &target)
+          .map(Into::into)
+          .map_err(Into::into)
+      }
+      Self::RequestDefaultHandler { target } => {
+        crate::api::default_handler::request_default(&identifier, &target)
+          .map(Into::into)
+          .map_err(Into::into)
+      }
       Self::Relaunch => Ok({
         restart_application(None);
         ().into()
@@ -0,0 +1,165 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+#[cfg(feature = "bluetooth")]
+use crate::{
+  api::bluetooth::{self, Connection, Notification},
+  sealed::ManagerBase,
+};
+use crate::{Params, Window};
+use serde::Deserialize;
+
+#[cfg(feature = "bluetooth")]
+use serde::Serialize;
+#[cfg(feature = "bluetooth")]
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+use uuid::Uuid;
+
+/// The connected devices, by device identifier.
+#[cfg(feature = "bluetooth")]
+#[derive(Default)]
+struct Connections(Mutex<HashMap<String, Arc<Connection>>>);
+
+#[cfg(feature = "bluetooth")]
+fn connection<M: Params>(window: &Window<M>, id: &str) -> crate::Result<Arc<Connection>> {
+  window
+    .manager()
+    .state()
+    .get_or_default::<Connections>()
+    .0
+    .lock()
+    .unwrap()
+    .get(id)
+    .cloned()
+    .ok_or_else(|| crate::Error::BluetoothDeviceNotConnected(id.to_string()))
+}
+
+/// The payload of the `tauri://bluetooth-notification` event.
+#[cfg(feature = "bluetooth")]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BluetoothNotification {
+  id: String,
+  #[serde(flatten)]
+  notification: Notification,
+}
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Scan for devices during the timeout, in milliseconds.
+  Scan { timeout: u64 },
+  /// Connect to a device, emitting its notifications to the window.
+  Connect { id: String },
+  /// List the characteristics of a connected device.
+  Characteristics { id: String },
+  /// Read a characteristic.
+  Read { id: String, characteristic: Uuid },
+  /// Write a characteristic.
+  #[serde(rename_all = "camelCase")]
+  Write {
+    id: String,
+    characteristic: Uuid,
+    value: Vec<u8>,
+    with_response: bool,
+  },
+  /// Enable the notifications of a characteristic.
+  Subscribe { id: String, characteristic: Uuid },
+  /// Disable the notifications of a characteristic.
+  Unsubscribe { id: String, characteristic: Uuid },
+  /// Disconnect from a device.
+  Disconnect { id: String },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub async fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    #[cfg(feature = "bluetooth")]
+    return match self {
+      Self::Scan { timeout } => bluetooth::scan(Duration::from_millis(timeout))
+        .await
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Connect { id } => connect(window, id).await.map(Into::into),
+      Self::Characteristics { id } => Ok(connection(&window, &id)?.characteristics().into()),
+      Self::Read { id, characteristic } => connection(&window, &id)?
+        .read(characteristic)
+        .await
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Write {
+        id,
+        characteristic,
+        value,
+        with_response,
+      } => connection(&window, &id)?
+        .write(characteristic, &value, with_response)
+        .await
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Subscribe { id, characteristic } => connection(&window, &id)?
+        .subscribe(characteristic)
+        .await
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Unsubscribe { id, characteristic } => connection(&window, &id)?
+        .unsubscribe(characteristic)
+        .await
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Disconnect { id } => {
+        let connections = window.manager().state().get_or_default::<Connections>();
+        let connection = connections.0.lock().unwrap().remove(&id);
+        match connection {
+          Some(connection) => connection
+            .disconnect()
+            .await
+            .map(Into::into)
+            .map_err(Into::into),
+          None => Ok(().into()),
+        }
+      }
+    };
+    #[cfg(not(feature = "bluetooth"))]
+    Err(crate::Error::ApiNotAllowlisted(
+      "bluetooth > all".to_string(),
+    ))
+  }
+}
+
+/// Connects to the device, emitting its notifications to the window until it disconnects.
+#[cfg(feature = "bluetooth")]
+async fn connect<M: Params>(window: Window<M>, id: String) -> crate::Result<()> {
+  let connection = bluetooth::connect(&id).await?;
+  let mut rx = connection.notifications().await?;
+  window
+    .manager()
+    .state()
+    .get_or_default::<Connections>()
+    .0
+    .lock()
+    .unwrap()
+    .insert(id.clone(), Arc::new(connection));
+  crate::async_runtime::spawn(async move {
+    while let Some(notification) = rx.recv().await {
+      let emitted = window.emit_internal(
+        "tauri://bluetooth-notification".to_string(),
+        Some(BluetoothNotification {
+          id: id.clone(),
+          notification,
+        }),
+      );
+      if emitted.is_err() {
+        break;
+      }
+    }
+  });
+  Ok(())
+}
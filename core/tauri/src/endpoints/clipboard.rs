@@ -0,0 +1,45 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+use serde::Deserialize;
+
+#[cfg(any(clipboard_write_text, clipboard_read_text))]
+use crate::api::clipboard;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Write a text string to the clipboard.
+  WriteText { data: String },
+  /// Read the text on the clipboard.
+  ReadText,
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run(self) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::WriteText { data } => {
+        #[cfg(clipboard_write_text)]
+        return clipboard::write_text(data)
+          .map(Into::into)
+          .map_err(Into::into);
+        #[cfg(not(clipboard_write_text))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "clipboard > writeText".to_string(),
+        ))
+      }
+      Self::ReadText => {
+        #[cfg(clipboard_read_text)]
+        return clipboard::read_text().map(Into::into).map_err(Into::into);
+        #[cfg(not(clipboard_read_text))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "clipboard > readText".to_string(),
+        ))
+      }
+    }
+  }
+}
@@ -3,17 +3,25 @@
 // SPDX-License-Identifier: MIT
 
 use super::InvokeResponse;
+use crate::api::dialog::DefaultButton;
+#[cfg(any(dialog_message, dialog_ask, dialog_confirm))]
+use crate::api::dialog::MessageDialogBuilder;
 #[cfg(any(dialog_open, dialog_save))]
-use crate::api::dialog::FileDialogBuilder;
-use crate::api::dialog::{ask as ask_dialog, message as message_dialog, AskResponse};
+use crate::{api::dialog::FileDialogBuilder, sealed::ManagerBase};
+use crate::{Params, Window};
 use serde::Deserialize;
+#[cfg(any(dialog_open, dialog_save, dialog_message, dialog_ask, dialog_confirm))]
+use tokio::sync::oneshot::channel;
 
 use std::path::PathBuf;
 
+/// A filter of the files shown on the dialog.
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DialogFilter {
+  /// The label of the filter, e.g. `Images`.
   name: String,
+  /// The extensions of the filter, without the dot, e.g. `["png", "jpg"]`.
   extensions: Vec<String>,
 }
 
@@ -30,7 +38,7 @@ pub struct OpenDialogOptions {
   /// Whether the dialog is a directory selection (`true` value) or file selection (`false` value).
   #[serde(default)]
   pub directory: bool,
-  /// The initial path of the dialog.
+  /// The initial path of the dialog: the starting directory, or the preselected file.
   pub default_path: Option<PathBuf>,
 }
 
@@ -41,7 +49,8 @@ pub struct SaveDialogOptions {
   /// The filters of the dialog.
   #[serde(default)]
   pub filters: Vec<DialogFilter>,
-  /// The initial path of the dialog.
+  /// The initial path of the dialog: the starting directory, or the suggested file name
+  /// with an optional directory, e.g. `report.pdf`.
   pub default_path: Option<PathBuf>,
 }
 
@@ -50,104 +59,204 @@ pub struct SaveDialogOptions {
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
   /// The open dialog API.
-  OpenDialog {
-    options: OpenDialogOptions,
-  },
+  OpenDialog { options: OpenDialogOptions },
   /// The save dialog API.
-  SaveDialog {
-    options: SaveDialogOptions,
-  },
+  SaveDialog { options: SaveDialogOptions },
+  /// The message dialog API.
   MessageDialog {
+    title: Option<String>,
     message: String,
   },
+  /// The yes/no dialog API.
+  #[serde(rename_all = "camelCase")]
   AskDialog {
     title: Option<String>,
     message: String,
+    #[serde(default)]
+    default_button: DefaultButton,
+  },
+  /// The ok/cancel dialog API.
+  #[serde(rename_all = "camelCase")]
+  ConfirmDialog {
+    title: Option<String>,
+    message: String,
+    #[serde(default)]
+    default_button: DefaultButton,
   },
 }
 
 impl Cmd {
-  pub fn run(self) -> crate::Result<InvokeResponse> {
+  #[allow(unused_variables)]
+  pub async fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
     match self {
       Self::OpenDialog { options } => {
         #[cfg(dialog_open)]
-        return open(options);
+        return open(window, options).await;
         #[cfg(not(dialog_open))]
         return Err(crate::Error::ApiNotAllowlisted("dialog > open".to_string()));
       }
       Self::SaveDialog { options } => {
         #[cfg(dialog_save)]
-        return save(options);
+        return save(window, options).await;
         #[cfg(not(dialog_save))]
         return Err(crate::Error::ApiNotAllowlisted("dialog > save".to_string()));
       }
-      Self::MessageDialog { message } => {
-        let exe = std::env::current_exe()?;
-        let app_name = exe
-          .file_stem()
-          .expect("failed to get binary filename")
-          .to_string_lossy()
-          .to_string();
-        message_dialog(app_name, message);
-        Ok(().into())
+      Self::MessageDialog { title, message } => {
+        #[cfg(dialog_message)]
+        {
+          let (tx, rx) = channel();
+          message_dialog(&window, title, message)?.show(move || {
+            let _ = tx.send(());
+          });
+          let _ = rx.await;
+          return Ok(().into());
+        }
+        #[cfg(not(dialog_message))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "dialog > message".to_string(),
+        ))
+      }
+      Self::AskDialog {
+        title,
+        message,
+        default_button,
+      } => {
+        #[cfg(dialog_ask)]
+        {
+          let (tx, rx) = channel();
+          message_dialog(&window, title, message)?
+            .default_button(default_button)
+            .ask(move |answer| {
+              let _ = tx.send(answer);
+            });
+          return Ok(rx.await.unwrap_or(false).into());
+        }
+        #[cfg(not(dialog_ask))]
+        Err(crate::Error::ApiNotAllowlisted("dialog > ask".to_string()))
       }
-      Self::AskDialog { title, message } => {
-        let exe = std::env::current_exe()?;
-        let answer = ask(
-          title.unwrap_or_else(|| {
-            exe
-              .file_stem()
-              .expect("failed to get binary filename")
-              .to_string_lossy()
-              .to_string()
-          }),
-          message,
-        )?;
-        Ok(answer)
+      Self::ConfirmDialog {
+        title,
+        message,
+        default_button,
+      } => {
+        #[cfg(dialog_confirm)]
+        {
+          let (tx, rx) = channel();
+          message_dialog(&window, title, message)?
+            .default_button(default_button)
+            .confirm(move |confirmed| {
+              let _ = tx.send(confirmed);
+            });
+          return Ok(rx.await.unwrap_or(false).into());
+        }
+        #[cfg(not(dialog_confirm))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "dialog > confirm".to_string(),
+        ))
       }
     }
   }
 }
 
+/// The title of the message dialogs without one, the name of the executable.
+#[cfg(any(dialog_message, dialog_ask, dialog_confirm))]
+fn default_title() -> crate::Result<String> {
+  let exe = std::env::current_exe()?;
+  Ok(
+    exe
+      .file_stem()
+      .expect("failed to get binary filename")
+      .to_string_lossy()
+      .to_string(),
+  )
+}
+
+/// A message dialog modal to the window that invoked it.
+#[cfg(any(dialog_message, dialog_ask, dialog_confirm))]
+fn message_dialog<M: Params>(
+  window: &Window<M>,
+  title: Option<String>,
+  message: String,
+) -> crate::Result<MessageDialogBuilder> {
+  let title = match title {
+    Some(title) => title,
+    None => default_title()?,
+  };
+  Ok(MessageDialogBuilder::new(title, message).parent(window))
+}
+
 /// Shows an open dialog.
+///
+/// The picked paths are allowed on the file system scope.
 #[cfg(dialog_open)]
-pub fn open(options: OpenDialogOptions) -> crate::Result<InvokeResponse> {
+pub async fn open<M: Params>(
+  window: Window<M>,
+  options: OpenDialogOptions,
+) -> crate::Result<InvokeResponse> {
   let mut dialog_builder = FileDialogBuilder::new();
   if let Some(default_path) = options.default_path {
-    dialog_builder = dialog_builder.set_directory(default_path);
+    dialog_builder = dialog_builder.set_default_path(default_path);
   }
   for filter in options.filters {
     let extensions: Vec<&str> = filter.extensions.iter().map(|s| &**s).collect();
     dialog_builder = dialog_builder.add_filter(filter.name, &extensions);
   }
-  let response = if options.directory {
-    dialog_builder.pick_folder().into()
-  } else if options.multiple {
-    dialog_builder.pick_files().into()
+  let (tx, rx) = channel();
+  let multiple = options.multiple && !options.directory;
+  if options.directory {
+    dialog_builder.pick_folder(move |folder| {
+      let _ = tx.send(folder.map(|path| vec![path]));
+    });
+  } else if multiple {
+    dialog_builder.pick_files(move |files| {
+      let _ = tx.send(files);
+    });
   } else {
-    dialog_builder.pick_file().into()
+    dialog_builder.pick_file(move |file| {
+      let _ = tx.send(file.map(|path| vec![path]));
+    });
+  }
+  let paths = rx.await.ok().flatten();
+
+  let scope = window.manager().fs_scope();
+  for path in paths.iter().flatten() {
+    if options.directory {
+      scope.allow_directory(path, true);
+    } else {
+      scope.allow_file(path);
+    }
+  }
+  let response = if multiple {
+    paths.into()
+  } else {
+    paths.and_then(|paths| paths.into_iter().next()).into()
   };
   Ok(response)
 }
 
 /// Shows a save dialog.
+///
+/// The picked path is allowed on the file system scope.
 #[cfg(dialog_save)]
-pub fn save(options: SaveDialogOptions) -> crate::Result<InvokeResponse> {
+pub async fn save<M: Params>(
+  window: Window<M>,
+  options: SaveDialogOptions,
+) -> crate::Result<InvokeResponse> {
   let mut dialog_builder = FileDialogBuilder::new();
   if let Some(default_path) = options.default_path {
-    dialog_builder = dialog_builder.set_directory(default_path);
+    dialog_builder = dialog_builder.set_default_path(default_path);
   }
   for filter in options.filters {
     let extensions: Vec<&str> = filter.extensions.iter().map(|s| &**s).collect();
     dialog_builder = dialog_builder.add_filter(filter.name, &extensions);
   }
-  Ok(dialog_builder.save_file().into())
-}
-
-/// Shows a dialog with a yes/no question.
-pub fn ask(title: String, message: String) -> crate::Result<InvokeResponse> {
-  match ask_dialog(title, message) {
-    AskResponse::Yes => Ok(true.into()),
-    _ => Ok(false.into()),
+  let (tx, rx) = channel();
+  dialog_builder.save_file(move |file| {
+    let _ = tx.send(file);
+  });
+  let file = rx.await.ok().flatten();
+  if let Some(path) = &file {
+    window.manager().fs_scope().allow_file(path);
   }
+  Ok(file.into())
 }
@@ -4,6 +4,7 @@
 
 use crate::{endpoints::InvokeResponse, sealed::ManagerBase, Manager, Params, Window};
 use serde::Deserialize;
+use serde_json::Value as JsonValue;
 
 /// The API descriptor.
 #[derive(Deserialize)]
@@ -16,12 +17,20 @@ pub enum Cmd {
   Unlisten { event_id: u64 },
   /// Emit an event to the webview associated with the given window.
   /// If the window_label is omitted, the event will be triggered on all listeners.
+  /// The webviews receive it with the label of the emitting window.
   #[serde(rename_all = "camelCase")]
   Emit {
     event: String,
     window_label: Option<String>,
     payload: Option<String>,
   },
+  /// Post a message to another window, see [`crate::Window::post_message`].
+  PostMessage {
+    target: String,
+    data: JsonValue,
+    #[serde(default)]
+    buffers: Vec<Vec<u8>>,
+  },
 }
 
 impl Cmd {
@@ -30,9 +39,19 @@ impl Cmd {
       Self::Listen { event, handler } => {
         let event_id = rand::random();
         window.eval(&listen_js(&window, event, event_id, handler))?;
+        window
+          .manager()
+          .register_js_listener(window.label().clone(), event_id);
         Ok(event_id.into())
       }
       Self::Unlisten { event_id } => {
+        // a window can only remove its own listeners
+        if !window
+          .manager()
+          .unregister_js_listener(window.label(), event_id)
+        {
+          return Err(crate::Error::EventListenerNotFound(event_id));
+        }
         window.eval(&unlisten_js(&window, event_id))?;
         Ok(().into())
       }
@@ -54,13 +73,30 @@ impl Cmd {
         // dispatch the event to Rust listeners
         window.trigger(e.clone(), payload.clone());
 
+        let source = window.label().clone();
         if let Some(target) = window_label {
-          window.emit_to(&target, e, payload)?;
+          window
+            .get_window(&target)
+            .ok_or(crate::Error::WebviewNotFound)?
+            .emit_from_internal(Some(&source), e, payload)?;
         } else {
-          window.emit_all(e, payload)?;
+          window
+            .manager()
+            .emit_filter_from(&source, e, payload, |_| true)?;
         }
         Ok(().into())
       }
+      Self::PostMessage {
+        target,
+        data,
+        buffers,
+      } => {
+        let target: M::Label = target.parse().map_err(|_| crate::Error::WebviewNotFound)?;
+        window
+          .manager()
+          .post_message(window.label(), &target, data, buffers)?;
+        Ok(().into())
+      }
     }
   }
 }
@@ -71,7 +107,7 @@ pub fn unlisten_js<M: Params>(window: &Window<M>, event_id: u64) -> String {
       for (var event in (window['{listeners}'] || {{}})) {{
         var listeners = (window['{listeners}'] || {{}})[event]
         if (listeners) {{
-          window['{listeners}'][event] = window['{listeners}'][event].filter(function (e) {{ e.id !== {event_id} }})
+          window['{listeners}'][event] = window['{listeners}'][event].filter(function (e) {{ return e.id !== {event_id} }})
         }}
       }}
     ",
@@ -3,7 +3,14 @@
 // SPDX-License-Identifier: MIT
 
 use super::InvokeResponse;
-use crate::api::path::BaseDirectory;
+use crate::{
+  api::{
+    config::{Config, PermissionPrompt},
+    path::BaseDirectory,
+  },
+  sealed::ManagerBase,
+  Params, Window,
+};
 
 use crate::api::{dir, file, path::resolve_path};
 use serde::{Deserialize, Serialize};
@@ -55,6 +62,23 @@ pub enum Cmd {
     contents: String,
     options: Option<FileOperationOptions>,
   },
+  /// The streaming read file API, emitting the file chunks with the `tauri://file-chunk` event.
+  #[serde(rename_all = "camelCase")]
+  ReadFileStream {
+    path: PathBuf,
+    /// Identifies the `tauri://file-chunk` events of the read.
+    stream_id: u32,
+    chunk_size: Option<usize>,
+    options: Option<FileOperationOptions>,
+  },
+  /// The streaming write file API, writing one base64 encoded chunk.
+  WriteFileStream {
+    path: PathBuf,
+    contents: String,
+    /// Whether the chunk is appended to the file, or replaces its contents.
+    append: bool,
+    options: Option<FileOperationOptions>,
+  },
   /// The read dir API.
   ReadDir {
     path: PathBuf,
@@ -88,6 +112,22 @@ pub enum Cmd {
     new_path: PathBuf,
     options: Option<FileOperationOptions>,
   },
+  /// The metadata API.
+  Metadata {
+    path: PathBuf,
+    options: Option<FileOperationOptions>,
+  },
+  /// The create symbolic link API.
+  CreateSymlink {
+    target: PathBuf,
+    path: PathBuf,
+    options: Option<FileOperationOptions>,
+  },
+  /// The read symbolic link API.
+  ReadLink {
+    path: PathBuf,
+    options: Option<FileOperationOptions>,
+  },
   /// The resolve path API
   ResolvePath {
     path: String,
@@ -96,11 +136,12 @@ pub enum Cmd {
 }
 
 impl Cmd {
-  pub fn run(self) -> crate::Result<InvokeResponse> {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
     match self {
       Self::ReadTextFile { path, options } => {
         #[cfg(fs_read_text_file)]
-        return read_text_file(path, options).map(Into::into);
+        return read_text_file(&window, path, options).map(Into::into);
         #[cfg(not(fs_read_text_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > readTextFile".to_string(),
@@ -108,7 +149,7 @@ impl Cmd {
       }
       Self::ReadBinaryFile { path, options } => {
         #[cfg(fs_read_binary_file)]
-        return read_binary_file(path, options).map(Into::into);
+        return read_binary_file(&window, path, options).map(Into::into);
         #[cfg(not(fs_read_binary_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "readBinaryFile".to_string(),
@@ -120,7 +161,7 @@ impl Cmd {
         options,
       } => {
         #[cfg(fs_write_file)]
-        return write_file(path, contents, options).map(Into::into);
+        return write_file(&window, path, contents, options).map(Into::into);
         #[cfg(not(fs_write_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > writeFile".to_string(),
@@ -132,7 +173,33 @@ impl Cmd {
         options,
       } => {
         #[cfg(fs_write_binary_file)]
-        return write_binary_file(path, contents, options).map(Into::into);
+        return write_binary_file(&window, path, contents, options).map(Into::into);
+        #[cfg(not(fs_write_binary_file))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "writeBinaryFile".to_string(),
+        ))
+      }
+      Self::ReadFileStream {
+        path,
+        stream_id,
+        chunk_size,
+        options,
+      } => {
+        #[cfg(fs_read_binary_file)]
+        return read_file_stream(&window, path, stream_id, chunk_size, options).map(Into::into);
+        #[cfg(not(fs_read_binary_file))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "readBinaryFile".to_string(),
+        ))
+      }
+      Self::WriteFileStream {
+        path,
+        contents,
+        append,
+        options,
+      } => {
+        #[cfg(fs_write_binary_file)]
+        return write_file_stream(&window, path, contents, append, options).map(Into::into);
         #[cfg(not(fs_write_binary_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "writeBinaryFile".to_string(),
@@ -140,7 +207,7 @@ impl Cmd {
       }
       Self::ReadDir { path, options } => {
         #[cfg(fs_read_dir)]
-        return read_dir(path, options).map(Into::into);
+        return read_dir(&window, path, options).map(Into::into);
         #[cfg(not(fs_read_dir))]
         Err(crate::Error::ApiNotAllowlisted("fs > readDir".to_string()))
       }
@@ -150,13 +217,13 @@ impl Cmd {
         options,
       } => {
         #[cfg(fs_copy_file)]
-        return copy_file(source, destination, options).map(Into::into);
+        return copy_file(&window, source, destination, options).map(Into::into);
         #[cfg(not(fs_copy_file))]
         Err(crate::Error::ApiNotAllowlisted("fs > copyFile".to_string()))
       }
       Self::CreateDir { path, options } => {
         #[cfg(fs_create_dir)]
-        return create_dir(path, options).map(Into::into);
+        return create_dir(&window, path, options).map(Into::into);
         #[cfg(not(fs_create_dir))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > createDir".to_string(),
@@ -164,7 +231,7 @@ impl Cmd {
       }
       Self::RemoveDir { path, options } => {
         #[cfg(fs_remove_dir)]
-        return remove_dir(path, options).map(Into::into);
+        return remove_dir(&window, path, options).map(Into::into);
         #[cfg(not(fs_remove_dir))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > removeDir".to_string(),
@@ -172,7 +239,7 @@ impl Cmd {
       }
       Self::RemoveFile { path, options } => {
         #[cfg(fs_remove_file)]
-        return remove_file(path, options).map(Into::into);
+        return remove_file(&window, path, options).map(Into::into);
         #[cfg(not(fs_remove_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > removeFile".to_string(),
@@ -184,15 +251,37 @@ impl Cmd {
         options,
       } => {
         #[cfg(fs_rename_file)]
-        return rename_file(old_path, new_path, options).map(Into::into);
+        return rename_file(&window, old_path, new_path, options).map(Into::into);
         #[cfg(not(fs_rename_file))]
         Err(crate::Error::ApiNotAllowlisted(
           "fs > renameFile".to_string(),
         ))
       }
+      Self::Metadata { path, options } => {
+        #[cfg(fs_metadata)]
+        return metadata(&window, path, options).map(Into::into);
+        #[cfg(not(fs_metadata))]
+        Err(crate::Error::ApiNotAllowlisted("fs > metadata".to_string()))
+      }
+      Self::CreateSymlink {
+        target,
+        path,
+        options,
+      } => {
+        #[cfg(fs_symlink)]
+        return create_symlink(&window, target, path, options).map(Into::into);
+        #[cfg(not(fs_symlink))]
+        Err(crate::Error::ApiNotAllowlisted("fs > symlink".to_string()))
+      }
+      Self::ReadLink { path, options } => {
+        #[cfg(fs_symlink)]
+        return read_link(&window, path, options).map(Into::into);
+        #[cfg(not(fs_symlink))]
+        Err(crate::Error::ApiNotAllowlisted("fs > symlink".to_string()))
+      }
       Self::ResolvePath { path, directory } => {
         #[cfg(fs_path)]
-        return resolve_path_handler(path, directory).map(Into::into);
+        return resolve_path_handler(window.manager().config(), path, directory).map(Into::into);
         #[cfg(not(fs_path))]
         Err(crate::Error::ApiNotAllowlisted("fs > pathApi".to_string()))
       }
@@ -202,7 +291,8 @@ impl Cmd {
 
 /// Reads a directory.
 #[cfg(fs_read_dir)]
-pub fn read_dir(
+pub fn read_dir<M: Params>(
+  window: &Window<M>,
   path: PathBuf,
   options: Option<DirOperationOptions>,
 ) -> crate::Result<Vec<dir::DiskEntry>> {
@@ -211,36 +301,52 @@ pub fn read_dir(
   } else {
     (false, None)
   };
-  dir::read_dir(resolve_path(path, dir)?, recursive).map_err(crate::Error::FailedToExecuteApi)
+  dir::read_dir(
+    resolve_scoped_path(window, path, dir, PermissionPrompt::FsReadOutsideScope)?,
+    recursive,
+  )
+  .map_err(crate::Error::FailedToExecuteApi)
 }
 
 /// Copies a file.
 #[cfg(fs_copy_file)]
-pub fn copy_file(
+pub fn copy_file<M: Params>(
+  window: &Window<M>,
   source: PathBuf,
   destination: PathBuf,
   options: Option<FileOperationOptions>,
 ) -> crate::Result<()> {
-  let (src, dest) = match options.and_then(|o| o.dir) {
-    Some(dir) => (
-      resolve_path(source, Some(dir.clone()))?,
-      resolve_path(destination, Some(dir))?,
-    ),
-    None => (source, destination),
-  };
+  let dir = options.and_then(|o| o.dir);
+  let src = resolve_scoped_path(
+    window,
+    source,
+    dir.clone(),
+    PermissionPrompt::FsReadOutsideScope,
+  )?;
+  let dest = resolve_scoped_path(
+    window,
+    destination,
+    dir,
+    PermissionPrompt::FsWriteOutsideScope,
+  )?;
   fs::copy(src, dest)?;
   Ok(())
 }
 
 /// Creates a directory.
 #[cfg(fs_create_dir)]
-pub fn create_dir(path: PathBuf, options: Option<DirOperationOptions>) -> crate::Result<()> {
+pub fn create_dir<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  options: Option<DirOperationOptions>,
+) -> crate::Result<()> {
   let (recursive, dir) = if let Some(options_value) = options {
     (options_value.recursive, options_value.dir)
   } else {
     (false, None)
   };
-  let resolved_path = resolve_path(path, dir)?;
+  let resolved_path =
+    resolve_scoped_path(window, path, dir, PermissionPrompt::FsWriteOutsideScope)?;
   if recursive {
     fs::create_dir_all(resolved_path)?;
   } else {
@@ -252,13 +358,18 @@ pub fn create_dir(path: PathBuf, options: Option<DirOperationOptions>) -> crate:
 
 /// Removes a directory.
 #[cfg(fs_remove_dir)]
-pub fn remove_dir(path: PathBuf, options: Option<DirOperationOptions>) -> crate::Result<()> {
+pub fn remove_dir<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  options: Option<DirOperationOptions>,
+) -> crate::Result<()> {
   let (recursive, dir) = if let Some(options_value) = options {
     (options_value.recursive, options_value.dir)
   } else {
     (false, None)
   };
-  let resolved_path = resolve_path(path, dir)?;
+  let resolved_path =
+    resolve_scoped_path(window, path, dir, PermissionPrompt::FsWriteOutsideScope)?;
   if recursive {
     fs::remove_dir_all(resolved_path)?;
   } else {
@@ -270,45 +381,116 @@ pub fn remove_dir(path: PathBuf, options: Option<DirOperationOptions>) -> crate:
 
 /// Removes a file
 #[cfg(fs_remove_file)]
-pub fn remove_file(path: PathBuf, options: Option<FileOperationOptions>) -> crate::Result<()> {
-  let resolved_path = resolve_path(path, options.and_then(|o| o.dir))?;
+pub fn remove_file<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<()> {
+  let resolved_path = resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsWriteOutsideScope,
+  )?;
   fs::remove_file(resolved_path)?;
   Ok(())
 }
 
 /// Renames a file.
 #[cfg(fs_rename_file)]
-pub fn rename_file(
+pub fn rename_file<M: Params>(
+  window: &Window<M>,
   old_path: PathBuf,
   new_path: PathBuf,
   options: Option<FileOperationOptions>,
 ) -> crate::Result<()> {
-  let (old, new) = match options.and_then(|o| o.dir) {
-    Some(dir) => (
-      resolve_path(old_path, Some(dir.clone()))?,
-      resolve_path(new_path, Some(dir))?,
-    ),
-    None => (old_path, new_path),
-  };
+  let dir = options.and_then(|o| o.dir);
+  let old = resolve_scoped_path(
+    window,
+    old_path,
+    dir.clone(),
+    PermissionPrompt::FsWriteOutsideScope,
+  )?;
+  let new = resolve_scoped_path(window, new_path, dir, PermissionPrompt::FsWriteOutsideScope)?;
   fs::rename(old, new).map_err(crate::Error::Io)
 }
 
+/// Reads the metadata of a file or directory.
+#[cfg(fs_metadata)]
+pub fn metadata<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<file::Metadata> {
+  file::metadata(resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsReadOutsideScope,
+  )?)
+  .map_err(crate::Error::FailedToExecuteApi)
+}
+
+/// Creates a symbolic link.
+///
+/// The target must be readable too, so the link can't expose a path outside of the scope.
+#[cfg(fs_symlink)]
+pub fn create_symlink<M: Params>(
+  window: &Window<M>,
+  target: PathBuf,
+  path: PathBuf,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<()> {
+  let dir = options.and_then(|o| o.dir);
+  let target = resolve_scoped_path(
+    window,
+    target,
+    dir.clone(),
+    PermissionPrompt::FsReadOutsideScope,
+  )?;
+  let link = resolve_scoped_path(window, path, dir, PermissionPrompt::FsWriteOutsideScope)?;
+  file::create_symlink(target, link).map_err(crate::Error::FailedToExecuteApi)
+}
+
+/// Reads the target of a symbolic link.
+#[cfg(fs_symlink)]
+pub fn read_link<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<PathBuf> {
+  file::read_link(resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsReadOutsideScope,
+  )?)
+  .map_err(crate::Error::FailedToExecuteApi)
+}
+
 /// Writes a text file.
 #[cfg(fs_write_file)]
-pub fn write_file(
+pub fn write_file<M: Params>(
+  window: &Window<M>,
   path: PathBuf,
   contents: String,
   options: Option<FileOperationOptions>,
 ) -> crate::Result<()> {
-  File::create(resolve_path(path, options.and_then(|o| o.dir))?)
-    .map_err(crate::Error::Io)
-    .and_then(|mut f| f.write_all(contents.as_bytes()).map_err(|err| err.into()))?;
+  File::create(resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsWriteOutsideScope,
+  )?)
+  .map_err(crate::Error::Io)
+  .and_then(|mut f| f.write_all(contents.as_bytes()).map_err(|err| err.into()))?;
   Ok(())
 }
 
 /// Writes a binary file.
 #[cfg(fs_write_binary_file)]
-pub fn write_binary_file(
+pub fn write_binary_file<M: Params>(
+  window: &Window<M>,
   path: PathBuf,
   contents: String,
   options: Option<FileOperationOptions>,
@@ -316,39 +498,162 @@ pub fn write_binary_file(
   base64::decode(contents)
     .map_err(crate::Error::Base64Decode)
     .and_then(|c| {
-      File::create(resolve_path(path, options.and_then(|o| o.dir))?)
-        .map_err(Into::into)
-        .and_then(|mut f| f.write_all(&c).map_err(|err| err.into()))
+      File::create(resolve_scoped_path(
+        window,
+        path,
+        options.and_then(|o| o.dir),
+        PermissionPrompt::FsWriteOutsideScope,
+      )?)
+      .map_err(Into::into)
+      .and_then(|mut f| f.write_all(&c).map_err(|err| err.into()))
     })?;
   Ok(())
 }
 
 /// Reads a text file.
 #[cfg(fs_read_text_file)]
-pub fn read_text_file(
+pub fn read_text_file<M: Params>(
+  window: &Window<M>,
   path: PathBuf,
   options: Option<FileOperationOptions>,
 ) -> crate::Result<String> {
-  file::read_string(resolve_path(path, options.and_then(|o| o.dir))?)
-    .map_err(crate::Error::FailedToExecuteApi)
+  file::read_string(resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsReadOutsideScope,
+  )?)
+  .map_err(crate::Error::FailedToExecuteApi)
 }
 
 /// Reads a binary file.
 #[cfg(fs_read_binary_file)]
-pub fn read_binary_file(
+pub fn read_binary_file<M: Params>(
+  window: &Window<M>,
   path: PathBuf,
   options: Option<FileOperationOptions>,
 ) -> crate::Result<Vec<u8>> {
-  file::read_binary(resolve_path(path, options.and_then(|o| o.dir))?)
-    .map_err(crate::Error::FailedToExecuteApi)
+  file::read_binary(resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsReadOutsideScope,
+  )?)
+  .map_err(crate::Error::FailedToExecuteApi)
+}
+
+/// The payload of the `tauri://file-chunk` event.
+#[cfg(fs_read_binary_file)]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileChunk {
+  stream_id: u32,
+  /// The id of the chunk bytes, read from `tauri-buffer://<id>`.
+  buffer: String,
+  read: u64,
+  total: u64,
+}
+
+/// The default size of the chunks emitted by [`read_file_stream`].
+#[cfg(fs_read_binary_file)]
+const DEFAULT_CHUNK_SIZE: usize = 512 * 1024;
+
+/// Reads a binary file in chunks, emitted to the window as they're read,
+/// so large files aren't serialized in a single response. Returns the number of bytes read.
+///
+/// The chunks aren't serialized in the events either: the window reads them from the
+/// `tauri-buffer` protocol.
+#[cfg(fs_read_binary_file)]
+pub fn read_file_stream<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  stream_id: u32,
+  chunk_size: Option<usize>,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<u64> {
+  let path = resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsReadOutsideScope,
+  )?;
+  let total = fs::metadata(&path)?.len();
+  let mut read = 0;
+  file::read_chunks(path, chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE), |chunk| {
+    read += chunk.len() as u64;
+    let buffer = uuid::Uuid::new_v4().to_simple().to_string();
+    window
+      .manager()
+      .store_message_buffers(window.label(), vec![(buffer.clone(), chunk.to_vec())]);
+    let payload = FileChunk {
+      stream_id,
+      buffer,
+      read,
+      total,
+    };
+    if let Err(e) = window.emit_internal("tauri://file-chunk".to_string(), Some(payload)) {
+      window.manager().report_error(e);
+    }
+  })
+  .map_err(crate::Error::FailedToExecuteApi)
+}
+
+/// Writes a base64 encoded chunk of a binary file,
+/// appending it or replacing the file contents.
+#[cfg(fs_write_binary_file)]
+pub fn write_file_stream<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  contents: String,
+  append: bool,
+  options: Option<FileOperationOptions>,
+) -> crate::Result<()> {
+  let contents = base64::decode(contents).map_err(crate::Error::Base64Decode)?;
+  let path = resolve_scoped_path(
+    window,
+    path,
+    options.and_then(|o| o.dir),
+    PermissionPrompt::FsWriteOutsideScope,
+  )?;
+  fs::OpenOptions::new()
+    .create(true)
+    .write(true)
+    .append(append)
+    .truncate(!append)
+    .open(path)?
+    .write_all(&contents)?;
+  Ok(())
+}
+
+/// Resolves the path, failing if it isn't in the file system scope
+/// and the user doesn't allow it on the permission prompt, if enabled.
+#[allow(dead_code)]
+fn resolve_scoped_path<M: Params>(
+  window: &Window<M>,
+  path: PathBuf,
+  dir: Option<BaseDirectory>,
+  prompt: PermissionPrompt,
+) -> crate::Result<PathBuf> {
+  let manager = window.manager();
+  let path = resolve_path(manager.config(), path, dir)?;
+  let allowed = manager.fs_scope().is_allowed(&path)
+    || manager
+      .request_permission(window.label(), prompt, &path.to_string_lossy())
+      .unwrap_or(false);
+  if allowed {
+    Ok(path)
+  } else {
+    Err(crate::Error::PathNotAllowed(path))
+  }
 }
 
 #[cfg(fs_path)]
 pub fn resolve_path_handler(
+  config: &Config,
   path: String,
   directory: Option<BaseDirectory>,
 ) -> crate::Result<PathBuf> {
-  resolve_path(path, directory).map_err(Into::into)
+  resolve_path(config, path, directory).map_err(Into::into)
 }
 
 // test webview functionality.
@@ -0,0 +1,38 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+use serde::Deserialize;
+
+#[cfg(font_all)]
+use crate::api::font;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// List the installed font families.
+  List,
+  /// List the installed font faces.
+  ListFaces,
+}
+
+impl Cmd {
+  pub fn run(self) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::List => {
+        #[cfg(font_all)]
+        return Ok(font::list().into());
+        #[cfg(not(font_all))]
+        Err(crate::Error::ApiNotAllowlisted("font > all".to_string()))
+      }
+      Self::ListFaces => {
+        #[cfg(font_all)]
+        return Ok(font::faces().into());
+        #[cfg(not(font_all))]
+        Err(crate::Error::ApiNotAllowlisted("font > all".to_string()))
+      }
+    }
+  }
+}
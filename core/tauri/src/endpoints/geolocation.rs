@@ -0,0 +1,92 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+use crate::{Params, Window};
+use serde::Deserialize;
+
+#[cfg(geolocation_all)]
+use crate::{
+  api::{config::Config, geolocation},
+  Manager,
+};
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Get the current position, requesting the permission if needed.
+  GetCurrentPosition,
+  /// The request geolocation permission API.
+  RequestPermission,
+  /// The geolocation permission check API.
+  IsPermissionGranted,
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::GetCurrentPosition => {
+        #[cfg(geolocation_all)]
+        return get_current_position(window.config()).map(Into::into);
+        #[cfg(not(geolocation_all))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "geolocation > all".to_string(),
+        ))
+      }
+      Self::RequestPermission => {
+        #[cfg(geolocation_all)]
+        return request_permission(window.config()).map(Into::into);
+        #[cfg(not(geolocation_all))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "geolocation > all".to_string(),
+        ))
+      }
+      Self::IsPermissionGranted => {
+        #[cfg(geolocation_all)]
+        return is_permission_granted(window.config()).map(Into::into);
+        #[cfg(not(geolocation_all))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "geolocation > all".to_string(),
+        ))
+      }
+    }
+  }
+}
+
+#[cfg(geolocation_all)]
+pub fn get_current_position(config: &Config) -> crate::Result<InvokeResponse> {
+  if request_permission(config)? != "granted" {
+    return Err(crate::Error::GeolocationPermissionDenied);
+  }
+  Ok(geolocation::current_position()?.into())
+}
+
+#[cfg(geolocation_all)]
+pub fn is_permission_granted(config: &Config) -> crate::Result<InvokeResponse> {
+  let settings = crate::settings::read_settings(config)?;
+  if let Some(allow_geolocation) = settings.allow_geolocation {
+    Ok(allow_geolocation.into())
+  } else {
+    Ok(().into())
+  }
+}
+
+#[cfg(geolocation_all)]
+pub fn request_permission(config: &Config) -> crate::Result<String> {
+  let mut settings = crate::settings::read_settings(config)?;
+  let granted = "granted".to_string();
+  let denied = "denied".to_string();
+  if let Some(allow_geolocation) = settings.allow_geolocation {
+    return Ok(if allow_geolocation { granted } else { denied });
+  }
+  let answer = crate::api::dialog::blocking::ask(
+    "Permissions",
+    "This app wants to know your location. Do you allow?",
+  );
+  settings.allow_geolocation = Some(answer);
+  crate::settings::write_settings(config, settings)?;
+  Ok(if answer { granted } else { denied })
+}
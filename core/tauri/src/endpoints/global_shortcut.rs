@@ -3,34 +3,22 @@
 // SPDX-License-Identifier: MIT
 
 use super::InvokeResponse;
-use crate::{runtime::Dispatch, Params, Window};
-use once_cell::sync::Lazy;
+use crate::{Params, Window};
 use serde::Deserialize;
-use std::sync::{Arc, Mutex};
 
 #[cfg(global_shortcut_all)]
-use crate::api::shortcuts::ShortcutManager;
-
-#[cfg(global_shortcut_all)]
-type ShortcutManagerHandle = Arc<Mutex<ShortcutManager>>;
-
-#[cfg(global_shortcut_all)]
-pub fn manager_handle() -> &'static ShortcutManagerHandle {
-  static MANAGER: Lazy<ShortcutManagerHandle> = Lazy::new(Default::default);
-  &MANAGER
-}
+use crate::Manager;
 
 /// The API descriptor.
+///
+/// The shortcuts are reported with the `tauri://global-shortcut` event.
 #[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
   /// Register a global shortcut.
-  Register { shortcut: String, handler: String },
+  Register { shortcut: String },
   /// Register a list of global shortcuts.
-  RegisterAll {
-    shortcuts: Vec<String>,
-    handler: String,
-  },
+  RegisterAll { shortcuts: Vec<String> },
   /// Unregister a global shortcut.
   Unregister { shortcut: String },
   /// Unregisters all registered shortcuts.
@@ -39,21 +27,6 @@ pub enum Cmd {
   IsRegistered { shortcut: String },
 }
 
-#[cfg(global_shortcut_all)]
-fn register_shortcut<D: Dispatch>(
-  dispatcher: D,
-  manager: &mut ShortcutManager,
-  shortcut: String,
-  handler: String,
-) -> crate::Result<()> {
-  manager.register(shortcut.clone(), move || {
-    let callback_string = crate::api::rpc::format_callback(handler.to_string(), &shortcut)
-      .expect("unable to serialize shortcut string to json");
-    let _ = dispatcher.eval_script(callback_string.as_str());
-  })?;
-  Ok(())
-}
-
 #[cfg(not(global_shortcut_all))]
 impl Cmd {
   pub fn run<M: Params>(self, _window: Window<M>) -> crate::Result<InvokeResponse> {
@@ -66,36 +39,27 @@ impl Cmd {
 #[cfg(global_shortcut_all)]
 impl Cmd {
   pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    let manager = window.global_shortcut_manager();
     match self {
-      Self::Register { shortcut, handler } => {
-        let dispatcher = window.dispatcher();
-        let mut manager = manager_handle().lock().unwrap();
-        register_shortcut(dispatcher, &mut manager, shortcut, handler)?;
+      Self::Register { shortcut } => {
+        manager.register(&shortcut)?;
         Ok(().into())
       }
-      Self::RegisterAll { shortcuts, handler } => {
-        let dispatcher = window.dispatcher();
-        let mut manager = manager_handle().lock().unwrap();
+      Self::RegisterAll { shortcuts } => {
         for shortcut in shortcuts {
-          let dispatch = dispatcher.clone();
-          register_shortcut(dispatch, &mut manager, shortcut, handler.clone())?;
+          manager.register(&shortcut)?;
         }
         Ok(().into())
       }
       Self::Unregister { shortcut } => {
-        let mut manager = manager_handle().lock().unwrap();
-        manager.unregister(shortcut)?;
+        manager.unregister(&shortcut)?;
         Ok(().into())
       }
       Self::UnregisterAll => {
-        let mut manager = manager_handle().lock().unwrap();
         manager.unregister_all()?;
         Ok(().into())
       }
-      Self::IsRegistered { shortcut } => {
-        let manager = manager_handle().lock().unwrap();
-        Ok(manager.is_registered(shortcut)?.into())
-      }
+      Self::IsRegistered { shortcut } => Ok(manager.is_registered(&shortcut)?.into()),
     }
   }
 }
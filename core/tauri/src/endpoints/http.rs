@@ -4,21 +4,42 @@
 
 use super::InvokeResponse;
 
-use crate::api::http::{Client, ClientBuilder, HttpRequestBuilder, ResponseData};
-use once_cell::sync::Lazy;
+use crate::{
+  api::{
+    http::{Client, ClientBuilder, HttpRequestBuilder, ResponseData},
+    path::BaseDirectory,
+  },
+  sealed::ManagerBase,
+  Params, Window,
+};
+use futures::future::AbortHandle;
 use serde::Deserialize;
 
-use std::{
-  collections::HashMap,
-  sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
 
 type ClientId = u32;
-type ClientStore = Arc<Mutex<HashMap<ClientId, Client>>>;
+type DownloadId = u32;
+
+/// The HTTP clients, by id.
+#[derive(Default)]
+struct Clients(Mutex<HashMap<ClientId, Client>>);
 
-fn clients() -> &'static ClientStore {
-  static STORE: Lazy<ClientStore> = Lazy::new(Default::default);
-  &STORE
+/// The downloads in progress, by window label and download id, cancelled with their abort handle.
+#[derive(Default)]
+struct Downloads(Mutex<HashMap<(String, DownloadId), AbortHandle>>);
+
+#[cfg(any(http_request, http_download))]
+fn client<M: Params>(window: &Window<M>, client_id: ClientId) -> crate::Result<Client> {
+  window
+    .manager()
+    .state()
+    .get_or_default::<Clients>()
+    .0
+    .lock()
+    .unwrap()
+    .get(&client_id)
+    .cloned()
+    .ok_or(crate::Error::HttpClientNotInitialized)
 }
 
 /// The API descriptor.
@@ -30,51 +51,178 @@ pub enum Cmd {
   /// Drop a HTTP client.
   DropClient { client: ClientId },
   /// The HTTP request API.
+  #[serde(rename_all = "camelCase")]
   HttpRequest {
     client: ClientId,
     options: Box<HttpRequestBuilder>,
+    /// Identifies the `tauri://download-progress` events of a `Stream` response.
+    stream_id: Option<u32>,
   },
+  /// Download a file, resuming a partial download.
+  #[serde(rename_all = "camelCase")]
+  DownloadFile {
+    client: ClientId,
+    url: String,
+    path: PathBuf,
+    dir: Option<BaseDirectory>,
+    /// Identifies the `tauri://download-progress` events and the download to cancel.
+    download_id: DownloadId,
+  },
+  /// Cancel a download.
+  #[serde(rename_all = "camelCase")]
+  CancelDownload { download_id: DownloadId },
+}
+
+/// The payload of the `tauri://download-progress` event of a file download.
+#[cfg(http_download)]
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileDownloadProgress {
+  download_id: DownloadId,
+  received: u64,
+  total: Option<u64>,
+}
+
+/// The payload of the `tauri://download-progress` event.
+#[cfg(http_request)]
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress {
+  stream_id: Option<u32>,
+  chunk: Vec<u8>,
+  received: u64,
+  total: Option<u64>,
 }
 
 impl Cmd {
-  pub async fn run(self) -> crate::Result<InvokeResponse> {
+  #[allow(unused_variables)]
+  pub async fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
     match self {
       Self::CreateClient { options } => {
         let client = options.unwrap_or_default().build()?;
-        let mut store = clients().lock().unwrap();
+        let clients = window.manager().state().get_or_default::<Clients>();
+        let mut store = clients.0.lock().unwrap();
         let id = rand::random::<ClientId>();
         store.insert(id, client);
         Ok(InvokeResponse::from(id))
       }
       Self::DropClient { client } => {
-        let mut store = clients().lock().unwrap();
+        let clients = window.manager().state().get_or_default::<Clients>();
+        let mut store = clients.0.lock().unwrap();
         store.remove(&client);
         Ok(().into())
       }
-      Self::HttpRequest { client, options } => {
+      Self::HttpRequest {
+        client,
+        options,
+        stream_id,
+      } => {
         #[cfg(http_request)]
-        return make_request(client, *options).await.map(Into::into);
+        return make_request(client, *options, window, stream_id)
+          .await
+          .map(Into::into);
         #[cfg(not(http_request))]
         Err(crate::Error::ApiNotAllowlisted(
           "http > request".to_string(),
         ))
       }
+      Self::DownloadFile {
+        client,
+        url,
+        path,
+        dir,
+        download_id,
+      } => {
+        #[cfg(http_download)]
+        return download_file(client, url, path, dir, download_id, window)
+          .await
+          .map(Into::into);
+        #[cfg(not(http_download))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > download".to_string(),
+        ))
+      }
+      Self::CancelDownload { download_id } => {
+        let key = (window.label().to_string(), download_id);
+        let downloads = window.manager().state().get_or_default::<Downloads>();
+        if let Some(handle) = downloads.0.lock().unwrap().remove(&key) {
+          handle.abort();
+        }
+        Ok(().into())
+      }
     }
   }
 }
 
-/// Makes an HTTP request and resolves the response to the webview
+/// Makes an HTTP request and resolves the response to the webview.
+///
+/// The chunks of a `Stream` response are emitted to the window as they're received.
 #[cfg(http_request)]
-pub async fn make_request(
+pub async fn make_request<M: Params>(
   client_id: ClientId,
   options: HttpRequestBuilder,
+  window: Window<M>,
+  stream_id: Option<u32>,
 ) -> crate::Result<ResponseData> {
-  let client = clients()
+  let response = client(&window, client_id)?.send(options).await?;
+  if let crate::api::http::ResponseType::Stream = response.response_type() {
+    let response = response
+      .read_chunks(|chunk, received, total| {
+        let progress = DownloadProgress {
+          stream_id,
+          chunk: chunk.to_vec(),
+          received,
+          total,
+        };
+        if let Err(e) =
+          window.emit_internal("tauri://download-progress".to_string(), Some(progress))
+        {
+          window.manager().report_error(e);
+        }
+      })
+      .await?;
+    Ok(response)
+  } else {
+    Ok(response.read().await?)
+  }
+}
+
+/// Downloads a file, emitting its progress to the window.
+#[cfg(http_download)]
+async fn download_file<M: Params>(
+  client_id: ClientId,
+  url: String,
+  path: PathBuf,
+  dir: Option<BaseDirectory>,
+  download_id: DownloadId,
+  window: Window<M>,
+) -> crate::Result<()> {
+  let client = client(&window, client_id)?;
+  let path = crate::api::path::resolve_path(window.manager().config(), path, dir)?;
+
+  let downloads = window.manager().state().get_or_default::<Downloads>();
+  let key = (window.label().to_string(), download_id);
+  let (abort_handle, abort_registration) = AbortHandle::new_pair();
+  downloads
+    .0
     .lock()
     .unwrap()
-    .get(&client_id)
-    .ok_or(crate::Error::HttpClientNotInitialized)?
-    .clone();
-  let response = client.send(options).await?;
-  Ok(response.read().await?)
+    .insert(key.clone(), abort_handle);
+  let download = client.download(&url, &path, |received, total| {
+    let progress = FileDownloadProgress {
+      download_id,
+      received,
+      total,
+    };
+    if let Err(e) = window.emit_internal("tauri://download-progress".to_string(), Some(progress)) {
+      window.manager().report_error(e);
+    }
+  });
+  let result = futures::future::Abortable::new(download, abort_registration).await;
+  downloads.0.lock().unwrap().remove(&key);
+
+  match result {
+    Ok(result) => result.map_err(Into::into),
+    Err(_) => Err(crate::api::Error::DownloadCancelled.into()),
+  }
 }
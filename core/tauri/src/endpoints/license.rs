@@ -0,0 +1,49 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+use crate::api::config::LicenseConfig;
+use serde::Deserialize;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Check the activated license or the trial.
+  Check,
+  /// Verify and activate a license key.
+  Activate { key: String },
+  /// Remove the activated license.
+  Deactivate,
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run(self, identifier: String, config: LicenseConfig) -> crate::Result<InvokeResponse> {
+    #[cfg(feature = "license")]
+    {
+      use crate::api::license;
+
+      let public_key = config.pubkey.ok_or(crate::Error::LicensePublicKeyMissing);
+      return match self {
+        Self::Check => {
+          let trial = config
+            .trial_days
+            .map(|days| std::time::Duration::from_secs(u64::from(days) * 24 * 60 * 60));
+          license::check(&identifier, &public_key?, trial)
+            .map(Into::into)
+            .map_err(Into::into)
+        }
+        Self::Activate { key } => license::activate(&identifier, &key, &public_key?)
+          .map(Into::into)
+          .map_err(Into::into),
+        Self::Deactivate => license::deactivate(&identifier)
+          .map(Into::into)
+          .map_err(Into::into),
+      };
+    }
+    #[cfg(not(feature = "license"))]
+    Err(crate::Error::ApiNotAllowlisted("license > all".to_string()))
+  }
+}
@@ -3,20 +3,59 @@
 // SPDX-License-Identifier: MIT
 
 use super::InvokeResponse;
+use crate::{Params, Window};
 use serde::Deserialize;
 
 #[cfg(notification_all)]
-use crate::api::notification::Notification;
+use crate::{
+  api::{
+    config::Config,
+    notification::{Notification, NotificationEvent},
+  },
+  sealed::ManagerBase,
+  Manager,
+};
+#[cfg(notification_all)]
+use serde::Serialize;
+#[cfg(notification_all)]
+use std::time::{Duration, UNIX_EPOCH};
+
+/// An action button of a notification.
+#[derive(Deserialize)]
+pub struct NotificationActionOptions {
+  /// The id reported by the `tauri://notification-action` event.
+  pub id: String,
+  /// The button title.
+  pub title: String,
+}
 
 /// The options for the notification API.
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NotificationOptions {
+  /// The id reported by the interaction events.
+  pub id: Option<String>,
   /// The notification title.
   pub title: String,
   /// The notification body.
   pub body: Option<String>,
   /// The notification icon.
   pub icon: Option<String>,
+  /// The notification sound, the name of a system sound.
+  pub sound: Option<String>,
+  /// The delivery time, in milliseconds since the Unix epoch. Shown right away if not set.
+  pub schedule_at: Option<u64>,
+  /// The action buttons.
+  #[serde(default)]
+  pub actions: Vec<NotificationActionOptions>,
+}
+
+/// The payload of the `tauri://notification-clicked` and `tauri://notification-action` events.
+#[cfg(notification_all)]
+#[derive(Clone, Serialize)]
+struct NotificationEventPayload {
+  id: Option<String>,
+  action: Option<String>,
 }
 
 /// The API descriptor.
@@ -32,23 +71,28 @@ pub enum Cmd {
 }
 
 impl Cmd {
-  pub fn run(self, identifier: String) -> crate::Result<InvokeResponse> {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(
+    self,
+    window: Window<M>,
+    identifier: String,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::Notification { options } => {
         #[cfg(notification_all)]
-        return send(options, identifier).map(Into::into);
+        return send(window, options, identifier).map(Into::into);
         #[cfg(not(notification_all))]
         Err(crate::Error::ApiNotAllowlisted("notification".to_string()))
       }
       Self::IsNotificationPermissionGranted => {
         #[cfg(notification_all)]
-        return is_permission_granted().map(Into::into);
+        return is_permission_granted(window.config()).map(Into::into);
         #[cfg(not(notification_all))]
         Err(crate::Error::ApiNotAllowlisted("notification".to_string()))
       }
       Self::RequestNotificationPermission => {
         #[cfg(notification_all)]
-        return request_permission().map(Into::into);
+        return request_permission(window.config()).map(Into::into);
         #[cfg(not(notification_all))]
         Err(crate::Error::ApiNotAllowlisted("notification".to_string()))
       }
@@ -56,8 +100,14 @@ impl Cmd {
   }
 }
 
+/// Shows the notification, emitting `tauri://notification-clicked` or
+/// `tauri://notification-action` to the windows and the Rust listeners when the user interacts.
 #[cfg(notification_all)]
-pub fn send(options: NotificationOptions, identifier: String) -> crate::Result<InvokeResponse> {
+pub fn send<M: Params>(
+  window: Window<M>,
+  options: NotificationOptions,
+  identifier: String,
+) -> crate::Result<InvokeResponse> {
   let mut notification = Notification::new(identifier).title(options.title);
   if let Some(body) = options.body {
     notification = notification.body(body);
@@ -65,13 +115,43 @@ pub fn send(options: NotificationOptions, identifier: String) -> crate::Result<I
   if let Some(icon) = options.icon {
     notification = notification.icon(icon);
   }
-  notification.show()?;
+  if let Some(sound) = options.sound {
+    notification = notification.sound(sound);
+  }
+  for action in options.actions {
+    notification = notification.action(action.id, action.title);
+  }
+  let id = options.id;
+  let manager = window.manager().clone();
+  let handler = move |event: NotificationEvent| {
+    let (name, action) = match event {
+      NotificationEvent::Clicked => ("tauri://notification-clicked", None),
+      NotificationEvent::Action(action) => ("tauri://notification-action", Some(action)),
+    };
+    let payload = NotificationEventPayload { id, action };
+    if let Err(e) = manager.emit_filter_internal(name.to_string(), Some(payload.clone()), |_| true)
+    {
+      manager.report_error(e);
+    }
+    if let Ok(tag) = name.parse() {
+      match serde_json::to_string(&payload) {
+        Ok(data) => manager.trigger(tag, None, Some(data)),
+        Err(e) => manager.report_error(e.into()),
+      }
+    }
+  };
+  match options.schedule_at {
+    Some(timestamp) => {
+      notification.schedule(UNIX_EPOCH + Duration::from_millis(timestamp), handler);
+    }
+    None => notification.show_with_handler(handler)?,
+  }
   Ok(().into())
 }
 
 #[cfg(notification_all)]
-pub fn is_permission_granted() -> crate::Result<InvokeResponse> {
-  let settings = crate::settings::read_settings()?;
+pub fn is_permission_granted(config: &Config) -> crate::Result<InvokeResponse> {
+  let settings = crate::settings::read_settings(config)?;
   if let Some(allow_notification) = settings.allow_notification {
     Ok(allow_notification.into())
   } else {
@@ -80,27 +160,18 @@ pub fn is_permission_granted() -> crate::Result<InvokeResponse> {
 }
 
 #[cfg(notification_all)]
-pub fn request_permission() -> crate::Result<String> {
-  let mut settings = crate::settings::read_settings()?;
+pub fn request_permission(config: &Config) -> crate::Result<String> {
+  let mut settings = crate::settings::read_settings(config)?;
   let granted = "granted".to_string();
   let denied = "denied".to_string();
   if let Some(allow_notification) = settings.allow_notification {
     return Ok(if allow_notification { granted } else { denied });
   }
-  let answer = crate::api::dialog::ask(
+  let answer = crate::api::dialog::blocking::ask(
     "Permissions",
     "This app wants to show notifications. Do you allow?",
   );
-  match answer {
-    crate::api::dialog::AskResponse::Yes => {
-      settings.allow_notification = Some(true);
-      crate::settings::write_settings(settings)?;
-      Ok(granted)
-    }
-    crate::api::dialog::AskResponse::No => {
-      settings.allow_notification = Some(false);
-      crate::settings::write_settings(settings)?;
-      Ok(denied)
-    }
-  }
+  settings.allow_notification = Some(answer);
+  crate::settings::write_settings(config, settings)?;
+  Ok(if answer { granted } else { denied })
 }
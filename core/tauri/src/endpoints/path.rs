@@ -0,0 +1,38 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+use crate::{api::path::BaseDirectory, Params, Window};
+use serde::Deserialize;
+
+#[cfg(path_all)]
+use crate::{api::path::resolve_path, Manager};
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Resolves the path with the optional base directory,
+  /// using the bundle identifier for the app directories.
+  ResolvePath {
+    path: String,
+    directory: Option<BaseDirectory>,
+  },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::ResolvePath { path, directory } => {
+        #[cfg(path_all)]
+        return resolve_path(window.config(), path, directory)
+          .map(Into::into)
+          .map_err(Into::into);
+        #[cfg(not(path_all))]
+        Err(crate::Error::ApiNotAllowlisted("path > all".to_string()))
+      }
+    }
+  }
+}
@@ -0,0 +1,81 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+#[cfg(printer_all)]
+use crate::{api::printer, sealed::ManagerBase};
+use crate::{Params, Window};
+use serde::Deserialize;
+
+use std::path::PathBuf;
+
+/// The options for the print API.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintOptions {
+  /// The printer name, the default printer if `None`.
+  pub printer: Option<String>,
+  /// The number of copies.
+  pub copies: Option<u32>,
+  /// The pages to print, e.g. `1-3,5`.
+  pub pages: Option<String>,
+}
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// List the printers.
+  List,
+  /// Print a file silently.
+  Print {
+    path: PathBuf,
+    options: Option<PrintOptions>,
+  },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::List => {
+        #[cfg(printer_all)]
+        return Ok(printer::list().into());
+        #[cfg(not(printer_all))]
+        Err(crate::Error::ApiNotAllowlisted("printer > all".to_string()))
+      }
+      Self::Print { path, options } => {
+        #[cfg(printer_all)]
+        return print(window, path, options);
+        #[cfg(not(printer_all))]
+        Err(crate::Error::ApiNotAllowlisted("printer > all".to_string()))
+      }
+    }
+  }
+}
+
+/// Prints the file if it's in the file system scope.
+#[cfg(printer_all)]
+fn print<M: Params>(
+  window: Window<M>,
+  path: PathBuf,
+  options: Option<PrintOptions>,
+) -> crate::Result<InvokeResponse> {
+  if !window.manager().fs_scope().is_allowed(&path) {
+    return Err(crate::Error::PathNotAllowed(path));
+  }
+  let mut job = printer::PrintJob::new(path);
+  if let Some(options) = options {
+    if let Some(name) = options.printer {
+      job = job.printer(name);
+    }
+    if let Some(copies) = options.copies {
+      job = job.copies(copies);
+    }
+    if let Some(pages) = options.pages {
+      job = job.pages(pages);
+    }
+  }
+  job.print().map(Into::into).map_err(Into::into)
+}
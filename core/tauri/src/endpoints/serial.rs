@@ -0,0 +1,136 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+#[cfg(feature = "serial")]
+use crate::{
+  api::serial::{self, SerialEvent, SerialPort},
+  sealed::ManagerBase,
+};
+use crate::{Params, Window};
+use serde::Deserialize;
+
+#[cfg(feature = "serial")]
+use serde::Serialize;
+#[cfg(feature = "serial")]
+use std::{collections::HashMap, sync::Mutex};
+
+type PortId = u32;
+
+/// The open ports, by window label and port id, closed when they're removed.
+///
+/// The ids are chosen by the windows, so a window can't reach the ports of another one.
+#[cfg(feature = "serial")]
+#[derive(Default)]
+struct Ports(Mutex<HashMap<(String, PortId), SerialPort>>);
+
+/// The payload of the `tauri://serial-data` event.
+#[cfg(feature = "serial")]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerialData {
+  port_id: PortId,
+  data: Vec<u8>,
+}
+
+/// The payload of the `tauri://serial-error` event.
+#[cfg(feature = "serial")]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerialError {
+  port_id: PortId,
+  error: String,
+}
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// List the serial ports.
+  AvailablePorts,
+  /// Open a serial port, emitting what's read from it to the window.
+  #[serde(rename_all = "camelCase")]
+  Open {
+    name: String,
+    baud_rate: u32,
+    /// Identifies the `tauri://serial-data` events and the port to write to.
+    port_id: PortId,
+  },
+  /// Write to an open port.
+  #[serde(rename_all = "camelCase")]
+  Write { port_id: PortId, data: Vec<u8> },
+  /// Close an open port.
+  #[serde(rename_all = "camelCase")]
+  Close { port_id: PortId },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    #[cfg(feature = "serial")]
+    let ports = window.manager().state().get_or_default::<Ports>();
+    #[cfg(feature = "serial")]
+    let label = window.label().to_string();
+    #[cfg(feature = "serial")]
+    return match self {
+      Self::AvailablePorts => serial::available_ports()
+        .map(Into::into)
+        .map_err(Into::into),
+      Self::Open {
+        name,
+        baud_rate,
+        port_id,
+      } => open(window, name, baud_rate, port_id).map(Into::into),
+      Self::Write { port_id, data } => match ports.0.lock().unwrap().get(&(label, port_id)) {
+        Some(port) => port.write(&data).map(Into::into).map_err(Into::into),
+        None => Err(crate::Error::SerialPortNotOpen(port_id)),
+      },
+      Self::Close { port_id } => {
+        ports.0.lock().unwrap().remove(&(label, port_id));
+        Ok(().into())
+      }
+    };
+    #[cfg(not(feature = "serial"))]
+    Err(crate::Error::ApiNotAllowlisted("serial > all".to_string()))
+  }
+}
+
+/// Opens the port, emitting its events to the window until it's closed.
+#[cfg(feature = "serial")]
+fn open<M: Params>(
+  window: Window<M>,
+  name: String,
+  baud_rate: u32,
+  port_id: PortId,
+) -> crate::Result<()> {
+  let ports = window.manager().state().get_or_default::<Ports>();
+  let key = (window.label().to_string(), port_id);
+  let mut open_ports = ports.0.lock().unwrap();
+  if open_ports.contains_key(&key) {
+    return Err(crate::Error::SerialPortAlreadyOpen(port_id));
+  }
+  let (mut rx, port) = serial::open(name, baud_rate)?;
+  open_ports.insert(key.clone(), port);
+  drop(open_ports);
+  std::thread::spawn(move || {
+    while let Some(event) = rx.blocking_recv() {
+      let emitted = match event {
+        SerialEvent::Data(data) => window.emit_internal(
+          "tauri://serial-data".to_string(),
+          Some(SerialData { port_id, data }),
+        ),
+        SerialEvent::Error(error) => window.emit_internal(
+          "tauri://serial-error".to_string(),
+          Some(SerialError { port_id, error }),
+        ),
+      };
+      if emitted.is_err() {
+        // the window is gone, so nothing can close the port anymore
+        let _port = ports.0.lock().unwrap().remove(&key);
+        break;
+      }
+    }
+  });
+  Ok(())
+}
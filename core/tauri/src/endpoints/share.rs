@@ -0,0 +1,49 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+#[cfg(share_all)]
+use crate::{
+  api::share::{self, ShareItem},
+  sealed::ManagerBase,
+};
+use crate::{Params, Window};
+use serde::Deserialize;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Show the share sheet.
+  Share {
+    // the items aren't read when the API isn't allowlisted
+    #[cfg(share_all)]
+    items: Vec<ShareItem>,
+  },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    match self {
+      #[cfg(share_all)]
+      Self::Share { items } => share(window, items),
+      #[cfg(not(share_all))]
+      Self::Share {} => Err(crate::Error::ApiNotAllowlisted("share > all".to_string())),
+    }
+  }
+}
+
+/// Shares the items if their files are in the file system scope.
+#[cfg(share_all)]
+fn share<M: Params>(window: Window<M>, items: Vec<ShareItem>) -> crate::Result<InvokeResponse> {
+  for item in &items {
+    if let ShareItem::File(path) = item {
+      if !window.manager().fs_scope().is_allowed(path) {
+        return Err(crate::Error::PathNotAllowed(path.clone()));
+      }
+    }
+  }
+  share::share(&items).map(Into::into).map_err(Into::into)
+}
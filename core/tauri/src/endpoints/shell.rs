@@ -2,28 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+#[cfg(any(shell_execute, shell_open))]
+use crate::sealed::ManagerBase;
 use crate::{
-  api::{
-    command::{Command, CommandChild, CommandEvent},
-    rpc::format_callback,
-  },
+  api::{command::CommandEvent, rpc::format_callback},
   endpoints::InvokeResponse,
   Params, Window,
 };
-use once_cell::sync::Lazy;
 use serde::Deserialize;
-use std::{
-  collections::HashMap,
-  sync::{Arc, Mutex},
-};
 
 type ChildId = u32;
-type ChildStore = Arc<Mutex<HashMap<ChildId, CommandChild>>>;
-
-fn command_childs() -> &'static ChildStore {
-  static STORE: Lazy<ChildStore> = Lazy::new(Default::default);
-  &STORE
-}
 
 #[derive(Deserialize)]
 #[serde(untagged)]
@@ -37,6 +25,8 @@ pub enum Buffer {
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
   /// The execute script API.
+  ///
+  /// The program is the name of a command on the shell scope.
   #[serde(rename_all = "camelCase")]
   Execute {
     program: String,
@@ -69,21 +59,29 @@ impl Cmd {
       } => {
         #[cfg(shell_execute)]
         {
-          let mut command = if sidecar {
-            Command::new_sidecar(program)
-          } else {
-            Command::new(program)
-          };
-          command = command.args(args);
+          let manager = window.manager();
+          let command = manager.shell_scope().prepare(&program, args, sidecar)?;
+          let allowed = manager
+            .request_permission(
+              window.label(),
+              crate::api::config::PermissionPrompt::ShellExecute,
+              &program,
+            )
+            // `None` means the prompt is disabled, leaving the scope check above
+            // as the only check, which the command already passed
+            .unwrap_or(true);
+          if !allowed {
+            return Err(crate::Error::PermissionDenied(program));
+          }
           let (mut rx, child) = command.spawn()?;
 
           let pid = child.pid();
-          command_childs().lock().unwrap().insert(pid, child);
+          manager.add_child_process(window.label().clone(), child);
 
           crate::async_runtime::spawn(async move {
             while let Some(event) = rx.recv().await {
               if matches!(event, CommandEvent::Terminated(_)) {
-                command_childs().lock().unwrap().remove(&pid);
+                window.manager().remove_child_process(pid);
               }
               let js = format_callback(on_event_fn.clone(), &event)
                 .expect("unable to serialize CommandEvent");
@@ -102,7 +100,8 @@ impl Cmd {
       Self::KillChild { pid } => {
         #[cfg(shell_execute)]
         {
-          if let Some(child) = command_childs().lock().unwrap().remove(&pid) {
+          // removed from the manager once it's terminated
+          if let Some(child) = window.manager().window_child_process(window.label(), pid)? {
             child.kill()?;
           }
           Ok(().into())
@@ -115,7 +114,7 @@ impl Cmd {
       Self::StdinWrite { pid, buffer } => {
         #[cfg(shell_execute)]
         {
-          if let Some(child) = command_childs().lock().unwrap().get_mut(&pid) {
+          if let Some(child) = window.manager().window_child_process(window.label(), pid)? {
             match buffer {
               Buffer::Text(t) => child.write(t.as_bytes())?,
               Buffer::Raw(r) => child.write(&r)?,
@@ -130,9 +129,20 @@ impl Cmd {
       }
       Self::Open { path, with } => {
         #[cfg(shell_open)]
-        match crate::api::shell::open(path, with) {
-          Ok(_) => Ok(().into()),
-          Err(err) => Err(crate::Error::FailedToExecuteApi(err)),
+        {
+          let scope = window.manager().shell_scope();
+          if !scope.is_open_allowed(&path) {
+            return Err(crate::Error::OpenNotAllowed(path));
+          }
+          if let Some(with) = &with {
+            if !scope.is_open_with_allowed(with) {
+              return Err(crate::Error::ProgramNotAllowed(with.clone()));
+            }
+          }
+          match crate::api::shell::open(path, with) {
+            Ok(_) => Ok(().into()),
+            Err(err) => Err(crate::Error::FailedToExecuteApi(err)),
+          }
         }
 
         #[cfg(not(shell_open))]
@@ -0,0 +1,134 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::InvokeResponse;
+#[cfg(system_all)]
+use crate::{
+  api::system::{Monitor, ProcessMetrics, SystemMetrics},
+  sealed::ManagerBase,
+};
+use crate::{Params, Window};
+use serde::Deserialize;
+
+#[cfg(system_all)]
+use serde::Serialize;
+#[cfg(system_all)]
+use std::{collections::HashSet, sync::Mutex, time::Duration};
+
+type SubscriptionId = u32;
+
+/// The monitor answering the one-off queries,
+/// so the CPU usage is measured since the previous query.
+#[cfg(system_all)]
+#[derive(Default)]
+struct QueryMonitor(Mutex<Monitor>);
+
+/// The active subscriptions, by window label and subscription id, stopped when they're removed.
+#[cfg(system_all)]
+#[derive(Default)]
+struct Subscriptions(Mutex<HashSet<(String, SubscriptionId)>>);
+
+/// The payload of the `tauri://system-metrics` event.
+#[cfg(system_all)]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricsEvent {
+  subscription_id: SubscriptionId,
+  system: SystemMetrics,
+  process: Option<ProcessMetrics>,
+}
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Get the system metrics.
+  SystemMetrics,
+  /// Get the metrics of the app process.
+  ProcessMetrics,
+  /// List the processes of the system.
+  Processes,
+  /// Emit the system and app process metrics to the window periodically.
+  #[serde(rename_all = "camelCase")]
+  Subscribe {
+    /// The polling interval, in milliseconds.
+    interval: u64,
+    /// Identifies the `tauri://system-metrics` events.
+    subscription_id: SubscriptionId,
+  },
+  /// Stop a subscription.
+  #[serde(rename_all = "camelCase")]
+  Unsubscribe { subscription_id: SubscriptionId },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    #[cfg(system_all)]
+    let monitor = window.manager().state().get_or_default::<QueryMonitor>();
+    #[cfg(system_all)]
+    return match self {
+      Self::SystemMetrics => {
+        let mut monitor = monitor.0.lock().unwrap();
+        monitor.refresh();
+        Ok(monitor.system().into())
+      }
+      Self::ProcessMetrics => {
+        let mut monitor = monitor.0.lock().unwrap();
+        monitor.refresh();
+        Ok(monitor.process().into())
+      }
+      Self::Processes => {
+        let mut monitor = monitor.0.lock().unwrap();
+        monitor.refresh_processes();
+        Ok(monitor.processes().into())
+      }
+      Self::Subscribe {
+        interval,
+        subscription_id,
+      } => {
+        subscribe(window, Duration::from_millis(interval), subscription_id);
+        Ok(().into())
+      }
+      Self::Unsubscribe { subscription_id } => {
+        let subscriptions = window.manager().state().get_or_default::<Subscriptions>();
+        let key = (window.label().to_string(), subscription_id);
+        subscriptions.0.lock().unwrap().remove(&key);
+        Ok(().into())
+      }
+    };
+    #[cfg(not(system_all))]
+    Err(crate::Error::ApiNotAllowlisted("system > all".to_string()))
+  }
+}
+
+/// Emits the metrics to the window every `interval` until the subscription is removed.
+#[cfg(system_all)]
+fn subscribe<M: Params>(window: Window<M>, interval: Duration, subscription_id: SubscriptionId) {
+  let subscriptions = window.manager().state().get_or_default::<Subscriptions>();
+  let key = (window.label().to_string(), subscription_id);
+  subscriptions.0.lock().unwrap().insert(key.clone());
+  std::thread::spawn(move || {
+    let mut monitor = Monitor::new();
+    loop {
+      std::thread::sleep(interval);
+      if !subscriptions.0.lock().unwrap().contains(&key) {
+        break;
+      }
+      monitor.refresh();
+      let event = MetricsEvent {
+        subscription_id,
+        system: monitor.system(),
+        process: monitor.process(),
+      };
+      if window
+        .emit_internal("tauri://system-metrics".to_string(), Some(event))
+        .is_err()
+      {
+        subscriptions.0.lock().unwrap().remove(&key);
+        break;
+      }
+    }
+  });
+}
@@ -3,8 +3,13 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-  api::config::WindowConfig, endpoints::InvokeResponse, runtime::window::PendingWindow, Manager,
-  Params, Window,
+  api::config::WindowConfig,
+  endpoints::InvokeResponse,
+  runtime::{
+    monitor::{Position, Size},
+    window::{CursorIcon, PendingWindow},
+  },
+  Manager, Params, Window,
 };
 use serde::Deserialize;
 
@@ -29,8 +34,17 @@ impl From<IconDto> for Icon {
 
 /// The API descriptor.
 #[derive(Deserialize)]
+pub struct Cmd {
+  /// The label of the window the command targets. Defaults to the window that sent it.
+  label: Option<String>,
+  #[serde(flatten)]
+  cmd: WindowCmd,
+}
+
+/// The window commands.
+#[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
-pub enum Cmd {
+pub enum WindowCmd {
   CreateWebview {
     options: WindowConfig,
   },
@@ -40,6 +54,9 @@ pub enum Cmd {
   SetTitle {
     title: String,
   },
+  SetAccessibilityTitle {
+    title: String,
+  },
   Maximize,
   Unmaximize,
   Minimize,
@@ -47,6 +64,8 @@ pub enum Cmd {
   Show,
   Hide,
   Close,
+  Restart,
+  CloseSplashscreen,
   SetDecorations {
     decorations: bool,
   },
@@ -54,35 +73,26 @@ pub enum Cmd {
   SetAlwaysOnTop {
     always_on_top: bool,
   },
-  SetWidth {
-    width: f64,
-  },
-  SetHeight {
-    height: f64,
+  ScaleFactor,
+  InnerSize,
+  OuterSize,
+  InnerPosition,
+  OuterPosition,
+  IsMaximized,
+  IsFullscreen,
+  IsVisible,
+  IsDecorated,
+  SetSize {
+    size: Size,
   },
-  Resize {
-    width: f64,
-    height: f64,
-  },
-  #[serde(rename_all = "camelCase")]
   SetMinSize {
-    min_width: f64,
-    min_height: f64,
+    size: Size,
   },
-  #[serde(rename_all = "camelCase")]
   SetMaxSize {
-    max_width: f64,
-    max_height: f64,
-  },
-  SetX {
-    x: f64,
-  },
-  SetY {
-    y: f64,
+    size: Size,
   },
   SetPosition {
-    x: f64,
-    y: f64,
+    position: Position,
   },
   SetFullscreen {
     fullscreen: bool,
@@ -90,6 +100,29 @@ pub enum Cmd {
   SetIcon {
     icon: IconDto,
   },
+  SetParentGroup {
+    group: Option<String>,
+  },
+  MergeAllWindows,
+  SelectNextTab,
+  SelectPreviousTab,
+  MoveTabToNewWindow,
+  GetCaptureSources {
+    thumbnails: bool,
+  },
+  GetMediaDevices,
+  SetCursorVisible {
+    visible: bool,
+  },
+  SetCursorGrab {
+    grab: bool,
+  },
+  SetCursorIcon {
+    icon: CursorIcon,
+  },
+  SetCursorPosition {
+    position: Position,
+  },
 }
 
 #[cfg(window_create)]
@@ -99,65 +132,115 @@ struct WindowCreatedEvent {
 }
 
 impl Cmd {
-  pub async fn run<M: Params>(self, mut window: Window<M>) -> crate::Result<InvokeResponse> {
-    if cfg!(not(window_all)) {
-      Err(crate::Error::ApiNotAllowlisted("window > all".to_string()))
-    } else {
-      match self {
-        Self::CreateWebview { options } => {
-          #[cfg(not(window_create))]
-          return Err(crate::Error::ApiNotAllowlisted(
-            "window > create".to_string(),
-          ));
-          #[cfg(window_create)]
-          {
-            // Panic if the user's `Tag` type decided to return an error while parsing.
-            let label: M::Label = options.label.parse().unwrap_or_else(|_| {
-              panic!(
-                "Window module received unknown window label: {}",
-                options.label
-              )
-            });
+  #[allow(unused_variables)]
+  pub async fn run<M: Params>(self, window: Window<M>) -> crate::Result<InvokeResponse> {
+    match self.cmd {
+      WindowCmd::CreateWebview { options } => {
+        #[cfg(not(window_create))]
+        return Err(crate::Error::ApiNotAllowlisted(
+          "window > create".to_string(),
+        ));
+        #[cfg(window_create)]
+        {
+          // Panic if the user's `Tag` type decided to return an error while parsing.
+          let label: M::Label = options.label.parse().unwrap_or_else(|_| {
+            panic!(
+              "Window module received unknown window label: {}",
+              options.label
+            )
+          });
 
-            let url = options.url.clone();
-            let pending = PendingWindow::with_config(options, label.clone(), url);
-            window.create_window(pending)?.emit_others_internal(
-              "tauri://window-created".to_string(),
-              Some(WindowCreatedEvent {
-                label: label.to_string(),
-              }),
-            )?;
+          let url = options.url.clone();
+          let pending = PendingWindow::with_config(options, label.clone(), url);
+          let mut window = window;
+          window.create_window(pending)?.emit_others_internal(
+            "tauri://window-created".to_string(),
+            Some(WindowCreatedEvent {
+              label: label.to_string(),
+            }),
+          )?;
+          Ok(().into())
+        }
+      }
+      cmd => {
+        if cfg!(not(window_all)) {
+          return Err(crate::Error::ApiNotAllowlisted("window > all".to_string()));
+        }
+        let window = match self.label {
+          Some(label) => {
+            let label: M::Label = label.parse().map_err(|_| crate::Error::WebviewNotFound)?;
+            window
+              .get_window(&label)
+              .ok_or(crate::Error::WebviewNotFound)?
+          }
+          None => window,
+        };
+        match cmd {
+          WindowCmd::GetCaptureSources { thumbnails } => {
+            Ok(crate::api::capture::sources(thumbnails).into())
           }
+          WindowCmd::GetMediaDevices => Ok(crate::api::media::devices().into()),
+          WindowCmd::ScaleFactor => Ok(window.scale_factor()?.into()),
+          WindowCmd::InnerSize => Ok(window.inner_size()?.into()),
+          WindowCmd::OuterSize => Ok(window.outer_size()?.into()),
+          WindowCmd::InnerPosition => Ok(window.inner_position()?.into()),
+          WindowCmd::OuterPosition => Ok(window.outer_position()?.into()),
+          WindowCmd::IsMaximized => Ok(window.is_maximized()?.into()),
+          WindowCmd::IsFullscreen => Ok(window.is_fullscreen()?.into()),
+          WindowCmd::IsVisible => Ok(window.is_visible()?.into()),
+          WindowCmd::IsDecorated => Ok(window.is_decorated()?.into()),
+          cmd => cmd.run(window).map(Into::into),
         }
-        Self::SetResizable { resizable } => window.set_resizable(resizable)?,
-        Self::SetTitle { title } => window.set_title(&title)?,
-        Self::Maximize => window.maximize()?,
-        Self::Unmaximize => window.unmaximize()?,
-        Self::Minimize => window.minimize()?,
-        Self::Unminimize => window.unminimize()?,
-        Self::Show => window.show()?,
-        Self::Hide => window.hide()?,
-        Self::Close => window.close()?,
-        Self::SetDecorations { decorations } => window.set_decorations(decorations)?,
-        Self::SetAlwaysOnTop { always_on_top } => window.set_always_on_top(always_on_top)?,
-        Self::SetWidth { width } => window.set_width(width)?,
-        Self::SetHeight { height } => window.set_height(height)?,
-        Self::Resize { width, height } => window.resize(width, height)?,
-        Self::SetMinSize {
-          min_width,
-          min_height,
-        } => window.set_min_size(min_width, min_height)?,
-        Self::SetMaxSize {
-          max_width,
-          max_height,
-        } => window.set_max_size(max_width, max_height)?,
-        Self::SetX { x } => window.set_x(x)?,
-        Self::SetY { y } => window.set_y(y)?,
-        Self::SetPosition { x, y } => window.set_position(x, y)?,
-        Self::SetFullscreen { fullscreen } => window.set_fullscreen(fullscreen)?,
-        Self::SetIcon { icon } => window.set_icon(icon.into())?,
       }
-      Ok(().into())
     }
   }
 }
+
+impl WindowCmd {
+  fn run<M: Params>(self, window: Window<M>) -> crate::Result<()> {
+    match self {
+      Self::CreateWebview { .. } => unreachable!("windows are created by `Cmd::run`"),
+      Self::SetResizable { resizable } => window.set_resizable(resizable)?,
+      Self::SetTitle { title } => window.set_title(&title)?,
+      Self::SetAccessibilityTitle { title } => window.set_accessibility_title(&title)?,
+      Self::Maximize => window.maximize()?,
+      Self::Unmaximize => window.unmaximize()?,
+      Self::Minimize => window.minimize()?,
+      Self::Unminimize => window.unminimize()?,
+      Self::Show => window.show()?,
+      Self::Hide => window.hide()?,
+      Self::Close => window.close()?,
+      Self::Restart => window.restart()?,
+      Self::CloseSplashscreen => window.close_splashscreen(),
+      Self::SetDecorations { decorations } => window.set_decorations(decorations)?,
+      Self::SetAlwaysOnTop { always_on_top } => window.set_always_on_top(always_on_top)?,
+      Self::ScaleFactor
+      | Self::InnerSize
+      | Self::OuterSize
+      | Self::InnerPosition
+      | Self::OuterPosition
+      | Self::IsMaximized
+      | Self::IsFullscreen
+      | Self::IsVisible
+      | Self::IsDecorated => unreachable!("the window getters are read by `Cmd::run`"),
+      Self::SetSize { size } => window.set_size(size)?,
+      Self::SetMinSize { size } => window.set_min_size(size)?,
+      Self::SetMaxSize { size } => window.set_max_size(size)?,
+      Self::SetPosition { position } => window.set_position(position)?,
+      Self::SetFullscreen { fullscreen } => window.set_fullscreen(fullscreen)?,
+      Self::SetIcon { icon } => window.set_icon(icon.into())?,
+      Self::SetParentGroup { group } => window.set_parent_group(group.as_deref())?,
+      Self::MergeAllWindows => window.merge_all_windows()?,
+      Self::SelectNextTab => window.select_next_tab()?,
+      Self::SelectPreviousTab => window.select_previous_tab()?,
+      Self::MoveTabToNewWindow => window.move_tab_to_new_window()?,
+      Self::GetCaptureSources { .. } => unreachable!("the sources are listed by `Cmd::run`"),
+      Self::GetMediaDevices => unreachable!("the devices are listed by `Cmd::run`"),
+      Self::SetCursorVisible { visible } => window.set_cursor_visible(visible)?,
+      Self::SetCursorGrab { grab } => window.set_cursor_grab(grab)?,
+      Self::SetCursorIcon { icon } => window.set_cursor_icon(icon)?,
+      Self::SetCursorPosition { position } => window.set_cursor_position(position)?,
+    }
+    Ok(())
+  }
+}
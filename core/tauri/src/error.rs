@@ -8,15 +8,28 @@ pub enum Error {
   /// Failed to create webview.
   #[error("failed to create webview")]
   CreateWebview,
+  /// The webview runtime is not installed or is too old, e.g. the WebView2 runtime on Windows or webkit2gtk on Linux.
+  #[error("the webview runtime is not installed or is outdated")]
+  WebviewRuntimeNotInstalled,
   /// Failed to create window.
   #[error("failed to create window")]
   CreateWindow,
   /// Can't access webview dispatcher because the webview was closed or not found.
   #[error("webview not found: invalid label or it was closed")]
   WebviewNotFound,
-  /// Failed to send message to webview.
-  #[error("failed to send message to the webview")]
-  FailedToSendMessage,
+  /// Failed to send a message to a window on the runtime.
+  #[error("failed to {operation} on window `{label}`: {cause}")]
+  Dispatch {
+    /// The label of the window the message was sent to.
+    label: String,
+    /// The operation the message requested.
+    operation: &'static str,
+    /// The error returned by the runtime.
+    cause: String,
+  },
+  /// The JS event listener wasn't registered by the window.
+  #[error("event listener {0} not found")]
+  EventListenerNotFound(u64),
   /// Embedded asset not found.
   #[error("asset not found: {0}")]
   AssetNotFound(String),
@@ -47,9 +60,77 @@ pub enum Error {
   /// API not whitelisted on tauri.conf.json
   #[error("'{0}' not on the allowlist (https://tauri.studio/docs/api/config#tauri.allowlist)")]
   ApiNotAllowlisted(String),
+  /// API module not on the allowlist of the window.
+  #[error("API module `{0}` not on the allowlist of window `{1}`")]
+  WindowApiNotAllowlisted(String, String),
+  /// Command not on the allowlist of the window.
+  #[error("command `{0}` not on the allowlist of window `{1}`")]
+  WindowCommandNotAllowlisted(String, String),
+  /// The license public key isn't configured.
+  #[error("the license public key isn't configured on `tauri > license > pubkey`")]
+  LicensePublicKeyMissing,
+  /// Serial port with specified ID not open.
+  #[error("serial port {0} closed or not open")]
+  SerialPortNotOpen(u32),
+  /// Serial port with specified ID already open by the window.
+  #[error("serial port {0} already open")]
+  SerialPortAlreadyOpen(u32),
+  /// Bluetooth device with specified ID not connected.
+  #[error("bluetooth device {0} disconnected or not connected")]
+  BluetoothDeviceNotConnected(String),
+  /// The user didn't allow the app to read the location.
+  #[error("geolocation permission denied")]
+  GeolocationPermissionDenied,
+  /// Path not in the file system scope.
+  #[error("path not allowed on the configured scope: {0}")]
+  PathNotAllowed(std::path::PathBuf),
+  /// Program not in the shell scope.
+  #[error("program `{0}` not on the configured shell scope")]
+  ProgramNotAllowed(String),
+  /// Arguments not matching the shell scope.
+  #[error("arguments not allowed for the scoped command `{0}`")]
+  ShellArgsNotAllowed(String),
+  /// Permission denied by the user on the prompt.
+  #[error("permission denied to use `{0}`")]
+  PermissionDenied(String),
+  /// Navigation rejected by the navigation hook.
+  #[error("navigation to `{0}` not allowed")]
+  NavigationNotAllowed(String),
+  /// Child process spawned by another window.
+  #[error("process `{0}` wasn't spawned by this window")]
+  ChildProcessNotOwned(u32),
+  /// Path or URL not matching the shell `open` scope.
+  #[error("`{0}` doesn't match the configured shell open scope")]
+  OpenNotAllowed(String),
   /// Invalid args when running a command.
   #[error("invalid args for command `{0}`: {1}")]
   InvalidArgs(&'static str, serde_json::Error),
+  /// The runtime event loop exited.
+  #[error("the event loop is closed")]
+  EventLoopClosed,
+  /// Failed to create or update the system tray icon.
+  #[error("system tray error: {0}")]
+  SystemTray(String),
+  /// The operation is not supported by the runtime or the current platform.
+  #[error("{0} is not supported by the runtime on this platform")]
+  UnsupportedOperation(&'static str),
+  /// A script evaluated with [`crate::Window::eval_with_result`] threw an exception.
+  #[error("script evaluation failed: {0}")]
+  EvalScript(String),
+  /// Two plugins were registered with the same name.
+  #[error("a plugin named `{0}` is already registered")]
+  DuplicatePlugin(&'static str),
+  /// A plugin depends on a plugin that wasn't registered.
+  #[error("plugin `{plugin}` depends on `{dependency}`, which is not registered")]
+  MissingPluginDependency {
+    /// The plugin that declared the dependency.
+    plugin: &'static str,
+    /// The missing dependency.
+    dependency: String,
+  },
+  /// The plugin dependencies have a cycle.
+  #[error("plugin `{0}` depends on itself through its dependencies")]
+  PluginDependencyCycle(&'static str),
   /// Encountered an error in the setup hook,
   #[error("error encountered during setup hood: {0}")]
   Setup(#[from] Box<dyn std::error::Error>),
@@ -87,7 +87,7 @@ impl<E: Tag, L: Tag> Listeners<E, L> {
 
   /// Randomly generated listener object name to represent the JavaScript event listener object.
   pub(crate) fn listeners_object_name(&self) -> String {
-    self.function_name.to_string()
+    self.listeners_object_name.to_string()
   }
 
   /// Randomly generated queue object name to represent the JavaScript event queue object.
@@ -154,6 +154,28 @@ impl<E: Tag, L: Tag> Listeners<E, L> {
       })
   }
 
+  /// Removes an event listener if it was registered on the given window.
+  pub(crate) fn unlisten_window(&self, handler_id: EventHandler, window: &L) {
+    self
+      .inner
+      .lock()
+      .expect("poisoned event mutex")
+      .values_mut()
+      .for_each(|handlers| {
+        handlers.retain(|id, handler| *id != handler_id || handler.window.as_ref() != Some(window))
+      })
+  }
+
+  /// Removes all the event listeners registered on the given window.
+  pub(crate) fn unlisten_all_window(&self, window: &L) {
+    self
+      .inner
+      .lock()
+      .expect("poisoned event mutex")
+      .values_mut()
+      .for_each(|handlers| handlers.retain(|_, handler| handler.window.as_ref() != Some(window)))
+  }
+
   /// Triggers the given global event with its payload.
   pub(crate) fn trigger(&self, event: E, window: Option<L>, data: Option<String>) {
     if let Some(handlers) = self
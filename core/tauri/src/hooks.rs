@@ -4,10 +4,12 @@
 
 use crate::{
   api::rpc::{format_callback, format_callback_result},
-  runtime::app::App,
+  runtime::{app::App, Dispatch},
+  sealed::ManagerBase,
   Params, Window,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use std::future::Future;
 
 /// A closure that is run when the Tauri application is setting up.
@@ -19,6 +21,50 @@ pub type InvokeHandler<M> = dyn Fn(InvokeMessage<M>) + Send + Sync + 'static;
 /// A closure that is run once every time a window is created and loaded.
 pub type OnPageLoad<M> = dyn Fn(Window<M>, PageLoadPayload) + Send + Sync + 'static;
 
+/// A closure that is run when a window navigates to a URL, returning whether the navigation is allowed.
+pub type OnNavigation<M> = dyn Fn(&Window<M>, &str) -> bool + Send + Sync + 'static;
+
+/// A closure that is run when an error that doesn't stop the application happens,
+/// e.g. when a message can't be sent to a window.
+pub type OnError = dyn Fn(crate::Error) + Send + Sync + 'static;
+
+/// A closure that is run when a script of a window throws an uncaught exception
+/// or rejects a promise without handling it.
+pub type OnJsError<M> = dyn Fn(&Window<M>, &JsError) + Send + Sync + 'static;
+
+/// A closure that handles the invokes of a Tauri API module, see [`crate::Manager::register_endpoint`].
+pub type EndpointHandler<M> = dyn Fn(InvokeMessage<M>, BuiltinEndpoint) + Send + Sync + 'static;
+
+/// The built-in handler of a Tauri API module, given to an [`EndpointHandler`]
+/// so it can forward the invoke after auditing or transforming it.
+pub struct BuiltinEndpoint {
+  module: String,
+}
+
+impl BuiltinEndpoint {
+  pub(crate) fn new(module: String) -> Self {
+    Self { module }
+  }
+
+  /// The name of the module, e.g. `Fs` or `Dialog`.
+  pub fn module(&self) -> &str {
+    &self.module
+  }
+
+  /// Handles the invoke with the built-in module handler.
+  /// The invoke is rejected if Tauri doesn't have a module with this name.
+  pub fn run<M: Params>(self, message: InvokeMessage<M>) {
+    let window = message.window();
+    let manager = window.manager();
+    crate::endpoints::handle_builtin(
+      self.module,
+      message,
+      manager.config(),
+      manager.package_info(),
+    );
+  }
+}
+
 /// The payload for the [`OnPageLoad`] hook.
 #[derive(Debug, Clone, Deserialize)]
 pub struct PageLoadPayload {
@@ -32,8 +78,61 @@ impl PageLoadPayload {
   }
 }
 
+/// How a [`JsError`] went unhandled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JsErrorKind {
+  /// An exception wasn't caught, reported by `window.onerror`.
+  Error,
+  /// A rejected promise had no rejection handler, reported by `unhandledrejection`.
+  UnhandledRejection,
+}
+
+/// The payload for the [`OnJsError`] hook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsError {
+  kind: JsErrorKind,
+  message: String,
+  stack: Option<String>,
+  source: Option<String>,
+  line: Option<u32>,
+  column: Option<u32>,
+}
+
+impl JsError {
+  /// How the error went unhandled.
+  pub fn kind(&self) -> JsErrorKind {
+    self.kind
+  }
+
+  /// The error message, or the rejection reason converted to a string.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// The stack trace, if the error was an `Error` object.
+  pub fn stack(&self) -> Option<&str> {
+    self.stack.as_deref()
+  }
+
+  /// The URL of the script that threw the exception, unknown for rejections.
+  pub fn source(&self) -> Option<&str> {
+    self.source.as_deref()
+  }
+
+  /// The line of the script that threw the exception, unknown for rejections.
+  pub fn line(&self) -> Option<u32> {
+    self.line
+  }
+
+  /// The column of the script that threw the exception, unknown for rejections.
+  pub fn column(&self) -> Option<u32> {
+    self.column
+  }
+}
+
 /// Payload from an invoke call.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct InvokePayload {
   #[serde(rename = "__tauriModule")]
   pub(crate) tauri_module: Option<String>,
@@ -64,6 +163,12 @@ impl<M: Params> InvokeMessage<M> {
     }
   }
 
+  /// Replaces the invoke command, e.g. to route a `plugin:name|command` invoke as `command`.
+  pub(crate) fn with_command(mut self, command: String) -> Self {
+    self.command = command;
+    self
+  }
+
   /// The invoke command.
   pub fn command(&self) -> &str {
     &self.command
@@ -93,8 +198,11 @@ impl<M: Params> InvokeMessage<M> {
         Self::return_task(self.window, task, self.payload.callback, self.payload.error).await;
       });
     } else {
+      // the application waits for the pending invokes before exiting
+      let pending = self.window.manager().pending_invoke();
       crate::async_runtime::spawn(async move {
         Self::return_task(self.window, task, self.payload.callback, self.payload.error).await;
+        drop(pending);
       });
     }
   }
@@ -158,13 +266,33 @@ impl<M: Params> InvokeMessage<M> {
     success_callback: String,
     error_callback: String,
   ) {
-    let callback_string =
-      match format_callback_result(result, success_callback, error_callback.clone()) {
-        Ok(callback_string) => callback_string,
-        Err(e) => format_callback(error_callback, &e.to_string())
-          .expect("unable to serialize shortcut string to json"),
+    let dispatcher = window.dispatcher();
+    if dispatcher.captures_invoke_responses() {
+      let response = match result {
+        Ok(value) => serde_json::to_value(value).map_err(|e| JsonValue::String(e.to_string())),
+        Err(error) => {
+          Err(serde_json::to_value(error).unwrap_or_else(|e| JsonValue::String(e.to_string())))
+        }
       };
+      dispatcher.capture_invoke_response(success_callback, response);
+      return;
+    }
 
-    let _ = window.eval(&callback_string);
+    let (callback_string, buffers) = crate::binary::collect_buffers(|| {
+      format_callback_result(result, success_callback, error_callback.clone())
+    });
+    let callback_string = match callback_string {
+      Ok(callback_string) => callback_string,
+      Err(e) => format_callback(error_callback, &e.to_string())
+        .expect("unable to serialize shortcut string to json"),
+    };
+    // the buffers must be readable before the promise resolves
+    window
+      .manager()
+      .store_message_buffers(window.label(), buffers);
+
+    if let Err(e) = window.eval(&callback_string) {
+      window.manager().report_error(e);
+    }
   }
 }
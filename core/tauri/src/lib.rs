@@ -13,9 +13,40 @@
 pub(crate) use crate::api::private::async_runtime;
 /// The Tauri error enum.
 pub use error::Error;
-pub use tauri_macros::{command, generate_handler};
+/// Turns a function into a command handler, registered with [`generate_handler!`].
+///
+/// The arguments are deserialized from the `invoke` payload (with camelCase keys) and the
+/// return value resolves the JS promise. Functions returning a `Result` resolve the promise
+/// with the `Ok` value and reject it with the `Err` value. `async fn` commands are spawned on
+/// the async runtime, so their future must be `Send`.
+/// The return value and the error must implement `serde::Serialize`.
+///
+/// Arguments of type `tauri::Window` or `::tauri::Window` aren't read from the payload:
+/// they receive the [`Window`] that invoked the command. The macro only sees the written path,
+/// so mark the arguments of an imported `Window` type with `#[window]`.
+/// `with_window` does the same for the first argument.
+/// Likewise, arguments of type `tauri::State` or `::tauri::State`, or marked with `#[state]`,
+/// receive the [`State`] managed by the application. The invoke is rejected if the value isn't
+/// managed.
+///
+/// # Example
+/// ```rust,ignore
+/// #[tauri::command]
+/// async fn read_config(path: String) -> Result<String, String> {
+///   tokio::fs::read_to_string(path).await.map_err(|e| e.to_string())
+/// }
+///
+/// tauri::Builder::default()
+///   .invoke_handler(tauri::generate_handler![read_config])
+/// ```
+pub use tauri_macros::command;
+/// Creates the invoke handler for the given list of [`command`]s.
+///
+/// Invokes of commands that aren't on the list are rejected.
+pub use tauri_macros::generate_handler;
 
 pub mod api;
+mod binary;
 /// The Tauri API endpoints.
 mod endpoints;
 mod error;
@@ -23,10 +54,25 @@ mod event;
 mod hooks;
 pub mod plugin;
 pub mod runtime;
+mod scope;
 /// The Tauri-specific settings for your runtime e.g. notification permission status.
 pub mod settings;
+mod state;
+#[cfg(all(feature = "test-automation", not(test)))]
+pub mod test;
+// the unit tests of the crate have their own `test` module
+#[cfg(all(feature = "test-automation", test))]
+#[path = "test/mod.rs"]
+pub mod testing;
 #[cfg(feature = "updater")]
 pub mod updater;
+/// The frontend state saved on close and restored on creation.
+#[cfg(feature = "window-snapshot")]
+pub mod window_snapshot;
+/// The window geometry saved on close and restored on creation,
+/// and the windows open on the last run, see [`App::restore_session`].
+#[cfg(feature = "window-state")]
+pub mod window_state;
 
 /// `Result<T, ::tauri::Error>`
 pub type Result<T> = std::result::Result<T, Error>;
@@ -39,7 +85,7 @@ use crate::api::config::Config;
 use crate::event::{Event, EventHandler};
 use crate::runtime::tag::Tag;
 use crate::runtime::window::PendingWindow;
-use crate::runtime::{Dispatch, Runtime};
+use crate::runtime::{Dispatch, Runtime, RuntimeHandle};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -47,10 +93,14 @@ use std::path::PathBuf;
 // Export types likely to be used by the application.
 pub use {
   api::config::WindowUrl,
-  hooks::InvokeMessage,
-  runtime::app::{App, Builder},
+  binary::Binary,
+  hooks::{BuiltinEndpoint, InvokeMessage, JsError, JsErrorKind},
+  runtime::app::{App, AppHandle, Builder},
   runtime::webview::Attributes,
   runtime::window::export::Window,
+  runtime::{ExitRequestApi, RunEvent},
+  scope::{FsScope, ShellScope},
+  state::State,
 };
 
 /// Reads the config file at compile time and generates a [`Context`] based on its content.
@@ -134,12 +184,28 @@ pub trait Manager<M: Params>: sealed::ManagerBase<M> {
     self.manager().config()
   }
 
-  /// Emits a event to all windows.
+  /// Emits an event to all windows.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.emit_all("ready".into(), Some("all windows are notified"))?;
+  ///     app.emit_to(&"main".into(), "ready".into(), Some("only main is notified"))?;
+  ///     Ok(())
+  ///   })
+  ///   .run(tauri::generate_context!())
+  ///   .expect("error while running tauri application");
+  /// ```
   fn emit_all<S: Serialize + Clone>(&self, event: M::Event, payload: Option<S>) -> Result<()> {
     self.manager().emit_filter(event, payload, |_| true)
   }
 
-  /// Emits an event to a window with the specified label.
+  /// Emits an event to the window with the specified label.
+  ///
+  /// Fails with [`Error::WebviewNotFound`] if there's no window with this label.
   fn emit_to<S: Serialize + Clone>(
     &self,
     label: &M::Label,
@@ -148,7 +214,9 @@ pub trait Manager<M: Params>: sealed::ManagerBase<M> {
   ) -> Result<()> {
     self
       .manager()
-      .emit_filter(event, payload, |w| w.label() == label)
+      .get_window(label)
+      .ok_or(Error::WebviewNotFound)?
+      .emit(&event, payload)
   }
 
   /// Creates a new [`Window`] on the [`Runtime`] and attaches it to the [`Manager`].
@@ -160,8 +228,64 @@ pub trait Manager<M: Params>: sealed::ManagerBase<M> {
     match self.runtime() {
       Runtime(runtime) => runtime.create_window(pending),
       Dispatch(mut dispatcher) => dispatcher.create_window(pending),
+      RuntimeHandle(handle) => handle.create_window(pending),
     }
-    .map(|window| self.manager().attach_window(window))
+    .map(|window| {
+      let window = self.manager().attach_window(window);
+      self.manager().open_configured_devtools(&window);
+      window
+    })
+  }
+
+  /// Creates a hidden [`Window`] that loads its page in the background, to be shown instantly later.
+  ///
+  /// # Example
+  /// ```rust,ignore
+  /// use tauri::Manager;
+  ///
+  /// let preloaded = app.create_preloaded_window(pending)?;
+  /// // ...
+  /// let window = preloaded.ready().await;
+  /// window.show()?;
+  /// ```
+  fn create_preloaded_window(
+    &mut self,
+    mut pending: PendingWindow<M>,
+  ) -> Result<crate::runtime::window::PreloadedWindow<M>> {
+    pending.attributes = pending.attributes.visible(false);
+    let ready = self.manager().watch_ready(pending.label.clone());
+    let window = self.create_window(pending)?;
+    Ok(crate::runtime::window::PreloadedWindow::new(window, ready))
+  }
+
+  /// Registers a handler for the invokes of a Tauri API module (the `__tauriModule` sent by the
+  /// `@tauri-apps/api` package, e.g. `Fs` or `Dialog`), replacing any handler already registered.
+  ///
+  /// The handler can override the built-in module, or wrap it by calling [`BuiltinEndpoint::run`].
+  /// Modules unknown to Tauri can be registered to extend the API.
+  ///
+  /// # Example
+  /// ```rust,ignore
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.register_endpoint("Fs", |message, builtin| {
+  ///       println!("fs access: {}", message.payload());
+  ///       builtin.run(message);
+  ///     });
+  ///     Ok(())
+  ///   })
+  ///   .run(tauri::generate_context!())
+  ///   .expect("error while running tauri application");
+  /// ```
+  fn register_endpoint<F>(&self, module: &str, handler: F)
+  where
+    F: Fn(InvokeMessage<M>, BuiltinEndpoint) + Send + Sync + 'static,
+  {
+    self
+      .manager()
+      .register_endpoint(module.to_string(), std::sync::Arc::new(handler))
   }
 
   /// Listen to a global event.
@@ -190,6 +314,68 @@ pub trait Manager<M: Params>: sealed::ManagerBase<M> {
     self.manager().unlisten(handler_id)
   }
 
+  /// The manager of the global shortcuts, which are reported with the
+  /// [`GLOBAL_SHORTCUT_EVENT`](crate::runtime::global_shortcut::GLOBAL_SHORTCUT_EVENT).
+  #[cfg(global_shortcut_all)]
+  fn global_shortcut_manager(&self) -> crate::runtime::global_shortcut::GlobalShortcutManager<M> {
+    crate::runtime::global_shortcut::GlobalShortcutManager::new(self.manager().clone())
+  }
+
+  /// Adds a value to the state managed by the application, retrieved with [`Manager::state`]
+  /// or with a [`State`] command argument.
+  ///
+  /// Returns `false` if a value of the same type is already managed, leaving it untouched.
+  fn manage<T: Send + Sync + 'static>(&self, state: T) -> bool {
+    self.manager().state().set(state)
+  }
+
+  /// Gets the managed value of the given type.
+  ///
+  /// # Panics
+  ///
+  /// Panics if no value of this type is managed, see [`Manager::try_state`].
+  fn state<T: Send + Sync + 'static>(&self) -> State<T> {
+    self.try_state().unwrap_or_else(|| {
+      panic!(
+        "state for type `{}` is not managed, call `manage` first",
+        std::any::type_name::<T>()
+      )
+    })
+  }
+
+  /// Gets the managed value of the given type, if any.
+  fn try_state<T: Send + Sync + 'static>(&self) -> Option<State<T>> {
+    self.manager().state().try_get()
+  }
+
+  /// The files served by the `asset` protocol, configured on `tauri > allowlist > protocol > assetScope`.
+  fn asset_protocol_scope(&self) -> FsScope {
+    self.manager().asset_protocol_scope()
+  }
+
+  /// The paths the file system APIs can access, configured on `tauri > allowlist > fs > scope`.
+  fn fs_scope(&self) -> FsScope {
+    self.manager().fs_scope()
+  }
+
+  /// A running process spawned with the shell `execute` API, to write to its stdin or kill it.
+  fn child_process(&self, pid: u32) -> Option<api::command::CommandChild> {
+    self.manager().child_process(pid)
+  }
+
+  /// The programs the shell APIs can run, configured on `tauri > allowlist > shell > scope`.
+  fn shell_scope(&self) -> ShellScope {
+    self.manager().shell_scope()
+  }
+
+  /// Shows the windows created hidden while the splashscreen shows and closes the splashscreen.
+  ///
+  /// Meant for splashscreens with the `splashscreenAutoClose` config disabled, e.g. called once
+  /// the app setup finished. Otherwise the splashscreen closes when the other windows load.
+  fn close_splashscreen(&self) {
+    self.manager().close_splashscreen()
+  }
+
   /// Fetch a single window from the manager.
   fn get_window(&self, label: &M::Label) -> Option<Window<M>> {
     self.manager().get_window(label)
@@ -216,6 +402,9 @@ pub(crate) mod sealed {
 
     /// A dispatcher to the running [`Runtime`].
     Dispatch(<P::Runtime as Runtime>::Dispatcher),
+
+    /// A handle to the running [`Runtime`].
+    RuntimeHandle(<P::Runtime as Runtime>::Handle),
   }
 
   /// Managed handle to the application runtime.
@@ -6,20 +6,38 @@
 
 use crate::{
   api::config::PluginConfig,
-  hooks::{InvokeMessage, PageLoadPayload},
-  Params, Window,
+  hooks::{InvokeHandler, InvokeMessage, PageLoadPayload},
+  AppHandle, Params, Window,
 };
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
 /// The plugin interface.
 pub trait Plugin<M: Params>: Send {
-  /// The plugin name. Used as key on the plugin config object.
+  /// The plugin name. Used as key on the plugin config object and as the `plugin:name|command`
+  /// invoke namespace. Must be unique.
   fn name(&self) -> &'static str;
 
-  /// Initialize the plugin.
+  /// The plugin version, usually `env!("CARGO_PKG_VERSION")`.
+  /// Required to invoke the plugin with a versioned namespace, e.g. `plugin:name@1|command`.
+  fn version(&self) -> Option<&'static str> {
+    None
+  }
+
+  /// The names of the plugins this plugin depends on.
+  /// They are initialized before this plugin.
+  fn dependencies(&self) -> Vec<&'static str> {
+    Vec::new()
+  }
+
+  /// Initialize the plugin, with its object of the `plugins` section of `tauri.conf.json`,
+  /// or `null` if it isn't configured.
+  ///
+  /// The app handle can be stored to use the app later, e.g. to emit events from a background thread.
+  /// It's called before the windows are created, with the plugin store locked,
+  /// so windows must be created on the [`crate::Builder::setup`] hook instead.
   #[allow(unused_variables)]
-  fn initialize(&mut self, config: JsonValue) -> crate::Result<()> {
+  fn initialize(&mut self, app: &AppHandle<M>, config: JsonValue) -> crate::Result<()> {
     Ok(())
   }
 
@@ -28,10 +46,23 @@ pub trait Plugin<M: Params>: Send {
   /// so global variables must be assigned to `window` instead of implicity declared.
   ///
   /// It's guaranteed that this script is executed before the page is loaded.
+  /// Like the Tauri scripts, it runs in an isolated script world where the webview supports it,
+  /// see [`crate::runtime::webview::Attributes::isolated_initialization_script`].
   fn initialization_script(&self) -> Option<String> {
     None
   }
 
+  /// The JS module shipped with the plugin, exposed to every window as `window.__TAURI_PLUGINS__.<name>`.
+  ///
+  /// The module uses the CommonJS format: its exports must be assigned to `module.exports` or `exports`.
+  /// Bundle it at build time and embed it with `include_str!`, so the Rust and the JS sides of the plugin
+  /// are always released together.
+  ///
+  /// Like the [`Plugin::initialization_script`], it's executed before the page is loaded.
+  fn js_module(&self) -> Option<&'static str> {
+    None
+  }
+
   /// Callback invoked when the webview is created.
   #[allow(unused_variables)]
   fn created(&mut self, window: Window<M>) {}
@@ -41,19 +72,197 @@ pub trait Plugin<M: Params>: Send {
   fn on_page_load(&mut self, window: Window<M>, payload: PageLoadPayload) {}
 
   /// Add invoke_handler API extension commands.
+  ///
+  /// The `plugin:name|command` invokes are received with the `command` name,
+  /// so they can be handled with [`crate::generate_handler`], see [`PluginBuilder::invoke_handler`].
   #[allow(unused_variables)]
   fn extend_api(&mut self, message: InvokeMessage<M>) {}
 }
 
+type SetupHook<M> = dyn FnMut(&AppHandle<M>, JsonValue) -> crate::Result<()> + Send;
+type WindowHook<M> = dyn FnMut(Window<M>) + Send;
+type PageLoadHook<M> = dyn FnMut(Window<M>, PageLoadPayload) + Send;
+
+/// Builds a [`Plugin`] from closures, with its commands handled by [`crate::generate_handler`].
+///
+/// # Example
+/// ```rust,no_run
+/// use tauri::{plugin::PluginBuilder, Params};
+///
+/// #[tauri::command]
+/// fn greet(name: String) -> String {
+///   format!("hello {}", name)
+/// }
+///
+/// fn greeter<M: Params>() -> impl tauri::plugin::Plugin<M> {
+///   // invoked from JS with `invoke('plugin:greeter|greet', { name: 'Tauri' })`
+///   PluginBuilder::new("greeter")
+///     .invoke_handler(tauri::generate_handler![greet])
+///     .build()
+/// }
+/// ```
+pub struct PluginBuilder<M: Params> {
+  name: &'static str,
+  version: Option<&'static str>,
+  dependencies: Vec<&'static str>,
+  invoke_handler: Box<InvokeHandler<M>>,
+  initialization_script: Option<String>,
+  js_module: Option<&'static str>,
+  setup: Option<Box<SetupHook<M>>>,
+  on_window_created: Option<Box<WindowHook<M>>>,
+  on_page_load: Option<Box<PageLoadHook<M>>>,
+}
+
+impl<M: Params> PluginBuilder<M> {
+  /// Creates a plugin builder, see [`Plugin::name`].
+  pub fn new(name: &'static str) -> Self {
+    Self {
+      name,
+      version: None,
+      dependencies: Vec::new(),
+      invoke_handler: Box::new(|message| {
+        let command = message.command().to_string();
+        message.reject(format!("command `{}` not found", command))
+      }),
+      initialization_script: None,
+      js_module: None,
+      setup: None,
+      on_window_created: None,
+      on_page_load: None,
+    }
+  }
+
+  /// Sets the plugin version, see [`Plugin::version`].
+  pub fn version(mut self, version: &'static str) -> Self {
+    self.version.replace(version);
+    self
+  }
+
+  /// Adds a plugin this plugin depends on, see [`Plugin::dependencies`].
+  pub fn dependency(mut self, name: &'static str) -> Self {
+    self.dependencies.push(name);
+    self
+  }
+
+  /// Defines the plugin commands, usually with [`crate::generate_handler`].
+  /// The commands are invoked as `plugin:name|command`.
+  pub fn invoke_handler<F>(mut self, invoke_handler: F) -> Self
+  where
+    F: Fn(InvokeMessage<M>) + Send + Sync + 'static,
+  {
+    self.invoke_handler = Box::new(invoke_handler);
+    self
+  }
+
+  /// Sets the script evaluated on webview initialization, see [`Plugin::initialization_script`].
+  pub fn initialization_script(mut self, script: impl Into<String>) -> Self {
+    self.initialization_script.replace(script.into());
+    self
+  }
+
+  /// Sets the JS module exposed to the windows, see [`Plugin::js_module`].
+  pub fn js_module(mut self, module: &'static str) -> Self {
+    self.js_module.replace(module);
+    self
+  }
+
+  /// Defines the hook called when the plugin is initialized, see [`Plugin::initialize`].
+  pub fn setup<F>(mut self, setup: F) -> Self
+  where
+    F: FnMut(&AppHandle<M>, JsonValue) -> crate::Result<()> + Send + 'static,
+  {
+    self.setup.replace(Box::new(setup));
+    self
+  }
+
+  /// Defines the hook called when a window is created, see [`Plugin::created`].
+  pub fn on_window_created<F>(mut self, on_window_created: F) -> Self
+  where
+    F: FnMut(Window<M>) + Send + 'static,
+  {
+    self.on_window_created.replace(Box::new(on_window_created));
+    self
+  }
+
+  /// Defines the hook called when a window loads a page, see [`Plugin::on_page_load`].
+  pub fn on_page_load<F>(mut self, on_page_load: F) -> Self
+  where
+    F: FnMut(Window<M>, PageLoadPayload) + Send + 'static,
+  {
+    self.on_page_load.replace(Box::new(on_page_load));
+    self
+  }
+
+  /// Builds the plugin, to register it with [`crate::Builder::plugin`].
+  pub fn build(self) -> BuiltPlugin<M> {
+    BuiltPlugin(self)
+  }
+}
+
+/// A plugin built with [`PluginBuilder`].
+pub struct BuiltPlugin<M: Params>(PluginBuilder<M>);
+
+impl<M: Params> Plugin<M> for BuiltPlugin<M> {
+  fn name(&self) -> &'static str {
+    self.0.name
+  }
+
+  fn version(&self) -> Option<&'static str> {
+    self.0.version
+  }
+
+  fn dependencies(&self) -> Vec<&'static str> {
+    self.0.dependencies.clone()
+  }
+
+  fn initialize(&mut self, app: &AppHandle<M>, config: JsonValue) -> crate::Result<()> {
+    match &mut self.0.setup {
+      Some(setup) => setup(app, config),
+      None => Ok(()),
+    }
+  }
+
+  fn initialization_script(&self) -> Option<String> {
+    self.0.initialization_script.clone()
+  }
+
+  fn js_module(&self) -> Option<&'static str> {
+    self.0.js_module
+  }
+
+  fn created(&mut self, window: Window<M>) {
+    if let Some(on_window_created) = &mut self.0.on_window_created {
+      on_window_created(window)
+    }
+  }
+
+  fn on_page_load(&mut self, window: Window<M>, payload: PageLoadPayload) {
+    if let Some(on_page_load) = &mut self.0.on_page_load {
+      on_page_load(window, payload)
+    }
+  }
+
+  fn extend_api(&mut self, message: InvokeMessage<M>) {
+    (self.0.invoke_handler)(message)
+  }
+}
+
 /// Plugin collection type.
 pub(crate) struct PluginStore<M: Params> {
   store: HashMap<&'static str, Box<dyn Plugin<M>>>,
+  /// The plugin names, in registration order until the plugins are initialized
+  /// and in dependency order after that.
+  order: Vec<&'static str>,
+  /// The names registered more than once.
+  duplicates: Vec<&'static str>,
 }
 
 impl<M: Params> Default for PluginStore<M> {
   fn default() -> Self {
     Self {
       store: HashMap::new(),
+      order: Vec::new(),
+      duplicates: Vec::new(),
     }
   }
 }
@@ -62,54 +271,325 @@ impl<M: Params> PluginStore<M> {
   /// Adds a plugin to the store.
   ///
   /// Returns `true` if a plugin with the same name is already in the store.
+  /// In that case the plugin is not added and [`Self::initialize`] fails.
   pub fn register<P: Plugin<M> + 'static>(&mut self, plugin: P) -> bool {
-    self.store.insert(plugin.name(), Box::new(plugin)).is_some()
+    let name = plugin.name();
+    if self.store.contains_key(name) {
+      self.duplicates.push(name);
+      true
+    } else {
+      self.store.insert(name, Box::new(plugin));
+      self.order.push(name);
+      false
+    }
   }
 
-  /// Initializes all plugins in the store.
-  pub(crate) fn initialize(&mut self, config: &PluginConfig) -> crate::Result<()> {
-    self.store.values_mut().try_for_each(|plugin| {
-      plugin.initialize(config.0.get(plugin.name()).cloned().unwrap_or_default())
-    })
+  /// Initializes all plugins in the store, dependencies first.
+  pub(crate) fn initialize(
+    &mut self,
+    app: &AppHandle<M>,
+    config: &PluginConfig,
+  ) -> crate::Result<()> {
+    if let Some(name) = self.duplicates.first() {
+      return Err(crate::Error::DuplicatePlugin(name));
+    }
+    self.order = self.dependency_order()?;
+    for name in &self.order {
+      let plugin = self.store.get_mut(name).expect("plugin not in store");
+      plugin.initialize(app, config.0.get(*name).cloned().unwrap_or_default())?;
+    }
+    Ok(())
+  }
+
+  /// Sorts the plugins so each plugin comes after its dependencies, keeping the registration order otherwise.
+  fn dependency_order(&self) -> crate::Result<Vec<&'static str>> {
+    let mut order = Vec::with_capacity(self.order.len());
+    let mut visiting = Vec::new();
+    for name in &self.order {
+      self.visit(name, &mut visiting, &mut order)?;
+    }
+    Ok(order)
   }
 
-  /// Generates an initialization script from all plugins in the store.
+  fn visit(
+    &self,
+    name: &'static str,
+    visiting: &mut Vec<&'static str>,
+    order: &mut Vec<&'static str>,
+  ) -> crate::Result<()> {
+    if order.contains(&name) {
+      return Ok(());
+    }
+    if visiting.contains(&name) {
+      return Err(crate::Error::PluginDependencyCycle(name));
+    }
+    visiting.push(name);
+    for dependency in self.store[name].dependencies() {
+      if !self.store.contains_key(dependency) {
+        return Err(crate::Error::MissingPluginDependency {
+          plugin: name,
+          dependency: dependency.to_string(),
+        });
+      }
+      self.visit(dependency, visiting, order)?;
+    }
+    visiting.pop();
+    order.push(name);
+    Ok(())
+  }
+
+  /// Generates an initialization script from all plugins in the store,
+  /// including the JS modules, which are defined before the plugin scripts run.
   pub(crate) fn initialization_script(&self) -> String {
+    let modules = self
+      .order
+      .iter()
+      .filter_map(|name| self.store[name].js_module().map(|module| (name, module)))
+      .fold(String::new(), |acc, (name, module)| {
+        format!(
+          r#"{}
+(function () {{
+  var module = {{ exports: {{}} }};
+  (function (module, exports) {{ {} }})(module, module.exports);
+  window.__TAURI_PLUGINS__ = window.__TAURI_PLUGINS__ || {{}};
+  window.__TAURI_PLUGINS__[{}] = module.exports;
+}})();"#,
+          acc,
+          module,
+          JsonValue::String(name.to_string())
+        )
+      });
     self
-      .store
-      .values()
-      .filter_map(|p| p.initialization_script())
-      .fold(String::new(), |acc, script| {
+      .order
+      .iter()
+      .filter_map(|name| self.store[name].initialization_script())
+      .fold(modules, |acc, script| {
         format!("{}\n(function () {{ {} }})();", acc, script)
       })
   }
 
   /// Runs the created hook for all plugins in the store.
   pub(crate) fn created(&mut self, window: Window<M>) {
-    self
-      .store
-      .values_mut()
-      .for_each(|plugin| plugin.created(window.clone()))
+    let store = &mut self.store;
+    self.order.iter().for_each(|name| {
+      store
+        .get_mut(name)
+        .expect("plugin not in store")
+        .created(window.clone())
+    })
   }
 
   /// Runs the on_page_load hook for all plugins in the store.
   pub(crate) fn on_page_load(&mut self, window: Window<M>, payload: PageLoadPayload) {
-    self
-      .store
-      .values_mut()
-      .for_each(|plugin| plugin.on_page_load(window.clone(), payload.clone()))
+    let store = &mut self.store;
+    self.order.iter().for_each(|name| {
+      store
+        .get_mut(name)
+        .expect("plugin not in store")
+        .on_page_load(window.clone(), payload.clone())
+    })
   }
 
+  /// Routes a `plugin:name|command` or `plugin:name@version|command` invoke to its plugin,
+  /// which receives it as `command`.
+  /// The invoke is rejected if the plugin isn't registered or its version doesn't match.
   pub(crate) fn extend_api(&mut self, command: String, message: InvokeMessage<M>) {
-    let target = command
-      .replace("plugin:", "")
-      .split('|')
-      .next()
-      .expect("target plugin name empty")
-      .to_string();
+    let mut parts = command.trim_start_matches("plugin:").splitn(2, '|');
+    let target = parts.next().unwrap_or_default();
+    let plugin_command = parts.next().unwrap_or_default().to_string();
+    let mut target = target.splitn(2, '@');
+    let name = target.next().unwrap_or_default();
+    let version = target.next();
+
+    match self.store.get_mut(name) {
+      Some(plugin) => match version {
+        Some(version) if !version_matches(version, plugin.version()) => message.reject(format!(
+          "plugin `{}` version {} doesn't match the requested version {}",
+          name,
+          plugin.version().unwrap_or("unknown"),
+          version
+        )),
+        _ => plugin.extend_api(message.with_command(plugin_command)),
+      },
+      None => message.reject(format!("plugin `{}` not found", name)),
+    }
+  }
+}
+
+/// Checks if a plugin version satisfies the version requirement of a namespace, e.g. `1` or `^1.2`.
+fn version_matches(requirement: &str, version: Option<&str>) -> bool {
+  match (
+    semver::VersionReq::parse(requirement),
+    version.map(semver::Version::parse),
+  ) {
+    (Ok(requirement), Some(Ok(version))) => requirement.matches(&version),
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::{
+    runtime::manager::Args,
+    test::{mock_context, MockRuntime, NoopAsset},
+  };
+
+  type TestParams = Args<String, String, NoopAsset, MockRuntime>;
+
+  fn app() -> AppHandle<TestParams> {
+    crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app")
+      .handle()
+  }
+
+  struct TestPlugin {
+    name: &'static str,
+    dependencies: Vec<&'static str>,
+  }
+
+  struct ModulePlugin;
+
+  impl Plugin<TestParams> for ModulePlugin {
+    fn name(&self) -> &'static str {
+      "greeter"
+    }
 
-    if let Some(plugin) = self.store.get_mut(target.as_str()) {
-      plugin.extend_api(message);
+    fn js_module(&self) -> Option<&'static str> {
+      Some("exports.greet = function () { return 'hello' }")
     }
   }
+
+  #[derive(Default)]
+  struct ConfigPlugin {
+    config: JsonValue,
+  }
+
+  impl Plugin<TestParams> for ConfigPlugin {
+    fn name(&self) -> &'static str {
+      "updater"
+    }
+
+    fn initialize(&mut self, _app: &AppHandle<TestParams>, config: JsonValue) -> crate::Result<()> {
+      self.config = config;
+      Ok(())
+    }
+
+    fn initialization_script(&self) -> Option<String> {
+      Some(format!("window.updaterConfig = {}", self.config))
+    }
+  }
+
+  impl Plugin<TestParams> for TestPlugin {
+    fn name(&self) -> &'static str {
+      self.name
+    }
+
+    fn dependencies(&self) -> Vec<&'static str> {
+      self.dependencies.clone()
+    }
+  }
+
+  fn store(plugins: Vec<(&'static str, Vec<&'static str>)>) -> PluginStore<TestParams> {
+    let mut store = PluginStore::default();
+    for (name, dependencies) in plugins {
+      store.register(TestPlugin { name, dependencies });
+    }
+    store
+  }
+
+  #[test]
+  fn initializes_dependencies_first() {
+    let mut store = store(vec![
+      ("app", vec!["store", "log"]),
+      ("store", vec!["log"]),
+      ("log", vec![]),
+    ]);
+    store
+      .initialize(&app(), &Default::default())
+      .expect("failed to initialize plugins");
+    assert_eq!(store.order, vec!["log", "store", "app"]);
+  }
+
+  #[test]
+  fn rejects_invalid_plugins() {
+    let mut duplicated = store(vec![("log", vec![]), ("log", vec![])]);
+    assert!(matches!(
+      duplicated.initialize(&app(), &Default::default()),
+      Err(crate::Error::DuplicatePlugin("log"))
+    ));
+
+    let mut missing = store(vec![("app", vec!["log"])]);
+    assert!(matches!(
+      missing.initialize(&app(), &Default::default()),
+      Err(crate::Error::MissingPluginDependency { plugin: "app", .. })
+    ));
+
+    let mut cycle = store(vec![("a", vec!["b"]), ("b", vec!["a"])]);
+    assert!(matches!(
+      cycle.initialize(&app(), &Default::default()),
+      Err(crate::Error::PluginDependencyCycle(_))
+    ));
+  }
+
+  #[test]
+  fn passes_plugin_config() {
+    let mut store = PluginStore::<TestParams>::default();
+    store.register(ConfigPlugin::default());
+    let mut config = HashMap::new();
+    config.insert(
+      "updater".to_string(),
+      serde_json::json!({ "endpoint": "https://releases.example.com" }),
+    );
+    store
+      .initialize(&app(), &PluginConfig(config))
+      .expect("failed to initialize plugins");
+    assert!(store
+      .initialization_script()
+      .contains(r#"window.updaterConfig = {"endpoint":"https://releases.example.com"}"#));
+  }
+
+  #[test]
+  fn exposes_js_module() {
+    let mut store = store(vec![]);
+    store.register(ModulePlugin);
+    let script = store.initialization_script();
+    assert!(script.contains("exports.greet = function () { return 'hello' }"));
+    assert!(script.contains(r#"window.__TAURI_PLUGINS__["greeter"] = module.exports;"#));
+  }
+
+  #[test]
+  fn invokes_builder_commands() {
+    use crate::testing::mock_invoke;
+
+    let greeter = PluginBuilder::new("greeter")
+      .version("1.0.0")
+      .invoke_handler(|message| match message.command() {
+        "greet" => message.resolve("hello"),
+        _ => message.reject("unknown"),
+      })
+      .build();
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .plugin(greeter)
+      .build(mock_context())
+      .expect("failed to build app");
+
+    assert_eq!(
+      mock_invoke(&app, "plugin:greeter|greet", JsonValue::Null),
+      Ok(JsonValue::String("hello".into()))
+    );
+    assert_eq!(
+      mock_invoke(&app, "plugin:greeter@1|greet", JsonValue::Null),
+      Ok(JsonValue::String("hello".into()))
+    );
+  }
+
+  #[test]
+  fn matches_namespace_version() {
+    assert!(version_matches("1", Some("1.2.3")));
+    assert!(version_matches("^1.2", Some("1.2.3")));
+    assert!(!version_matches("2", Some("1.2.3")));
+    assert!(!version_matches("1", None));
+  }
 }
@@ -3,21 +3,132 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-  api::{assets::Assets, config::WindowUrl},
-  hooks::{InvokeHandler, InvokeMessage, OnPageLoad, PageLoadPayload, SetupHook},
+  api::{assets::Assets, config::WindowUrl, idle::IdleState},
+  hooks::{
+    InvokeHandler, InvokeMessage, JsError, OnError, OnJsError, OnNavigation, OnPageLoad,
+    PageLoadPayload, SetupHook,
+  },
   plugin::{Plugin, PluginStore},
   runtime::{
-    flavors::wry::Wry, manager::WindowManager, tag::Tag, webview::Attributes,
-    window::PendingWindow, Dispatch, Runtime,
+    flavors::wry::Wry,
+    manager::{WindowManager, DEFAULT_WINDOW_EVENT_THROTTLE},
+    menu::{MenuItem, SystemTray, SystemTrayEvent},
+    monitor::{Monitor, PhysicalPosition},
+    tag::Tag,
+    webview::{Attributes, CustomProtocolHandler, CustomProtocolResponse},
+    window::PendingWindow,
+    Dispatch, RunEvent, Runtime, RuntimeHandle,
   },
   sealed::{ManagerBase, RuntimeOrDispatch},
-  Context, Manager, Params, Window,
+  state::StateManager,
+  Context, Icon, Manager, Params, Window,
 };
 
 use crate::runtime::manager::Args;
 #[cfg(feature = "updater")]
 use crate::updater;
 
+use std::{collections::HashMap, future::Future, path::PathBuf, sync::Arc, time::Duration};
+
+/// How often the appearance preferences are checked when [`Builder::appearance_events`] is enabled.
+const APPEARANCE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often the idle time is checked when [`Builder::idle_events`] is enabled.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A thread-safe handle to the currently running application, not associated with any window.
+///
+/// This type implements [`Manager`] which allows for manipulation of global application items.
+pub struct AppHandle<P: Params> {
+  runtime_handle: <P::Runtime as Runtime>::Handle,
+  manager: WindowManager<P>,
+}
+
+impl<P: Params> Clone for AppHandle<P> {
+  fn clone(&self) -> Self {
+    Self {
+      runtime_handle: self.runtime_handle.clone(),
+      manager: self.manager.clone(),
+    }
+  }
+}
+
+impl<P: Params> Manager<P> for AppHandle<P> {}
+impl<P: Params> ManagerBase<P> for AppHandle<P> {
+  fn manager(&self) -> &WindowManager<P> {
+    &self.manager
+  }
+
+  fn runtime(&mut self) -> RuntimeOrDispatch<'_, P> {
+    RuntimeOrDispatch::RuntimeHandle(self.runtime_handle.clone())
+  }
+}
+
+impl<P: Params> AppHandle<P> {
+  pub(crate) fn new(
+    runtime_handle: <P::Runtime as Runtime>::Handle,
+    manager: WindowManager<P>,
+  ) -> Self {
+    Self {
+      runtime_handle,
+      manager,
+    }
+  }
+
+  /// Gets the position of the mouse cursor on the virtual desktop, in physical pixels.
+  pub fn cursor_position(&self) -> crate::Result<PhysicalPosition> {
+    self.runtime_handle.cursor_position()
+  }
+
+  /// Lists the monitors connected to the system, including their work area.
+  pub fn available_monitors(&self) -> crate::Result<Vec<Monitor>> {
+    self.runtime_handle.available_monitors()
+  }
+
+  /// Gets the primary monitor of the system.
+  pub fn primary_monitor(&self) -> crate::Result<Option<Monitor>> {
+    self.runtime_handle.primary_monitor()
+  }
+
+  /// Gets the monitor the mouse cursor is on.
+  pub fn cursor_monitor(&self) -> crate::Result<Option<Monitor>> {
+    let position = self.cursor_position()?;
+    Ok(
+      self
+        .available_monitors()?
+        .into_iter()
+        .find(|monitor| monitor.contains(position)),
+    )
+  }
+
+  /// Updates the system tray icon set with [`Builder::system_tray`].
+  pub fn set_tray_icon(&self, icon: Icon) -> crate::Result<()> {
+    self.runtime_handle.set_tray_icon(icon)
+  }
+
+  /// Updates the context menu of the system tray icon set with [`Builder::system_tray`].
+  pub fn set_tray_menu(&self, menu: Vec<MenuItem>) -> crate::Result<()> {
+    self.runtime_handle.set_tray_menu(menu)
+  }
+
+  /// Exits the application with the given code.
+  ///
+  /// The pending async commands get up to a second to respond, then the windows are closed,
+  /// [`RunEvent::Exit`] is sent to the [`App::run`] callback and the processes spawned with the
+  /// shell API are killed before the process exits.
+  /// Closing the last window and the `quit` close behavior exit the same way.
+  pub fn exit(&self, code: i32) -> ! {
+    self.manager.exit(code)
+  }
+
+  /// Relaunches the application with the same arguments and exits, e.g. to apply an update.
+  ///
+  /// It only returns if the new instance failed to start, see [`crate::api::process::relaunch`].
+  pub fn restart(&self) -> crate::Result<()> {
+    crate::api::process::relaunch()?;
+    self.exit(0)
+  }
+}
+
 /// A handle to the currently running application.
 ///
 /// This type implements [`Manager`] which allows for manipulation of global application items.
@@ -37,6 +148,98 @@ impl<P: Params> ManagerBase<P> for App<P> {
   }
 }
 
+impl<P: Params> App<P> {
+  /// Gets a thread-safe handle to the application, which can be moved to other threads
+  /// and used after the application starts running.
+  pub fn handle(&self) -> AppHandle<P> {
+    AppHandle::new(self.runtime.handle(), self.manager.clone())
+  }
+
+  /// Exits the application with the given code, see [`AppHandle::exit`].
+  pub fn exit(&self, code: i32) -> ! {
+    self.handle().exit(code)
+  }
+
+  /// Runs the application, sending the [`RunEvent`]s to the callback.
+  ///
+  /// Preventing the exit on [`RunEvent::ExitRequested`] keeps the application running after its
+  /// last window closes, e.g. for apps living in the system tray.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// use tauri::RunEvent;
+  ///
+  /// tauri::Builder::default()
+  ///   .build(tauri::generate_context!())
+  ///   .expect("error while building tauri application")
+  ///   .run(|_app, event| {
+  ///     if let RunEvent::ExitRequested(api) = event {
+  ///       api.prevent_exit();
+  ///     }
+  ///   });
+  /// ```
+  pub fn run<F: Fn(&AppHandle<P>, RunEvent<P::Label>) + Send + Sync + 'static>(self, callback: F) {
+    let handle = self.handle();
+    let callback = Arc::new(move |event: RunEvent<P::Label>| callback(&handle, event));
+    self.manager.set_run_event_handler(callback.clone());
+    self.runtime.run(move |event| callback(event));
+  }
+
+  /// Recreates the windows that were open when the application last ran and starts recording
+  /// the open windows, so they can be restored on the next startup.
+  ///
+  /// Windows that are already open, e.g. the ones defined on the configuration file, are skipped.
+  /// The label and the loaded URL of each window are restored, and its geometry like any window.
+  /// This is usually called in the [`Builder::setup`] hook.
+  ///
+  /// The session is kept on the window state file, next to the geometry of the windows.
+  /// Windows are removed from it when they're closed, except on exit and for the last window.
+  #[cfg(feature = "window-state")]
+  pub fn restore_session(&mut self) -> crate::Result<Vec<Window<P>>> {
+    self.manager.enable_session();
+    let store = crate::window_state::read_window_store(self.manager.config())?;
+    let mut windows = Vec::new();
+    for window in store.session {
+      let label: P::Label = match window.label.parse() {
+        Ok(label) => label,
+        Err(_) => continue,
+      };
+      if self.manager.get_window(&label).is_some() {
+        continue;
+      }
+      let url = self.manager.session_window_url(&window.url);
+      let attributes = <<P::Runtime as Runtime>::Dispatcher as Dispatch>::Attributes::new();
+      windows.push(self.create_window(PendingWindow::new(attributes, label, url))?);
+    }
+    self.manager.save_session();
+    Ok(windows)
+  }
+
+  /// Feeds the invokes and events of a recording created with [`Builder::record_events`]
+  /// back to the application, keeping their original order and timing.
+  ///
+  /// Invokes are handled again, events emitted to the windows are emitted again and events
+  /// triggered on the Rust listeners are triggered again. Records targeting windows that
+  /// are not open are skipped. The replay runs on a separate thread; this is usually
+  /// called in the [`Builder::setup`] hook.
+  ///
+  /// The invokes of the API modules with side effects, like writing files, running programs
+  /// or sending requests, are skipped unless `side_effects` is set. The commands of the
+  /// application and its plugins are always replayed.
+  ///
+  /// Only available on debug builds with the `event-replay` feature.
+  #[cfg(all(feature = "event-replay", debug_assertions))]
+  pub fn replay_events<T: AsRef<std::path::Path>>(
+    &self,
+    path: T,
+    side_effects: bool,
+  ) -> crate::Result<()> {
+    let records = crate::runtime::recorder::read_recording(path)?;
+    self.manager.replay(records, side_effects);
+    Ok(())
+  }
+}
+
 #[cfg(feature = "updater")]
 impl<M: Params> App<M> {
   /// Runs the updater hook with built-in dialog.
@@ -113,13 +316,59 @@ where
   /// Page load hook.
   on_page_load: Box<OnPageLoad<Args<E, L, A, R>>>,
 
+  /// The navigation hook, allowing every navigation by default.
+  on_navigation: Box<OnNavigation<Args<E, L, A, R>>>,
+
+  /// The hook for errors that don't stop the application.
+  on_error: Box<OnError>,
+
+  /// The hook for the uncaught exceptions and unhandled rejections of the windows.
+  on_js_error: Box<OnJsError<Args<E, L, A, R>>>,
+
   /// windows to create when starting up.
   pending_windows: Vec<PendingWindow<Args<E, L, A, R>>>,
 
   /// All passed plugins
   plugins: PluginStore<Args<E, L, A, R>>,
+
+  /// Whether the webview accessibility tree should always be enabled.
+  force_accessibility: bool,
+
+  /// Whether accessibility settings changes should be emitted to the windows.
+  accessibility_events: bool,
+
+  /// Whether appearance preferences changes should be emitted to the windows.
+  appearance_events: bool,
+
+  /// The idle time after which `tauri://user-idle` is emitted to the windows.
+  idle_threshold: Option<Duration>,
+
+  /// The minimum interval between two resize or move events of a window.
+  window_event_throttle: Duration,
+
+  /// The time a closing window waits for the state of its page.
+  #[cfg(feature = "window-snapshot")]
+  window_snapshot_timeout: Duration,
+
+  /// The file the invokes and events are recorded to.
+  recording_path: Option<PathBuf>,
+
+  /// The system tray icon.
+  system_tray: Option<SystemTray>,
+
+  /// The system tray event handler.
+  system_tray_event_handler: Option<Box<SystemTrayEventHandler<Args<E, L, A, R>>>>,
+
+  /// The values registered with [`Builder::manage`].
+  state: StateManager,
+
+  /// The protocols registered with [`Builder::register_uri_scheme_protocol`].
+  uri_scheme_protocols: HashMap<String, Arc<CustomProtocolHandler>>,
 }
 
+/// A closure that is run when the system tray icon or one of its menu items is clicked.
+type SystemTrayEventHandler<P> = dyn Fn(&AppHandle<P>, &SystemTrayEvent) + Send + Sync + 'static;
+
 impl<E, L, A, R> Builder<E, L, A, R>
 where
   E: Tag,
@@ -133,8 +382,23 @@ where
       setup: Box::new(|_| Ok(())),
       invoke_handler: Box::new(|_| ()),
       on_page_load: Box::new(|_, _| ()),
+      on_navigation: Box::new(|_, _| true),
+      on_error: Box::new(|error| eprintln!("{}", error)), // TODO log::error!
+      on_js_error: Box::new(|_, _| ()),
       pending_windows: Default::default(),
       plugins: PluginStore::default(),
+      force_accessibility: false,
+      accessibility_events: false,
+      appearance_events: false,
+      idle_threshold: None,
+      window_event_throttle: DEFAULT_WINDOW_EVENT_THROTTLE,
+      #[cfg(feature = "window-snapshot")]
+      window_snapshot_timeout: crate::runtime::manager::DEFAULT_WINDOW_SNAPSHOT_TIMEOUT,
+      recording_path: None,
+      system_tray: None,
+      system_tray_event_handler: None,
+      state: StateManager::default(),
+      uri_scheme_protocols: Default::default(),
     }
   }
 
@@ -147,6 +411,69 @@ where
     self
   }
 
+  /// Adds a value to the state managed by the application, retrieved with [`Manager::state`]
+  /// or with a [`State`](crate::State) command argument.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a value of the same type is already managed.
+  pub fn manage<T: Send + Sync + 'static>(self, state: T) -> Self {
+    let type_name = std::any::type_name::<T>();
+    if !self.state.set(state) {
+      panic!("state for type `{}` is already being managed", type_name);
+    }
+    self
+  }
+
+  /// Registers a protocol available on all windows, resolving the requests to `<name>://`.
+  /// Registering a protocol with the same name replaces it.
+  ///
+  /// The handler runs on the webview thread, so it must return quickly.
+  /// Use [`Builder::register_asynchronous_uri_scheme_protocol`] for slow operations.
+  ///
+  /// # Panics
+  ///
+  /// [`Builder::build`] panics if the name is the one of the protocol serving the application
+  /// assets, `tauri` unless changed with the `tauri > security > scheme` config.
+  ///
+  /// # Example
+  /// ```rust,ignore
+  /// use tauri::runtime::webview::CustomProtocolResponse;
+  ///
+  /// tauri::Builder::default().register_uri_scheme_protocol("app-icon", |_url| {
+  ///   Ok(
+  ///     CustomProtocolResponse::new(std::fs::read("icons/icon.png")?)
+  ///       .mimetype("image/png"),
+  ///   )
+  /// })
+  /// ```
+  pub fn register_uri_scheme_protocol<N, H>(mut self, name: N, handler: H) -> Self
+  where
+    N: Into<String>,
+    H: Fn(&str) -> crate::Result<CustomProtocolResponse> + Send + Sync + 'static,
+  {
+    self
+      .uri_scheme_protocols
+      .insert(name.into(), Arc::new(handler));
+    self
+  }
+
+  /// Registers a protocol with a handler returning a future, executed on the Tauri async runtime.
+  /// See [`Builder::register_uri_scheme_protocol`].
+  ///
+  /// The webview waits for the future, so it must not wait for the window itself,
+  /// e.g. by evaluating a script on it.
+  pub fn register_asynchronous_uri_scheme_protocol<N, H, F>(self, name: N, handler: H) -> Self
+  where
+    N: Into<String>,
+    H: Fn(String) -> F + Send + Sync + 'static,
+    F: Future<Output = crate::Result<CustomProtocolResponse>> + Send + 'static,
+  {
+    self.register_uri_scheme_protocol(name, move |url| {
+      crate::async_runtime::block_on(handler(url.to_string()))
+    })
+  }
+
   /// Defines the setup hook.
   pub fn setup<F>(mut self, setup: F) -> Self
   where
@@ -165,12 +492,127 @@ where
     self
   }
 
+  /// Defines the navigation hook, returning whether a window can navigate to a URL,
+  /// e.g. to only allow the app and the identity provider origins during an OAuth flow.
+  ///
+  /// It's checked by [`Window::navigate`] and by the webview before it starts loading a page,
+  /// including the links and redirects, so a rejected page never loads nor gets the IPC scripts.
+  pub fn on_navigation<F>(mut self, on_navigation: F) -> Self
+  where
+    F: Fn(&Window<Args<E, L, A, R>>, &str) -> bool + Send + Sync + 'static,
+  {
+    self.on_navigation = Box::new(on_navigation);
+    self
+  }
+
+  /// Defines the hook for errors that don't stop the application, e.g. a message that
+  /// couldn't be sent to a window or an event that couldn't be emitted.
+  /// By default the errors are printed to stderr.
+  pub fn on_error<F>(mut self, on_error: F) -> Self
+  where
+    F: Fn(crate::Error) + Send + Sync + 'static,
+  {
+    self.on_error = Box::new(on_error);
+    self
+  }
+
+  /// Defines the hook for the uncaught exceptions and unhandled promise rejections of the
+  /// windows scripts, e.g. to send the frontend crashes to a reporting service.
+  /// The errors are printed to stderr with their stack trace regardless of this hook.
+  pub fn on_js_error<F>(mut self, on_js_error: F) -> Self
+  where
+    F: Fn(&Window<Args<E, L, A, R>>, &JsError) + Send + Sync + 'static,
+  {
+    self.on_js_error = Box::new(on_js_error);
+    self
+  }
+
   /// Adds a plugin to the runtime.
   pub fn plugin<P: Plugin<Args<E, L, A, R>> + 'static>(mut self, plugin: P) -> Self {
     self.plugins.register(plugin);
     self
   }
 
+  /// Forces the webviews to expose their accessibility tree to assistive technologies,
+  /// even if no screen reader was running when the application started.
+  pub fn force_accessibility(mut self, force: bool) -> Self {
+    self.force_accessibility = force;
+    self
+  }
+
+  /// Emits the `tauri://accessibility-changed` event to all windows when the screen reader
+  /// or the high contrast mode is turned on or off.
+  /// The payload is an [`AccessibilityState`](crate::api::accessibility::AccessibilityState).
+  pub fn accessibility_events(mut self, enabled: bool) -> Self {
+    self.accessibility_events = enabled;
+    self
+  }
+
+  /// Emits the `tauri://appearance-changed` event to all windows when the accent color,
+  /// the reduced motion, reduced transparency or contrast preferences change.
+  /// The payload is an [`AppearancePreferences`](crate::api::appearance::AppearancePreferences).
+  pub fn appearance_events(mut self, enabled: bool) -> Self {
+    self.appearance_events = enabled;
+    self
+  }
+
+  /// Emits the `tauri://user-idle` event to all windows when the user hasn't used the keyboard
+  /// or the mouse for the given threshold, and `tauri://user-active` when they use it again,
+  /// e.g. to lock the app or update the user presence.
+  pub fn idle_events(mut self, threshold: Duration) -> Self {
+    self.idle_threshold.replace(threshold);
+    self
+  }
+
+  /// Sets the minimum interval between two resize or move events delivered for a window,
+  /// to the [`Window::on_window_event`] listeners and to the webview as `tauri://resize`
+  /// and `tauri://move`. Defaults to 16 milliseconds.
+  ///
+  /// The events reported during the interval are coalesced, only the latest one is delivered
+  /// when it ends. A zero interval delivers every event.
+  pub fn window_event_throttle(mut self, interval: Duration) -> Self {
+    self.window_event_throttle = interval;
+    self
+  }
+
+  /// Sets the time a closing window waits for the state of its page. Defaults to 500 milliseconds.
+  ///
+  /// When the window is closed, the state returned by the snapshot provider the page registered
+  /// with `setWindowSnapshotProvider` is saved to the app directory, and restored on the window
+  /// with the same label when the app launches again, read with `getWindowSnapshot`.
+  /// The window closes without saving the state if the page doesn't reply in time.
+  #[cfg(feature = "window-snapshot")]
+  pub fn window_snapshot_timeout(mut self, timeout: Duration) -> Self {
+    self.window_snapshot_timeout = timeout;
+    self
+  }
+
+  /// Records every invoke and every emitted or triggered event to the given file,
+  /// so it can be fed back with [`App::replay_events`] to reproduce an issue.
+  /// See [`crate::runtime::recorder`] for the file format.
+  pub fn record_events<T: Into<PathBuf>>(mut self, path: T) -> Self {
+    self.recording_path.replace(path.into());
+    self
+  }
+
+  /// Adds the application icon to the system tray.
+  ///
+  /// The tray events are emitted to all windows as `tauri://system-tray`,
+  /// with a [`SystemTrayEvent`] payload, and sent to the [`Builder::on_system_tray_event`] handler.
+  pub fn system_tray(mut self, system_tray: SystemTray) -> Self {
+    self.system_tray.replace(system_tray);
+    self
+  }
+
+  /// Defines the handler of the system tray events.
+  pub fn on_system_tray_event<F>(mut self, handler: F) -> Self
+  where
+    F: Fn(&AppHandle<Args<E, L, A, R>>, &SystemTrayEvent) + Send + Sync + 'static,
+  {
+    self.system_tray_event_handler.replace(Box::new(handler));
+    self
+  }
+
   /// Creates a new webview.
   pub fn create_window<F>(mut self, label: L, url: WindowUrl, setup: F) -> Self
   where
@@ -183,35 +625,76 @@ where
     self
   }
 
-  /// Runs the configured Tauri application.
-  pub fn run(mut self, context: Context<A>) -> crate::Result<()> {
+  /// Builds the configured Tauri application: creates its windows and runs the setup hook,
+  /// without starting the event loop.
+  pub fn build(mut self, context: Context<A>) -> crate::Result<App<Args<E, L, A, R>>> {
+    let scheme = &context.config.tauri.security.scheme;
+    if self.uri_scheme_protocols.contains_key(scheme) {
+      panic!(
+        "the `{}` protocol is reserved for the application assets",
+        scheme
+      );
+    }
+
     let manager = WindowManager::with_handlers(
       context,
       self.plugins,
       self.invoke_handler,
       self.on_page_load,
+      self.on_navigation,
+      self.on_error,
+      self.on_js_error,
+      self.state,
+      self.uri_scheme_protocols,
     );
 
+    manager.set_window_event_throttle(self.window_event_throttle);
+    #[cfg(feature = "window-snapshot")]
+    manager.set_window_snapshot_timeout(self.window_snapshot_timeout);
+
     // set up all the windows defined in the config
-    for config in manager.config().tauri.windows.clone() {
+    let configs = manager.config().tauri.windows.clone();
+    let has_splashscreen = configs.iter().any(|config| config.splashscreen);
+    // the splashscreens are created first so they show while the other windows load
+    let (splashscreens, windows): (Vec<_>, Vec<_>) =
+      configs.into_iter().partition(|config| config.splashscreen);
+    for mut config in splashscreens.into_iter().chain(windows) {
       let url = config.url.clone();
-      let label = config
+      let label: L = config
         .label
         .parse()
         .unwrap_or_else(|_| panic!("bad label found in config: {}", config.label));
 
+      if config.splashscreen {
+        manager.set_splashscreen(label.clone());
+        if !config.splashscreen_auto_close {
+          manager.set_splashscreen_auto_close(false);
+        }
+      } else if has_splashscreen {
+        config.visible = false;
+        manager.show_when_ready(label.clone());
+      }
+
       self
         .pending_windows
         .push(PendingWindow::with_config(config, label, url));
     }
 
-    manager.initialize_plugins()?;
+    if let Some(path) = self.recording_path {
+      manager.start_recording(path)?;
+    }
+
+    if self.force_accessibility {
+      crate::api::accessibility::force_accessibility_tree();
+    }
 
     let mut app = App {
       runtime: R::new()?,
       manager,
     };
 
+    app.manager.initialize_plugins(&app.handle())?;
+
     let pending_labels = self
       .pending_windows
       .iter()
@@ -225,6 +708,7 @@ where
       let pending = app.manager.prepare_window(pending, &pending_labels)?;
       let detached = app.runtime.create_window(pending)?;
       let _window = app.manager.attach_window(detached);
+      app.manager.open_configured_devtools(&_window);
       #[cfg(feature = "updater")]
       if main_window.is_none() {
         main_window = Some(_window);
@@ -234,8 +718,81 @@ where
     #[cfg(feature = "updater")]
     app.run_updater(main_window);
 
+    if let Some(system_tray) = self.system_tray {
+      let handle = app.handle();
+      let handler = self.system_tray_event_handler;
+      app.runtime.on_system_tray_event(move |event| {
+        if let Err(e) = handle.manager.emit_filter_internal(
+          "tauri://system-tray".to_string(),
+          Some(event.clone()),
+          |_| true,
+        ) {
+          handle.manager.report_error(e);
+        }
+        if let Some(handler) = &handler {
+          handler(&handle, event);
+        }
+      });
+      app.runtime.system_tray(system_tray)?;
+    }
+
+    if self.accessibility_events {
+      let manager = app.manager.clone();
+      let mut states = crate::api::accessibility::watch();
+      crate::async_runtime::spawn(async move {
+        while let Some(state) = states.recv().await {
+          if let Err(e) = manager.emit_filter_internal(
+            "tauri://accessibility-changed".to_string(),
+            Some(state),
+            |_| true,
+          ) {
+            manager.report_error(e);
+          }
+        }
+      });
+    }
+
+    if self.appearance_events {
+      let manager = app.manager.clone();
+      let mut preferences = crate::api::appearance::watch(APPEARANCE_POLL_INTERVAL);
+      crate::async_runtime::spawn(async move {
+        while let Some(preferences) = preferences.recv().await {
+          if let Err(e) = manager.emit_filter_internal(
+            "tauri://appearance-changed".to_string(),
+            Some(preferences),
+            |_| true,
+          ) {
+            manager.report_error(e);
+          }
+        }
+      });
+    }
+
+    if let Some(threshold) = self.idle_threshold {
+      let manager = app.manager.clone();
+      let mut states = crate::api::idle::watch(threshold, IDLE_POLL_INTERVAL);
+      crate::async_runtime::spawn(async move {
+        // the initial state isn't a change
+        states.recv().await;
+        while let Some(state) = states.recv().await {
+          let event = match state {
+            IdleState::Idle => "tauri://user-idle",
+            IdleState::Active => "tauri://user-active",
+          };
+          if let Err(e) = manager.emit_filter_internal(event.to_string(), None::<()>, |_| true) {
+            manager.report_error(e);
+          }
+        }
+      });
+    }
+
     (self.setup)(&mut app)?;
-    app.runtime.run();
+    Ok(app)
+  }
+
+  /// Runs the configured Tauri application.
+  pub fn run(self, context: Context<A>) -> crate::Result<()> {
+    self.build(context)?.run(|_, _| {});
     Ok(())
   }
 }
@@ -3,106 +3,359 @@
 // SPDX-License-Identifier: MIT
 
 //! The [`wry`] Tauri [`Runtime`].
+//!
+//! The windows are owned by the event loop, running on the main thread.
+//! The [`WryDispatcher`] and the [`WryHandle`] send their calls to the event loop, which also
+//! reports the window events to Tauri.
 
 use crate::{
   api::config::WindowConfig,
   runtime::{
+    menu::{Menu, MenuEvent, MenuItem, SystemTray, SystemTrayEvent},
+    monitor::{
+      LogicalPosition, LogicalSize, Monitor, PhysicalPosition, PhysicalSize, Position, Size,
+    },
+    tag::Tag,
     webview::{
-      Attributes, AttributesBase, CustomProtocol, FileDropEvent, FileDropHandler, RpcRequest,
-      WebviewRpcHandler,
+      mimetype_from_extension, Attributes, AttributesBase, CustomProtocol, FileDropEvent,
+      RpcRequest,
     },
-    window::{DetachedWindow, PendingWindow},
-    Dispatch, Params, Runtime,
+    window::{CursorIcon, DetachedWindow, PendingWindow, Theme, WindowEvent},
+    Dispatch, Params, RunEvent, Runtime, RuntimeHandle,
   },
   Icon,
 };
-use std::{convert::TryFrom, path::PathBuf};
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  convert::TryFrom,
+  fmt::Display,
+  path::PathBuf,
+  rc::Rc,
+  sync::mpsc::{channel, Sender},
+  thread::{current, ThreadId},
+};
+use wry::{
+  application::{
+    dpi::{
+      LogicalPosition as WryLogicalPosition, LogicalSize as WryLogicalSize,
+      PhysicalPosition as WryPhysicalPosition, PhysicalSize as WryPhysicalSize,
+      Position as WryPosition, Size as WrySize,
+    },
+    error::NotSupportedError,
+    event::{Event, TrayEvent, WindowEvent as WryWindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopClosed, EventLoopProxy, EventLoopWindowTarget},
+    menu::{ContextMenu, MenuBar, MenuId, MenuItem as WryMenuItem, MenuItemAttributes, MenuType},
+    monitor::MonitorHandle,
+    system_tray::{SystemTray as WrySystemTray, SystemTrayBuilder},
+    window::{
+      CursorIcon as WryCursorIcon, Fullscreen, Icon as WindowIcon, Theme as WryTheme,
+      WindowBuilder, WindowId,
+    },
+  },
+  webview::{
+    FileDropEvent as WryFileDropEvent, RpcRequest as WryRpcRequest, WebView, WebViewBuilder,
+  },
+};
 
+mod cursor;
+#[cfg(target_os = "macos")]
+mod tabbing;
+pub mod webkit2gtk;
 #[cfg(target_os = "windows")]
-use crate::api::path::{resolve_path, BaseDirectory};
-#[cfg(target_os = "windows")]
-use std::fs::create_dir_all;
+mod webview2;
 
-/// Wrapper around a [`wry::Icon`] that can be created from an [`Icon`].
-pub struct WryIcon(wry::Icon);
+/// Wrapper around a [`wry`] window icon that can be created from an [`Icon`].
+pub struct WryIcon(WindowIcon);
 
 impl TryFrom<Icon> for WryIcon {
   type Error = crate::Error;
   fn try_from(icon: Icon) -> Result<Self, Self::Error> {
-    let icon = match icon {
-      Icon::File(path) => {
-        wry::Icon::from_file(path).map_err(|e| crate::Error::InvalidIcon(e.to_string()))?
+    let image = match icon {
+      Icon::File(path) => image::open(path),
+      Icon::Raw(raw) => image::load_from_memory(&raw),
+    }
+    .map_err(|e| crate::Error::InvalidIcon(e.to_string()))?
+    .into_rgba8();
+    let (width, height) = image.dimensions();
+    WindowIcon::from_rgba(image.into_raw(), width, height)
+      .map(Self)
+      .map_err(|e| crate::Error::InvalidIcon(e.to_string()))
+  }
+}
+
+/// wry takes both units, so the window does the conversion with the scale factor of its monitor.
+fn wry_size(size: Size) -> WrySize {
+  match size {
+    Size::Logical(size) => WryLogicalSize::new(size.width, size.height).into(),
+    Size::Physical(size) => {
+      WryPhysicalSize::new(size.width.round() as u32, size.height.round() as u32).into()
+    }
+  }
+}
+
+/// Same as [`wry_size`], for positions.
+fn wry_position(position: Position) -> WryPosition {
+  match position {
+    Position::Logical(position) => WryLogicalPosition::new(position.x, position.y).into(),
+    Position::Physical(position) => {
+      WryPhysicalPosition::new(position.x.round() as i32, position.y.round() as i32).into()
+    }
+  }
+}
+
+fn cursor_icon(icon: CursorIcon) -> WryCursorIcon {
+  match icon {
+    CursorIcon::Default => WryCursorIcon::Default,
+    CursorIcon::Crosshair => WryCursorIcon::Crosshair,
+    CursorIcon::Hand => WryCursorIcon::Hand,
+    CursorIcon::Arrow => WryCursorIcon::Arrow,
+    CursorIcon::Move => WryCursorIcon::Move,
+    CursorIcon::Text => WryCursorIcon::Text,
+    CursorIcon::Wait => WryCursorIcon::Wait,
+    CursorIcon::Help => WryCursorIcon::Help,
+    CursorIcon::Progress => WryCursorIcon::Progress,
+    CursorIcon::NotAllowed => WryCursorIcon::NotAllowed,
+    CursorIcon::ContextMenu => WryCursorIcon::ContextMenu,
+    CursorIcon::Cell => WryCursorIcon::Cell,
+    CursorIcon::VerticalText => WryCursorIcon::VerticalText,
+    CursorIcon::Alias => WryCursorIcon::Alias,
+    CursorIcon::Copy => WryCursorIcon::Copy,
+    CursorIcon::NoDrop => WryCursorIcon::NoDrop,
+    CursorIcon::Grab => WryCursorIcon::Grab,
+    CursorIcon::Grabbing => WryCursorIcon::Grabbing,
+    CursorIcon::AllScroll => WryCursorIcon::AllScroll,
+    CursorIcon::ZoomIn => WryCursorIcon::ZoomIn,
+    CursorIcon::ZoomOut => WryCursorIcon::ZoomOut,
+    CursorIcon::EResize => WryCursorIcon::EResize,
+    CursorIcon::NResize => WryCursorIcon::NResize,
+    CursorIcon::NeResize => WryCursorIcon::NeResize,
+    CursorIcon::NwResize => WryCursorIcon::NwResize,
+    CursorIcon::SResize => WryCursorIcon::SResize,
+    CursorIcon::SeResize => WryCursorIcon::SeResize,
+    CursorIcon::SwResize => WryCursorIcon::SwResize,
+    CursorIcon::WResize => WryCursorIcon::WResize,
+    CursorIcon::EwResize => WryCursorIcon::EwResize,
+    CursorIcon::NsResize => WryCursorIcon::NsResize,
+    CursorIcon::NeswResize => WryCursorIcon::NeswResize,
+    CursorIcon::NwseResize => WryCursorIcon::NwseResize,
+    CursorIcon::ColResize => WryCursorIcon::ColResize,
+    CursorIcon::RowResize => WryCursorIcon::RowResize,
+  }
+}
+
+/// The ids of the custom menu items, since wry reports the clicks with a hash of the id.
+type MenuIds = HashMap<MenuId, String>;
+
+/// The menus wry builds the same way, i.e. the window menu bar and the tray context menu.
+trait WryMenu {
+  fn add_custom_item(&mut self, attributes: MenuItemAttributes<'_>);
+  fn add_native(&mut self, item: WryMenuItem);
+}
+
+impl WryMenu for MenuBar {
+  fn add_custom_item(&mut self, attributes: MenuItemAttributes<'_>) {
+    self.add_item(attributes);
+  }
+
+  fn add_native(&mut self, item: WryMenuItem) {
+    self.add_native_item(item);
+  }
+}
+
+impl WryMenu for ContextMenu {
+  fn add_custom_item(&mut self, attributes: MenuItemAttributes<'_>) {
+    self.add_item(attributes);
+  }
+
+  fn add_native(&mut self, item: WryMenuItem) {
+    self.add_native_item(item);
+  }
+}
+
+/// Adds the items to the menu, recording the ids of the custom items.
+fn add_menu_items<T: WryMenu>(menu: &mut T, items: &[MenuItem], ids: &mut MenuIds) {
+  for item in items {
+    let native = match item {
+      MenuItem::Custom(item) => {
+        let id = MenuId::new(&item.id);
+        ids.insert(id, item.id.clone());
+        menu.add_custom_item(
+          MenuItemAttributes::new(&item.title)
+            .with_id(id)
+            .with_enabled(item.enabled),
+        );
+        continue;
       }
-      Icon::Raw(raw) => {
-        wry::Icon::from_bytes(raw).map_err(|e| crate::Error::InvalidIcon(e.to_string()))?
+      MenuItem::Separator => WryMenuItem::Separator,
+      MenuItem::About(name) => WryMenuItem::About(name.clone()),
+      MenuItem::Hide => WryMenuItem::Hide,
+      MenuItem::Quit => WryMenuItem::Quit,
+      MenuItem::CloseWindow => WryMenuItem::CloseWindow,
+      MenuItem::Minimize => WryMenuItem::Minimize,
+      MenuItem::Undo => WryMenuItem::Undo,
+      MenuItem::Redo => WryMenuItem::Redo,
+      MenuItem::Cut => WryMenuItem::Cut,
+      MenuItem::Copy => WryMenuItem::Copy,
+      MenuItem::Paste => WryMenuItem::Paste,
+      MenuItem::SelectAll => WryMenuItem::SelectAll,
+    };
+    menu.add_native(native);
+  }
+}
+
+fn menu_bar(menu: &[Menu], ids: &mut MenuIds) -> MenuBar {
+  let mut menu_bar = MenuBar::new();
+  for submenu in menu {
+    let mut items = MenuBar::new();
+    add_menu_items(&mut items, &submenu.items, ids);
+    menu_bar.add_submenu(&submenu.title, true, items);
+  }
+  menu_bar
+}
+
+fn context_menu(items: &[MenuItem], ids: &mut MenuIds) -> ContextMenu {
+  let mut menu = ContextMenu::new();
+  add_menu_items(&mut menu, items, ids);
+  menu
+}
+
+/// The tray icon as wry takes it, i.e. the path of the image on Linux and its bytes elsewhere.
+#[cfg(target_os = "linux")]
+fn tray_icon(icon: Icon) -> crate::Result<PathBuf> {
+  use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+  };
+  match icon {
+    Icon::File(path) => Ok(path),
+    Icon::Raw(bytes) => {
+      // named after the content, so updating the icon changes the path the tray reads
+      let mut hasher = DefaultHasher::new();
+      bytes.hash(&mut hasher);
+      let path = std::env::temp_dir().join(format!("tauri-tray-{:x}.png", hasher.finish()));
+      std::fs::write(&path, bytes)?;
+      Ok(path)
+    }
+  }
+}
+
+/// The tray icon as wry takes it, i.e. the path of the image on Linux and its bytes elsewhere.
+#[cfg(not(target_os = "linux"))]
+fn tray_icon(icon: Icon) -> crate::Result<Vec<u8>> {
+  match icon {
+    Icon::File(path) => std::fs::read(path).map_err(Into::into),
+    Icon::Raw(bytes) => Ok(bytes),
+  }
+}
+
+/// The system tray icon of the event loop.
+struct Tray {
+  tray: WrySystemTray,
+  menu_ids: MenuIds,
+}
+
+thread_local! {
+  /// The system tray icon, only accessed from the main thread.
+  static TRAY: RefCell<Option<Tray>> = RefCell::new(None);
+}
+
+/// A call to the system tray, handled by the event loop.
+enum TrayMessage {
+  #[cfg(target_os = "linux")]
+  SetIcon(PathBuf),
+  #[cfg(not(target_os = "linux"))]
+  SetIcon(Vec<u8>),
+  SetMenu(Vec<MenuItem>),
+}
+
+fn handle_tray_message(message: TrayMessage) {
+  TRAY.with(|tray| {
+    let mut tray = tray.borrow_mut();
+    let tray = match tray.as_mut() {
+      Some(tray) => tray,
+      None => {
+        eprintln!("failed to update the system tray: the app has no tray icon"); // TODO log::error!
+        return;
       }
     };
-    Ok(Self(icon))
+    match message {
+      TrayMessage::SetIcon(icon) => tray.tray.set_icon(icon),
+      TrayMessage::SetMenu(items) => {
+        let mut menu_ids = MenuIds::new();
+        tray.tray.set_menu(&context_menu(&items, &mut menu_ids));
+        tray.menu_ids = menu_ids;
+      }
+    }
+  })
+}
+
+/// Translates an event of the system tray icon.
+fn tray_event(event: TrayEvent, x: f64, y: f64) -> SystemTrayEvent {
+  match event {
+    TrayEvent::LeftClick => SystemTrayEvent::LeftClick { x, y },
+    TrayEvent::RightClick => SystemTrayEvent::RightClick { x, y },
+    TrayEvent::DoubleClick => SystemTrayEvent::DoubleClick { x, y },
   }
 }
 
-impl AttributesBase for wry::Attributes {}
-impl Attributes for wry::Attributes {
+/// The webview window attributes of [`Wry`], applied when the window is created.
+#[derive(Clone)]
+pub struct WryAttributes {
+  window_builder: WindowBuilder,
+  url: Option<String>,
+  initialization_scripts: Vec<String>,
+  user_data_path: Option<PathBuf>,
+  transparent: bool,
+  menu_ids: MenuIds,
+  proxy_url: Option<String>,
+  hardware_acceleration: bool,
+}
+
+impl AttributesBase for WryAttributes {}
+impl Attributes for WryAttributes {
   type Icon = WryIcon;
 
   fn new() -> Self {
-    Default::default()
+    Self {
+      window_builder: WindowBuilder::new(),
+      url: None,
+      initialization_scripts: Vec::new(),
+      user_data_path: None,
+      transparent: false,
+      menu_ids: MenuIds::new(),
+      proxy_url: None,
+      hardware_acceleration: true,
+    }
   }
 
   fn with_config(config: WindowConfig) -> Self {
-    let mut webview = wry::Attributes::default()
+    let mut webview = WryAttributes::new()
       .title(config.title.to_string())
-      .width(config.width)
-      .height(config.height)
+      .size(LogicalSize::new(config.width, config.height))
       .visible(config.visible)
       .resizable(config.resizable)
       .decorations(config.decorations)
       .maximized(config.maximized)
       .fullscreen(config.fullscreen)
       .transparent(config.transparent)
-      .always_on_top(config.always_on_top);
-
-    if let Some(min_width) = config.min_width {
-      webview = webview.min_width(min_width);
-    }
-    if let Some(min_height) = config.min_height {
-      webview = webview.min_height(min_height);
-    }
-    if let Some(max_width) = config.max_width {
-      webview = webview.max_width(max_width);
+      .always_on_top(config.always_on_top)
+      .proxy_url(config.proxy_url)
+      .hardware_acceleration(config.hardware_acceleration)
+      .focusable(config.focusable);
+
+    // the config is in logical pixels and the window takes both dimensions
+    if let (Some(min_width), Some(min_height)) = (config.min_width, config.min_height) {
+      webview = webview.min_size(LogicalSize::new(min_width, min_height));
     }
-    if let Some(max_height) = config.max_height {
-      webview = webview.max_height(max_height);
+    if let (Some(max_width), Some(max_height)) = (config.max_width, config.max_height) {
+      webview = webview.max_size(LogicalSize::new(max_width, max_height));
     }
-    if let Some(x) = config.x {
-      webview = webview.x(x);
+    if let (Some(x), Some(y)) = (config.x, config.y) {
+      webview = webview.position(LogicalPosition::new(x, y));
     }
-    if let Some(y) = config.y {
-      webview = webview.y(y);
-    }
-
-    // If we are on windows use App Data Local as user_data
-    // to prevent any bundled application to failed.
-
-    // Should fix:
-    // https://github.com/tauri-apps/tauri/issues/1365
 
-    #[cfg(target_os = "windows")]
-    {
-      //todo(lemarier): we should replace with AppName from the context
-      // will be available when updater will merge
-
-      // https://docs.rs/dirs-next/2.0.0/dirs_next/fn.data_local_dir.html
-
-      let local_app_data = resolve_path("Tauri", Some(BaseDirectory::LocalData));
-
-      if let Ok(user_data_dir) = local_app_data {
-        // Make sure the directory exist without panic
-        if let Ok(()) = create_dir_all(&user_data_dir) {
-          webview = webview.user_data_path(Some(user_data_dir));
-        }
-      }
-    }
+    // the webview data directory is set by the window manager, named after the app
 
     webview
   }
@@ -112,93 +365,103 @@ impl Attributes for wry::Attributes {
     self
   }
 
-  fn x(mut self, x: f64) -> Self {
-    self.x = Some(x);
-    self
-  }
-
-  fn y(mut self, y: f64) -> Self {
-    self.y = Some(y);
-    self
-  }
-
-  fn width(mut self, width: f64) -> Self {
-    self.width = width;
-    self
-  }
-
-  fn height(mut self, height: f64) -> Self {
-    self.height = height;
-    self
+  /// The script runs in the page world, where the frozen bridge protects the Tauri globals.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS**: `WKContentWorld` would isolate it, but wry doesn't expose the script worlds yet.
+  /// - **Windows / Linux**: WebView2 and WebKitGTK have no isolated world for the user scripts.
+  fn isolated_initialization_script(self, init: &str) -> Self {
+    self.initialization_script(init)
   }
 
-  fn min_width(mut self, min_width: f64) -> Self {
-    self.min_width = Some(min_width);
+  fn position<P: Into<Position>>(mut self, position: P) -> Self {
+    self.window_builder = self
+      .window_builder
+      .with_position(wry_position(position.into()));
     self
   }
 
-  fn min_height(mut self, min_height: f64) -> Self {
-    self.min_height = Some(min_height);
+  fn size<S: Into<Size>>(mut self, size: S) -> Self {
+    self.window_builder = self.window_builder.with_inner_size(wry_size(size.into()));
     self
   }
 
-  fn max_width(mut self, max_width: f64) -> Self {
-    self.max_width = Some(max_width);
+  fn min_size<S: Into<Size>>(mut self, min_size: S) -> Self {
+    self.window_builder = self
+      .window_builder
+      .with_min_inner_size(wry_size(min_size.into()));
     self
   }
 
-  fn max_height(mut self, max_height: f64) -> Self {
-    self.max_height = Some(max_height);
+  fn max_size<S: Into<Size>>(mut self, max_size: S) -> Self {
+    self.window_builder = self
+      .window_builder
+      .with_max_inner_size(wry_size(max_size.into()));
     self
   }
 
   fn resizable(mut self, resizable: bool) -> Self {
-    self.resizable = resizable;
+    self.window_builder = self.window_builder.with_resizable(resizable);
     self
   }
 
   fn title<S: Into<String>>(mut self, title: S) -> Self {
-    self.title = title.into();
+    self.window_builder = self.window_builder.with_title(title);
     self
   }
 
   fn fullscreen(mut self, fullscreen: bool) -> Self {
-    self.fullscreen = fullscreen;
+    self.window_builder = self.window_builder.with_fullscreen(if fullscreen {
+      Some(Fullscreen::Borderless(None))
+    } else {
+      None
+    });
     self
   }
 
   fn maximized(mut self, maximized: bool) -> Self {
-    self.maximized = maximized;
+    self.window_builder = self.window_builder.with_maximized(maximized);
     self
   }
 
   fn visible(mut self, visible: bool) -> Self {
-    self.visible = visible;
+    self.window_builder = self.window_builder.with_visible(visible);
     self
   }
 
   fn transparent(mut self, transparent: bool) -> Self {
+    self.window_builder = self.window_builder.with_transparent(transparent);
     self.transparent = transparent;
     self
   }
 
   fn decorations(mut self, decorations: bool) -> Self {
-    self.decorations = decorations;
+    self.window_builder = self.window_builder.with_decorations(decorations);
     self
   }
 
   fn always_on_top(mut self, always_on_top: bool) -> Self {
-    self.always_on_top = always_on_top;
+    self.window_builder = self.window_builder.with_always_on_top(always_on_top);
+    self
+  }
+
+  fn menu(mut self, menu: Vec<Menu>) -> Self {
+    let mut menu_ids = MenuIds::new();
+    self.window_builder = self
+      .window_builder
+      .with_menu(menu_bar(&menu, &mut menu_ids));
+    self.menu_ids = menu_ids;
     self
   }
 
   fn icon(mut self, icon: Self::Icon) -> Self {
-    self.icon = Some(icon.0);
+    self.window_builder = self.window_builder.with_window_icon(Some(icon.0));
     self
   }
 
   fn has_icon(&self) -> bool {
-    self.icon.is_some()
+    self.window_builder.window.window_icon.is_some()
   }
 
   fn user_data_path(mut self, user_data_path: Option<PathBuf>) -> Self {
@@ -206,6 +469,36 @@ impl Attributes for wry::Attributes {
     self
   }
 
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: passed to WebView2 with the `--proxy-server` browser argument.
+  /// - **Linux**: the WebKitGTK network process, shared by the webviews, reads it from the
+  ///   `http_proxy` and `https_proxy` environment variables when it starts,
+  ///   so the first window creating it decides the proxy of every window.
+  /// - **macOS**: WKWebView has no proxy setting, so creating the window fails
+  ///   with [`crate::Error::UnsupportedOperation`].
+  fn proxy_url(mut self, proxy_url: Option<String>) -> Self {
+    self.proxy_url = proxy_url;
+    self
+  }
+
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: passed to WebView2 with the `--disable-gpu` browser argument.
+  /// - **Linux**: sets `WEBKIT_DISABLE_COMPOSITING_MODE`, read by the web processes started
+  ///   after the window is created.
+  /// - **macOS**: ignored, WKWebView always renders with the GPU.
+  fn hardware_acceleration(mut self, enabled: bool) -> Self {
+    self.hardware_acceleration = enabled;
+    self
+  }
+
+  /// wry can't make a window unfocusable yet, so only hidden windows, which never take focus,
+  /// behave as unfocusable.
+  fn focusable(self, _focusable: bool) -> Self {
+    self
+  }
+
   fn url(mut self, url: String) -> Self {
     self.url.replace(url);
     self
@@ -216,8 +509,8 @@ impl Attributes for wry::Attributes {
   }
 }
 
-impl From<wry::RpcRequest> for RpcRequest {
-  fn from(request: wry::RpcRequest) -> Self {
+impl From<WryRpcRequest> for RpcRequest {
+  fn from(request: WryRpcRequest) -> Self {
     Self {
       command: request.method,
       params: request.params,
@@ -225,12 +518,357 @@ impl From<wry::RpcRequest> for RpcRequest {
   }
 }
 
-impl From<wry::FileDropEvent> for FileDropEvent {
-  fn from(event: wry::FileDropEvent) -> Self {
+impl From<WryFileDropEvent> for FileDropEvent {
+  fn from(event: WryFileDropEvent) -> Self {
     match event {
-      wry::FileDropEvent::Hovered(paths) => FileDropEvent::Hovered(paths),
-      wry::FileDropEvent::Dropped(paths) => FileDropEvent::Dropped(paths),
-      wry::FileDropEvent::Cancelled => FileDropEvent::Cancelled,
+      WryFileDropEvent::Hovered(paths) => FileDropEvent::Hovered(paths),
+      WryFileDropEvent::Dropped(paths) => FileDropEvent::Dropped(paths),
+      WryFileDropEvent::Cancelled => FileDropEvent::Cancelled,
+    }
+  }
+}
+
+/// Translates a window event of the event loop, `None` for the events Tauri doesn't report.
+///
+/// [`WindowEvent::Destroyed`] isn't translated: it's reported when the runtime destroys the window,
+/// see [`close_window`].
+fn window_event(event: &WryWindowEvent<'_>) -> Option<WindowEvent> {
+  let event = match event {
+    WryWindowEvent::CloseRequested => WindowEvent::CloseRequested(Default::default()),
+    WryWindowEvent::Focused(focused) => WindowEvent::Focused(*focused),
+    WryWindowEvent::Resized(size) => WindowEvent::Resized {
+      width: size.width,
+      height: size.height,
+    },
+    WryWindowEvent::Moved(position) => WindowEvent::Moved {
+      x: position.x,
+      y: position.y,
+    },
+    WryWindowEvent::ThemeChanged(theme) => WindowEvent::ThemeChanged(match theme {
+      WryTheme::Light => Theme::Light,
+      WryTheme::Dark => Theme::Dark,
+    }),
+    _ => return None,
+  };
+  Some(event)
+}
+
+/// Reports an event to the handler of its window.
+///
+/// Returns whether the window must be destroyed,
+/// i.e. it's a close request the handler didn't prevent.
+fn dispatch_window_event(handler: Option<&dyn Fn(WindowEvent)>, event: WindowEvent) -> bool {
+  if let Some(handler) = handler {
+    handler(event.clone());
+  }
+  match event {
+    WindowEvent::CloseRequested(api) => !api.is_close_prevented(),
+    _ => false,
+  }
+}
+
+/// A webview window owned by the event loop.
+struct WindowWrapper {
+  label: String,
+  webview: WebView,
+  window_event_handler: Option<Rc<dyn Fn(WindowEvent)>>,
+  menu_event_handler: Option<Rc<dyn Fn(MenuEvent)>>,
+  menu_ids: MenuIds,
+}
+
+thread_local! {
+  /// The windows of the event loop, only accessed from the main thread.
+  static WINDOWS: RefCell<HashMap<WindowId, WindowWrapper>> = RefCell::new(HashMap::new());
+}
+
+/// Finds the window with the given label.
+fn window_id(label: &str) -> Option<WindowId> {
+  WINDOWS.with(|windows| {
+    windows
+      .borrow()
+      .iter()
+      .find(|(_, window)| window.label == label)
+      .map(|(id, _)| *id)
+  })
+}
+
+/// Destroys the window, then reports [`WindowEvent::Destroyed`] to its handler.
+fn close_window(window_id: WindowId) {
+  // removed before calling the handler, which may use the other windows
+  let window = WINDOWS.with(|windows| windows.borrow_mut().remove(&window_id));
+  if let Some(window) = window {
+    let handler = window.window_event_handler.clone();
+    drop(window);
+    if let Some(handler) = handler {
+      handler(WindowEvent::Destroyed);
+    }
+  }
+}
+
+/// A call to a window, handled by the event loop.
+enum WindowMessage {
+  SetResizable(bool),
+  SetTitle(String),
+  Maximize,
+  Unmaximize,
+  Minimize,
+  Unminimize,
+  Show,
+  Hide,
+  Close,
+  SetDecorations(bool),
+  SetAlwaysOnTop(bool),
+  SetSize(WrySize),
+  SetMinSize(WrySize),
+  SetMaxSize(WrySize),
+  SetPosition(WryPosition),
+  SetFullscreen(bool),
+  SetIcon(WindowIcon),
+  EvaluateScript(String),
+  SetMenu(Vec<Menu>),
+  ScaleFactor(Sender<f64>),
+  InnerSize(Sender<WryPhysicalSize<u32>>),
+  OuterSize(Sender<WryPhysicalSize<u32>>),
+  InnerPosition(Sender<Result<WryPhysicalPosition<i32>, NotSupportedError>>),
+  OuterPosition(Sender<Result<WryPhysicalPosition<i32>, NotSupportedError>>),
+  IsMaximized(Sender<bool>),
+  IsFullscreen(Sender<bool>),
+  IsVisible(Sender<bool>),
+  IsDecorated(Sender<bool>),
+  SetCursorVisible(bool),
+  SetCursorIcon(WryCursorIcon),
+  SetCursorGrab(bool, Sender<Result<(), String>>),
+  SetCursorPosition(WryPosition, Sender<Result<(), String>>),
+  OpenDevTools,
+  CloseDevTools,
+  #[cfg(target_os = "macos")]
+  Tabbing(Tabbing),
+}
+
+/// A call to the native tabs of a window, only available on macOS.
+enum Tabbing {
+  SetIdentifier(String),
+  MergeAllWindows,
+  SelectNextTab,
+  SelectPreviousTab,
+  MoveTabToNewWindow,
+}
+
+impl WindowMessage {
+  /// Whether the caller waits for the reply of the event loop.
+  fn has_reply(&self) -> bool {
+    matches!(
+      self,
+      Self::ScaleFactor(_)
+        | Self::InnerSize(_)
+        | Self::OuterSize(_)
+        | Self::InnerPosition(_)
+        | Self::OuterPosition(_)
+        | Self::IsMaximized(_)
+        | Self::IsFullscreen(_)
+        | Self::IsVisible(_)
+        | Self::IsDecorated(_)
+        | Self::SetCursorGrab(..)
+        | Self::SetCursorPosition(..)
+    )
+  }
+}
+
+/// A query answered by the event loop, which sends the answer back itself.
+type Query = Box<dyn FnOnce(&EventLoopWindowTarget<Message>) + Send>;
+
+/// Creates a window on the event loop.
+type CreateWindowHandler =
+  Box<dyn FnOnce(&EventLoopWindowTarget<Message>) -> crate::Result<WindowWrapper> + Send>;
+
+/// A message sent to the event loop.
+enum Message {
+  /// A call to the window with the given label.
+  Window(String, WindowMessage),
+  /// Creates a window, sending the result back if someone waits for it.
+  CreateWindow(CreateWindowHandler, Option<Sender<crate::Result<()>>>),
+  /// A call to the system tray.
+  Tray(TrayMessage),
+  Query(Query),
+}
+
+/// Handles a call to a window.
+///
+/// The message is given back if no window has the label, e.g. because it's not created yet.
+fn handle_window_message(label: &str, message: WindowMessage) -> Option<WindowMessage> {
+  let id = match window_id(label) {
+    Some(id) => id,
+    None => return Some(message),
+  };
+  if let WindowMessage::Close = message {
+    close_window(id);
+    return None;
+  }
+  if let WindowMessage::SetMenu(menu) = message {
+    let mut menu_ids = MenuIds::new();
+    let menu_bar = menu_bar(&menu, &mut menu_ids);
+    WINDOWS.with(|windows| {
+      if let Some(window) = windows.borrow_mut().get_mut(&id) {
+        window.webview.window().set_menu(Some(menu_bar));
+        window.menu_ids = menu_ids;
+      }
+    });
+    return None;
+  }
+  WINDOWS.with(|windows| {
+    let windows = windows.borrow();
+    let webview = &windows[&id].webview;
+    let window = webview.window();
+    match message {
+      WindowMessage::SetResizable(resizable) => window.set_resizable(resizable),
+      WindowMessage::SetTitle(title) => window.set_title(&title),
+      WindowMessage::Maximize => window.set_maximized(true),
+      WindowMessage::Unmaximize => window.set_maximized(false),
+      WindowMessage::Minimize => window.set_minimized(true),
+      WindowMessage::Unminimize => window.set_minimized(false),
+      WindowMessage::Show => window.set_visible(true),
+      WindowMessage::Hide => window.set_visible(false),
+      WindowMessage::Close | WindowMessage::SetMenu(_) => unreachable!(),
+      WindowMessage::SetDecorations(decorations) => window.set_decorations(decorations),
+      WindowMessage::SetAlwaysOnTop(always_on_top) => window.set_always_on_top(always_on_top),
+      WindowMessage::SetSize(size) => window.set_inner_size(size),
+      WindowMessage::SetMinSize(size) => window.set_min_inner_size(Some(size)),
+      WindowMessage::SetMaxSize(size) => window.set_max_inner_size(Some(size)),
+      WindowMessage::SetPosition(position) => window.set_outer_position(position),
+      WindowMessage::SetFullscreen(fullscreen) => window.set_fullscreen(if fullscreen {
+        Some(Fullscreen::Borderless(None))
+      } else {
+        None
+      }),
+      WindowMessage::SetIcon(icon) => window.set_window_icon(Some(icon)),
+      WindowMessage::EvaluateScript(script) => {
+        if let Err(e) = webview.evaluate_script(&script) {
+          eprintln!("failed to evaluate a script on window `{}`: {}", label, e);
+          // TODO log::error!
+        }
+      }
+      WindowMessage::ScaleFactor(tx) => {
+        let _ = tx.send(window.scale_factor());
+      }
+      WindowMessage::InnerSize(tx) => {
+        let _ = tx.send(window.inner_size());
+      }
+      WindowMessage::OuterSize(tx) => {
+        let _ = tx.send(window.outer_size());
+      }
+      WindowMessage::InnerPosition(tx) => {
+        let _ = tx.send(window.inner_position());
+      }
+      WindowMessage::OuterPosition(tx) => {
+        let _ = tx.send(window.outer_position());
+      }
+      WindowMessage::IsMaximized(tx) => {
+        let _ = tx.send(window.is_maximized());
+      }
+      WindowMessage::IsFullscreen(tx) => {
+        let _ = tx.send(window.fullscreen().is_some());
+      }
+      WindowMessage::IsVisible(tx) => {
+        let _ = tx.send(window.is_visible());
+      }
+      WindowMessage::IsDecorated(tx) => {
+        let _ = tx.send(window.is_decorated());
+      }
+      WindowMessage::OpenDevTools => webview.open_devtools(),
+      WindowMessage::CloseDevTools => webview.close_devtools(),
+      WindowMessage::SetCursorVisible(visible) => window.set_cursor_visible(visible),
+      WindowMessage::SetCursorIcon(icon) => window.set_cursor_icon(icon),
+      WindowMessage::SetCursorGrab(grab, tx) => {
+        let _ = tx.send(window.set_cursor_grab(grab).map_err(|e| e.to_string()));
+      }
+      #[cfg(target_os = "macos")]
+      WindowMessage::Tabbing(tabbing) => tabbing::handle(window, tabbing),
+      WindowMessage::SetCursorPosition(position, tx) => {
+        let _ = tx.send(
+          window
+            .set_cursor_position(position)
+            .map_err(|e| e.to_string()),
+        );
+      }
+    }
+  });
+  None
+}
+
+/// Handles a message sent to the event loop.
+fn handle_message(event_loop: &EventLoopWindowTarget<Message>, message: Message) {
+  match message {
+    // the window was closed
+    Message::Window(label, message) => {
+      handle_window_message(&label, message);
+    }
+    Message::CreateWindow(create, tx) => {
+      let result = create(event_loop).map(|window| {
+        let id = window.webview.window().id();
+        WINDOWS.with(|windows| windows.borrow_mut().insert(id, window));
+      });
+      match tx {
+        Some(tx) => {
+          let _ = tx.send(result);
+        }
+        None => {
+          if let Err(e) = result {
+            eprintln!("failed to create window: {}", e); // TODO log::error!
+          }
+        }
+      }
+    }
+    Message::Tray(message) => handle_tray_message(message),
+    Message::Query(query) => query(event_loop),
+  }
+}
+
+/// What the dispatchers and the handles share to reach the event loop.
+#[derive(Clone)]
+struct Context {
+  proxy: EventLoopProxy<Message>,
+  main_thread_id: ThreadId,
+}
+
+impl Context {
+  fn is_main_thread(&self) -> bool {
+    current().id() == self.main_thread_id
+  }
+
+  /// Sends a call to a window. On the main thread, e.g. from a window event handler,
+  /// the call is handled right away instead, since the event loop is busy running the caller.
+  fn send_window_message(
+    &self,
+    label: &str,
+    message: WindowMessage,
+  ) -> Result<(), EventLoopClosed<Message>> {
+    let message = if self.is_main_thread() {
+      match handle_window_message(label, message) {
+        // the reply would never come since the event loop waits for the caller,
+        // dropping the message drops its sender instead
+        Some(message) if message.has_reply() => return Ok(()),
+        // the window may be created by a message the event loop didn't handle yet
+        Some(message) => message,
+        None => return Ok(()),
+      }
+    } else {
+      message
+    };
+    self
+      .proxy
+      .send_event(Message::Window(label.to_string(), message))
+  }
+
+  /// Sends a call to the system tray, handled right away on the main thread.
+  fn send_tray_message(&self, message: TrayMessage) -> crate::Result<()> {
+    if self.is_main_thread() {
+      handle_tray_message(message);
+      Ok(())
+    } else {
+      self
+        .proxy
+        .send_event(Message::Tray(message))
+        .map_err(|_| crate::Error::SystemTray("the event loop is closed".into()))
     }
   }
 }
@@ -238,317 +876,841 @@ impl From<wry::FileDropEvent> for FileDropEvent {
 /// The Tauri [`Dispatch`] for [`Wry`].
 #[derive(Clone)]
 pub struct WryDispatcher {
-  window: wry::WindowProxy,
-  application: wry::ApplicationProxy,
+  label: String,
+  context: Context,
+}
+
+impl WryDispatcher {
+  fn dispatch_error(&self, operation: &'static str, error: impl Display) -> crate::Error {
+    crate::Error::Dispatch {
+      label: self.label.clone(),
+      operation,
+      cause: error.to_string(),
+    }
+  }
+
+  fn send(&self, operation: &'static str, message: WindowMessage) -> crate::Result<()> {
+    self
+      .context
+      .send_window_message(&self.label, message)
+      .map_err(|_| self.dispatch_error(operation, "the event loop is closed"))
+  }
+
+  /// Sends a call to the window and waits for the reply of the event loop.
+  fn get<T>(
+    &self,
+    operation: &'static str,
+    message: impl FnOnce(Sender<T>) -> WindowMessage,
+  ) -> crate::Result<T> {
+    let (tx, rx) = channel();
+    self.send(operation, message(tx))?;
+    rx.recv()
+      .map_err(|_| self.dispatch_error(operation, "the window doesn't exist"))
+  }
+
+  #[cfg(target_os = "macos")]
+  fn send_tabbing(&self, operation: &'static str, tabbing: Tabbing) -> crate::Result<()> {
+    self.send(operation, WindowMessage::Tabbing(tabbing))
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  fn send_tabbing(&self, _operation: &'static str, _tabbing: Tabbing) -> crate::Result<()> {
+    Err(crate::Error::UnsupportedOperation("window tabbing"))
+  }
+}
+
+fn physical_size(size: WryPhysicalSize<u32>) -> PhysicalSize {
+  PhysicalSize::new(size.width as f64, size.height as f64)
+}
+
+fn physical_position(position: WryPhysicalPosition<i32>) -> PhysicalPosition {
+  PhysicalPosition::new(position.x as f64, position.y as f64)
+}
+
+/// wry doesn't expose the work area of the monitors, so it's the whole monitor.
+fn monitor(handle: MonitorHandle) -> Monitor {
+  let position = physical_position(handle.position());
+  let size = physical_size(handle.size());
+  Monitor {
+    name: handle.name(),
+    position,
+    size,
+    work_area_position: position,
+    work_area_size: size,
+    scale_factor: handle.scale_factor(),
+  }
 }
 
 impl Dispatch for WryDispatcher {
   type Runtime = Wry;
   type Icon = WryIcon;
-  type Attributes = wry::Attributes;
+  type Attributes = WryAttributes;
+
+  fn runtime_handle(&self) -> WryHandle {
+    WryHandle {
+      context: self.context.clone(),
+    }
+  }
 
   fn create_window<M: Params<Runtime = Self::Runtime>>(
     &mut self,
     pending: PendingWindow<M>,
   ) -> crate::Result<DetachedWindow<M>> {
-    let PendingWindow {
-      attributes,
-      rpc_handler,
-      custom_protocol,
-      file_drop_handler,
-      label,
-      ..
-    } = pending;
-
-    let proxy = self.application.clone();
-
-    let rpc_handler =
-      rpc_handler.map(|handler| create_rpc_handler(proxy.clone(), label.clone(), handler));
-
-    let file_drop_handler = file_drop_handler
-      .map(|handler| create_file_drop_handler(proxy.clone(), label.clone(), handler));
-
-    let window = self
-      .application
-      .add_window_with_configs(
-        attributes,
-        rpc_handler,
-        custom_protocol
-          .map(create_custom_protocol)
-          .unwrap_or_default(),
-        file_drop_handler,
-      )
-      .map_err(|_| crate::Error::CreateWebview)?;
-
-    let dispatcher = WryDispatcher {
-      window,
-      application: proxy,
-    };
-
-    Ok(DetachedWindow { label, dispatcher })
+    self.runtime_handle().create_window(pending)
   }
 
   fn set_resizable(&self, resizable: bool) -> crate::Result<()> {
-    self
-      .window
-      .set_resizable(resizable)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send(
+      "set the resizable flag",
+      WindowMessage::SetResizable(resizable),
+    )
+  }
+
+  fn set_menu(&self, menu: Vec<Menu>) -> crate::Result<()> {
+    self.send("set the menu", WindowMessage::SetMenu(menu))
   }
 
   fn set_title<S: Into<String>>(&self, title: S) -> crate::Result<()> {
-    self
-      .window
-      .set_title(title)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("set the title", WindowMessage::SetTitle(title.into()))
   }
 
   fn maximize(&self) -> crate::Result<()> {
-    self
-      .window
-      .maximize()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("maximize", WindowMessage::Maximize)
   }
 
   fn unmaximize(&self) -> crate::Result<()> {
-    self
-      .window
-      .unmaximize()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("unmaximize", WindowMessage::Unmaximize)
   }
 
   fn minimize(&self) -> crate::Result<()> {
-    self
-      .window
-      .minimize()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("minimize", WindowMessage::Minimize)
   }
 
   fn unminimize(&self) -> crate::Result<()> {
-    self
-      .window
-      .unminimize()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("unminimize", WindowMessage::Unminimize)
   }
 
   fn show(&self) -> crate::Result<()> {
-    self
-      .window
-      .show()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("show", WindowMessage::Show)
   }
 
   fn hide(&self) -> crate::Result<()> {
-    self
-      .window
-      .hide()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("hide", WindowMessage::Hide)
   }
 
   fn close(&self) -> crate::Result<()> {
-    self
-      .window
-      .close()
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send("close", WindowMessage::Close)
   }
 
   fn set_decorations(&self, decorations: bool) -> crate::Result<()> {
-    self
-      .window
-      .set_decorations(decorations)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send(
+      "set the decorations",
+      WindowMessage::SetDecorations(decorations),
+    )
   }
 
   fn set_always_on_top(&self, always_on_top: bool) -> crate::Result<()> {
-    self
-      .window
-      .set_always_on_top(always_on_top)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send(
+      "set the always on top flag",
+      WindowMessage::SetAlwaysOnTop(always_on_top),
+    )
   }
 
-  fn set_width(&self, width: f64) -> crate::Result<()> {
-    self
-      .window
-      .set_width(width)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+  fn scale_factor(&self) -> crate::Result<f64> {
+    self.get("get the scale factor", WindowMessage::ScaleFactor)
   }
 
-  fn set_height(&self, height: f64) -> crate::Result<()> {
+  fn inner_size(&self) -> crate::Result<PhysicalSize> {
     self
-      .window
-      .set_height(height)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .get("get the inner size", WindowMessage::InnerSize)
+      .map(physical_size)
   }
 
-  fn resize(&self, width: f64, height: f64) -> crate::Result<()> {
+  fn outer_size(&self) -> crate::Result<PhysicalSize> {
     self
-      .window
-      .resize(width, height)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .get("get the outer size", WindowMessage::OuterSize)
+      .map(physical_size)
   }
 
-  fn set_min_size(&self, min_width: f64, min_height: f64) -> crate::Result<()> {
+  fn inner_position(&self) -> crate::Result<PhysicalPosition> {
     self
-      .window
-      .set_min_size(min_width, min_height)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .get("get the inner position", WindowMessage::InnerPosition)?
+      .map(physical_position)
+      .map_err(|e| self.dispatch_error("get the inner position", e))
   }
 
-  fn set_max_size(&self, max_width: f64, max_height: f64) -> crate::Result<()> {
+  fn outer_position(&self) -> crate::Result<PhysicalPosition> {
     self
-      .window
-      .set_max_size(max_width, max_height)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .get("get the outer position", WindowMessage::OuterPosition)?
+      .map(physical_position)
+      .map_err(|e| self.dispatch_error("get the outer position", e))
   }
 
-  fn set_x(&self, x: f64) -> crate::Result<()> {
-    self
-      .window
-      .set_x(x)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+  fn is_maximized(&self) -> crate::Result<bool> {
+    self.get("get the maximized state", WindowMessage::IsMaximized)
   }
 
-  fn set_y(&self, y: f64) -> crate::Result<()> {
-    self
-      .window
-      .set_y(y)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+  fn is_fullscreen(&self) -> crate::Result<bool> {
+    self.get("get the fullscreen state", WindowMessage::IsFullscreen)
   }
 
-  fn set_position(&self, x: f64, y: f64) -> crate::Result<()> {
-    self
-      .window
-      .set_position(x, y)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+  fn is_visible(&self) -> crate::Result<bool> {
+    self.get("get the visibility", WindowMessage::IsVisible)
+  }
+
+  fn is_decorated(&self) -> crate::Result<bool> {
+    self.get("get the decorations", WindowMessage::IsDecorated)
+  }
+
+  fn set_size(&self, size: Size) -> crate::Result<()> {
+    self.send("resize", WindowMessage::SetSize(wry_size(size)))
+  }
+
+  fn set_min_size(&self, min_size: Size) -> crate::Result<()> {
+    self.send(
+      "set the min size",
+      WindowMessage::SetMinSize(wry_size(min_size)),
+    )
+  }
+
+  fn set_max_size(&self, max_size: Size) -> crate::Result<()> {
+    self.send(
+      "set the max size",
+      WindowMessage::SetMaxSize(wry_size(max_size)),
+    )
+  }
+
+  fn set_position(&self, position: Position) -> crate::Result<()> {
+    self.send(
+      "set the position",
+      WindowMessage::SetPosition(wry_position(position)),
+    )
   }
 
   fn set_fullscreen(&self, fullscreen: bool) -> crate::Result<()> {
-    self
-      .window
-      .set_fullscreen(fullscreen)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+    self.send(
+      "set the fullscreen state",
+      WindowMessage::SetFullscreen(fullscreen),
+    )
   }
 
   fn set_icon(&self, icon: Self::Icon) -> crate::Result<()> {
+    self.send("set the icon", WindowMessage::SetIcon(icon.0))
+  }
+
+  /// A window without identifier gets its label, so it isn't merged with the others.
+  fn set_tabbing_identifier(&self, identifier: Option<String>) -> crate::Result<()> {
+    let identifier = identifier.unwrap_or_else(|| self.label.clone());
+    self.send_tabbing(
+      "set the tabbing identifier",
+      Tabbing::SetIdentifier(identifier),
+    )
+  }
+
+  fn merge_all_windows(&self) -> crate::Result<()> {
+    self.send_tabbing("merge the windows", Tabbing::MergeAllWindows)
+  }
+
+  fn select_next_tab(&self) -> crate::Result<()> {
+    self.send_tabbing("select the next tab", Tabbing::SelectNextTab)
+  }
+
+  fn select_previous_tab(&self) -> crate::Result<()> {
+    self.send_tabbing("select the previous tab", Tabbing::SelectPreviousTab)
+  }
+
+  fn move_tab_to_new_window(&self) -> crate::Result<()> {
+    self.send_tabbing("move the tab to a new window", Tabbing::MoveTabToNewWindow)
+  }
+
+  /// The webviews enable their dev tools on debug builds only.
+  fn open_devtools(&self) -> crate::Result<()> {
+    if cfg!(debug_assertions) {
+      self.send("open the dev tools", WindowMessage::OpenDevTools)
+    } else {
+      Err(crate::Error::UnsupportedOperation(
+        "dev tools on release builds",
+      ))
+    }
+  }
+
+  fn close_devtools(&self) -> crate::Result<()> {
+    if cfg!(debug_assertions) {
+      self.send("close the dev tools", WindowMessage::CloseDevTools)
+    } else {
+      Err(crate::Error::UnsupportedOperation(
+        "dev tools on release builds",
+      ))
+    }
+  }
+
+  fn set_cursor_visible(&self, visible: bool) -> crate::Result<()> {
+    self.send(
+      "set the cursor visibility",
+      WindowMessage::SetCursorVisible(visible),
+    )
+  }
+
+  fn set_cursor_grab(&self, grab: bool) -> crate::Result<()> {
     self
-      .window
-      .set_icon(icon.0)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .get("grab the cursor", |tx| {
+        WindowMessage::SetCursorGrab(grab, tx)
+      })?
+      .map_err(|e| self.dispatch_error("grab the cursor", e))
+  }
+
+  fn set_cursor_icon(&self, icon: CursorIcon) -> crate::Result<()> {
+    self.send(
+      "set the cursor icon",
+      WindowMessage::SetCursorIcon(cursor_icon(icon)),
+    )
+  }
+
+  fn set_cursor_position(&self, position: Position) -> crate::Result<()> {
+    let position = wry_position(position);
+    self
+      .get("set the cursor position", |tx| {
+        WindowMessage::SetCursorPosition(position, tx)
+      })?
+      .map_err(|e| self.dispatch_error("set the cursor position", e))
   }
 
   fn eval_script<S: Into<String>>(&self, script: S) -> crate::Result<()> {
+    self.send(
+      "evaluate a script",
+      WindowMessage::EvaluateScript(script.into()),
+    )
+  }
+}
+
+/// The Tauri [`RuntimeHandle`] for [`Wry`].
+#[derive(Clone)]
+pub struct WryHandle {
+  context: Context,
+}
+
+impl WryHandle {
+  /// Runs the query on the event loop and waits for its answer.
+  fn query<T, F>(&self, query: F) -> crate::Result<T>
+  where
+    T: Send + 'static,
+    F: FnOnce(&EventLoopWindowTarget<Message>) -> T + Send + 'static,
+  {
+    let (tx, rx) = channel();
     self
-      .window
-      .evaluate_script(script)
-      .map_err(|_| crate::Error::FailedToSendMessage)
+      .context
+      .proxy
+      .send_event(Message::Query(Box::new(move |event_loop| {
+        let _ = tx.send(query(event_loop));
+      })))
+      .map_err(|_| crate::Error::EventLoopClosed)?;
+    rx.recv().map_err(|_| crate::Error::EventLoopClosed)
+  }
+
+  /// The available monitors and the primary one.
+  ///
+  /// On the main thread the event loop is busy running the caller, so they're read from a window.
+  fn monitors(&self) -> crate::Result<(Vec<Monitor>, Option<Monitor>)> {
+    if !self.context.is_main_thread() {
+      return self.query(|event_loop| {
+        let monitors = event_loop.available_monitors().map(monitor).collect();
+        (monitors, event_loop.primary_monitor().map(monitor))
+      });
+    }
+    WINDOWS.with(|windows| {
+      let windows = windows.borrow();
+      let window = windows
+        .values()
+        .next()
+        .ok_or(crate::Error::UnsupportedOperation(
+          "monitor queries on the main thread without windows",
+        ))?
+        .webview
+        .window();
+      let monitors = window.available_monitors().map(monitor).collect();
+      Ok((monitors, window.primary_monitor().map(monitor)))
+    })
   }
 }
 
-/// A Tauri [`Runtime`] wrapper around [`wry::Application`].
+impl RuntimeHandle for WryHandle {
+  type Runtime = Wry;
+
+  /// On the main thread, e.g. from a window event handler, the window is created once the event
+  /// loop is done with the current event, and the creation errors are only logged.
+  fn create_window<M: Params<Runtime = Self::Runtime>>(
+    &self,
+    pending: PendingWindow<M>,
+  ) -> crate::Result<DetachedWindow<M>> {
+    let label = pending.label.clone();
+    let context = self.context.clone();
+    let create: CreateWindowHandler =
+      Box::new(move |event_loop: &EventLoopWindowTarget<Message>| {
+        create_webview(event_loop, context, pending)
+      });
+
+    if self.context.is_main_thread() {
+      self
+        .context
+        .proxy
+        .send_event(Message::CreateWindow(create, None))
+        .map_err(|_| crate::Error::CreateWindow)?;
+    } else {
+      let (tx, rx) = channel();
+      self
+        .context
+        .proxy
+        .send_event(Message::CreateWindow(create, Some(tx)))
+        .map_err(|_| crate::Error::CreateWindow)?;
+      rx.recv().map_err(|_| crate::Error::CreateWindow)??;
+    }
+
+    let dispatcher = WryDispatcher {
+      label: label.to_string(),
+      context: self.context.clone(),
+    };
+    Ok(DetachedWindow { label, dispatcher })
+  }
+
+  fn cursor_position(&self) -> crate::Result<PhysicalPosition> {
+    if self.context.is_main_thread() {
+      cursor::cursor_position()
+    } else {
+      self.query(|_| cursor::cursor_position())?
+    }
+  }
+
+  fn available_monitors(&self) -> crate::Result<Vec<Monitor>> {
+    self.monitors().map(|(monitors, _)| monitors)
+  }
+
+  fn primary_monitor(&self) -> crate::Result<Option<Monitor>> {
+    self.monitors().map(|(_, primary)| primary)
+  }
+
+  fn set_tray_icon(&self, icon: Icon) -> crate::Result<()> {
+    self
+      .context
+      .send_tray_message(TrayMessage::SetIcon(tray_icon(icon)?))
+  }
+
+  fn set_tray_menu(&self, menu: Vec<MenuItem>) -> crate::Result<()> {
+    self.context.send_tray_message(TrayMessage::SetMenu(menu))
+  }
+}
+
+/// A Tauri [`Runtime`] wrapper around the [`wry`] event loop.
 pub struct Wry {
-  inner: wry::Application,
+  event_loop: EventLoop<Message>,
+  context: Context,
+  system_tray_event_handler: Option<Box<dyn Fn(&SystemTrayEvent) + Send>>,
 }
 
 impl Runtime for Wry {
   type Dispatcher = WryDispatcher;
+  type Handle = WryHandle;
 
   fn new() -> crate::Result<Self> {
-    let app = wry::Application::new().map_err(|_| crate::Error::CreateWebview)?;
-    Ok(Self { inner: app })
+    #[cfg(target_os = "windows")]
+    webview2::ensure_runtime()?;
+    #[cfg(target_os = "linux")]
+    webkit2gtk::ensure_dependencies()?;
+    let event_loop = EventLoop::with_user_event();
+    let context = Context {
+      proxy: event_loop.create_proxy(),
+      main_thread_id: current().id(),
+    };
+    Ok(Self {
+      event_loop,
+      context,
+      system_tray_event_handler: None,
+    })
+  }
+
+  fn handle(&self) -> Self::Handle {
+    WryHandle {
+      context: self.context.clone(),
+    }
   }
 
   fn create_window<M: Params<Runtime = Self>>(
     &mut self,
     pending: PendingWindow<M>,
   ) -> crate::Result<DetachedWindow<M>> {
-    let PendingWindow {
-      attributes,
-      rpc_handler,
-      custom_protocol,
-      file_drop_handler,
-      label,
-      ..
-    } = pending;
-
-    let proxy = self.inner.application_proxy();
-
-    let rpc_handler =
-      rpc_handler.map(|handler| create_rpc_handler(proxy.clone(), label.clone(), handler));
-
-    let file_drop_handler = file_drop_handler
-      .map(|handler| create_file_drop_handler(proxy.clone(), label.clone(), handler));
-
-    let window = self
-      .inner
-      .add_window_with_configs(
-        attributes,
-        rpc_handler,
-        custom_protocol
-          .map(create_custom_protocol)
-          .unwrap_or_default(),
-        file_drop_handler,
-      )
-      .map_err(|_| crate::Error::CreateWebview)?;
+    let label = pending.label.clone();
+    let window = create_webview(&self.event_loop, self.context.clone(), pending)?;
+    let id = window.webview.window().id();
+    WINDOWS.with(|windows| windows.borrow_mut().insert(id, window));
 
     let dispatcher = WryDispatcher {
-      window,
-      application: proxy,
+      label: label.to_string(),
+      context: self.context.clone(),
     };
-
     Ok(DetachedWindow { label, dispatcher })
   }
 
-  fn run(self) {
-    wry::Application::run(self.inner)
+  fn system_tray(&mut self, system_tray: SystemTray) -> crate::Result<()> {
+    let mut menu_ids = MenuIds::new();
+    let menu = context_menu(&system_tray.menu, &mut menu_ids);
+    let tray = SystemTrayBuilder::new(tray_icon(system_tray.icon)?, Some(menu))
+      .build(&self.event_loop)
+      .map_err(|e| crate::Error::SystemTray(e.to_string()))?;
+    TRAY.with(|t| t.replace(Some(Tray { tray, menu_ids })));
+    Ok(())
+  }
+
+  fn on_system_tray_event<F: Fn(&SystemTrayEvent) + Send + 'static>(&mut self, handler: F) {
+    self.system_tray_event_handler.replace(Box::new(handler));
+  }
+
+  /// The window events are reported to the window handlers,
+  /// which send the other [`RunEvent`]s once the windows are destroyed.
+  fn run<L: Tag, F: Fn(RunEvent<L>) + Send + Sync + 'static>(self, callback: F) {
+    callback(RunEvent::Ready);
+    let system_tray_event_handler = self.system_tray_event_handler;
+    self.event_loop.run(move |event, event_loop, control_flow| {
+      *control_flow = ControlFlow::Wait;
+      match event {
+        Event::WindowEvent { window_id, event } => {
+          let handler = WINDOWS.with(|windows| {
+            let windows = windows.borrow();
+            let window = windows.get(&window_id)?;
+            if let WryWindowEvent::Resized(_) = event {
+              if let Err(e) = window.webview.resize() {
+                eprintln!("failed to resize the webview of `{}`: {}", window.label, e);
+                // TODO log::error!
+              }
+            }
+            window.window_event_handler.clone()
+          });
+          if let Some(event) = window_event(&event) {
+            if dispatch_window_event(handler.as_deref(), event) {
+              close_window(window_id);
+            }
+          }
+        }
+        Event::TrayEvent {
+          event, position, ..
+        } => {
+          if let Some(handler) = &system_tray_event_handler {
+            handler(&tray_event(event, position.x, position.y));
+          }
+        }
+        Event::MenuEvent {
+          window_id,
+          menu_id,
+          origin: MenuType::MenuBar,
+          ..
+        } => {
+          // the macOS menu bar is shared by the windows, so its clicks have no window
+          let handlers = WINDOWS.with(|windows| {
+            windows
+              .borrow()
+              .iter()
+              .filter(|(id, _)| window_id.map_or(true, |window_id| window_id == **id))
+              .filter_map(|(_, window)| {
+                let id = window.menu_ids.get(&menu_id)?.clone();
+                Some((id, window.menu_event_handler.clone()?))
+              })
+              .collect::<Vec<_>>()
+          });
+          for (menu_item_id, handler) in handlers {
+            handler(MenuEvent { menu_item_id });
+          }
+        }
+        Event::MenuEvent {
+          menu_id,
+          origin: MenuType::ContextMenu,
+          ..
+        } => {
+          let id = TRAY.with(|tray| tray.borrow().as_ref()?.menu_ids.get(&menu_id).cloned());
+          if let (Some(id), Some(handler)) = (id, &system_tray_event_handler) {
+            handler(&SystemTrayEvent::MenuItemClick { id });
+          }
+        }
+        Event::UserEvent(message) => handle_message(event_loop, message),
+        _ => {}
+      }
+    })
   }
 }
 
-/// Create a wry rpc handler from a tauri rpc handler.
-fn create_rpc_handler<M: Params<Runtime = Wry>>(
-  app_proxy: wry::ApplicationProxy,
-  label: M::Label,
-  handler: WebviewRpcHandler<M>,
-) -> wry::WindowRpcHandler {
-  Box::new(move |window, request| {
-    handler(
-      DetachedWindow {
-        dispatcher: WryDispatcher {
-          window,
-          application: app_proxy.clone(),
-        },
-        label: label.clone(),
-      },
-      request.into(),
-    );
-    None
+/// Creates a webview window with its handlers on the event loop.
+fn create_webview<M: Params<Runtime = Wry>>(
+  event_loop: &EventLoopWindowTarget<Message>,
+  context: Context,
+  pending: PendingWindow<M>,
+) -> crate::Result<WindowWrapper> {
+  let PendingWindow {
+    label,
+    attributes,
+    rpc_handler,
+    custom_protocols,
+    file_drop_handler,
+    navigation_handler,
+    menu_event_handler,
+    window_event_handler,
+    ..
+  } = pending;
+
+  let detached = DetachedWindow {
+    label: label.clone(),
+    dispatcher: WryDispatcher {
+      label: label.to_string(),
+      context,
+    },
+  };
+
+  set_engine_options(
+    attributes.proxy_url.as_deref(),
+    attributes.hardware_acceleration,
+  )?;
+
+  let window = attributes
+    .window_builder
+    .build(event_loop)
+    .map_err(|_| crate::Error::CreateWindow)?;
+  let mut webview = WebViewBuilder::new(window)
+    .map_err(|_| crate::Error::CreateWebview)?
+    .with_transparent(attributes.transparent)
+    // the context menu and the dev tools API only inspect debug builds
+    .with_devtools(cfg!(debug_assertions));
+  if let Some(url) = &attributes.url {
+    webview = webview
+      .with_url(url)
+      .map_err(|_| crate::Error::CreateWebview)?;
+  }
+  for script in &attributes.initialization_scripts {
+    webview = webview.with_initialization_script(script);
+  }
+  if let Some(user_data_path) = attributes.user_data_path {
+    webview = webview.with_data_directory(user_data_path);
+  }
+  if let Some(handler) = rpc_handler {
+    let window = detached.clone();
+    webview = webview.with_rpc_handler(move |_, request| {
+      handler(window.clone(), request.into());
+      None
+    });
+  }
+  if let Some(handler) = file_drop_handler {
+    let window = detached.clone();
+    webview = webview.with_file_drop_handler(move |_, event| handler(event.into(), window.clone()));
+  }
+  if let Some(handler) = navigation_handler {
+    let window = detached.clone();
+    // runs before the request is sent, so a rejected page never loads nor gets the IPC scripts
+    webview = webview.with_navigation_handler(move |url| handler(url, window.clone()));
+  }
+  for custom_protocol in custom_protocols {
+    let name = custom_protocol.name.clone();
+    webview = webview.with_custom_protocol(name, create_custom_protocol(custom_protocol));
+  }
+  let webview = webview.build().map_err(|_| crate::Error::CreateWebview)?;
+
+  let window_event_handler = window_event_handler.map(|handler| {
+    let window = detached.clone();
+    Rc::new(move |event| handler(event, window.clone())) as Rc<dyn Fn(WindowEvent)>
+  });
+  let menu_event_handler = menu_event_handler.map(|handler| {
+    let window = detached.clone();
+    Rc::new(move |event| handler(event, window.clone())) as Rc<dyn Fn(MenuEvent)>
+  });
+
+  Ok(WindowWrapper {
+    label: label.to_string(),
+    webview,
+    window_event_handler,
+    menu_event_handler,
+    menu_ids: attributes.menu_ids,
   })
 }
 
-/// Create a wry file drop handler from a tauri file drop handler.
-fn create_file_drop_handler<M: Params<Runtime = Wry>>(
-  app_proxy: wry::ApplicationProxy,
-  label: M::Label,
-  handler: FileDropHandler<M>,
-) -> wry::WindowFileDropHandler {
-  Box::new(move |window, event| {
-    handler(
-      event.into(),
-      DetachedWindow {
-        dispatcher: WryDispatcher {
-          window,
-          application: app_proxy.clone(),
-        },
-        label: label.clone(),
-      },
-    )
-  })
+/// Sets the options the webview engine reads from the environment when the webview is created,
+/// see [`WryAttributes::proxy_url`] and [`WryAttributes::hardware_acceleration`].
+#[allow(unused_variables)]
+fn set_engine_options(proxy_url: Option<&str>, hardware_acceleration: bool) -> crate::Result<()> {
+  #[cfg(windows)]
+  {
+    // WebView2 reads the arguments when each webview creates its environment,
+    // so the flags of the previous window are replaced
+    let key = "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS";
+    let args = std::env::var(key).unwrap_or_default();
+    let mut args = args
+      .split_whitespace()
+      .filter(|arg| !arg.starts_with("--proxy-server=") && *arg != "--disable-gpu")
+      .map(ToString::to_string)
+      .collect::<Vec<_>>();
+    if let Some(proxy_url) = proxy_url {
+      args.push(format!("--proxy-server={}", proxy_url));
+    }
+    if !hardware_acceleration {
+      args.push("--disable-gpu".into());
+    }
+    std::env::set_var(key, args.join(" "));
+  }
+  #[cfg(target_os = "linux")]
+  {
+    if let Some(proxy_url) = proxy_url {
+      std::env::set_var("http_proxy", proxy_url);
+      std::env::set_var("https_proxy", proxy_url);
+    }
+    if !hardware_acceleration {
+      std::env::set_var("WEBKIT_DISABLE_COMPOSITING_MODE", "1");
+    }
+  }
+  #[cfg(target_os = "macos")]
+  {
+    if proxy_url.is_some() {
+      return Err(crate::Error::UnsupportedOperation("webview proxy"));
+    }
+  }
+  Ok(())
 }
 
-/// Create a wry custom protocol from a tauri custom protocol.
-fn create_custom_protocol(custom_protocol: CustomProtocol) -> Vec<wry::CustomProtocol> {
-  vec![wry::CustomProtocol {
-    name: custom_protocol.name.clone(),
-    handler: Box::new(move |data| {
-      (custom_protocol.handler)(data).map_err(|_| wry::Error::InitScriptError)
-    }),
-  }]
+/// Create a wry custom protocol handler from a tauri custom protocol.
+///
+/// wry doesn't expose the scheme registration options yet,
+/// so the [`SchemePrivileges`](crate::runtime::webview::SchemePrivileges),
+/// including whether service workers are allowed, are left to the webview defaults.
+/// It also only takes the response body and its MIME type,
+/// so responses with an error status fail the request.
+fn create_custom_protocol(
+  custom_protocol: CustomProtocol,
+) -> impl Fn(&wry::application::window::Window, &str) -> wry::Result<(Vec<u8>, String)> + 'static {
+  let handler = custom_protocol.handler;
+  move |_window, url| match handler(url) {
+    Ok(response) if response.status < 400 => {
+      let mimetype = response
+        .mimetype
+        .unwrap_or_else(|| mimetype_from_url(url).to_string());
+      Ok((response.body, mimetype))
+    }
+    Ok(response) => Err(wry::Error::Io(std::io::Error::new(
+      std::io::ErrorKind::Other,
+      format!("`{}` responded with status {}", url, response.status),
+    ))),
+    Err(e) => Err(wry::Error::Io(std::io::Error::new(
+      std::io::ErrorKind::Other,
+      e.to_string(),
+    ))),
+  }
+}
+
+/// Guesses the MIME type of a custom protocol response from the extension of its URL.
+fn mimetype_from_url(url: &str) -> &'static str {
+  let path = url
+    .split(|c| c == '?' || c == '#')
+    .next()
+    .unwrap_or_default();
+  let extension = path
+    .rsplit('/')
+    .next()
+    .and_then(|name| {
+      name
+        .rsplit('.')
+        .next()
+        .filter(|extension| *extension != name)
+    })
+    .unwrap_or_default();
+  if extension.is_empty() {
+    // the app origin, e.g. `tauri://localhost`, serves `index.html`
+    "text/html"
+  } else {
+    mimetype_from_extension(extension)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::runtime::window::CloseRequestApi;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn translates_window_events() {
+    assert!(matches!(
+      window_event(&WryWindowEvent::CloseRequested),
+      Some(WindowEvent::CloseRequested(_))
+    ));
+    assert!(matches!(
+      window_event(&WryWindowEvent::Focused(false)),
+      Some(WindowEvent::Focused(false))
+    ));
+    assert!(matches!(
+      window_event(&WryWindowEvent::Resized(WryPhysicalSize::new(800, 600))),
+      Some(WindowEvent::Resized {
+        width: 800,
+        height: 600
+      })
+    ));
+    assert!(matches!(
+      window_event(&WryWindowEvent::Moved(WryPhysicalPosition::new(10, -20))),
+      Some(WindowEvent::Moved { x: 10, y: -20 })
+    ));
+    assert!(matches!(
+      window_event(&WryWindowEvent::ThemeChanged(WryTheme::Dark)),
+      Some(WindowEvent::ThemeChanged(Theme::Dark))
+    ));
+    // reported by `close_window` once the window is removed
+    assert!(window_event(&WryWindowEvent::Destroyed).is_none());
+  }
+
+  #[test]
+  fn destroys_window_unless_close_prevented() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    let handler = move |event: WindowEvent| {
+      if let WindowEvent::CloseRequested(api) = &event {
+        api.prevent_close();
+      }
+      events_.lock().unwrap().push(format!("{:?}", event));
+    };
+
+    let event = window_event(&WryWindowEvent::CloseRequested).unwrap();
+    assert!(!dispatch_window_event(Some(&handler), event));
+    assert!(!dispatch_window_event(
+      Some(&handler),
+      WindowEvent::Focused(true)
+    ));
+    assert_eq!(events.lock().unwrap().len(), 2);
+
+    // without a handler to prevent it, the close request destroys the window
+    let api = CloseRequestApi::default();
+    assert!(dispatch_window_event(
+      None,
+      WindowEvent::CloseRequested(api.clone())
+    ));
+    assert!(!api.is_close_prevented());
+  }
+
+  #[test]
+  fn guesses_protocol_mimetype() {
+    assert_eq!(mimetype_from_url("tauri://localhost"), "text/html");
+    assert_eq!(mimetype_from_url("tauri://localhost/"), "text/html");
+    assert_eq!(
+      mimetype_from_url("tauri://localhost/assets/index.JS?v=1"),
+      "text/javascript"
+    );
+    assert_eq!(
+      mimetype_from_url("asset://localhost/%2Fhome%2Fuser%2Fdata.bin"),
+      "application/octet-stream"
+    );
+    assert_eq!(
+      mimetype_from_url("asset://localhost/%2Fhome%2Fuser%2Fclip.MP4"),
+      "video/mp4"
+    );
+  }
+
+  #[test]
+  fn translates_tray_events() {
+    assert_eq!(
+      tray_event(TrayEvent::LeftClick, 10., 20.),
+      SystemTrayEvent::LeftClick { x: 10., y: 20. }
+    );
+    assert_eq!(
+      tray_event(TrayEvent::DoubleClick, 0., 5.),
+      SystemTrayEvent::DoubleClick { x: 0., y: 5. }
+    );
+  }
 }
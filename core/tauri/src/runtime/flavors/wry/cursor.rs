@@ -0,0 +1,89 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The mouse cursor position, which the [`wry`](super) event loop doesn't expose.
+//!
+//! It's read from the platform on the main thread, like the other calls to the windowing system.
+
+use crate::runtime::monitor::PhysicalPosition;
+
+/// The cursor position on the virtual desktop.
+///
+/// tao makes the process DPI aware, so `GetCursorPos` returns physical pixels.
+#[cfg(target_os = "windows")]
+pub fn cursor_position() -> crate::Result<PhysicalPosition> {
+  use winapi::{shared::windef::POINT, um::winuser::GetCursorPos};
+  let mut point = POINT { x: 0, y: 0 };
+  if unsafe { GetCursorPos(&mut point) } == 0 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(PhysicalPosition::new(point.x as f64, point.y as f64))
+}
+
+/// The cursor position on the virtual desktop.
+///
+/// AppKit gives it in points from the bottom left corner of the primary screen,
+/// so it's flipped and scaled with the screen under the cursor, like the window positions.
+#[cfg(target_os = "macos")]
+pub fn cursor_position() -> crate::Result<PhysicalPosition> {
+  use cocoa::foundation::{NSPoint, NSRect};
+  use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+
+  unsafe {
+    let location: NSPoint = msg_send![class!(NSEvent), mouseLocation];
+    let screens: *mut Object = msg_send![class!(NSScreen), screens];
+    let count: usize = msg_send![screens, count];
+    if count == 0 {
+      return Err(crate::Error::UnsupportedOperation(
+        "cursor position without screens",
+      ));
+    }
+    let primary: *mut Object = msg_send![screens, objectAtIndex: 0usize];
+    let primary_frame: NSRect = msg_send![primary, frame];
+    let mut scale_factor = 1.;
+    for i in 0..count {
+      let screen: *mut Object = msg_send![screens, objectAtIndex: i];
+      let frame: NSRect = msg_send![screen, frame];
+      if location.x >= frame.origin.x
+        && location.x < frame.origin.x + frame.size.width
+        && location.y >= frame.origin.y
+        && location.y < frame.origin.y + frame.size.height
+      {
+        scale_factor = msg_send![screen, backingScaleFactor];
+        break;
+      }
+    }
+    Ok(PhysicalPosition::new(
+      location.x * scale_factor,
+      (primary_frame.size.height - location.y) * scale_factor,
+    ))
+  }
+}
+
+/// The cursor position on the virtual desktop.
+///
+/// GDK gives it in application pixels, so it's scaled with the monitor under the cursor.
+#[cfg(target_os = "linux")]
+pub fn cursor_position() -> crate::Result<PhysicalPosition> {
+  let no_pointer = || std::io::Error::new(std::io::ErrorKind::NotFound, "no pointer device");
+  let display = gdk::Display::get_default().ok_or_else(no_pointer)?;
+  let pointer = display
+    .get_default_seat()
+    .and_then(|seat| seat.get_pointer())
+    .ok_or_else(no_pointer)?;
+  let (_, x, y) = pointer.get_position();
+  let scale_factor = display
+    .get_monitor_at_point(x, y)
+    .map(|monitor| monitor.get_scale_factor())
+    .unwrap_or(1);
+  Ok(PhysicalPosition::new(
+    (x * scale_factor) as f64,
+    (y * scale_factor) as f64,
+  ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn cursor_position() -> crate::Result<PhysicalPosition> {
+  Err(crate::Error::UnsupportedOperation("cursor position"))
+}
@@ -0,0 +1,40 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The native window tabs of macOS, which the [`wry`](super) window doesn't expose.
+
+use super::Tabbing;
+use cocoa::{
+  base::{id, nil},
+  foundation::NSString,
+};
+use objc::{msg_send, sel, sel_impl};
+use wry::application::{platform::macos::WindowExtMacOS, window::Window};
+
+/// Calls the `NSWindow` tabbing API of the window.
+pub fn handle(window: &Window, tabbing: Tabbing) {
+  let ns_window = window.ns_window() as id;
+  unsafe {
+    match tabbing {
+      Tabbing::SetIdentifier(identifier) => {
+        let identifier = NSString::alloc(nil).init_str(&identifier);
+        // the window copies the identifier
+        let () = msg_send![ns_window, setTabbingIdentifier: identifier];
+        let () = msg_send![identifier, release];
+      }
+      Tabbing::MergeAllWindows => {
+        let () = msg_send![ns_window, mergeAllWindows: nil];
+      }
+      Tabbing::SelectNextTab => {
+        let () = msg_send![ns_window, selectNextTab: nil];
+      }
+      Tabbing::SelectPreviousTab => {
+        let () = msg_send![ns_window, selectPreviousTab: nil];
+      }
+      Tabbing::MoveTabToNewWindow => {
+        let () = msg_send![ns_window, moveTabToNewWindow: nil];
+      }
+    }
+  }
+}
@@ -0,0 +1,200 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Probing of the webkit2gtk and libsoup libraries the [`wry`](super) runtime uses on Linux.
+//!
+//! The libraries are checked when the runtime is created. If any of them is too old,
+//! a native dialog lists the packages to install and [`crate::Error::WebviewRuntimeNotInstalled`] is returned.
+
+use once_cell::sync::OnceCell;
+
+/// A system library required by the webview that is missing or outdated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDependency {
+  /// The library name, e.g. `webkit2gtk-4.0`.
+  pub library: &'static str,
+  /// The oldest supported version.
+  pub required_version: String,
+  /// The installed version.
+  pub installed_version: Option<String>,
+  /// The package providing the library on the running distribution, if it's known.
+  pub package: Option<&'static str>,
+}
+
+type MessageBuilder = dyn Fn(&[MissingDependency]) -> String + Send + Sync;
+
+static MESSAGE_BUILDER: OnceCell<Box<MessageBuilder>> = OnceCell::new();
+
+/// Customizes the message of the dialog shown when the webview libraries are missing or outdated.
+///
+/// Must be called before the runtime is created, e.g. before [`crate::Builder::run`].
+/// Only the first call has any effect. This is a no-op on platforms other than Linux.
+///
+/// # Example
+/// ```rust,no_run
+/// tauri::runtime::flavors::wry::webkit2gtk::set_missing_dependencies_message(|missing| {
+///   let packages = missing
+///     .iter()
+///     .filter_map(|dependency| dependency.package)
+///     .collect::<Vec<_>>();
+///   format!("Please run `sudo apt install {}` and restart the app.", packages.join(" "))
+/// });
+/// ```
+pub fn set_missing_dependencies_message<F>(builder: F)
+where
+  F: Fn(&[MissingDependency]) -> String + Send + Sync + 'static,
+{
+  let _ = MESSAGE_BUILDER.set(Box::new(builder));
+}
+
+#[cfg(target_os = "linux")]
+pub(super) use probe::ensure_dependencies;
+
+#[cfg(target_os = "linux")]
+mod probe {
+  use super::{distribution_packages, MissingDependency, MESSAGE_BUILDER};
+  use crate::api::dialog::blocking::message;
+
+  #[link(name = "webkit2gtk-4.0")]
+  extern "C" {
+    fn webkit_get_major_version() -> u32;
+    fn webkit_get_minor_version() -> u32;
+    fn webkit_get_micro_version() -> u32;
+  }
+
+  #[link(name = "soup-2.4")]
+  extern "C" {
+    fn soup_get_major_version() -> u32;
+    fn soup_get_minor_version() -> u32;
+    fn soup_get_micro_version() -> u32;
+  }
+
+  /// The oldest webkit2gtk version with the APIs wry uses.
+  const MIN_WEBKIT2GTK_VERSION: (u32, u32, u32) = (2, 22, 0);
+  /// The oldest libsoup version webkit2gtk 2.22 is built against.
+  const MIN_LIBSOUP_VERSION: (u32, u32, u32) = (2, 62, 0);
+  const DIALOG_TITLE: &str = "Missing system libraries";
+
+  fn format_version((major, minor, micro): (u32, u32, u32)) -> String {
+    format!("{}.{}.{}", major, minor, micro)
+  }
+
+  /// Makes sure the webview libraries are recent enough before the first window is created.
+  pub(in super::super) fn ensure_dependencies() -> crate::Result<()> {
+    // the libraries are linked to the binary, so they're always loaded when we get here
+    let (webkit2gtk, libsoup) = unsafe {
+      (
+        (
+          webkit_get_major_version(),
+          webkit_get_minor_version(),
+          webkit_get_micro_version(),
+        ),
+        (
+          soup_get_major_version(),
+          soup_get_minor_version(),
+          soup_get_micro_version(),
+        ),
+      )
+    };
+
+    let os_release = std::fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let (webkit2gtk_package, libsoup_package) = distribution_packages(&os_release);
+
+    let mut missing = Vec::new();
+    if webkit2gtk < MIN_WEBKIT2GTK_VERSION {
+      missing.push(MissingDependency {
+        library: "webkit2gtk-4.0",
+        required_version: format_version(MIN_WEBKIT2GTK_VERSION),
+        installed_version: Some(format_version(webkit2gtk)),
+        package: webkit2gtk_package,
+      });
+    }
+    if libsoup < MIN_LIBSOUP_VERSION {
+      missing.push(MissingDependency {
+        library: "libsoup-2.4",
+        required_version: format_version(MIN_LIBSOUP_VERSION),
+        installed_version: Some(format_version(libsoup)),
+        package: libsoup_package,
+      });
+    }
+
+    if missing.is_empty() {
+      return Ok(());
+    }
+
+    let text = match MESSAGE_BUILDER.get() {
+      Some(builder) => builder(&missing),
+      None => default_message(&missing),
+    };
+    message(DIALOG_TITLE, text);
+    Err(crate::Error::WebviewRuntimeNotInstalled)
+  }
+
+  fn default_message(missing: &[MissingDependency]) -> String {
+    let mut text =
+      "This application requires the following libraries, which are missing or outdated:\n"
+        .to_string();
+    for dependency in missing {
+      text.push_str(&format!(
+        "\n- {} {} or newer",
+        dependency.library, dependency.required_version
+      ));
+      if let Some(installed) = &dependency.installed_version {
+        text.push_str(&format!(" (found {})", installed));
+      }
+      if let Some(package) = dependency.package {
+        text.push_str(&format!(", provided by the `{}` package", package));
+      }
+    }
+    text.push_str("\n\nPlease install or update them with your distribution's package manager.");
+    text
+  }
+}
+
+/// Finds the webkit2gtk and libsoup package names from the contents of `/etc/os-release`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn distribution_packages(os_release: &str) -> (Option<&'static str>, Option<&'static str>) {
+  let mut ids = Vec::new();
+  for line in os_release.lines() {
+    let mut parts = line.splitn(2, '=');
+    if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+      if key == "ID" || key == "ID_LIKE" {
+        let value = value.trim_matches('"');
+        ids.extend(value.split_whitespace().map(|id| id.to_string()));
+      }
+    }
+  }
+
+  for id in ids {
+    match id.as_str() {
+      "debian" | "ubuntu" => return (Some("libwebkit2gtk-4.0-37"), Some("libsoup2.4-1")),
+      "fedora" | "rhel" | "centos" => return (Some("webkit2gtk3"), Some("libsoup")),
+      "arch" | "manjaro" => return (Some("webkit2gtk"), Some("libsoup")),
+      "opensuse" | "suse" | "opensuse-leap" | "opensuse-tumbleweed" => {
+        return (Some("libwebkit2gtk-4_0-37"), Some("libsoup-2_4-1"))
+      }
+      _ => {}
+    }
+  }
+  (None, None)
+}
+
+#[cfg(test)]
+mod test {
+  use super::distribution_packages;
+
+  #[test]
+  fn finds_distribution_packages() {
+    let ubuntu = "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\n";
+    assert_eq!(
+      distribution_packages(ubuntu),
+      (Some("libwebkit2gtk-4.0-37"), Some("libsoup2.4-1"))
+    );
+    let pop = "NAME=\"Pop!_OS\"\nID=pop\nID_LIKE=\"ubuntu debian\"\n";
+    assert_eq!(distribution_packages(pop).0, Some("libwebkit2gtk-4.0-37"));
+    let fedora = "NAME=Fedora\nID=fedora\n";
+    assert_eq!(distribution_packages(fedora).0, Some("webkit2gtk3"));
+    assert_eq!(distribution_packages("ID=gentoo\n"), (None, None));
+  }
+}
@@ -0,0 +1,133 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detection and installation of the Microsoft Edge WebView2 runtime.
+
+use crate::api::{
+  dialog::blocking::{ask, message},
+  system_settings::registry_value,
+};
+use std::{path::PathBuf, process::Command};
+
+/// The WebView2 runtime client identifier, used as the EdgeUpdate registry key.
+const CLIENT_ID: &str = "{F3017226-FE2A-4295-8BDF-00C3A9A7E4C5}";
+/// The oldest runtime version supported by the WebView2 SDK wry is built with.
+const MIN_VERSION: &str = "86.0.616.0";
+/// The file name of the bootstrapper, which may be bundled next to the executable.
+const BOOTSTRAPPER_NAME: &str = "MicrosoftEdgeWebview2Setup.exe";
+/// The download link of the bootstrapper.
+const BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
+const DIALOG_TITLE: &str = "Microsoft Edge WebView2 Runtime";
+
+/// Gets the version of the installed WebView2 runtime, checking the per-machine and per-user installations.
+fn installed_version() -> Option<String> {
+  [
+    format!(
+      "HKLM\\SOFTWARE\\WOW6432Node\\Microsoft\\EdgeUpdate\\Clients\\{}",
+      CLIENT_ID
+    ),
+    format!(
+      "HKLM\\SOFTWARE\\Microsoft\\EdgeUpdate\\Clients\\{}",
+      CLIENT_ID
+    ),
+    format!(
+      "HKCU\\Software\\Microsoft\\EdgeUpdate\\Clients\\{}",
+      CLIENT_ID
+    ),
+  ]
+  .iter()
+  .filter_map(|key| registry_value(key, "pv"))
+  // an uninstalled runtime leaves the `0.0.0.0` version behind
+  .find(|version| version != "0.0.0.0")
+}
+
+/// Compares two dotted versions with any number of numeric components.
+fn is_older(version: &str, other: &str) -> bool {
+  let parse = |version: &str| {
+    version
+      .split('.')
+      .map(|part| part.parse::<u32>().unwrap_or(0))
+      .collect::<Vec<_>>()
+  };
+  parse(version) < parse(other)
+}
+
+/// Makes sure a supported WebView2 runtime is installed before the first window is created.
+///
+/// If it's missing or too old, the user is asked to install it with the bootstrapper
+/// bundled next to the executable, or with the one downloaded from Microsoft.
+pub(super) fn ensure_runtime() -> crate::Result<()> {
+  let prompt = match installed_version() {
+    Some(version) if !is_older(&version, MIN_VERSION) => return Ok(()),
+    Some(version) => format!(
+      "This application requires the Microsoft Edge WebView2 Runtime {} or newer, but version {} is installed. Do you want to update it now?",
+      MIN_VERSION, version
+    ),
+    None => "This application requires the Microsoft Edge WebView2 Runtime, which is not installed. Do you want to install it now?".to_string(),
+  };
+
+  if !ask(DIALOG_TITLE, prompt) {
+    return Err(crate::Error::WebviewRuntimeNotInstalled);
+  }
+
+  if let Err(e) = install() {
+    message(
+      DIALOG_TITLE,
+      format!("Failed to install the WebView2 Runtime: {}", e),
+    );
+    return Err(e);
+  }
+
+  match installed_version() {
+    Some(version) if !is_older(&version, MIN_VERSION) => Ok(()),
+    _ => Err(crate::Error::WebviewRuntimeNotInstalled),
+  }
+}
+
+/// Runs the bootstrapper silently and waits for the installation to finish.
+fn install() -> crate::Result<()> {
+  let bundled = std::env::current_exe()
+    .ok()
+    .and_then(|exe| exe.parent().map(|dir| dir.join(BOOTSTRAPPER_NAME)))
+    .filter(|path| path.exists());
+  let bootstrapper = match bundled {
+    Some(path) => path,
+    None => download_bootstrapper()?,
+  };
+
+  let status = Command::new(&bootstrapper)
+    .args(&["/silent", "/install"])
+    .status()?;
+  if status.success() {
+    Ok(())
+  } else {
+    Err(crate::Error::WebviewRuntimeNotInstalled)
+  }
+}
+
+fn download_bootstrapper() -> crate::Result<PathBuf> {
+  let bytes = crate::async_runtime::block_on(async {
+    reqwest::get(BOOTSTRAPPER_URL)
+      .await?
+      .error_for_status()?
+      .bytes()
+      .await
+  })
+  .map_err(crate::api::Error::Network)?;
+  let path = std::env::temp_dir().join(BOOTSTRAPPER_NAME);
+  std::fs::write(&path, bytes)?;
+  Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+  use super::is_older;
+
+  #[test]
+  fn compares_versions() {
+    assert!(is_older("85.0.564.68", "86.0.616.0"));
+    assert!(!is_older("90.0.818.66", "86.0.616.0"));
+    assert!(!is_older("86.0.616.0", "86.0.616.0"));
+  }
+}
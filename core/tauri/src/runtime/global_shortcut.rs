@@ -0,0 +1,89 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! System-wide keyboard shortcuts, reported through the event system.
+
+use crate::{api::shortcuts::ShortcutManager, runtime::manager::WindowManager, Params};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The event emitted when a registered shortcut is pressed, with the shortcut as payload.
+pub const GLOBAL_SHORTCUT_EVENT: &str = "tauri://global-shortcut";
+
+/// The shortcuts are registered on the operating system, so they're shared by the whole process.
+fn shortcut_manager() -> MutexGuard<'static, ShortcutManager> {
+  static MANAGER: Lazy<Arc<Mutex<ShortcutManager>>> = Lazy::new(Default::default);
+  MANAGER.lock().expect("poisoned shortcut manager")
+}
+
+/// Registers global shortcuts, triggered even when the application isn't focused.
+///
+/// Pressing a registered shortcut emits the [`GLOBAL_SHORTCUT_EVENT`] to every window
+/// and to the global listeners, with the shortcut as payload.
+///
+/// # Example
+/// ```rust,ignore
+/// use tauri::{runtime::global_shortcut::GLOBAL_SHORTCUT_EVENT, Manager};
+///
+/// app.global_shortcut_manager().register("CmdOrControl+Shift+K")?;
+/// app.listen_global(GLOBAL_SHORTCUT_EVENT.into(), |event| {
+///   println!("pressed {:?}", event.payload());
+/// });
+/// ```
+pub struct GlobalShortcutManager<P: Params> {
+  manager: WindowManager<P>,
+}
+
+impl<P: Params> Clone for GlobalShortcutManager<P> {
+  fn clone(&self) -> Self {
+    Self {
+      manager: self.manager.clone(),
+    }
+  }
+}
+
+impl<P: Params> GlobalShortcutManager<P> {
+  pub(crate) fn new(manager: WindowManager<P>) -> Self {
+    Self { manager }
+  }
+
+  /// Determines whether the given shortcut is registered by this application or not.
+  pub fn is_registered(&self, shortcut: &str) -> crate::Result<bool> {
+    Ok(shortcut_manager().is_registered(shortcut.to_string())?)
+  }
+
+  /// Registers a shortcut, e.g. `CmdOrControl+Q`.
+  pub fn register(&self, shortcut: &str) -> crate::Result<()> {
+    let manager = self.manager.clone();
+    let accelerator = shortcut.to_string();
+    shortcut_manager().register(shortcut.to_string(), move || {
+      if let Err(e) = manager.emit_filter_internal(
+        GLOBAL_SHORTCUT_EVENT.to_string(),
+        Some(accelerator.clone()),
+        |_| true,
+      ) {
+        manager.report_error(e);
+      }
+      if let Ok(event) = GLOBAL_SHORTCUT_EVENT.parse() {
+        match serde_json::to_string(&accelerator) {
+          Ok(data) => manager.trigger(event, None, Some(data)),
+          Err(e) => manager.report_error(e.into()),
+        }
+      }
+    })?;
+    Ok(())
+  }
+
+  /// Unregisters a shortcut.
+  pub fn unregister(&self, shortcut: &str) -> crate::Result<()> {
+    shortcut_manager().unregister(shortcut.to_string())?;
+    Ok(())
+  }
+
+  /// Unregisters all shortcuts registered by this application.
+  pub fn unregister_all(&self) -> crate::Result<()> {
+    shortcut_manager().unregister_all()?;
+    Ok(())
+  }
+}
@@ -0,0 +1,211 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Synthetic user input, used to drive the webview on integration tests.
+//!
+//! The events are dispatched as DOM events on the webview document, so they reach the page's
+//! event listeners (including the ones that implement drag regions and keyboard shortcuts)
+//! but they are not seen by the operating system.
+
+use serde::Serialize;
+
+/// The script that dispatches a serialized [`InputEvent`] on the document.
+const DISPATCH_INPUT_SCRIPT: &str = r#"
+  (function (input) {
+    var modifiers = input.modifiers || {}
+    var button = input.button === 'right' ? 2 : input.button === 'middle' ? 1 : 0
+    var hasPosition = typeof input.x === 'number'
+    var target = (hasPosition
+      ? document.elementFromPoint(input.x, input.y)
+      : document.activeElement) || document.body
+    var init = {
+      bubbles: true,
+      cancelable: true,
+      view: window,
+      button: button,
+      buttons: input.type === 'mouseDown' ? 1 << button : 0,
+      clientX: hasPosition ? input.x : 0,
+      clientY: hasPosition ? input.y : 0,
+      ctrlKey: !!modifiers.ctrl,
+      shiftKey: !!modifiers.shift,
+      altKey: !!modifiers.alt,
+      metaKey: !!modifiers.meta
+    }
+    function fire(type, Constructor, extra) {
+      return target.dispatchEvent(new Constructor(type, Object.assign({}, init, extra || {})))
+    }
+    switch (input.type) {
+      case 'mouseMove':
+        fire('mousemove', MouseEvent)
+        break
+      case 'mouseDown':
+        fire('mousedown', MouseEvent)
+        break
+      case 'mouseUp':
+        fire('mouseup', MouseEvent)
+        break
+      case 'click':
+        fire('mousedown', MouseEvent)
+        fire('mouseup', MouseEvent)
+        fire(button === 2 ? 'contextmenu' : 'click', MouseEvent)
+        break
+      case 'keyDown':
+        fire('keydown', KeyboardEvent, { key: input.key })
+        break
+      case 'keyUp':
+        fire('keyup', KeyboardEvent, { key: input.key })
+        break
+      case 'text':
+        for (var i = 0; i < input.text.length; i++) {
+          var key = input.text[i]
+          if (fire('keydown', KeyboardEvent, { key: key })) {
+            fire('keypress', KeyboardEvent, { key: key })
+            document.execCommand('insertText', false, key)
+          }
+          fire('keyup', KeyboardEvent, { key: key })
+        }
+        break
+    }
+  })
+"#;
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MouseButton {
+  /// The primary button.
+  Left,
+  /// The secondary button.
+  Right,
+  /// The wheel button.
+  Middle,
+}
+
+impl Default for MouseButton {
+  fn default() -> Self {
+    Self::Left
+  }
+}
+
+/// The modifier keys held while an input event happens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Modifiers {
+  /// The Control key.
+  pub ctrl: bool,
+  /// The Shift key.
+  pub shift: bool,
+  /// The Alt (Option) key.
+  pub alt: bool,
+  /// The Meta (Command or Windows) key.
+  pub meta: bool,
+}
+
+/// A synthetic input event. Positions are in CSS pixels relative to the webview viewport.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InputEvent {
+  /// Moves the mouse to the given position.
+  MouseMove {
+    /// The horizontal position.
+    x: f64,
+    /// The vertical position.
+    y: f64,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Presses a mouse button at the given position.
+  MouseDown {
+    /// The horizontal position.
+    x: f64,
+    /// The vertical position.
+    y: f64,
+    /// The pressed button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Releases a mouse button at the given position.
+  MouseUp {
+    /// The horizontal position.
+    x: f64,
+    /// The vertical position.
+    y: f64,
+    /// The released button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Presses and releases a mouse button at the given position.
+  Click {
+    /// The horizontal position.
+    x: f64,
+    /// The vertical position.
+    y: f64,
+    /// The clicked button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Presses a key on the focused element. The key uses the `KeyboardEvent.key` values, e.g. `Enter` or `a`.
+  KeyDown {
+    /// The pressed key.
+    key: String,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Releases a key on the focused element.
+  KeyUp {
+    /// The released key.
+    key: String,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Types the given text on the focused element.
+  Text {
+    /// The text to type.
+    text: String,
+  },
+}
+
+impl InputEvent {
+  /// Creates a left click event at the given position.
+  pub fn click(x: f64, y: f64) -> Self {
+    Self::Click {
+      x,
+      y,
+      button: MouseButton::Left,
+      modifiers: Modifiers::default(),
+    }
+  }
+
+  /// Creates a text input event.
+  pub fn text(text: impl Into<String>) -> Self {
+    Self::Text { text: text.into() }
+  }
+
+  /// The script that dispatches this event on the webview.
+  pub(crate) fn to_script(&self) -> crate::Result<String> {
+    Ok(format!(
+      "{}({})",
+      DISPATCH_INPUT_SCRIPT.trim(),
+      serde_json::to_string(self)?
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn serializes_event_on_script() {
+    let script = InputEvent::click(10., 20.)
+      .to_script()
+      .expect("failed to create script");
+    assert!(script.starts_with("(function (input) {"));
+    assert!(script.ends_with(
+      r#"({"type":"click","x":10.0,"y":20.0,"button":"left","modifiers":{"ctrl":false,"shift":false,"alt":false,"meta":false}})"#
+    ));
+  }
+}
@@ -0,0 +1,37 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The accessibility labels, see [`crate::Window::set_accessibility_title`].
+
+use super::WindowManager;
+use crate::{Params, Window};
+
+impl<P: Params> WindowManager<P> {
+  pub fn set_accessibility_title(&self, label: &P::Label, title: String) {
+    self
+      .inner
+      .accessibility_titles
+      .lock()
+      .expect("poisoned accessibility titles")
+      .insert(label.clone(), title);
+  }
+
+  /// Sets the label on the document loaded on the window, if one was set.
+  pub fn apply_accessibility_title(&self, window: &Window<P>) -> crate::Result<()> {
+    let title = self
+      .inner
+      .accessibility_titles
+      .lock()
+      .expect("poisoned accessibility titles")
+      .get(window.label())
+      .cloned();
+    match title {
+      Some(title) => window.eval(&format!(
+        "document.documentElement.setAttribute('aria-label', {})",
+        serde_json::to_string(&title)?
+      )),
+      None => Ok(()),
+    }
+  }
+}
@@ -0,0 +1,50 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The window groups, see [`crate::Window::set_parent_group`].
+
+use super::WindowManager;
+use crate::{Params, Window};
+
+impl<P: Params> WindowManager<P> {
+  pub fn set_window_group(&self, label: &P::Label, group: Option<String>) {
+    let mut groups = self
+      .inner
+      .window_groups
+      .lock()
+      .expect("poisoned window groups");
+    match group {
+      Some(group) => groups.insert(label.clone(), group),
+      None => groups.remove(label),
+    };
+  }
+
+  pub fn window_group(&self, label: &P::Label) -> Option<String> {
+    self
+      .inner
+      .window_groups
+      .lock()
+      .expect("poisoned window groups")
+      .get(label)
+      .cloned()
+  }
+
+  pub fn group_windows(&self, group: &str) -> Vec<Window<P>> {
+    // collected first, so the groups aren't locked while waiting for the windows
+    let labels: Vec<P::Label> = self
+      .inner
+      .window_groups
+      .lock()
+      .expect("poisoned window groups")
+      .iter()
+      .filter(|(_, g)| *g == group)
+      .map(|(label, _)| label.clone())
+      .collect();
+    let windows = self.windows_lock();
+    labels
+      .iter()
+      .filter_map(|label| windows.get(label).cloned())
+      .collect()
+  }
+}
@@ -0,0 +1,1903 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  api::{
+    assets::Assets,
+    command::CommandChild,
+    config::{CloseBehavior, Config, PermissionPrompt, WindowConfig, WindowUrl},
+    PackageInfo,
+  },
+  event::{Event, EventHandler, Listeners},
+  hooks::{
+    EndpointHandler, InvokeHandler, InvokeMessage, InvokePayload, JsError, JsErrorKind, OnError,
+    OnJsError, OnNavigation, OnPageLoad, PageLoadPayload,
+  },
+  plugin::PluginStore,
+  runtime::{
+    menu::MenuEventHandler,
+    recorder::{RecordedEvent, Recorder},
+    tag::{tags_to_javascript_array, Tag, ToJavascript},
+    webview::{
+      Attributes, CustomProtocol, CustomProtocolHandler, DataDropEvent, FileDropEvent,
+      FileDropHandler, NavigationHandler, SchemePrivileges, TamperingEvent, WebviewRpcHandler,
+    },
+    window::{DetachedWindow, PendingWindow, WindowEvent, WindowEventHandler, WindowEventListener},
+    Dispatch, ExitRequestApi, Icon, RunEvent, Runtime,
+  },
+  scope::{FsScope, ShellScope},
+  sealed::ParamsBase,
+  state::StateManager,
+  AppHandle, Context, Params, Window,
+};
+#[cfg(feature = "window-snapshot")]
+use crate::{
+  runtime::window::CloseRequestApi,
+  window_snapshot::{read_window_snapshots, write_window_snapshots},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::marker::PhantomData;
+use std::{
+  collections::{HashMap, HashSet},
+  convert::TryInto,
+  path::Path,
+  sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex, MutexGuard,
+  },
+  thread::sleep,
+  time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+mod accessibility;
+mod groups;
+mod protocol;
+mod scope;
+mod session;
+
+/// Reports the data other than files dropped onto the page, unless the page handles the drop itself.
+/// The drops on editable elements keep their default behavior, inserting the text.
+const DATA_DROP_SCRIPT: &str = include_str!("../../../scripts/drop.js");
+
+/// The API modules whose invokes change the system or talk to other programs or devices,
+/// only replayed when asked, see [`WindowManager::replay`].
+#[cfg(all(feature = "event-replay", debug_assertions))]
+const SIDE_EFFECT_MODULES: &[&str] = &[
+  "App",
+  "Bluetooth",
+  "Clipboard",
+  "Dialog",
+  "Fs",
+  "GlobalShortcut",
+  "Http",
+  "License",
+  "Notification",
+  "Printer",
+  "Serial",
+  "Share",
+  "Shell",
+];
+
+/// Whether the record is an invoke of one of the [`SIDE_EFFECT_MODULES`].
+#[cfg(all(feature = "event-replay", debug_assertions))]
+fn has_side_effects(event: &RecordedEvent) -> bool {
+  match event {
+    RecordedEvent::Invoke { payload, .. } => payload
+      .get("__tauriModule")
+      .and_then(JsonValue::as_str)
+      .map_or(false, |module| SIDE_EFFECT_MODULES.contains(&module)),
+    _ => false,
+  }
+}
+
+/// How long [`WindowManager::teardown`] waits for the pending async invokes to respond.
+const PENDING_INVOKES_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// An async invoke that hasn't responded yet, see [`WindowManager::pending_invoke`].
+pub(crate) struct PendingInvoke(Arc<AtomicUsize>);
+
+impl Drop for PendingInvoke {
+  fn drop(&mut self) {
+    self.0.fetch_sub(1, Ordering::SeqCst);
+  }
+}
+
+/// The payload of the `tauri://message` event, see [`WindowManager::post_message`].
+#[derive(Clone, Serialize)]
+struct WindowMessage {
+  source: String,
+  data: JsonValue,
+  /// The ids of the buffers, read from `tauri-buffer://<id>`.
+  buffers: Vec<String>,
+}
+
+/// Reports the uncaught exceptions and unhandled promise rejections of the page
+/// with the `__jsError` command.
+const JS_ERROR_SCRIPT: &str = include_str!("../../../scripts/js-error.js");
+
+/// Mirrors the `console` messages of the page to the terminal with the `__console` command,
+/// see [`WindowManager::run_on_console`].
+const CONSOLE_SCRIPT: &str = include_str!("../../../scripts/console.js");
+
+/// The payload of the `__console` command, see [`CONSOLE_SCRIPT`].
+#[derive(Debug, Deserialize)]
+pub(crate) struct ConsoleMessage {
+  level: String,
+  message: String,
+}
+
+/// Evaluates a script and reports its value, awaited if it's a promise, or the exception it threw
+/// with the `__evalResult` invoke, see [`WindowManager::eval_with_result`].
+/// `__ID__` and `__SCRIPT__` are replaced with the JSON encoded id and script.
+const EVAL_WITH_RESULT_SCRIPT: &str = include_str!("../../../scripts/eval-with-result.js");
+
+/// Asks the snapshot provider registered by the page for its state,
+/// resolving to `{ snapshot }`, or to `null` when the page didn't register one.
+#[cfg(feature = "window-snapshot")]
+const WINDOW_SNAPSHOT_SCRIPT: &str = include_str!("../../../scripts/window-snapshot.js");
+
+/// Freezes the Tauri globals, see [`WindowManager::freeze_script`].
+/// `__HANDLER__` is replaced with the name of the event handler function.
+const FREEZE_SCRIPT: &str = include_str!("../../../scripts/freeze.js");
+
+/// The payload of the `__evalResult` invoke, see [`EVAL_WITH_RESULT_SCRIPT`].
+#[derive(Debug, Deserialize)]
+pub(crate) struct EvalResult {
+  id: String,
+  #[serde(default)]
+  result: JsonValue,
+  error: Option<String>,
+}
+
+/// Receives the value of a script evaluated with [`WindowManager::eval_with_result`],
+/// or the exception it threw.
+pub(crate) type EvalResultReceiver = tokio::sync::oneshot::Receiver<Result<JsonValue, String>>;
+
+/// The callback of [`crate::App::run`].
+pub(crate) type RunEventHandler<L> = Arc<dyn Fn(RunEvent<L>) + Send + Sync>;
+
+/// The default minimum interval between two resize or move events of a window,
+/// see [`crate::Builder::window_event_throttle`].
+pub(crate) const DEFAULT_WINDOW_EVENT_THROTTLE: Duration = Duration::from_millis(16);
+
+/// The default time a window waits for the state of its page before closing,
+/// see [`crate::Builder::window_snapshot_timeout`].
+#[cfg(feature = "window-snapshot")]
+pub(crate) const DEFAULT_WINDOW_SNAPSHOT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The high-frequency window events coalesced by [`WindowManager::throttle_window_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ThrottledEventKind {
+  Resized,
+  Moved,
+}
+
+pub struct InnerWindowManager<M: Params> {
+  windows: Mutex<HashMap<M::Label, Window<M>>>,
+  /// The handlers registered with [`Window::on_window_event`].
+  window_event_listeners: Mutex<HashMap<M::Label, Vec<Arc<WindowEventListener>>>>,
+  /// The minimum interval between two resize or move events delivered for a window.
+  window_event_throttle: Mutex<Duration>,
+  /// The latest resize or move event received during the throttle interval of each window,
+  /// delivered when the interval ends. The key is removed once an interval passes without events.
+  throttled_window_events: Mutex<HashMap<(M::Label, ThrottledEventKind), Option<WindowEvent>>>,
+  /// The splashscreen windows, closed when a window shown with [`Window::show_main_when_ready`] loads.
+  splashscreens: Mutex<HashSet<M::Label>>,
+  /// The hidden windows waiting for their page to load, see [`Window::show_main_when_ready`].
+  show_when_ready: Mutex<HashSet<M::Label>>,
+  /// Whether the splashscreens close when a window waiting for its page loads,
+  /// otherwise they close on [`crate::Manager::close_splashscreen`].
+  splashscreen_auto_close: AtomicBool,
+  /// Notifies the [`crate::runtime::window::PreloadedWindow`]s when their page loads.
+  ready_senders: Mutex<HashMap<M::Label, tokio::sync::watch::Sender<bool>>>,
+  /// The scripts waiting for their value, by id, with the window evaluating them.
+  eval_results: Mutex<
+    HashMap<
+      String,
+      (
+        M::Label,
+        tokio::sync::oneshot::Sender<Result<JsonValue, String>>,
+      ),
+    >,
+  >,
+  /// The ids of the JS event listeners registered by each window, see [`crate::endpoints`].
+  js_event_listeners: Mutex<HashMap<M::Label, HashSet<u64>>>,
+  /// The buffers of the window messages and the [`crate::Binary`] invoke responses,
+  /// fetched once by the receiving window with the `tauri-buffer` protocol,
+  /// see [`WindowManager::post_message`].
+  message_buffers: Arc<Mutex<HashMap<String, (M::Label, Vec<u8>)>>>,
+  /// The group each window was assigned to with [`Window::set_parent_group`].
+  window_groups: Mutex<HashMap<M::Label, String>>,
+  /// The label set with [`Window::set_accessibility_title`], applied again on each page load.
+  accessibility_titles: Mutex<HashMap<M::Label, String>>,
+  /// The URL loaded on each window, in creation order, recorded for session restore.
+  window_urls: Mutex<Vec<(M::Label, String)>>,
+  /// Whether the open windows are written to the window state file,
+  /// see [`crate::App::restore_session`].
+  #[cfg(feature = "window-state")]
+  session_enabled: AtomicBool,
+  /// Whether a write of the session is already waiting for the changes to settle.
+  #[cfg(feature = "window-state")]
+  session_save_scheduled: AtomicBool,
+  /// Held while the window state file is read and written back.
+  #[cfg(feature = "window-state")]
+  window_store_lock: Mutex<()>,
+  /// The time a closing window waits for the state of its page.
+  #[cfg(feature = "window-snapshot")]
+  window_snapshot_timeout: Mutex<Duration>,
+  /// Writes the invokes and events to a file, see [`crate::Builder::record_events`].
+  recorder: Mutex<Option<Recorder>>,
+  plugins: Mutex<PluginStore<M>>,
+  listeners: Listeners<M::Event, M::Label>,
+
+  /// The JS message handler.
+  invoke_handler: Box<InvokeHandler<M>>,
+
+  /// The page load hook, invoked when the webview performs a navigation.
+  on_page_load: Box<OnPageLoad<M>>,
+  on_navigation: Box<OnNavigation<M>>,
+
+  /// The hook for errors that don't stop the application.
+  on_error: Box<OnError>,
+
+  /// The hook for the uncaught exceptions and unhandled rejections of the windows.
+  on_js_error: Box<OnJsError<M>>,
+
+  /// The handlers that override or extend the Tauri API modules.
+  endpoints: Mutex<HashMap<String, Arc<EndpointHandler<M>>>>,
+
+  config: Config,
+  assets: Arc<M::Assets>,
+  default_window_icon: Option<Vec<u8>>,
+
+  /// A list of salts that are valid for the current application.
+  salts: Mutex<HashSet<Uuid>>,
+  package_info: PackageInfo,
+
+  /// The values registered with [`crate::Manager::manage`].
+  state: StateManager,
+  /// The protocols registered with [`crate::Builder::register_uri_scheme_protocol`].
+  uri_scheme_protocols: HashMap<String, Arc<CustomProtocolHandler>>,
+  /// The files served by the `asset` protocol.
+  asset_protocol_scope: FsScope,
+  /// The paths the file system APIs can access.
+  fs_scope: FsScope,
+  /// The programs the shell APIs can run.
+  shell_scope: ShellScope,
+  /// The answers to the permission prompts, by window label, prompt and target.
+  permission_answers: Mutex<HashMap<(M::Label, PermissionPrompt, String), bool>>,
+  /// The processes spawned with the shell `execute` API and the label of their window, by pid.
+  child_processes: Mutex<HashMap<u32, (M::Label, CommandChild)>>,
+  /// Receives the window and exit events once the application runs.
+  run_event_handler: Mutex<Option<RunEventHandler<M::Label>>>,
+  /// Whether the application is exiting, so closing the last window doesn't ask to exit.
+  exiting: AtomicBool,
+  /// The number of async invokes that haven't responded yet, awaited on exit.
+  pending_invokes: Arc<AtomicUsize>,
+}
+
+/// A [Zero Sized Type] marker representing a full [`Params`].
+///
+/// [Zero Sized Type]: https://doc.rust-lang.org/nomicon/exotic-sizes.html#zero-sized-types-zsts
+pub struct Args<E: Tag, L: Tag, A: Assets, R: Runtime> {
+  _event: PhantomData<fn() -> E>,
+  _label: PhantomData<fn() -> L>,
+  _assets: PhantomData<fn() -> A>,
+  _runtime: PhantomData<fn() -> R>,
+}
+
+impl<E: Tag, L: Tag, A: Assets, R: Runtime> Default for Args<E, L, A, R> {
+  fn default() -> Self {
+    Self {
+      _event: PhantomData,
+      _label: PhantomData,
+      _assets: PhantomData,
+      _runtime: PhantomData,
+    }
+  }
+}
+
+impl<E: Tag, L: Tag, A: Assets, R: Runtime> ParamsBase for Args<E, L, A, R> {}
+impl<E: Tag, L: Tag, A: Assets, R: Runtime> Params for Args<E, L, A, R> {
+  type Event = E;
+  type Label = L;
+  type Assets = A;
+  type Runtime = R;
+}
+
+pub struct WindowManager<P: Params> {
+  pub inner: Arc<InnerWindowManager<P>>,
+  _marker: Args<P::Event, P::Label, P::Assets, P::Runtime>,
+}
+
+impl<P: Params> Clone for WindowManager<P> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      _marker: Args::default(),
+    }
+  }
+}
+
+impl<P: Params> WindowManager<P> {
+  pub(crate) fn with_handlers(
+    context: Context<P::Assets>,
+    plugins: PluginStore<P>,
+    invoke_handler: Box<InvokeHandler<P>>,
+    on_page_load: Box<OnPageLoad<P>>,
+    on_navigation: Box<OnNavigation<P>>,
+    on_error: Box<OnError>,
+    on_js_error: Box<OnJsError<P>>,
+    state: StateManager,
+    uri_scheme_protocols: HashMap<String, Arc<CustomProtocolHandler>>,
+  ) -> Self {
+    let asset_protocol_scope = FsScope::for_patterns(
+      &context.config,
+      &context.config.tauri.allowlist.protocol.asset_scope,
+    );
+    let fs_scope = FsScope::for_patterns(&context.config, &context.config.tauri.allowlist.fs.scope);
+    let shell_scope = ShellScope::new(&context.config.tauri.allowlist.shell);
+    Self {
+      inner: Arc::new(InnerWindowManager {
+        windows: Mutex::default(),
+        window_event_listeners: Mutex::default(),
+        window_event_throttle: Mutex::new(DEFAULT_WINDOW_EVENT_THROTTLE),
+        throttled_window_events: Mutex::default(),
+        splashscreens: Mutex::default(),
+        ready_senders: Mutex::default(),
+        eval_results: Mutex::default(),
+        show_when_ready: Mutex::default(),
+        splashscreen_auto_close: AtomicBool::new(true),
+        js_event_listeners: Mutex::default(),
+        message_buffers: Arc::default(),
+        window_groups: Mutex::default(),
+        accessibility_titles: Mutex::default(),
+        window_urls: Mutex::default(),
+        #[cfg(feature = "window-state")]
+        session_enabled: AtomicBool::new(false),
+        #[cfg(feature = "window-state")]
+        session_save_scheduled: AtomicBool::new(false),
+        #[cfg(feature = "window-state")]
+        window_store_lock: Mutex::default(),
+        #[cfg(feature = "window-snapshot")]
+        window_snapshot_timeout: Mutex::new(DEFAULT_WINDOW_SNAPSHOT_TIMEOUT),
+        recorder: Mutex::default(),
+        plugins: Mutex::new(plugins),
+        listeners: Listeners::default(),
+        invoke_handler,
+        on_page_load,
+        on_navigation,
+        on_error,
+        on_js_error,
+        endpoints: Mutex::default(),
+        config: context.config,
+        assets: Arc::new(context.assets),
+        default_window_icon: context.default_window_icon,
+        salts: Mutex::default(),
+        package_info: context.package_info,
+        state,
+        uri_scheme_protocols,
+        asset_protocol_scope,
+        fs_scope,
+        shell_scope,
+        permission_answers: Mutex::default(),
+        child_processes: Mutex::default(),
+        run_event_handler: Mutex::default(),
+        exiting: AtomicBool::new(false),
+        pending_invokes: Arc::default(),
+      }),
+      _marker: Args::default(),
+    }
+  }
+
+  /// The values registered with [`crate::Manager::manage`].
+  pub(crate) fn state(&self) -> &StateManager {
+    &self.inner.state
+  }
+
+  /// Get a locked handle to the windows.
+  pub(crate) fn windows_lock(&self) -> MutexGuard<'_, HashMap<P::Label, Window<P>>> {
+    self.inner.windows.lock().expect("poisoned window manager")
+  }
+
+  /// The origin of the protocol serving the app assets, `tauri://${bundleIdentifier}` unless
+  /// changed with the `tauri > security > scheme` and `host` config.
+  fn app_origin(&self) -> String {
+    let config = &self.inner.config.tauri;
+    format!(
+      "{}://{}",
+      config.security.scheme,
+      config
+        .security
+        .host
+        .as_ref()
+        .unwrap_or(&config.bundle.identifier)
+    )
+  }
+
+  // setup content for dev-server
+  #[cfg(dev)]
+  fn get_url(&self) -> String {
+    if self.inner.config.build.dev_path.starts_with("http") {
+      self.inner.config.build.dev_path.clone()
+    } else {
+      self.app_origin()
+    }
+  }
+
+  #[cfg(custom_protocol)]
+  fn get_url(&self) -> String {
+    self.app_origin()
+  }
+
+  fn prepare_attributes(
+    &self,
+    attrs: <<P::Runtime as Runtime>::Dispatcher as Dispatch>::Attributes,
+    url: String,
+    label: P::Label,
+    pending_labels: &[P::Label],
+  ) -> crate::Result<<<P::Runtime as Runtime>::Dispatcher as Dispatch>::Attributes> {
+    let is_init_global = self.inner.config.build.with_global_tauri;
+    let plugin_init = self
+      .inner
+      .plugins
+      .lock()
+      .expect("poisoned plugin store")
+      .initialization_script();
+
+    let mut attributes = attrs
+      .url(url)
+      .isolated_initialization_script(&self.initialization_script(&plugin_init, is_init_global))
+      .initialization_script(&format!(
+        r#"
+              window.__TAURI__.__windows = {window_labels_array}.map(function (label) {{ return {{ label: label }} }});
+              window.__TAURI__.__currentWindow = {{ label: {current_window_label} }}
+            "#,
+        window_labels_array = tags_to_javascript_array(pending_labels)?,
+        current_window_label = label.to_javascript()?,
+      ));
+
+    #[cfg(feature = "window-snapshot")]
+    {
+      attributes = attributes.initialization_script(&self.window_snapshot_script(&label)?);
+    }
+
+    attributes = attributes.initialization_script(&self.freeze_script());
+
+    if !attributes.has_icon() {
+      if let Some(default_window_icon) = &self.inner.default_window_icon {
+        let icon = Icon::Raw(default_window_icon.clone());
+        let icon = icon.try_into().expect("infallible icon convert failed");
+        attributes = attributes.icon(icon);
+      }
+    }
+
+    // If we are on windows use App Data Local as webview temp dir
+    // to prevent any bundled application to failed.
+    // Fix: https://github.com/tauri-apps/tauri/issues/1365
+    #[cfg(windows)]
+    {
+      // Should return a path similar to C:\Users\<User>\AppData\Local\<AppName>
+      let local_app_data = crate::api::path::resolve_path(
+        &self.inner.config,
+        self.inner.package_info.name,
+        Some(crate::api::path::BaseDirectory::LocalData),
+      );
+      // Make sure the directory exist without panic
+      if let Ok(user_data_dir) = local_app_data {
+        if let Ok(()) = std::fs::create_dir_all(&user_data_dir) {
+          attributes = attributes.user_data_path(Some(user_data_dir));
+        }
+      }
+    }
+
+    Ok(attributes)
+  }
+
+  fn prepare_rpc_handler(&self) -> WebviewRpcHandler<P> {
+    let manager = self.clone();
+    Box::new(move |window, request| {
+      let window = manager.attach_window(window);
+      let command = request.command.clone();
+
+      let arg = request
+        .params
+        .unwrap()
+        .as_array_mut()
+        .unwrap()
+        .first_mut()
+        .unwrap_or(&mut JsonValue::Null)
+        .take();
+      match serde_json::from_value::<InvokePayload>(arg) {
+        Ok(message) => {
+          if let Err(e) = window.on_message(command, message) {
+            manager.report_error(e);
+          }
+        }
+        Err(e) => {
+          let error: crate::Error = e.into();
+          if let Err(e) = window.eval(&format!(
+            r#"console.error({})"#,
+            JsonValue::String(error.to_string())
+          )) {
+            manager.report_error(e);
+          }
+        }
+      }
+    })
+  }
+
+  fn prepare_window_event_handler(&self) -> WindowEventHandler<P> {
+    let manager = self.clone();
+    Box::new(move |event, window| {
+      let kind = match event {
+        WindowEvent::Resized { .. } => Some(ThrottledEventKind::Resized),
+        WindowEvent::Moved { .. } => Some(ThrottledEventKind::Moved),
+        _ => None,
+      };
+      if let Some(kind) = kind {
+        manager.throttle_window_event(kind, event, window);
+        return;
+      }
+
+      manager.deliver_window_event(&event, &window);
+
+      if let WindowEvent::CloseRequested(api) = &event {
+        if !api.is_close_prevented() {
+          let behavior = manager
+            .window_config(&window.label)
+            .map(|config| config.close_behavior)
+            .unwrap_or_default();
+          #[cfg(feature = "window-snapshot")]
+          if behavior != CloseBehavior::Hide {
+            manager.close_after_snapshot(&window, api, behavior);
+            return;
+          }
+          if behavior == CloseBehavior::Hide {
+            api.prevent_close();
+          }
+          manager.close_with_behavior(&window, behavior, true);
+        }
+      }
+
+      if let WindowEvent::Destroyed = event {
+        manager
+          .inner
+          .window_event_listeners
+          .lock()
+          .expect("poisoned window event listeners")
+          .remove(&window.label);
+        manager
+          .inner
+          .throttled_window_events
+          .lock()
+          .expect("poisoned throttled window events")
+          .retain(|(label, _), _| label != &window.label);
+        manager
+          .inner
+          .ready_senders
+          .lock()
+          .expect("poisoned ready senders")
+          .remove(&window.label);
+        // dropping the senders rejects the pending evaluations
+        manager
+          .inner
+          .eval_results
+          .lock()
+          .expect("poisoned eval results")
+          .retain(|_, (label, _)| label != &window.label);
+        manager
+          .inner
+          .js_event_listeners
+          .lock()
+          .expect("poisoned js event listeners")
+          .remove(&window.label);
+        manager
+          .inner
+          .message_buffers
+          .lock()
+          .expect("poisoned message buffers")
+          .retain(|_, (target, _)| target != &window.label);
+        manager
+          .inner
+          .permission_answers
+          .lock()
+          .expect("poisoned permission answers")
+          .retain(|(label, _, _), _| label != &window.label);
+        manager
+          .inner
+          .window_groups
+          .lock()
+          .expect("poisoned window groups")
+          .remove(&window.label);
+        manager
+          .inner
+          .accessibility_titles
+          .lock()
+          .expect("poisoned accessibility titles")
+          .remove(&window.label);
+        manager.inner.listeners.unlisten_all_window(&window.label);
+        let is_last = {
+          let mut windows = manager.windows_lock();
+          windows.remove(&window.label);
+          windows.is_empty()
+        };
+        // the windows closed on exit and the last window stay on the session,
+        // so they're restored on the next run
+        if is_last || manager.inner.exiting.load(Ordering::SeqCst) {
+          #[cfg(feature = "window-state")]
+          manager.flush_session();
+        } else {
+          manager.remove_window_url(&window.label);
+        }
+        manager.on_window_closed(window.label.clone(), is_last);
+      }
+    })
+  }
+
+  /// Runs the [`Window::on_window_event`] listeners and emits the event to the webview,
+  /// see [`WindowEvent`] for the names of the emitted events.
+  fn deliver_window_event(&self, event: &WindowEvent, window: &DetachedWindow<P>) {
+    // the listeners are cloned so they can register other listeners
+    let listeners = self
+      .inner
+      .window_event_listeners
+      .lock()
+      .expect("poisoned window event listeners")
+      .get(&window.label)
+      .cloned()
+      .unwrap_or_default();
+    for listener in listeners {
+      listener(event);
+    }
+
+    let (name, payload) = match *event {
+      WindowEvent::CloseRequested(_) => ("tauri://close-requested", JsonValue::Null),
+      WindowEvent::Focused(true) => ("tauri://focus", JsonValue::Null),
+      WindowEvent::Focused(false) => ("tauri://blur", JsonValue::Null),
+      WindowEvent::Resized { width, height } => (
+        "tauri://resize",
+        serde_json::json!({ "width": width, "height": height }),
+      ),
+      WindowEvent::Moved { x, y } => ("tauri://move", serde_json::json!({ "x": x, "y": y })),
+      WindowEvent::ThemeChanged(theme) => ("tauri://theme-changed", serde_json::json!(theme)),
+      WindowEvent::Destroyed => return,
+    };
+    if let Some(window) = self.get_window(&window.label) {
+      if let Err(e) = window.emit_internal(name.to_string(), Some(payload)) {
+        self.report_error(e);
+      }
+    }
+  }
+
+  /// Coalesces the resize and move events, which are reported continuously while the user drags
+  /// the window: the first event is delivered right away, then only the latest event of each
+  /// throttle interval, so the final size and position are always delivered.
+  fn throttle_window_event(
+    &self,
+    kind: ThrottledEventKind,
+    event: WindowEvent,
+    window: DetachedWindow<P>,
+  ) {
+    let interval = *self
+      .inner
+      .window_event_throttle
+      .lock()
+      .expect("poisoned window event throttle");
+    if interval == Duration::default() {
+      self.deliver_window_event(&event, &window);
+      return;
+    }
+
+    let key = (window.label.clone(), kind);
+    {
+      let mut pending = self
+        .inner
+        .throttled_window_events
+        .lock()
+        .expect("poisoned throttled window events");
+      if let Some(latest) = pending.get_mut(&key) {
+        latest.replace(event);
+        return;
+      }
+      pending.insert(key.clone(), None);
+    }
+    self.deliver_window_event(&event, &window);
+
+    // a single timer per window and event kind, on the async runtime
+    let manager = self.clone();
+    crate::async_runtime::spawn(async move {
+      loop {
+        tokio::time::sleep(interval).await;
+        let latest = {
+          let mut pending = manager
+            .inner
+            .throttled_window_events
+            .lock()
+            .expect("poisoned throttled window events");
+          match pending.get_mut(&key).and_then(Option::take) {
+            Some(latest) => latest,
+            None => {
+              pending.remove(&key);
+              break;
+            }
+          }
+        };
+        manager.deliver_window_event(&latest, &window);
+      }
+    });
+  }
+
+  fn prepare_menu_event_handler(&self) -> MenuEventHandler<P> {
+    let manager = self.clone();
+    Box::new(move |event, window| {
+      let window = manager.attach_window(window);
+      if let Err(e) = window.emit_internal("tauri://menu".to_string(), Some(event.clone())) {
+        manager.report_error(e);
+      }
+      if let Ok(tag) = "tauri://menu".parse() {
+        match serde_json::to_string(&event) {
+          Ok(data) => manager.trigger(tag, Some(window.label().clone()), Some(data)),
+          Err(e) => manager.report_error(e.into()),
+        }
+      }
+    })
+  }
+
+  fn prepare_file_drop(&self) -> FileDropHandler<P> {
+    let manager = self.clone();
+    Box::new(move |event, window| {
+      let manager = manager.clone();
+      crate::async_runtime::block_on(async move {
+        let window = manager.attach_window(window);
+        let result = match event {
+          FileDropEvent::Hovered(paths) => {
+            window.emit_internal("tauri://file-drop-hover".to_string(), Some(paths))
+          }
+          FileDropEvent::Dropped(paths) => {
+            window.emit_internal("tauri://file-drop".to_string(), Some(paths))
+          }
+          FileDropEvent::Cancelled => {
+            window.emit_internal("tauri://file-drop-cancelled".to_string(), Some(()))
+          }
+        };
+        if let Err(e) = result {
+          manager.report_error(e);
+        }
+      });
+      true
+    })
+  }
+
+  fn prepare_navigation_handler(&self) -> NavigationHandler<P> {
+    let manager = self.clone();
+    Box::new(move |url, window| {
+      let window = manager.attach_window(window);
+      let allowed = manager.is_navigation_allowed(&window, &url);
+      if allowed {
+        // the URL reported by the webview, which the page scripts can't spoof
+        manager.set_window_url(window.label(), url);
+      }
+      allowed
+    })
+  }
+
+  fn initialization_script(
+    &self,
+    plugin_initialization_script: &str,
+    with_global_tauri: bool,
+  ) -> String {
+    format!(
+      r#"
+      {bundle_script}
+      {core_script}
+      {event_initialization_script}
+      if (window.rpc) {{
+        window.__TAURI__.invoke("__initialized", {{ url: window.location.href }})
+      }} else {{
+        window.addEventListener('DOMContentLoaded', function () {{
+          window.__TAURI__.invoke("__initialized", {{ url: window.location.href }})
+        }})
+      }}
+      {drop_script}
+      {js_error_script}
+      {console_script}
+      {plugin_initialization_script}
+    "#,
+      core_script = include_str!("../../../scripts/core.js"),
+      bundle_script = if with_global_tauri {
+        include_str!("../../../scripts/bundle.js")
+      } else {
+        ""
+      },
+      event_initialization_script = self.event_initialization_script(),
+      drop_script = DATA_DROP_SCRIPT,
+      js_error_script = JS_ERROR_SCRIPT,
+      console_script = if cfg!(dev) && self.inner.config.build.dev_console {
+        CONSOLE_SCRIPT
+      } else {
+        ""
+      },
+      plugin_initialization_script = plugin_initialization_script
+    )
+  }
+
+  /// Freezes the Tauri globals and reports the page scripts trying to override them,
+  /// or polluting the prototypes the bridge relies on, with the `__tampering` command.
+  ///
+  /// It runs last, in the page world, so the page scripts always see the frozen API,
+  /// even when the webview runs the initialization script in the page world too,
+  /// see [`crate::runtime::webview::Attributes::isolated_initialization_script`].
+  fn freeze_script(&self) -> String {
+    FREEZE_SCRIPT.replace("__HANDLER__", &self.inner.listeners.function_name())
+  }
+
+  fn event_initialization_script(&self) -> String {
+    return format!(
+      "
+      window['{queue}'] = [];
+      window['{function}'] = function (eventData, salt, ignoreQueue) {{
+      const listeners = (window['{listeners}'] && window['{listeners}'][eventData.event]) || []
+      if (!ignoreQueue && listeners.length === 0) {{
+        window['{queue}'].push({{
+          eventData: eventData,
+          salt: salt
+        }})
+      }}
+
+      if (listeners.length > 0) {{
+        window.__TAURI__.invoke('tauri', {{
+          __tauriModule: 'Internal',
+          message: {{
+            cmd: 'validateSalt',
+            salt: salt
+          }}
+        }}).then(function (flag) {{
+          if (flag) {{
+            for (let i = listeners.length - 1; i >= 0; i--) {{
+              const listener = listeners[i]
+              eventData.id = listener.id
+              listener.handler(eventData)
+            }}
+          }}
+        }})
+      }}
+    }}
+    ",
+      function = self.inner.listeners.function_name(),
+      queue = self.inner.listeners.queue_object_name(),
+      listeners = self.inner.listeners.listeners_object_name()
+    );
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Args, WindowManager};
+  use crate::{generate_context, plugin::PluginStore, runtime::flavors::wry::Wry};
+
+  #[test]
+  fn check_get_url() {
+    let context = generate_context!("test/fixture/src-tauri/tauri.conf.json", crate);
+    let manager: WindowManager<Args<String, String, _, Wry>> = WindowManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Box::new(|_, _| true),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Default::default(),
+      Default::default(),
+    );
+
+    #[cfg(custom_protocol)]
+    assert_eq!(manager.get_url(), "tauri://studio.tauri.example");
+
+    #[cfg(dev)]
+    assert_eq!(manager.get_url(), manager.config().build.dev_path);
+  }
+
+  #[test]
+  fn uses_configured_app_origin() {
+    let mut context = generate_context!("test/fixture/src-tauri/tauri.conf.json", crate);
+    context.config.tauri.security.scheme = "app".into();
+    context.config.tauri.security.host = Some("myapp".into());
+    let manager: WindowManager<Args<String, String, _, Wry>> = WindowManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Box::new(|_, _| true),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Default::default(),
+      Default::default(),
+    );
+
+    assert_eq!(manager.app_origin(), "app://myapp");
+    assert_eq!(manager.prepare_custom_protocol().name, "app");
+  }
+
+  #[test]
+  fn enables_service_workers_on_app_protocol() {
+    let mut context = generate_context!("test/fixture/src-tauri/tauri.conf.json", crate);
+    context.config.tauri.security.service_workers = true;
+    let manager: WindowManager<Args<String, String, _, Wry>> = WindowManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Box::new(|_, _| true),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Default::default(),
+      Default::default(),
+    );
+
+    let privileges = manager.prepare_custom_protocol().privileges;
+    assert!(privileges.secure);
+    assert!(privileges.service_workers);
+    assert!(
+      !manager
+        .prepare_message_buffer_protocol("main".into())
+        .privileges
+        .service_workers
+    );
+  }
+
+  #[test]
+  fn serves_buffers_to_their_window() {
+    let context = generate_context!("test/fixture/src-tauri/tauri.conf.json", crate);
+    let manager: WindowManager<Args<String, String, _, Wry>> = WindowManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Box::new(|_, _| true),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Default::default(),
+      Default::default(),
+    );
+    manager.store_message_buffers(&"main".into(), vec![("id".into(), vec![1, 2])]);
+
+    let other = manager.prepare_message_buffer_protocol("other".into());
+    assert!((other.handler)("tauri-buffer://id").is_err());
+    let main = manager.prepare_message_buffer_protocol("main".into());
+    assert!((main.handler)("tauri-buffer://id").is_ok());
+    // the buffers are read once
+    assert!((main.handler)("tauri-buffer://id").is_err());
+  }
+
+  #[cfg(all(feature = "event-replay", debug_assertions))]
+  #[test]
+  fn filters_side_effects_on_replay() {
+    use crate::runtime::recorder::RecordedEvent;
+
+    let invoke = |payload| RecordedEvent::Invoke {
+      window: "main".into(),
+      command: "tauri".into(),
+      payload,
+    };
+    assert!(super::has_side_effects(&invoke(
+      serde_json::json!({ "__tauriModule": "Fs", "message": { "cmd": "writeFile" } })
+    )));
+    assert!(!super::has_side_effects(&invoke(
+      serde_json::json!({ "__tauriModule": "Window", "message": { "cmd": "maximize" } })
+    )));
+    assert!(!super::has_side_effects(&invoke(
+      serde_json::json!({ "callback": "1", "error": "2" })
+    )));
+  }
+}
+
+impl<P: Params> WindowManager<P> {
+  pub fn run_invoke_handler(&self, message: InvokeMessage<P>) {
+    (self.inner.invoke_handler)(message);
+  }
+  pub fn register_endpoint(&self, module: String, handler: Arc<EndpointHandler<P>>) {
+    self
+      .inner
+      .endpoints
+      .lock()
+      .expect("poisoned endpoint registry")
+      .insert(module, handler);
+  }
+  pub fn endpoint_handler(&self, module: &str) -> Option<Arc<EndpointHandler<P>>> {
+    self
+      .inner
+      .endpoints
+      .lock()
+      .expect("poisoned endpoint registry")
+      .get(module)
+      .cloned()
+  }
+  pub fn report_error(&self, error: crate::Error) {
+    (self.inner.on_error)(error);
+  }
+  /// Prints a `console` message of the window to the terminal during development,
+  /// the warnings and errors to stderr.
+  pub fn run_on_console(&self, window: Window<P>, message: ConsoleMessage) {
+    if !cfg!(dev) || !self.inner.config.build.dev_console {
+      return;
+    }
+    match message.level.as_str() {
+      "warn" | "error" => eprintln!(
+        "[{}] {}: {}",
+        window.label(),
+        message.level,
+        message.message
+      ),
+      _ => println!("[{}] {}", window.label(), message.message),
+    }
+  }
+  /// Logs an uncaught exception or unhandled rejection of the window and runs the hook.
+  pub fn run_on_js_error(&self, window: Window<P>, error: JsError) {
+    let kind = match error.kind() {
+      JsErrorKind::Error => "uncaught exception",
+      JsErrorKind::UnhandledRejection => "unhandled rejection",
+    };
+    let location = match (error.source(), error.line(), error.column()) {
+      (Some(source), Some(line), Some(column)) => format!(" at {}:{}:{}", source, line, column),
+      _ => String::new(),
+    };
+    // TODO log::error!
+    eprintln!(
+      "{} on window `{}`{}: {}",
+      kind,
+      window.label(),
+      location,
+      error.message()
+    );
+    if let Some(stack) = error.stack() {
+      eprintln!("{}", stack);
+    }
+    (self.inner.on_js_error)(&window, &error);
+  }
+
+  /// Sets the callback of [`crate::App::run`].
+  pub(crate) fn set_run_event_handler(&self, handler: RunEventHandler<P::Label>) {
+    *self
+      .inner
+      .run_event_handler
+      .lock()
+      .expect("poisoned run event handler") = Some(handler);
+  }
+
+  fn run_event_handler(&self) -> Option<RunEventHandler<P::Label>> {
+    // cloned so the callback can create windows
+    self
+      .inner
+      .run_event_handler
+      .lock()
+      .expect("poisoned run event handler")
+      .clone()
+  }
+
+  /// Sends [`RunEvent::WindowClosed`] and, after the last window, asks to exit.
+  ///
+  /// The handler is set by [`crate::App::run`], so the windows closed before the application
+  /// runs, e.g. on its setup hook, don't exit it.
+  fn on_window_closed(&self, label: P::Label, is_last: bool) {
+    if let Some(handler) = self.run_event_handler() {
+      handler(RunEvent::WindowClosed(label));
+      if is_last && !self.inner.exiting.load(Ordering::SeqCst) {
+        let api = ExitRequestApi::default();
+        handler(RunEvent::ExitRequested(api.clone()));
+        if !api.is_exit_prevented() {
+          self.exit(0);
+        }
+      }
+    }
+  }
+
+  /// Tears down the application and exits the process with the given code,
+  /// see [`crate::AppHandle::exit`].
+  pub(crate) fn exit(&self, code: i32) -> ! {
+    self.teardown();
+    std::process::exit(code)
+  }
+
+  /// Tracks an async invoke until the returned guard is dropped, once it responded.
+  pub(crate) fn pending_invoke(&self) -> PendingInvoke {
+    self.inner.pending_invokes.fetch_add(1, Ordering::SeqCst);
+    PendingInvoke(self.inner.pending_invokes.clone())
+  }
+
+  /// Waits for the pending async invokes to respond, up to [`PENDING_INVOKES_TIMEOUT`].
+  fn wait_pending_invokes(&self) {
+    let deadline = Instant::now() + PENDING_INVOKES_TIMEOUT;
+    while self.inner.pending_invokes.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+      sleep(Duration::from_millis(10));
+    }
+  }
+
+  /// Waits for the pending async invokes, closes the windows, sends [`RunEvent::Exit`]
+  /// and kills the child processes, see [`crate::AppHandle::exit`].
+  pub(crate) fn teardown(&self) {
+    self.inner.exiting.store(true, Ordering::SeqCst);
+    self.wait_pending_invokes();
+    #[cfg(feature = "window-state")]
+    self.flush_session();
+    for window in self.windows().values() {
+      if let Err(e) = window.dispatcher().close() {
+        self.report_error(e);
+      }
+    }
+    if let Some(handler) = self.run_event_handler() {
+      handler(RunEvent::Exit);
+    }
+    let children = std::mem::take(
+      &mut *self
+        .inner
+        .child_processes
+        .lock()
+        .expect("poisoned child processes"),
+    );
+    for (_, child) in children.values() {
+      if let Err(e) = child.kill() {
+        self.report_error(e.into());
+      }
+    }
+  }
+  pub fn set_splashscreen(&self, label: P::Label) {
+    self
+      .inner
+      .splashscreens
+      .lock()
+      .expect("poisoned splashscreen list")
+      .insert(label);
+  }
+  pub fn set_splashscreen_auto_close(&self, auto_close: bool) {
+    self
+      .inner
+      .splashscreen_auto_close
+      .store(auto_close, Ordering::SeqCst);
+  }
+  /// Closes the window as configured, after saving its geometry.
+  ///
+  /// The runtime destroys the window itself when `requested` by the user,
+  /// otherwise the window is closed here.
+  fn close_with_behavior(
+    &self,
+    window: &DetachedWindow<P>,
+    behavior: CloseBehavior,
+    requested: bool,
+  ) {
+    #[cfg(feature = "window-state")]
+    self.save_window_state(window);
+    match behavior {
+      CloseBehavior::Destroy => {
+        if !requested {
+          if let Err(e) = window.dispatcher.close() {
+            self.report_error(e);
+          }
+        }
+      }
+      CloseBehavior::Hide => {
+        if let Err(e) = window.dispatcher.hide() {
+          self.report_error(e);
+        }
+      }
+      CloseBehavior::Quit => self.exit(0),
+    }
+  }
+  /// Keeps the window open while its page state is saved, then closes it.
+  #[cfg(feature = "window-snapshot")]
+  fn close_after_snapshot(
+    &self,
+    window: &DetachedWindow<P>,
+    api: &CloseRequestApi,
+    behavior: CloseBehavior,
+  ) {
+    api.prevent_close();
+    let manager = self.clone();
+    let window = window.clone();
+    crate::async_runtime::spawn(async move {
+      manager
+        .save_window_snapshot(&Window::new(manager.clone(), window.clone()))
+        .await;
+      manager.close_with_behavior(&window, behavior, false);
+    });
+  }
+  /// Saves the state returned by the snapshot provider of the page,
+  /// ignored when the page doesn't reply before the timeout.
+  #[cfg(feature = "window-snapshot")]
+  async fn save_window_snapshot(&self, window: &Window<P>) {
+    let timeout = *self
+      .inner
+      .window_snapshot_timeout
+      .lock()
+      .expect("poisoned window snapshot timeout");
+    let reply =
+      match tokio::time::timeout(timeout, window.eval_with_result(WINDOW_SNAPSHOT_SCRIPT)).await {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(e)) => {
+          self.report_error(e);
+          return;
+        }
+        Err(_) => return,
+      };
+    // the page didn't register a snapshot provider
+    let snapshot = match reply {
+      JsonValue::Object(mut reply) => reply.remove("snapshot").unwrap_or(JsonValue::Null),
+      _ => return,
+    };
+    let label = window.label().to_string();
+    let result = read_window_snapshots(self.config()).and_then(|mut snapshots| {
+      if snapshot.is_null() {
+        snapshots.remove(&label);
+      } else {
+        snapshots.insert(label, snapshot);
+      }
+      write_window_snapshots(self.config(), &snapshots)
+    });
+    if let Err(e) = result {
+      self.report_error(e);
+    }
+  }
+  /// The script restoring the state saved when the window was last closed,
+  /// read by the page with `getWindowSnapshot`.
+  #[cfg(feature = "window-snapshot")]
+  fn window_snapshot_script(&self, label: &P::Label) -> crate::Result<String> {
+    let snapshot = match read_window_snapshots(self.config()) {
+      Ok(mut snapshots) => snapshots.remove(&label.to_string()),
+      Err(e) => {
+        self.report_error(e);
+        None
+      }
+    };
+    Ok(format!(
+      "window.__TAURI__.__windowSnapshot = {}",
+      serde_json::to_string(&snapshot.unwrap_or(JsonValue::Null))?
+    ))
+  }
+  #[cfg(feature = "window-snapshot")]
+  pub fn set_window_snapshot_timeout(&self, timeout: Duration) {
+    *self
+      .inner
+      .window_snapshot_timeout
+      .lock()
+      .expect("poisoned window snapshot timeout") = timeout;
+  }
+  pub fn set_window_event_throttle(&self, interval: Duration) {
+    *self
+      .inner
+      .window_event_throttle
+      .lock()
+      .expect("poisoned window event throttle") = interval;
+  }
+  pub fn show_when_ready(&self, label: P::Label) {
+    self
+      .inner
+      .show_when_ready
+      .lock()
+      .expect("poisoned show when ready list")
+      .insert(label);
+  }
+  pub fn watch_ready(&self, label: P::Label) -> tokio::sync::watch::Receiver<bool> {
+    let (sender, receiver) = tokio::sync::watch::channel(false);
+    self
+      .inner
+      .ready_senders
+      .lock()
+      .expect("poisoned ready senders")
+      .insert(label, sender);
+    receiver
+  }
+  /// Shows the window and closes the splashscreens.
+  fn show_main_window(&self, window: &Window<P>) {
+    if let Err(e) = window.show() {
+      self.report_error(e);
+    }
+    self.close_splashscreens();
+  }
+  /// Shows the windows waiting for their page to load and closes the splashscreens.
+  pub fn close_splashscreen(&self) {
+    let labels = self
+      .inner
+      .show_when_ready
+      .lock()
+      .expect("poisoned show when ready list")
+      .drain()
+      .collect::<Vec<_>>();
+    for label in labels {
+      if let Some(window) = self.get_window(&label) {
+        if let Err(e) = window.show() {
+          self.report_error(e);
+        }
+      }
+    }
+    self.close_splashscreens();
+  }
+  fn close_splashscreens(&self) {
+    let splashscreens = self
+      .inner
+      .splashscreens
+      .lock()
+      .expect("poisoned splashscreen list")
+      .drain()
+      .collect::<Vec<_>>();
+    for label in splashscreens {
+      if let Some(splashscreen) = self.get_window(&label) {
+        if let Err(e) = splashscreen.close() {
+          self.report_error(e);
+        }
+      }
+    }
+  }
+  /// Emits the data dropped onto the window to the webview and the Rust listeners.
+  pub fn run_on_data_drop(&self, window: Window<P>, event: DataDropEvent) {
+    if let Err(e) = window.emit_internal("tauri://drop".to_string(), Some(event.clone())) {
+      self.report_error(e);
+    }
+    if let Ok(tag) = "tauri://drop".parse() {
+      match serde_json::to_string(&event) {
+        Ok(data) => self.trigger(tag, Some(window.label().clone()), Some(data)),
+        Err(e) => self.report_error(e.into()),
+      }
+    }
+  }
+  /// Notifies the Rust listeners that a page script tampered with the injected API.
+  ///
+  /// It isn't emitted to the webview, since its scripts can't be trusted anymore.
+  pub fn run_on_tampering(&self, window: Window<P>, event: TamperingEvent) {
+    if let Ok(tag) = "tauri://tampering".parse() {
+      match serde_json::to_string(&event) {
+        Ok(data) => self.trigger(tag, Some(window.label().clone()), Some(data)),
+        Err(e) => self.report_error(e.into()),
+      }
+    }
+  }
+  pub fn run_on_page_load(&self, window: Window<P>, payload: PageLoadPayload) {
+    // the navigation handler cancels the rejected navigations before they load,
+    // so this only skips the hooks when the page reports a URL the hook rejects
+    if !self.is_navigation_allowed(&window, payload.url()) {
+      return;
+    }
+    if let Err(e) = self.apply_accessibility_title(&window) {
+      self.report_error(e);
+    }
+    // without the automatic close, the window waits for `close_splashscreen`
+    let ready = self.inner.splashscreen_auto_close.load(Ordering::SeqCst)
+      && self
+        .inner
+        .show_when_ready
+        .lock()
+        .expect("poisoned show when ready list")
+        .remove(window.label());
+    if ready {
+      self.show_main_window(&window);
+    }
+    if let Some(sender) = self
+      .inner
+      .ready_senders
+      .lock()
+      .expect("poisoned ready senders")
+      .remove(window.label())
+    {
+      let _ = sender.send(true);
+    }
+    if let Ok(event) = "tauri://ready".parse() {
+      self.trigger(event, Some(window.label().clone()), None);
+    }
+    (self.inner.on_page_load)(window.clone(), payload.clone());
+    self
+      .inner
+      .plugins
+      .lock()
+      .expect("poisoned plugin store")
+      .on_page_load(window, payload);
+  }
+  /// Evaluates the script on the window, returning a receiver for its value.
+  pub fn eval_with_result(
+    &self,
+    window: &Window<P>,
+    js: &str,
+  ) -> crate::Result<EvalResultReceiver> {
+    let id = Uuid::new_v4().to_string();
+    let script = EVAL_WITH_RESULT_SCRIPT
+      .replace("__ID__", &serde_json::to_string(&id)?)
+      .replace("__SCRIPT__", &serde_json::to_string(js)?);
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    self
+      .inner
+      .eval_results
+      .lock()
+      .expect("poisoned eval results")
+      .insert(id.clone(), (window.label().clone(), sender));
+    if let Err(e) = window.eval(&script) {
+      self
+        .inner
+        .eval_results
+        .lock()
+        .expect("poisoned eval results")
+        .remove(&id);
+      return Err(e);
+    }
+    Ok(receiver)
+  }
+  /// Sends the value of a script evaluated with [`WindowManager::eval_with_result`].
+  ///
+  /// The result is ignored unless it comes from the window evaluating the script.
+  pub fn run_on_eval_result(&self, window: Window<P>, result: EvalResult) {
+    let mut eval_results = self
+      .inner
+      .eval_results
+      .lock()
+      .expect("poisoned eval results");
+    let is_evaluating = eval_results
+      .get(&result.id)
+      .map(|(label, _)| label == window.label())
+      .unwrap_or(false);
+    if is_evaluating {
+      if let Some((_, sender)) = eval_results.remove(&result.id) {
+        let _ = sender.send(match result.error {
+          Some(error) => Err(error),
+          None => Ok(result.result),
+        });
+      }
+    }
+  }
+  pub fn extend_api(&self, command: String, message: InvokeMessage<P>) {
+    self
+      .inner
+      .plugins
+      .lock()
+      .expect("poisoned plugin store")
+      .extend_api(command, message);
+  }
+  pub fn initialize_plugins(&self, app: &AppHandle<P>) -> crate::Result<()> {
+    self
+      .inner
+      .plugins
+      .lock()
+      .expect("poisoned plugin store")
+      .initialize(app, &self.inner.config.plugins)
+  }
+
+  pub fn prepare_window(
+    &self,
+    mut pending: PendingWindow<P>,
+    pending_labels: &[P::Label],
+  ) -> crate::Result<PendingWindow<P>> {
+    let (is_local, url) = match &pending.url {
+      WindowUrl::App(path) => {
+        let url = self.get_url();
+        (
+          true,
+          // ignore "index.html" just to simplify the url
+          if path.to_str() != Some("index.html") {
+            format!("{}/{}", url, path.to_string_lossy())
+          } else {
+            url
+          },
+        )
+      }
+      WindowUrl::External(url) => (url.as_str().starts_with("tauri://"), url.to_string()),
+    };
+
+    self.set_window_url(&pending.label, url.clone());
+
+    let attributes = pending.attributes.clone();
+    if is_local {
+      let label = pending.label.clone();
+      pending.attributes = self.prepare_attributes(attributes, url, label, pending_labels)?;
+      pending.rpc_handler = Some(self.prepare_rpc_handler());
+      pending
+        .custom_protocols
+        .push(self.prepare_custom_protocol());
+      // remote pages must not read the message buffers or the scoped local files
+      pending
+        .custom_protocols
+        .push(self.prepare_message_buffer_protocol(pending.label.clone()));
+      #[cfg(protocol_asset)]
+      pending.custom_protocols.push(self.prepare_asset_protocol());
+    } else {
+      pending.attributes = attributes.url(url);
+    }
+
+    for (name, handler) in &self.inner.uri_scheme_protocols {
+      pending.custom_protocols.push(CustomProtocol {
+        name: name.clone(),
+        handler: handler.clone(),
+        privileges: SchemePrivileges::default(),
+      });
+    }
+
+    if let Some(config) = self.window_config(&pending.label) {
+      for script in &config.initialization_scripts {
+        pending.attributes = pending.attributes.initialization_script(script);
+      }
+    }
+
+    #[cfg(feature = "window-state")]
+    {
+      pending = self.restore_window_state(pending);
+    }
+
+    pending.file_drop_handler = Some(self.prepare_file_drop());
+    pending.navigation_handler = Some(self.prepare_navigation_handler());
+    pending.menu_event_handler = Some(self.prepare_menu_event_handler());
+    pending.window_event_handler = Some(self.prepare_window_event_handler());
+
+    Ok(pending)
+  }
+  pub fn attach_window(&self, window: DetachedWindow<P>) -> Window<P> {
+    let window = Window::new(self.clone(), window);
+
+    // insert the window into our manager
+    {
+      self
+        .windows_lock()
+        .insert(window.label().clone(), window.clone());
+    }
+
+    // let plugins know that a new window has been added to the manager
+    {
+      self
+        .inner
+        .plugins
+        .lock()
+        .expect("poisoned plugin store")
+        .created(window.clone());
+    }
+
+    window
+  }
+  /// Opens the dev tools of a new window if its config asks for it,
+  /// only on debug builds, so the end users never see them.
+  pub fn open_configured_devtools(&self, window: &Window<P>) {
+    let devtools = self
+      .window_config(window.label())
+      .map(|config| config.devtools)
+      .unwrap_or(false);
+    if cfg!(debug_assertions) && devtools {
+      if let Err(e) = window.open_devtools() {
+        self.report_error(e);
+      }
+    }
+  }
+  pub fn emit_filter_internal<S: Serialize + Clone, F: Fn(&Window<P>) -> bool>(
+    &self,
+    event: String,
+    payload: Option<S>,
+    filter: F,
+  ) -> crate::Result<()> {
+    self
+      .windows_lock()
+      .values()
+      .filter(|&w| filter(w))
+      .try_for_each(|window| window.emit_internal(event.clone(), payload.clone()))
+  }
+  pub fn emit_filter<S: Serialize + Clone, F: Fn(&Window<P>) -> bool>(
+    &self,
+    event: P::Event,
+    payload: Option<S>,
+    filter: F,
+  ) -> crate::Result<()> {
+    self
+      .windows_lock()
+      .values()
+      .filter(|&w| filter(w))
+      .try_for_each(|window| window.emit(&event, payload.clone()))
+  }
+  /// Emits an event sent by the `source` window to the filtered windows.
+  pub fn emit_filter_from<S: Serialize + Clone, F: Fn(&Window<P>) -> bool>(
+    &self,
+    source: &P::Label,
+    event: P::Event,
+    payload: Option<S>,
+    filter: F,
+  ) -> crate::Result<()> {
+    self
+      .windows_lock()
+      .values()
+      .filter(|&w| filter(w))
+      .try_for_each(|window| {
+        window.emit_from_internal(Some(source), event.clone(), payload.clone())
+      })
+  }
+  pub fn labels(&self) -> HashSet<P::Label> {
+    self.windows_lock().keys().cloned().collect()
+  }
+  pub fn config(&self) -> &Config {
+    &self.inner.config
+  }
+  pub fn package_info(&self) -> &PackageInfo {
+    &self.inner.package_info
+  }
+  /// Stores a process spawned by the window, so it can write to its stdin or kill it.
+  pub fn add_child_process(&self, owner: P::Label, child: CommandChild) {
+    self
+      .inner
+      .child_processes
+      .lock()
+      .expect("poisoned child processes")
+      .insert(child.pid(), (owner, child));
+  }
+  pub fn remove_child_process(&self, pid: u32) {
+    self
+      .inner
+      .child_processes
+      .lock()
+      .expect("poisoned child processes")
+      .remove(&pid);
+  }
+  pub fn child_process(&self, pid: u32) -> Option<CommandChild> {
+    self
+      .inner
+      .child_processes
+      .lock()
+      .expect("poisoned child processes")
+      .get(&pid)
+      .map(|(_, child)| child.clone())
+  }
+  /// The process spawned by the window, failing if another window spawned it.
+  pub fn window_child_process(
+    &self,
+    label: &P::Label,
+    pid: u32,
+  ) -> crate::Result<Option<CommandChild>> {
+    match self
+      .inner
+      .child_processes
+      .lock()
+      .expect("poisoned child processes")
+      .get(&pid)
+    {
+      Some((owner, child)) if owner == label => Ok(Some(child.clone())),
+      Some(_) => Err(crate::Error::ChildProcessNotOwned(pid)),
+      None => Ok(None),
+    }
+  }
+  /// The declaration of the window in the config, if any.
+  fn window_config(&self, label: &P::Label) -> Option<&WindowConfig> {
+    let label = label.to_string();
+    self
+      .inner
+      .config
+      .tauri
+      .windows
+      .iter()
+      .find(|config| config.label == label)
+  }
+  pub fn unlisten(&self, handler_id: EventHandler) {
+    self.inner.listeners.unlisten(handler_id)
+  }
+  pub fn unlisten_window(&self, handler_id: EventHandler, window: &P::Label) {
+    self.inner.listeners.unlisten_window(handler_id, window)
+  }
+  /// Records a JS event listener registered by the window.
+  pub(crate) fn register_js_listener(&self, window: P::Label, event_id: u64) {
+    self
+      .inner
+      .js_event_listeners
+      .lock()
+      .expect("poisoned js event listeners")
+      .entry(window)
+      .or_default()
+      .insert(event_id);
+  }
+  /// Forgets a JS event listener. Returns `false` if the window didn't register it.
+  pub(crate) fn unregister_js_listener(&self, window: &P::Label, event_id: u64) -> bool {
+    self
+      .inner
+      .js_event_listeners
+      .lock()
+      .expect("poisoned js event listeners")
+      .get_mut(window)
+      .map(|ids| ids.remove(&event_id))
+      .unwrap_or(false)
+  }
+  /// Posts a message to the target window, emitted as the `tauri://message` event.
+  ///
+  /// The buffers aren't serialized in the event: the target window reads them from the
+  /// `tauri-buffer` protocol.
+  pub fn post_message(
+    &self,
+    source: &P::Label,
+    target: &P::Label,
+    data: JsonValue,
+    buffers: Vec<Vec<u8>>,
+  ) -> crate::Result<()> {
+    let target_window = self
+      .get_window(target)
+      .ok_or(crate::Error::WebviewNotFound)?;
+    let buffers: Vec<(String, Vec<u8>)> = buffers
+      .into_iter()
+      .map(|buffer| (Uuid::new_v4().to_simple().to_string(), buffer))
+      .collect();
+    let ids = buffers.iter().map(|(id, _)| id.clone()).collect();
+    self.store_message_buffers(target, buffers);
+    target_window.emit_internal(
+      "tauri://message".to_string(),
+      Some(WindowMessage {
+        source: source.to_string(),
+        data,
+        buffers: ids,
+      }),
+    )
+  }
+
+  /// Stores buffers read once by the target window from the `tauri-buffer` protocol,
+  /// identified by the given ids. They're dropped when the window is destroyed.
+  pub(crate) fn store_message_buffers(&self, target: &P::Label, buffers: Vec<(String, Vec<u8>)>) {
+    if buffers.is_empty() {
+      return;
+    }
+    let mut store = self
+      .inner
+      .message_buffers
+      .lock()
+      .expect("poisoned message buffers");
+    for (id, buffer) in buffers {
+      store.insert(id, (target.clone(), buffer));
+    }
+  }
+  pub fn trigger(&self, event: P::Event, window: Option<P::Label>, data: Option<String>) {
+    self.record(|| RecordedEvent::Trigger {
+      window: window.as_ref().map(ToString::to_string),
+      event: event.to_string(),
+      data: data.clone(),
+    });
+    self.inner.listeners.trigger(event, window, data)
+  }
+  pub fn on_window_event<F: Fn(&WindowEvent) + Send + Sync + 'static>(
+    &self,
+    label: P::Label,
+    handler: F,
+  ) {
+    self
+      .inner
+      .window_event_listeners
+      .lock()
+      .expect("poisoned window event listeners")
+      .entry(label)
+      .or_insert_with(Vec::new)
+      .push(Arc::new(handler));
+  }
+  pub fn listen<F: Fn(Event) + Send + 'static>(
+    &self,
+    event: P::Event,
+    window: Option<P::Label>,
+    handler: F,
+  ) -> EventHandler {
+    self.inner.listeners.listen(event, window, handler)
+  }
+  pub fn once<F: Fn(Event) + Send + 'static>(
+    &self,
+    event: P::Event,
+    window: Option<P::Label>,
+    handler: F,
+  ) -> EventHandler {
+    self.inner.listeners.once(event, window, handler)
+  }
+  pub fn event_listeners_object_name(&self) -> String {
+    self.inner.listeners.listeners_object_name()
+  }
+  pub fn event_queue_object_name(&self) -> String {
+    self.inner.listeners.queue_object_name()
+  }
+  pub fn event_emit_function_name(&self) -> String {
+    self.inner.listeners.function_name()
+  }
+  pub fn generate_salt(&self) -> Uuid {
+    let salt = Uuid::new_v4();
+    self
+      .inner
+      .salts
+      .lock()
+      .expect("poisoned salt mutex")
+      .insert(salt);
+    salt
+  }
+  pub fn verify_salt(&self, salt: String) -> bool {
+    // flat out ignore any invalid uuids
+    let uuid: Uuid = match salt.parse() {
+      Ok(uuid) => uuid,
+      Err(_) => return false,
+    };
+
+    // HashSet::remove lets us know if the entry was found
+    self
+      .inner
+      .salts
+      .lock()
+      .expect("poisoned salt mutex")
+      .remove(&uuid)
+  }
+  /// Whether the navigation hook allows the window to navigate to the URL.
+  pub fn is_navigation_allowed(&self, window: &Window<P>, url: &str) -> bool {
+    (self.inner.on_navigation)(window, url)
+  }
+  pub fn start_recording<T: AsRef<Path>>(&self, path: T) -> crate::Result<()> {
+    let recorder = Recorder::new(path)?;
+    self
+      .inner
+      .recorder
+      .lock()
+      .expect("poisoned recorder")
+      .replace(recorder);
+    Ok(())
+  }
+  /// Writes a record if the recorder is enabled. The event is only created when recording.
+  pub fn record<F: FnOnce() -> RecordedEvent>(&self, event: F) {
+    let mut recorder = self.inner.recorder.lock().expect("poisoned recorder");
+    if let Some(recorder) = recorder.as_mut() {
+      if let Err(e) = recorder.record(event()) {
+        self.report_error(e);
+      }
+    }
+  }
+  /// Feeds the records back on a separate thread, keeping their original timing.
+  ///
+  /// The invokes of the [`SIDE_EFFECT_MODULES`] are skipped unless `side_effects` is set.
+  #[cfg(all(feature = "event-replay", debug_assertions))]
+  pub fn replay(&self, records: Vec<crate::runtime::recorder::Record>, side_effects: bool) {
+    let manager = self.clone();
+    std::thread::spawn(move || {
+      let started = Instant::now();
+      for record in records {
+        if !side_effects && has_side_effects(&record.event) {
+          continue;
+        }
+        if let Some(wait) = Duration::from_millis(record.elapsed).checked_sub(started.elapsed()) {
+          sleep(wait);
+        }
+        if let Err(e) = manager.replay_event(record.event) {
+          manager.report_error(e);
+        }
+      }
+    });
+  }
+  #[cfg(all(feature = "event-replay", debug_assertions))]
+  fn replay_event(&self, event: RecordedEvent) -> crate::Result<()> {
+    let find_window = |label: &str| {
+      label
+        .parse::<P::Label>()
+        .ok()
+        .and_then(|l| self.get_window(&l))
+    };
+    match event {
+      RecordedEvent::Invoke {
+        window,
+        command,
+        payload,
+      } => {
+        if let Some(window) = find_window(&window) {
+          window.on_message(command, serde_json::from_value(payload)?)?;
+        }
+      }
+      RecordedEvent::Emit {
+        window,
+        event,
+        payload,
+      } => {
+        if let Some(window) = find_window(&window) {
+          window.emit_internal(event, Some(payload))?;
+        }
+      }
+      RecordedEvent::Trigger {
+        window,
+        event,
+        data,
+      } => {
+        if let Ok(event) = event.parse() {
+          self.trigger(event, window.and_then(|l| l.parse().ok()), data);
+        }
+      }
+    }
+    Ok(())
+  }
+  pub fn get_window(&self, label: &P::Label) -> Option<Window<P>> {
+    self.windows_lock().get(label).cloned()
+  }
+  pub fn windows(&self) -> HashMap<P::Label, Window<P>> {
+    self.windows_lock().clone()
+  }
+}
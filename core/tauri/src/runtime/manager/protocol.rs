@@ -0,0 +1,122 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The custom protocols registered on the windows.
+
+use super::WindowManager;
+use crate::{
+  api::assets::Assets,
+  runtime::webview::{
+    mimetype_from_extension, CustomProtocol, CustomProtocolResponse, SchemePrivileges,
+  },
+  Params,
+};
+use std::{borrow::Cow, sync::Arc};
+
+impl<P: Params> WindowManager<P> {
+  /// The protocol serving the buffers of the window messages and invoke responses,
+  /// removing them once they're read.
+  ///
+  /// A window can only read the buffers sent to it, with the `label`.
+  pub(super) fn prepare_message_buffer_protocol(&self, label: P::Label) -> CustomProtocol {
+    let buffers = self.inner.message_buffers.clone();
+    CustomProtocol {
+      name: "tauri-buffer".into(),
+      handler: Arc::new(move |url| {
+        let id = url
+          .trim_start_matches("tauri-buffer://")
+          .trim_end_matches('/');
+        let mut buffers = buffers.lock().expect("poisoned message buffers");
+        match buffers.get(id) {
+          Some((target, _)) if target == &label => {}
+          _ => return Err(crate::Error::AssetNotFound(url.to_string())),
+        }
+        let (_, buffer) = buffers.remove(id).expect("buffer removed while locked");
+        Ok(CustomProtocolResponse::new(buffer).mimetype("application/octet-stream"))
+      }),
+      privileges: SchemePrivileges {
+        fetch_enabled: true,
+        cors_enabled: true,
+        ..Default::default()
+      },
+    }
+  }
+
+  /// The protocol serving the files in the asset scope, requested as
+  /// `asset://localhost/<percent-encoded path>`.
+  #[cfg(protocol_asset)]
+  pub(super) fn prepare_asset_protocol(&self) -> CustomProtocol {
+    let scope = self.inner.asset_protocol_scope.clone();
+    CustomProtocol {
+      name: "asset".into(),
+      handler: Arc::new(move |url| {
+        let path = url.trim_start_matches("asset://");
+        let path = path.trim_start_matches("localhost/");
+        // ignore query string
+        let path = percent_encoding::percent_decode_str(path.split('?').next().unwrap_or_default())
+          .decode_utf8_lossy()
+          .into_owned();
+        if !scope.is_allowed(&path) {
+          return Err(crate::Error::AssetNotFound(path));
+        }
+        let mimetype = mimetype_from_extension(
+          std::path::Path::new(&path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default(),
+        );
+        let body = std::fs::read(&path).map_err(|_| crate::Error::AssetNotFound(path))?;
+        Ok(CustomProtocolResponse::new(body).mimetype(mimetype))
+      }),
+      privileges: SchemePrivileges {
+        fetch_enabled: true,
+        ..Default::default()
+      },
+    }
+  }
+
+  pub(super) fn prepare_custom_protocol(&self) -> CustomProtocol {
+    let assets = self.inner.assets.clone();
+    let origin = self.app_origin();
+    CustomProtocol {
+      name: self.inner.config.tauri.security.scheme.clone(),
+      handler: Arc::new(move |path| {
+        let mut path = path
+          .split('?')
+          // ignore query string
+          .next()
+          .unwrap()
+          .to_string()
+          .replace(&origin, "");
+        if path.ends_with('/') {
+          path.pop();
+        }
+        let path = if path.is_empty() {
+          // if the url is the app origin, e.g. `tauri://${appId}`, we should load `index.html`
+          "index.html".to_string()
+        } else {
+          // skip leading `/`
+          path.chars().skip(1).collect::<String>()
+        };
+
+        let asset_response = assets
+          .get(&path)
+          .ok_or(crate::Error::AssetNotFound(path))
+          .map(Cow::into_owned);
+        match asset_response {
+          Ok(asset) => Ok(asset.into()),
+          Err(e) => {
+            #[cfg(debug_assertions)]
+            eprintln!("{:?}", e); // TODO log::error!
+            Err(e)
+          }
+        }
+      }),
+      privileges: SchemePrivileges {
+        service_workers: self.inner.config.tauri.security.service_workers,
+        ..SchemePrivileges::trusted()
+      },
+    }
+  }
+}
@@ -0,0 +1,132 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! What the windows can access: the scopes, the permission prompts and the allowlist
+//! of the API modules and commands.
+
+use super::WindowManager;
+use crate::{
+  api::config::PermissionPrompt,
+  scope::{FsScope, ShellScope},
+  Params,
+};
+
+/// The API modules used by the injected Tauri scripts, available regardless of the window allowlist.
+const ALWAYS_ALLOWED_MODULES: &[&str] = &["Event", "Internal"];
+
+impl<P: Params> WindowManager<P> {
+  pub fn asset_protocol_scope(&self) -> FsScope {
+    self.inner.asset_protocol_scope.clone()
+  }
+
+  pub fn fs_scope(&self) -> FsScope {
+    self.inner.fs_scope.clone()
+  }
+
+  pub fn shell_scope(&self) -> ShellScope {
+    self.inner.shell_scope.clone()
+  }
+
+  /// Asks the user to allow the window to use the target, a path or a command,
+  /// if the prompt is enabled on `tauri > security > permissionPrompts`.
+  ///
+  /// The answer is remembered until the window is closed. Returns `None` when the prompt isn't enabled.
+  pub fn request_permission(
+    &self,
+    label: &P::Label,
+    prompt: PermissionPrompt,
+    target: &str,
+  ) -> Option<bool> {
+    if !self
+      .inner
+      .config
+      .tauri
+      .security
+      .permission_prompts
+      .contains(&prompt)
+    {
+      return None;
+    }
+    let key = (label.clone(), prompt, target.to_string());
+    if let Some(allowed) = self
+      .inner
+      .permission_answers
+      .lock()
+      .expect("poisoned permission answers")
+      .get(&key)
+    {
+      return Some(*allowed);
+    }
+    let message = match prompt {
+      PermissionPrompt::FsReadOutsideScope => format!(
+        "The window `{}` wants to read `{}`, outside of the files the app can access. Allow it?",
+        label, target
+      ),
+      PermissionPrompt::FsWriteOutsideScope => format!(
+        "The window `{}` wants to write to `{}`, outside of the files the app can access. Allow it?",
+        label, target
+      ),
+      PermissionPrompt::ShellExecute => format!(
+        "The window `{}` wants to run the `{}` command. Allow it?",
+        label, target
+      ),
+    };
+    // the lock is released while the dialog waits for the user, so the other invokes go on
+    let allowed = crate::api::dialog::blocking::ask(&self.inner.package_info.name, message);
+    // an answer to the same prompt shown meanwhile wins, so the window gets a single answer
+    let allowed = *self
+      .inner
+      .permission_answers
+      .lock()
+      .expect("poisoned permission answers")
+      .entry(key)
+      .or_insert(allowed);
+    Some(allowed)
+  }
+
+  /// Whether the window can use the API module, according to its allowlist on the config.
+  pub(crate) fn is_module_allowed(&self, label: &P::Label, module: &str) -> bool {
+    if ALWAYS_ALLOWED_MODULES
+      .iter()
+      .any(|allowed| allowed.eq_ignore_ascii_case(module))
+    {
+      return true;
+    }
+    match self
+      .window_config(label)
+      .and_then(|config| config.allowlist.as_ref())
+    {
+      Some(allowlist) => allowlist
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(module)),
+      None => true,
+    }
+  }
+
+  /// Whether the window can invoke the app or plugin command, according to its config allowlist.
+  ///
+  /// An app command is listed as `command:<name>`, and a plugin as `plugin:<name>`
+  /// for all its commands or `plugin:<name>|<command>` for one of them.
+  pub(crate) fn is_command_allowed(&self, label: &P::Label, command: &str) -> bool {
+    let allowlist = match self
+      .window_config(label)
+      .and_then(|config| config.allowlist.as_ref())
+    {
+      Some(allowlist) => allowlist,
+      None => return true,
+    };
+    allowlist.iter().any(|allowed| {
+      if command.starts_with("plugin:") {
+        // `plugin:<name>|<command>`
+        let plugin = command.split('|').next().unwrap_or_default();
+        allowed == command || allowed == plugin
+      } else {
+        allowed
+          .strip_prefix("command:")
+          .map(|allowed| allowed == command)
+          .unwrap_or(false)
+      }
+    })
+  }
+}
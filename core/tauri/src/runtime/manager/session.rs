@@ -0,0 +1,260 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The session of the open windows and the geometry of the closed ones.
+
+use super::WindowManager;
+use crate::Params;
+#[cfg(feature = "window-state")]
+use crate::{
+  api::config::WindowUrl,
+  runtime::{
+    monitor::{PhysicalPosition, PhysicalSize},
+    webview::Attributes,
+    window::{DetachedWindow, PendingWindow},
+    Dispatch,
+  },
+  window_state::{read_window_store, write_window_store, WindowSession, WindowStore},
+};
+#[cfg(feature = "window-state")]
+use std::{
+  sync::atomic::Ordering,
+  thread::{sleep, spawn},
+  time::Duration,
+};
+#[cfg(feature = "window-state")]
+use url::Url;
+
+/// How long the session waits for the windows to settle before it's written,
+/// so a burst of page loads is written once.
+#[cfg(feature = "window-state")]
+const SESSION_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+impl<P: Params> WindowManager<P> {
+  pub fn set_window_url(&self, label: &P::Label, url: String) {
+    {
+      let mut urls = self.inner.window_urls.lock().expect("poisoned window urls");
+      match urls.iter_mut().find(|(l, _)| l == label) {
+        Some((_, current)) => *current = url,
+        None => urls.push((label.clone(), url)),
+      }
+    }
+    #[cfg(feature = "window-state")]
+    self.save_session();
+  }
+
+  /// The URL of the page loaded on the window.
+  pub fn window_url(&self, label: &P::Label) -> Option<String> {
+    self
+      .inner
+      .window_urls
+      .lock()
+      .expect("poisoned window urls")
+      .iter()
+      .find(|(l, _)| l == label)
+      .map(|(_, url)| url.clone())
+  }
+
+  pub fn remove_window_url(&self, label: &P::Label) {
+    self
+      .inner
+      .window_urls
+      .lock()
+      .expect("poisoned window urls")
+      .retain(|(l, _)| l != label);
+    #[cfg(feature = "window-state")]
+    self.save_session();
+  }
+
+  #[cfg(feature = "window-state")]
+  pub fn enable_session(&self) {
+    self.inner.session_enabled.store(true, Ordering::SeqCst);
+  }
+
+  /// Writes the session once the windows settle, unless a write is already scheduled.
+  #[cfg(feature = "window-state")]
+  pub fn save_session(&self) {
+    if !self.inner.session_enabled.load(Ordering::SeqCst)
+      || self.inner.exiting.load(Ordering::SeqCst)
+      || self
+        .inner
+        .session_save_scheduled
+        .swap(true, Ordering::SeqCst)
+    {
+      return;
+    }
+    let manager = self.clone();
+    spawn(move || {
+      sleep(SESSION_SAVE_DELAY);
+      manager.flush_session();
+    });
+  }
+
+  /// Writes the session right away, e.g. before the application exits.
+  #[cfg(feature = "window-state")]
+  pub fn flush_session(&self) {
+    if !self.inner.session_enabled.load(Ordering::SeqCst) {
+      return;
+    }
+    self
+      .inner
+      .session_save_scheduled
+      .store(false, Ordering::SeqCst);
+    let session = self
+      .inner
+      .window_urls
+      .lock()
+      .expect("poisoned window urls")
+      .iter()
+      .map(|(label, url)| WindowSession {
+        label: label.to_string(),
+        url: url.clone(),
+      })
+      .collect();
+    self.update_window_store(|store| store.session = session);
+  }
+
+  /// Reads the window state file and writes it back with the changes.
+  #[cfg(feature = "window-state")]
+  fn update_window_store<F: FnOnce(&mut WindowStore)>(&self, update: F) {
+    let _lock = self
+      .inner
+      .window_store_lock
+      .lock()
+      .expect("poisoned window store lock");
+    let result = read_window_store(self.config()).and_then(|mut store| {
+      update(&mut store);
+      write_window_store(self.config(), &store)
+    });
+    if let Err(e) = result {
+      self.report_error(e);
+    }
+  }
+
+  /// Applies the geometry the window had when it was last closed.
+  #[cfg(feature = "window-state")]
+  pub(super) fn restore_window_state(&self, mut pending: PendingWindow<P>) -> PendingWindow<P> {
+    let store = match read_window_store(self.config()) {
+      Ok(store) => store,
+      Err(e) => {
+        self.report_error(e);
+        return pending;
+      }
+    };
+    if let Some(state) = store.states.get(&pending.label.to_string()) {
+      pending.attributes = pending
+        .attributes
+        .position(PhysicalPosition::new(state.x, state.y))
+        .size(PhysicalSize::new(state.width, state.height))
+        .maximized(state.maximized);
+    }
+    pending
+  }
+
+  /// Saves the window geometry, ignored when the runtime can't read it.
+  #[cfg(feature = "window-state")]
+  pub(super) fn save_window_state(&self, window: &DetachedWindow<P>) {
+    let dispatcher = &window.dispatcher;
+    let geometry = dispatcher.outer_size().and_then(|size| {
+      let position = dispatcher.outer_position()?;
+      Ok((size, position, dispatcher.is_maximized()?))
+    });
+    let (size, position, maximized) = match geometry {
+      Ok(geometry) => geometry,
+      Err(_) => return,
+    };
+    self.update_window_store(|store| {
+      store
+        .states
+        .entry(window.label.to_string())
+        .or_default()
+        .update(size.width, size.height, position.x, position.y, maximized)
+    });
+  }
+
+  /// Converts a URL recorded on the session back to the [`WindowUrl`] used to create the window.
+  ///
+  /// The URLs of the application, i.e. with the origin it's served from, are made relative to it.
+  #[cfg(feature = "window-state")]
+  pub fn session_window_url(&self, url: &str) -> WindowUrl {
+    let url = match Url::parse(url) {
+      Ok(url) => url,
+      Err(_) => return WindowUrl::default(),
+    };
+    // the origin of the custom schemes is opaque, so its parts are compared instead
+    let is_app_url = Url::parse(&self.get_url())
+      .map(|base| {
+        base.scheme() == url.scheme()
+          && base.host_str() == url.host_str()
+          && base.port_or_known_default() == url.port_or_known_default()
+      })
+      .unwrap_or(false);
+    if !is_app_url {
+      return WindowUrl::External(url);
+    }
+    let mut path = url.path().trim_start_matches('/').to_string();
+    if let Some(query) = url.query() {
+      path.push('?');
+      path.push_str(query);
+    }
+    if let Some(fragment) = url.fragment() {
+      path.push('#');
+      path.push_str(fragment);
+    }
+    if path.is_empty() {
+      WindowUrl::default()
+    } else {
+      WindowUrl::App(path.into())
+    }
+  }
+}
+
+#[cfg(all(test, feature = "window-state"))]
+mod test {
+  use crate::{
+    api::config::WindowUrl,
+    generate_context,
+    plugin::PluginStore,
+    runtime::{
+      flavors::wry::Wry,
+      manager::{Args, WindowManager},
+    },
+  };
+
+  #[test]
+  fn check_session_window_url() {
+    let context = generate_context!("test/fixture/src-tauri/tauri.conf.json", crate);
+    let manager: WindowManager<Args<String, String, _, Wry>> = WindowManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Box::new(|_, _| true),
+      Box::new(|_| ()),
+      Box::new(|_, _| ()),
+      Default::default(),
+      Default::default(),
+    );
+
+    let base = manager.get_url();
+    assert_eq!(manager.session_window_url(&base), WindowUrl::default());
+    assert_eq!(
+      manager.session_window_url(&format!("{}/settings.html", base)),
+      WindowUrl::App("settings.html".into())
+    );
+    assert_eq!(
+      manager.session_window_url(&format!("{}/index.html#/editor", base)),
+      WindowUrl::App("index.html#/editor".into())
+    );
+    // the origin must match, not only the beginning of the URL
+    assert!(matches!(
+      manager.session_window_url(&format!("{}.evil.com/index.html", base)),
+      WindowUrl::External(_)
+    ));
+    assert!(matches!(
+      manager.session_window_url("https://tauri.studio/"),
+      WindowUrl::External(_)
+    ));
+  }
+}
@@ -0,0 +1,187 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Menus and the system tray.
+
+use crate::{
+  runtime::{window::DetachedWindow, Params},
+  Icon,
+};
+use serde::Serialize;
+
+/// A menu item with an identifier, emitted when the item is clicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomMenuItem {
+  /// The identifier of the item, sent on its click events.
+  pub id: String,
+  /// The text shown on the item.
+  pub title: String,
+  /// Whether the item can be clicked.
+  pub enabled: bool,
+}
+
+impl CustomMenuItem {
+  /// Creates an enabled menu item.
+  pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+    Self {
+      id: id.into(),
+      title: title.into(),
+      enabled: true,
+    }
+  }
+
+  /// Disables the item.
+  pub fn disabled(mut self) -> Self {
+    self.enabled = false;
+    self
+  }
+}
+
+/// A menu entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuItem {
+  /// A clickable item.
+  Custom(CustomMenuItem),
+  /// A line separating groups of items.
+  Separator,
+  /// The native "About" item, showing the given application name.
+  About(String),
+  /// The native item that hides the application.
+  Hide,
+  /// The native item that quits the application.
+  Quit,
+  /// The native item that closes the window.
+  CloseWindow,
+  /// The native item that minimizes the window.
+  Minimize,
+  /// The native "Undo" item.
+  Undo,
+  /// The native "Redo" item.
+  Redo,
+  /// The native "Cut" item.
+  Cut,
+  /// The native "Copy" item.
+  Copy,
+  /// The native "Paste" item.
+  Paste,
+  /// The native "Select All" item.
+  SelectAll,
+}
+
+impl From<CustomMenuItem> for MenuItem {
+  fn from(item: CustomMenuItem) -> Self {
+    Self::Custom(item)
+  }
+}
+
+/// A submenu of the window menu bar, e.g. `File`, `Edit` or `Help`.
+///
+/// Clicking a [`CustomMenuItem`] emits the `tauri://menu` event on its window with a [`MenuEvent`] payload,
+/// while the native items are handled by the operating system.
+///
+/// # Example
+/// ```
+/// use tauri::runtime::menu::{CustomMenuItem, Menu, MenuItem};
+///
+/// let menu = vec![
+///   Menu::new("File")
+///     .add_item(CustomMenuItem::new("open", "Open..."))
+///     .add_item(MenuItem::Separator)
+///     .add_item(MenuItem::Quit),
+///   Menu::new("Edit")
+///     .add_item(MenuItem::Copy)
+///     .add_item(MenuItem::Paste),
+/// ];
+/// # assert_eq!(menu.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Menu {
+  /// The text shown on the menu bar.
+  pub title: String,
+  /// The menu items.
+  pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+  /// Creates an empty menu.
+  pub fn new(title: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      items: Vec::new(),
+    }
+  }
+
+  /// Adds an item to the menu.
+  pub fn add_item<I: Into<MenuItem>>(mut self, item: I) -> Self {
+    self.items.push(item.into());
+    self
+  }
+}
+
+/// The payload of the `tauri://menu` event, emitted when a window menu item is clicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuEvent {
+  /// The [`CustomMenuItem::id`] of the clicked item.
+  pub menu_item_id: String,
+}
+
+/// How to handle a click on one of the menu items of a window.
+pub(crate) type MenuEventHandler<M> = Box<dyn Fn(MenuEvent, DetachedWindow<M>) + Send>;
+
+/// The application icon on the system tray, with its context menu.
+pub struct SystemTray {
+  /// The tray icon.
+  pub icon: Icon,
+  /// The context menu items.
+  pub menu: Vec<MenuItem>,
+}
+
+impl SystemTray {
+  /// Creates a system tray icon without context menu.
+  pub fn new(icon: Icon) -> Self {
+    Self {
+      icon,
+      menu: Vec::new(),
+    }
+  }
+
+  /// Adds an item to the context menu.
+  pub fn with_item<I: Into<MenuItem>>(mut self, item: I) -> Self {
+    self.menu.push(item.into());
+    self
+  }
+}
+
+/// An event triggered by the system tray icon.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SystemTrayEvent {
+  /// The icon was clicked with the primary button.
+  LeftClick {
+    /// The cursor position on the virtual desktop.
+    x: f64,
+    /// The cursor position on the virtual desktop.
+    y: f64,
+  },
+  /// The icon was clicked with the secondary button.
+  RightClick {
+    /// The cursor position on the virtual desktop.
+    x: f64,
+    /// The cursor position on the virtual desktop.
+    y: f64,
+  },
+  /// The icon was double clicked.
+  DoubleClick {
+    /// The cursor position on the virtual desktop.
+    x: f64,
+    /// The cursor position on the virtual desktop.
+    y: f64,
+  },
+  /// An item of the context menu was clicked.
+  MenuItemClick {
+    /// The [`CustomMenuItem::id`] of the item.
+    id: String,
+  },
+}
@@ -6,36 +6,126 @@
 
 use crate::{
   runtime::{
+    menu::{Menu, MenuItem, SystemTray, SystemTrayEvent},
+    monitor::{Monitor, PhysicalPosition, PhysicalSize, Position, Size},
+    tag::Tag,
     webview::AttributesBase,
-    window::{DetachedWindow, PendingWindow},
+    window::{CursorIcon, DetachedWindow, PendingWindow},
   },
   Attributes, Icon, Params,
 };
-use std::convert::TryFrom;
+use serde_json::Value as JsonValue;
+use std::{
+  convert::TryFrom,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+};
 
 pub(crate) mod app;
 pub mod flavors;
+#[cfg(global_shortcut_all)]
+pub mod global_shortcut;
+#[cfg(feature = "test-automation")]
+pub mod input;
 pub(crate) mod manager;
+pub mod menu;
+pub mod monitor;
+pub mod recorder;
 pub mod tag;
 pub mod webview;
 pub mod window;
 
+/// An event of the application lifecycle, see [`crate::App::run`].
+#[derive(Debug, Clone)]
+pub enum RunEvent<L: Tag> {
+  /// The runtime started its event loop.
+  Ready,
+  /// A window was destroyed.
+  WindowClosed(L),
+  /// The last window was destroyed and the application is about to exit.
+  ///
+  /// The application exits after the callback runs, unless it calls
+  /// [`ExitRequestApi::prevent_exit`], e.g. to keep running in the system tray.
+  ExitRequested(ExitRequestApi),
+  /// The application is exiting.
+  Exit,
+}
+
+/// Lets a [`RunEvent::ExitRequested`] callback keep the application running.
+#[derive(Debug, Clone, Default)]
+pub struct ExitRequestApi(Arc<AtomicBool>);
+
+impl ExitRequestApi {
+  /// Keeps the application running without windows.
+  pub fn prevent_exit(&self) {
+    self.0.store(true, Ordering::SeqCst);
+  }
+
+  /// Whether the callback prevented the exit.
+  pub fn is_exit_prevented(&self) -> bool {
+    self.0.load(Ordering::SeqCst)
+  }
+}
+
 /// The webview runtime interface.
 pub trait Runtime: Sized + 'static {
   /// The message dispatcher.
   type Dispatcher: Dispatch<Runtime = Self>;
 
+  /// The runtime handle type.
+  type Handle: RuntimeHandle<Runtime = Self>;
+
   /// Creates a new webview runtime.
   fn new() -> crate::Result<Self>;
 
+  /// Gets a runtime handle.
+  fn handle(&self) -> Self::Handle;
+
   /// Create a new webview window.
   fn create_window<P: Params<Runtime = Self>>(
     &mut self,
     pending: PendingWindow<P>,
   ) -> crate::Result<DetachedWindow<P>>;
 
-  /// Run the webview runtime.
-  fn run(self);
+  /// Adds the application icon to the system tray.
+  fn system_tray(&mut self, system_tray: SystemTray) -> crate::Result<()>;
+
+  /// Registers the handler of the system tray events.
+  fn on_system_tray_event<F: Fn(&SystemTrayEvent) + Send + 'static>(&mut self, handler: F);
+
+  /// Run the webview runtime, sending [`RunEvent::Ready`] to the callback once it starts.
+  ///
+  /// The window and exit events are sent by Tauri when the windows are destroyed.
+  fn run<L: Tag, F: Fn(RunEvent<L>) + Send + Sync + 'static>(self, callback: F);
+}
+
+/// A thread-safe handle to the runtime, not associated with any window.
+pub trait RuntimeHandle: Clone + Send + Sized + 'static {
+  /// The runtime this [`RuntimeHandle`] runs under.
+  type Runtime: Runtime<Handle = Self>;
+
+  /// Create a new webview window.
+  fn create_window<P: Params<Runtime = Self::Runtime>>(
+    &self,
+    pending: PendingWindow<P>,
+  ) -> crate::Result<DetachedWindow<P>>;
+
+  /// Gets the position of the mouse cursor on the virtual desktop.
+  fn cursor_position(&self) -> crate::Result<PhysicalPosition>;
+
+  /// Lists the monitors connected to the system.
+  fn available_monitors(&self) -> crate::Result<Vec<Monitor>>;
+
+  /// Gets the primary monitor of the system.
+  fn primary_monitor(&self) -> crate::Result<Option<Monitor>>;
+
+  /// Updates the system tray icon.
+  fn set_tray_icon(&self, icon: Icon) -> crate::Result<()>;
+
+  /// Updates the system tray context menu.
+  fn set_tray_menu(&self, menu: Vec<MenuItem>) -> crate::Result<()>;
 }
 
 /// Webview dispatcher. A thread-safe handle to the webview API.
@@ -49,6 +139,9 @@ pub trait Dispatch: Clone + Send + Sized + 'static {
   /// The webview builder type.
   type Attributes: Attributes<Icon = Self::Icon> + AttributesBase + Clone + Send;
 
+  /// Gets a handle to the runtime this window belongs to.
+  fn runtime_handle(&self) -> <Self::Runtime as Runtime>::Handle;
+
   /// Create a new webview window.
   fn create_window<P: Params<Runtime = Self::Runtime>>(
     &mut self,
@@ -58,6 +151,9 @@ pub trait Dispatch: Clone + Send + Sized + 'static {
   /// Updates the window resizable flag.
   fn set_resizable(&self, resizable: bool) -> crate::Result<()>;
 
+  /// Updates the window menu bar.
+  fn set_menu(&self, menu: Vec<Menu>) -> crate::Result<()>;
+
   /// Updates the window title.
   fn set_title<S: Into<String>>(&self, title: S) -> crate::Result<()>;
 
@@ -88,29 +184,44 @@ pub trait Dispatch: Clone + Send + Sized + 'static {
   /// Updates the window alwaysOnTop flag.
   fn set_always_on_top(&self, always_on_top: bool) -> crate::Result<()>;
 
-  /// Updates the window width.
-  fn set_width(&self, width: f64) -> crate::Result<()>;
+  /// The factor that maps logical pixels to the physical pixels of the window's monitor.
+  fn scale_factor(&self) -> crate::Result<f64>;
+
+  /// The size of the window's client area, i.e. without its decorations.
+  fn inner_size(&self) -> crate::Result<PhysicalSize>;
+
+  /// The size of the whole window, including its decorations.
+  fn outer_size(&self) -> crate::Result<PhysicalSize>;
+
+  /// The position of the top left corner of the window's client area on the desktop.
+  fn inner_position(&self) -> crate::Result<PhysicalPosition>;
+
+  /// The position of the top left corner of the window on the desktop.
+  fn outer_position(&self) -> crate::Result<PhysicalPosition>;
 
-  /// Updates the window height.
-  fn set_height(&self, height: f64) -> crate::Result<()>;
+  /// Whether the window is maximized.
+  fn is_maximized(&self) -> crate::Result<bool>;
+
+  /// Whether the window is fullscreen.
+  fn is_fullscreen(&self) -> crate::Result<bool>;
+
+  /// Whether the window is visible.
+  fn is_visible(&self) -> crate::Result<bool>;
+
+  /// Whether the window is decorated.
+  fn is_decorated(&self) -> crate::Result<bool>;
 
   /// Resizes the window.
-  fn resize(&self, width: f64, height: f64) -> crate::Result<()>;
+  fn set_size(&self, size: Size) -> crate::Result<()>;
 
   /// Updates the window min size.
-  fn set_min_size(&self, min_width: f64, min_height: f64) -> crate::Result<()>;
+  fn set_min_size(&self, min_size: Size) -> crate::Result<()>;
 
   /// Updates the window max size.
-  fn set_max_size(&self, max_width: f64, max_height: f64) -> crate::Result<()>;
-
-  /// Updates the X position.
-  fn set_x(&self, x: f64) -> crate::Result<()>;
-
-  /// Updates the Y position.
-  fn set_y(&self, y: f64) -> crate::Result<()>;
+  fn set_max_size(&self, max_size: Size) -> crate::Result<()>;
 
   /// Updates the window position.
-  fn set_position(&self, x: f64, y: f64) -> crate::Result<()>;
+  fn set_position(&self, position: Position) -> crate::Result<()>;
 
   /// Updates the window fullscreen state.
   fn set_fullscreen(&self, fullscreen: bool) -> crate::Result<()>;
@@ -118,6 +229,49 @@ pub trait Dispatch: Clone + Send + Sized + 'static {
   /// Updates the window icon.
   fn set_icon(&self, icon: Self::Icon) -> crate::Result<()>;
 
+  /// Sets the identifier used to group the window with others as native tabs. Only supported on macOS.
+  fn set_tabbing_identifier(&self, identifier: Option<String>) -> crate::Result<()>;
+
+  /// Merges all windows sharing this window's tabbing identifier into its tab group.
+  fn merge_all_windows(&self) -> crate::Result<()>;
+
+  /// Selects the next tab of the window's tab group.
+  fn select_next_tab(&self) -> crate::Result<()>;
+
+  /// Selects the previous tab of the window's tab group.
+  fn select_previous_tab(&self) -> crate::Result<()>;
+
+  /// Moves the window out of its tab group into a standalone window.
+  fn move_tab_to_new_window(&self) -> crate::Result<()>;
+
+  /// Opens the webview dev tools.
+  fn open_devtools(&self) -> crate::Result<()>;
+
+  /// Closes the webview dev tools.
+  fn close_devtools(&self) -> crate::Result<()>;
+
+  /// Shows or hides the cursor while it's over the window.
+  fn set_cursor_visible(&self, visible: bool) -> crate::Result<()>;
+
+  /// Confines the cursor to the window.
+  fn set_cursor_grab(&self, grab: bool) -> crate::Result<()>;
+
+  /// Updates the cursor icon shown over the window.
+  fn set_cursor_icon(&self, icon: CursorIcon) -> crate::Result<()>;
+
+  /// Moves the cursor to a position relative to the top left corner of the window.
+  fn set_cursor_position(&self, position: Position) -> crate::Result<()>;
+
   /// Executes javascript on the window this [`Dispatch`] represents.
   fn eval_script<S: Into<String>>(&self, script: S) -> crate::Result<()>;
+
+  /// Whether the invoke responses are given to [`Dispatch::capture_invoke_response`]
+  /// instead of being evaluated on the webview, e.g. by a runtime without webview.
+  fn captures_invoke_responses(&self) -> bool {
+    false
+  }
+
+  /// Receives the response of the invoke identified by its success callback,
+  /// see [`Dispatch::captures_invoke_responses`].
+  fn capture_invoke_response(&self, _callback: String, _response: Result<JsonValue, JsonValue>) {}
 }
@@ -0,0 +1,239 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Screen geometry types.
+//!
+//! Physical pixels are the pixels of the monitor, while logical pixels are scaled by its scale factor,
+//! so a logical size looks the same on monitors of different densities.
+//! A window moved to a monitor with another scale factor keeps its logical size.
+
+use serde::{Deserialize, Serialize};
+
+/// A position on the screen, in physical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhysicalPosition {
+  /// The horizontal coordinate.
+  pub x: f64,
+  /// The vertical coordinate.
+  pub y: f64,
+}
+
+impl PhysicalPosition {
+  /// Creates a position from its physical coordinates.
+  pub fn new(x: f64, y: f64) -> Self {
+    Self { x, y }
+  }
+
+  /// Converts the position to logical pixels.
+  pub fn to_logical(self, scale_factor: f64) -> LogicalPosition {
+    LogicalPosition::new(self.x / scale_factor, self.y / scale_factor)
+  }
+}
+
+/// A position on the screen, in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogicalPosition {
+  /// The horizontal coordinate.
+  pub x: f64,
+  /// The vertical coordinate.
+  pub y: f64,
+}
+
+impl LogicalPosition {
+  /// Creates a position from its logical coordinates.
+  pub fn new(x: f64, y: f64) -> Self {
+    Self { x, y }
+  }
+
+  /// Converts the position to physical pixels.
+  pub fn to_physical(self, scale_factor: f64) -> PhysicalPosition {
+    PhysicalPosition::new(self.x * scale_factor, self.y * scale_factor)
+  }
+}
+
+/// A position in either physical or logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum Position {
+  /// A position in physical pixels.
+  Physical(PhysicalPosition),
+  /// A position in logical pixels.
+  Logical(LogicalPosition),
+}
+
+impl Position {
+  /// The position in physical pixels, converting it with the scale factor if needed.
+  pub fn to_physical(self, scale_factor: f64) -> PhysicalPosition {
+    match self {
+      Self::Physical(position) => position,
+      Self::Logical(position) => position.to_physical(scale_factor),
+    }
+  }
+
+  /// The position in logical pixels, converting it with the scale factor if needed.
+  pub fn to_logical(self, scale_factor: f64) -> LogicalPosition {
+    match self {
+      Self::Physical(position) => position.to_logical(scale_factor),
+      Self::Logical(position) => position,
+    }
+  }
+}
+
+impl From<PhysicalPosition> for Position {
+  fn from(position: PhysicalPosition) -> Self {
+    Self::Physical(position)
+  }
+}
+
+impl From<LogicalPosition> for Position {
+  fn from(position: LogicalPosition) -> Self {
+    Self::Logical(position)
+  }
+}
+
+/// A size on the screen, in physical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhysicalSize {
+  /// The width.
+  pub width: f64,
+  /// The height.
+  pub height: f64,
+}
+
+impl PhysicalSize {
+  /// Creates a size from its physical dimensions.
+  pub fn new(width: f64, height: f64) -> Self {
+    Self { width, height }
+  }
+
+  /// Converts the size to logical pixels.
+  pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+    LogicalSize::new(self.width / scale_factor, self.height / scale_factor)
+  }
+}
+
+/// A size on the screen, in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogicalSize {
+  /// The width.
+  pub width: f64,
+  /// The height.
+  pub height: f64,
+}
+
+impl LogicalSize {
+  /// Creates a size from its logical dimensions.
+  pub fn new(width: f64, height: f64) -> Self {
+    Self { width, height }
+  }
+
+  /// Converts the size to physical pixels.
+  pub fn to_physical(self, scale_factor: f64) -> PhysicalSize {
+    PhysicalSize::new(self.width * scale_factor, self.height * scale_factor)
+  }
+}
+
+/// A size in either physical or logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum Size {
+  /// A size in physical pixels.
+  Physical(PhysicalSize),
+  /// A size in logical pixels.
+  Logical(LogicalSize),
+}
+
+impl Size {
+  /// The size in physical pixels, converting it with the scale factor if needed.
+  pub fn to_physical(self, scale_factor: f64) -> PhysicalSize {
+    match self {
+      Self::Physical(size) => size,
+      Self::Logical(size) => size.to_physical(scale_factor),
+    }
+  }
+
+  /// The size in logical pixels, converting it with the scale factor if needed.
+  pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+    match self {
+      Self::Physical(size) => size.to_logical(scale_factor),
+      Self::Logical(size) => size,
+    }
+  }
+}
+
+impl From<PhysicalSize> for Size {
+  fn from(size: PhysicalSize) -> Self {
+    Self::Physical(size)
+  }
+}
+
+impl From<LogicalSize> for Size {
+  fn from(size: LogicalSize) -> Self {
+    Self::Logical(size)
+  }
+}
+
+/// A monitor (screen) connected to the system.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Monitor {
+  /// A human-readable name of the monitor, if available.
+  pub name: Option<String>,
+  /// The top left corner of the monitor on the virtual desktop.
+  pub position: PhysicalPosition,
+  /// The monitor resolution.
+  pub size: PhysicalSize,
+  /// The top left corner of the area not covered by the taskbar, dock or menu bar.
+  pub work_area_position: PhysicalPosition,
+  /// The size of the area not covered by the taskbar, dock or menu bar.
+  pub work_area_size: PhysicalSize,
+  /// The factor that maps logical pixels to the physical pixels of this monitor.
+  pub scale_factor: f64,
+}
+
+impl Monitor {
+  /// Whether the given position is inside this monitor.
+  pub fn contains(&self, position: PhysicalPosition) -> bool {
+    position.x >= self.position.x
+      && position.y >= self.position.y
+      && position.x < self.position.x + self.size.width
+      && position.y < self.position.y + self.size.height
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn monitor_contains() {
+    let monitor = Monitor {
+      name: None,
+      position: PhysicalPosition::new(1920., 0.),
+      size: PhysicalSize::new(1280., 1024.),
+      work_area_position: PhysicalPosition::new(1920., 0.),
+      work_area_size: PhysicalSize::new(1280., 984.),
+      scale_factor: 1.,
+    };
+    assert!(monitor.contains(PhysicalPosition::new(1920., 0.)));
+    assert!(monitor.contains(PhysicalPosition::new(3199., 1023.)));
+    assert!(!monitor.contains(PhysicalPosition::new(1919., 10.)));
+    assert!(!monitor.contains(PhysicalPosition::new(3200., 10.)));
+  }
+
+  #[test]
+  fn converts_units() {
+    let size = Size::from(LogicalSize::new(800., 600.));
+    assert_eq!(size.to_physical(2.), PhysicalSize::new(1600., 1200.));
+    assert_eq!(size.to_logical(2.), LogicalSize::new(800., 600.));
+    let position = Position::from(PhysicalPosition::new(300., 150.));
+    assert_eq!(position.to_logical(1.5), LogicalPosition::new(200., 100.));
+    assert_eq!(position.to_physical(1.5), PhysicalPosition::new(300., 150.));
+    assert_eq!(
+      serde_json::from_str::<Size>(r#"{"type":"Logical","data":{"width":800,"height":600}}"#)
+        .unwrap(),
+      size
+    );
+  }
+}
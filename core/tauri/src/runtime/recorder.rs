@@ -0,0 +1,143 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Recording of the messages exchanged between the webviews and the core.
+//!
+//! A recording is a [JSON Lines](https://jsonlines.org) file where each line is a [`Record`].
+//! It is written when [`Builder::record_events`](crate::Builder::record_events) is set
+//! and fed back with [`App::replay_events`](crate::App::replay_events), so race conditions
+//! reported by users can be reproduced on the developer machine.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+  fs::File,
+  io::{BufRead, BufReader, BufWriter, Write},
+  path::Path,
+  time::Instant,
+};
+
+/// A message exchanged between a webview and the core.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RecordedEvent {
+  /// A command invoked by a window.
+  Invoke {
+    /// The label of the window that invoked the command.
+    window: String,
+    /// The invoked command.
+    command: String,
+    /// The invoke payload, including the callback identifiers.
+    payload: JsonValue,
+  },
+  /// An event emitted to a window.
+  Emit {
+    /// The label of the window that received the event.
+    window: String,
+    /// The event name.
+    event: String,
+    /// The event payload.
+    payload: JsonValue,
+  },
+  /// An event triggered on the Rust listeners.
+  Trigger {
+    /// The label of the window the event was triggered on, if any.
+    window: Option<String>,
+    /// The event name.
+    event: String,
+    /// The event data.
+    data: Option<String>,
+  },
+}
+
+/// A line of a recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+  /// Milliseconds elapsed since the recording started.
+  pub elapsed: u64,
+  /// The recorded message.
+  #[serde(flatten)]
+  pub event: RecordedEvent,
+}
+
+/// Reads the records of a recording file, in recording order.
+pub fn read_recording<P: AsRef<Path>>(path: P) -> crate::Result<Vec<Record>> {
+  let reader = BufReader::new(File::open(path)?);
+  let mut records = Vec::new();
+  for line in reader.lines() {
+    let line = line?;
+    if !line.trim().is_empty() {
+      records.push(serde_json::from_str(&line)?);
+    }
+  }
+  Ok(records)
+}
+
+/// Writes the records to a recording file.
+pub(crate) struct Recorder {
+  writer: BufWriter<File>,
+  started: Instant,
+}
+
+impl Recorder {
+  /// Creates the recording file, truncating it if it exists.
+  pub(crate) fn new<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+    Ok(Self {
+      writer: BufWriter::new(File::create(path)?),
+      started: Instant::now(),
+    })
+  }
+
+  /// Appends a record. The file is flushed after each record so a crash doesn't lose it.
+  pub(crate) fn record(&mut self, event: RecordedEvent) -> crate::Result<()> {
+    let record = Record {
+      elapsed: self.started.elapsed().as_millis() as u64,
+      event,
+    };
+    serde_json::to_writer(&mut self.writer, &record)?;
+    self.writer.write_all(b"\n")?;
+    self.writer.flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn writes_and_reads_recording() {
+    let path = std::env::temp_dir().join(format!("tauri-recording-{}.jsonl", uuid::Uuid::new_v4()));
+    let events = vec![
+      RecordedEvent::Invoke {
+        window: "main".into(),
+        command: "save".into(),
+        payload: serde_json::json!({ "callback": "_1", "error": "_2", "path": "a.txt" }),
+      },
+      RecordedEvent::Emit {
+        window: "main".into(),
+        event: "saved".into(),
+        payload: JsonValue::Null,
+      },
+      RecordedEvent::Trigger {
+        window: None,
+        event: "exit".into(),
+        data: Some("0".into()),
+      },
+    ];
+
+    let mut recorder = Recorder::new(&path).expect("failed to create recording");
+    for event in events.clone() {
+      recorder.record(event).expect("failed to write record");
+    }
+    drop(recorder);
+
+    let records = read_recording(&path).expect("failed to read recording");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+      records.into_iter().map(|r| r.event).collect::<Vec<_>>(),
+      events
+    );
+  }
+}
@@ -4,10 +4,15 @@
 
 //! Items specific to the [`Runtime`](crate::runtime::Runtime)'s webview.
 
-use crate::runtime::Icon;
+use crate::runtime::{
+  menu::Menu,
+  monitor::{Position, Size},
+  Icon,
+};
 use crate::{api::config::WindowConfig, runtime::window::DetachedWindow};
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::{convert::TryFrom, path::PathBuf};
+use std::{collections::HashMap, convert::TryFrom, path::PathBuf, sync::Arc};
 
 /// Do **NOT** implement this trait except for use in a custom [`Runtime`](crate::runtime::Runtime).
 ///
@@ -31,29 +36,24 @@ pub trait Attributes: AttributesBase {
   /// Sets the init script.
   fn initialization_script(self, init: &str) -> Self;
 
-  /// The horizontal position of the window's top left corner.
-  fn x(self, x: f64) -> Self;
+  /// Sets an init script running in an isolated script world, so the page scripts can't
+  /// override the globals it relies on.
+  ///
+  /// Runtimes without isolated worlds run it like [`Attributes::initialization_script`],
+  /// and the manager freezes the Tauri globals it defines as a fallback.
+  fn isolated_initialization_script(self, init: &str) -> Self;
 
-  /// The vertical position of the window's top left corner.
-  fn y(self, y: f64) -> Self;
+  /// The position of the window's top left corner.
+  fn position<P: Into<Position>>(self, position: P) -> Self;
 
-  /// Window width.
-  fn width(self, width: f64) -> Self;
+  /// Window size.
+  fn size<S: Into<Size>>(self, size: S) -> Self;
 
-  /// Window height.
-  fn height(self, height: f64) -> Self;
+  /// Window min size.
+  fn min_size<S: Into<Size>>(self, min_size: S) -> Self;
 
-  /// Window min width.
-  fn min_width(self, min_width: f64) -> Self;
-
-  /// Window min height.
-  fn min_height(self, min_height: f64) -> Self;
-
-  /// Window max width.
-  fn max_width(self, max_width: f64) -> Self;
-
-  /// Window max height.
-  fn max_height(self, max_height: f64) -> Self;
+  /// Window max size.
+  fn max_size<S: Into<Size>>(self, max_size: S) -> Self;
 
   /// Whether the window is resizable or not.
   fn resizable(self, resizable: bool) -> Self;
@@ -80,6 +80,9 @@ pub trait Attributes: AttributesBase {
   /// Whether the window should always be on top of other windows.
   fn always_on_top(self, always_on_top: bool) -> Self;
 
+  /// Sets the window menu bar.
+  fn menu(self, menu: Vec<Menu>) -> Self;
+
   /// Sets the window icon.
   fn icon(self, icon: Self::Icon) -> Self;
 
@@ -89,6 +92,20 @@ pub trait Attributes: AttributesBase {
   /// User data path for the webview. Actually only supported on Windows.
   fn user_data_path(self, user_data_path: Option<PathBuf>) -> Self;
 
+  /// The proxy the webview sends its network requests through, e.g. `http://proxy.local:3128`.
+  ///
+  /// Runtimes that can't set it fail to create the window
+  /// with [`crate::Error::UnsupportedOperation`] rather than bypassing the proxy.
+  fn proxy_url(self, proxy_url: Option<String>) -> Self;
+
+  /// Whether the webview renders with the GPU. Disabling it forces software rendering.
+  ///
+  /// Ignored by the webviews that can't render without the GPU.
+  fn hardware_acceleration(self, enabled: bool) -> Self;
+
+  /// Whether the window can receive focus.
+  fn focusable(self, focusable: bool) -> Self;
+
   /// Sets the webview url.
   fn url(self, url: String) -> Self;
 
@@ -104,12 +121,125 @@ pub struct RpcRequest {
   pub params: Option<JsonValue>,
 }
 
+/// How the webview treats the URLs of a custom protocol scheme.
+///
+/// The default privileges are the ones of a standard scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchemePrivileges {
+  /// Whether the scheme is a secure context like `https`,
+  /// so secure-only APIs such as `crypto.subtle` are available
+  /// and loading it from `https` isn't mixed content.
+  pub secure: bool,
+  /// Whether `fetch()` and `XMLHttpRequest` can request the scheme,
+  /// which is required to load ES modules.
+  pub fetch_enabled: bool,
+  /// Whether the scheme is exempt from CORS checks, so pages on other origins can request it.
+  pub cors_enabled: bool,
+  /// Whether pages on the scheme can register service workers and use the Cache API.
+  /// Engines only allow them on secure schemes.
+  pub service_workers: bool,
+}
+
+impl SchemePrivileges {
+  /// The privileges of a scheme that serves the application itself:
+  /// secure, fetch enabled and CORS exempt.
+  pub fn trusted() -> Self {
+    Self {
+      secure: true,
+      fetch_enabled: true,
+      cors_enabled: true,
+      service_workers: false,
+    }
+  }
+}
+
+/// The response of a custom protocol handler.
+#[derive(Debug, Clone)]
+pub struct CustomProtocolResponse {
+  /// The response body.
+  pub body: Vec<u8>,
+  /// The MIME type of the body. Guessed from the URL extension if not set.
+  pub mimetype: Option<String>,
+  /// The HTTP status code.
+  /// Runtimes that can't send it, like `wry`, fail the request on an error status instead.
+  pub status: u16,
+}
+
+impl CustomProtocolResponse {
+  /// A `200 OK` response with the given body.
+  pub fn new(body: Vec<u8>) -> Self {
+    Self {
+      body,
+      mimetype: None,
+      status: 200,
+    }
+  }
+
+  /// Sets the MIME type of the body.
+  pub fn mimetype(mut self, mimetype: impl Into<String>) -> Self {
+    self.mimetype = Some(mimetype.into());
+    self
+  }
+
+  /// Sets the HTTP status code.
+  pub fn status(mut self, status: u16) -> Self {
+    self.status = status;
+    self
+  }
+}
+
+impl From<Vec<u8>> for CustomProtocolResponse {
+  fn from(body: Vec<u8>) -> Self {
+    Self::new(body)
+  }
+}
+
+/// The MIME type of a custom protocol response body, from its file extension.
+pub(crate) fn mimetype_from_extension(extension: &str) -> &'static str {
+  match extension.to_lowercase().as_str() {
+    "html" | "htm" => "text/html",
+    "js" | "mjs" => "text/javascript",
+    "css" => "text/css",
+    "json" => "application/json",
+    "svg" => "image/svg+xml",
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "ico" => "image/x-icon",
+    "mp4" | "m4v" => "video/mp4",
+    "webm" => "video/webm",
+    "ogg" | "ogv" => "video/ogg",
+    "mov" => "video/quicktime",
+    "mp3" => "audio/mpeg",
+    "m4a" => "audio/mp4",
+    "wav" => "audio/wav",
+    "flac" => "audio/flac",
+    "wasm" => "application/wasm",
+    "pdf" => "application/pdf",
+    "woff" => "font/woff",
+    "woff2" => "font/woff2",
+    "ttf" => "font/ttf",
+    "txt" => "text/plain",
+    _ => "application/octet-stream",
+  }
+}
+
+/// Resolves the requests of a custom protocol, receiving the request URL.
+pub type CustomProtocolHandler =
+  dyn Fn(&str) -> crate::Result<CustomProtocolResponse> + Send + Sync + 'static;
+
 /// Uses a custom handler to resolve file requests
+#[derive(Clone)]
 pub struct CustomProtocol {
   /// Name of the protocol
   pub name: String,
   /// Handler for protocol
-  pub handler: Box<dyn Fn(&str) -> crate::Result<Vec<u8>> + Send>,
+  pub handler: Arc<CustomProtocolHandler>,
+  /// How the webview treats the protocol URLs.
+  /// A [`Runtime`](crate::runtime::Runtime) applies them when registering the scheme,
+  /// if the webview supports it.
+  pub privileges: SchemePrivileges,
 }
 
 /// The file drop event payload.
@@ -123,9 +253,41 @@ pub enum FileDropEvent {
   Cancelled,
 }
 
+/// Data other than files dropped onto the webview, emitted as the `tauri://drop` event.
+///
+/// Dropped files are reported with [`FileDropEvent`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataDropEvent {
+  /// The dropped data indexed by MIME type, e.g. `text/plain`, `text/uri-list` or `text/html`.
+  pub items: HashMap<String, String>,
+  /// Where the data was dropped, in CSS pixels relative to the webview.
+  pub position: DropPosition,
+}
+
+/// A page script tampering with the injected Tauri API,
+/// emitted to the Rust listeners as the `tauri://tampering` event.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TamperingEvent {
+  /// What the script did, e.g. `set window.__TAURI__.invoke` or `polluted Object.prototype`.
+  pub reason: String,
+}
+
+/// The position of a drop, see [`DataDropEvent`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct DropPosition {
+  /// The horizontal position.
+  pub x: f64,
+  /// The vertical position.
+  pub y: f64,
+}
+
 /// Rpc handler.
 pub(crate) type WebviewRpcHandler<M> = Box<dyn Fn(DetachedWindow<M>, RpcRequest) + Send>;
 
 /// File drop handler callback
 /// Return `true` in the callback to block the OS' default behavior of handling a file drop.
 pub(crate) type FileDropHandler<M> = Box<dyn Fn(FileDropEvent, DetachedWindow<M>) -> bool + Send>;
+
+/// Navigation handler callback, run before the webview starts loading a URL.
+/// Return `false` in the callback to cancel the navigation.
+pub(crate) type NavigationHandler<M> = Box<dyn Fn(String, DetachedWindow<M>) -> bool + Send>;
@@ -8,22 +8,210 @@ use crate::api::config::WindowConfig;
 use crate::{
   api::config::WindowUrl,
   event::{Event, EventHandler},
-  hooks::{InvokeMessage, InvokePayload, PageLoadPayload},
+  hooks::{InvokeMessage, InvokePayload, JsError, PageLoadPayload},
   runtime::{
-    tag::ToJavascript,
-    webview::{CustomProtocol, FileDropHandler, WebviewRpcHandler},
+    menu::{Menu, MenuEventHandler},
+    recorder::RecordedEvent,
+    tag::{Tag, ToJavascript},
+    webview::{
+      CustomProtocol, DataDropEvent, FileDropHandler, NavigationHandler, TamperingEvent,
+      WebviewRpcHandler,
+    },
     Dispatch, Runtime,
   },
   sealed::{ManagerBase, RuntimeOrDispatch},
   Attributes, Icon, Manager, Params,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::{
   convert::TryInto,
   hash::{Hash, Hasher},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
 };
 
+/// An event reported by the [`Runtime`] for a webview window.
+///
+/// Besides the [`crate::Window::on_window_event`] listeners, the events are emitted to the
+/// webview as `tauri://close-requested`, `tauri://focus`, `tauri://blur`, `tauri://resize`,
+/// `tauri://move` and `tauri://theme-changed`. The file drops are emitted as `tauri://file-drop`,
+/// `tauri://file-drop-hover` and `tauri://file-drop-cancelled`.
+#[derive(Debug, Clone)]
+pub enum WindowEvent {
+  /// The user asked to close the window, e.g. with its close button.
+  ///
+  /// The window is closed after the handlers run, unless one of them calls [`CloseRequestApi::prevent_close`].
+  /// Windows declared on the config then follow their `close_behavior`, e.g. hiding instead.
+  /// The webview is notified but can't prevent the close.
+  CloseRequested(CloseRequestApi),
+  /// The window gained (`true`) or lost (`false`) the focus.
+  Focused(bool),
+  /// The window was resized.
+  Resized {
+    /// The new width, in physical pixels.
+    width: u32,
+    /// The new height, in physical pixels.
+    height: u32,
+  },
+  /// The window was moved.
+  Moved {
+    /// The new position of the left edge, in physical pixels.
+    x: i32,
+    /// The new position of the top edge, in physical pixels.
+    y: i32,
+  },
+  /// The system theme of the window changed.
+  ThemeChanged(Theme),
+  /// The window was closed and destroyed.
+  Destroyed,
+}
+
+/// The light or dark theme of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+  /// The light theme.
+  Light,
+  /// The dark theme.
+  Dark,
+}
+
+/// The icon of the mouse cursor over a window, named after the CSS `cursor` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CursorIcon {
+  /// The platform-dependent default cursor, usually an arrow.
+  Default,
+  /// A simple crosshair.
+  Crosshair,
+  /// A hand, often used to indicate links.
+  Hand,
+  /// An arrow.
+  Arrow,
+  /// Something can be moved.
+  Move,
+  /// Text can be selected.
+  Text,
+  /// The program is busy and the user can't interact with it.
+  Wait,
+  /// Help is available.
+  Help,
+  /// The program is busy but the user can still interact with it.
+  Progress,
+  /// The requested action won't be carried out.
+  NotAllowed,
+  /// A context menu is available.
+  ContextMenu,
+  /// A cell or a set of cells can be selected.
+  Cell,
+  /// Vertical text can be selected.
+  VerticalText,
+  /// An alias or a shortcut is to be created.
+  Alias,
+  /// Something is to be copied.
+  Copy,
+  /// The dragged item can't be dropped here.
+  NoDrop,
+  /// Something can be grabbed.
+  Grab,
+  /// Something is being grabbed.
+  Grabbing,
+  /// Something can be scrolled in any direction.
+  AllScroll,
+  /// Something can be zoomed in.
+  ZoomIn,
+  /// Something can be zoomed out.
+  ZoomOut,
+  /// The east edge can be moved.
+  EResize,
+  /// The north edge can be moved.
+  NResize,
+  /// The north-east corner can be moved.
+  NeResize,
+  /// The north-west corner can be moved.
+  NwResize,
+  /// The south edge can be moved.
+  SResize,
+  /// The south-east corner can be moved.
+  SeResize,
+  /// The south-west corner can be moved.
+  SwResize,
+  /// The west edge can be moved.
+  WResize,
+  /// Something can be resized horizontally.
+  EwResize,
+  /// Something can be resized vertically.
+  NsResize,
+  /// Something can be resized along the north-east to south-west diagonal.
+  NeswResize,
+  /// Something can be resized along the north-west to south-east diagonal.
+  NwseResize,
+  /// A column can be resized horizontally.
+  ColResize,
+  /// A row can be resized vertically.
+  RowResize,
+}
+
+/// Lets a [`WindowEvent::CloseRequested`] handler cancel the close.
+#[derive(Debug, Clone, Default)]
+pub struct CloseRequestApi(Arc<AtomicBool>);
+
+impl CloseRequestApi {
+  /// Keeps the window open, e.g. to ask the user to save their changes first.
+  pub fn prevent_close(&self) {
+    self.0.store(true, Ordering::SeqCst);
+  }
+
+  /// Whether a handler prevented the close. Checked by the [`Runtime`] after the handlers run.
+  pub fn is_close_prevented(&self) -> bool {
+    self.0.load(Ordering::SeqCst)
+  }
+}
+
+/// A hidden window loading its page, created with [`Manager::create_preloaded_window`].
+///
+/// The window emits the `tauri://ready` event when its page has loaded.
+pub struct PreloadedWindow<M: Params> {
+  window: Window<M>,
+  ready: tokio::sync::watch::Receiver<bool>,
+}
+
+impl<M: Params> PreloadedWindow<M> {
+  pub(crate) fn new(window: Window<M>, ready: tokio::sync::watch::Receiver<bool>) -> Self {
+    Self { window, ready }
+  }
+
+  /// The window, which may still be loading.
+  pub fn window(&self) -> &Window<M> {
+    &self.window
+  }
+
+  /// Whether the window page has loaded.
+  pub fn is_ready(&self) -> bool {
+    *self.ready.borrow()
+  }
+
+  /// Waits for the window page to load. The window is still hidden, ready to be shown instantly.
+  pub async fn ready(mut self) -> Window<M> {
+    while !*self.ready.borrow() {
+      // the window was destroyed before loading
+      if self.ready.changed().await.is_err() {
+        break;
+      }
+    }
+    self.window
+  }
+}
+
+/// How the [`Runtime`] reports the events of a webview window.
+pub(crate) type WindowEventHandler<M> = Box<dyn Fn(WindowEvent, DetachedWindow<M>) + Send>;
+
+/// A closure registered with [`Window::on_window_event`].
+pub(crate) type WindowEventListener = dyn Fn(&WindowEvent) + Send + Sync;
+
 /// A webview window that has yet to be built.
 pub struct PendingWindow<M: Params> {
   /// The label that the window will be named.
@@ -39,10 +227,19 @@ pub struct PendingWindow<M: Params> {
   pub rpc_handler: Option<WebviewRpcHandler<M>>,
 
   /// How to handle custom protocols for the webview window.
-  pub custom_protocol: Option<CustomProtocol>,
+  pub custom_protocols: Vec<CustomProtocol>,
 
   /// How to handle a file dropping onto the webview window.
   pub file_drop_handler: Option<FileDropHandler<M>>,
+
+  /// How to decide whether the webview window can navigate to a URL.
+  pub navigation_handler: Option<NavigationHandler<M>>,
+
+  /// How to handle a click on the window menu items.
+  pub menu_event_handler: Option<MenuEventHandler<M>>,
+
+  /// How to handle the window events.
+  pub window_event_handler: Option<WindowEventHandler<M>>,
 }
 
 impl<M: Params> PendingWindow<M> {
@@ -57,8 +254,11 @@ impl<M: Params> PendingWindow<M> {
       label,
       url,
       rpc_handler: None,
-      custom_protocol: None,
+      custom_protocols: Vec::new(),
       file_drop_handler: None,
+      navigation_handler: None,
+      menu_event_handler: None,
+      window_event_handler: None,
     }
   }
 
@@ -71,8 +271,11 @@ impl<M: Params> PendingWindow<M> {
       label,
       url,
       rpc_handler: None,
-      custom_protocol: None,
+      custom_protocols: Vec::new(),
       file_drop_handler: None,
+      navigation_handler: None,
+      menu_event_handler: None,
+      window_event_handler: None,
     }
   }
 }
@@ -113,7 +316,10 @@ impl<M: Params> PartialEq for DetachedWindow<M> {
 /// We want to export the runtime related window at the crate root, but not look like a re-export.
 pub(crate) mod export {
   use super::*;
-  use crate::runtime::manager::WindowManager;
+  use crate::runtime::{
+    manager::{ConsoleMessage, EvalResult, WindowManager},
+    monitor::{PhysicalPosition, PhysicalSize, Position, Size},
+  };
 
   /// A webview window managed by Tauri.
   ///
@@ -181,11 +387,34 @@ pub(crate) mod export {
       if &command == "__initialized" {
         let payload: PageLoadPayload = serde_json::from_value(payload.inner)?;
         manager.run_on_page_load(self, payload);
+      } else if &command == "__drop" {
+        let event: DataDropEvent = serde_json::from_value(payload.inner)?;
+        manager.run_on_data_drop(self, event);
+      } else if &command == "__console" {
+        let message: ConsoleMessage = serde_json::from_value(payload.inner)?;
+        manager.run_on_console(self, message);
+      } else if &command == "__jsError" {
+        let error: JsError = serde_json::from_value(payload.inner)?;
+        manager.run_on_js_error(self, error);
+      } else if &command == "__evalResult" {
+        let result: EvalResult = serde_json::from_value(payload.inner)?;
+        manager.run_on_eval_result(self, result);
+      } else if &command == "__tampering" {
+        let event: TamperingEvent = serde_json::from_value(payload.inner)?;
+        manager.run_on_tampering(self, event);
       } else {
+        manager.record(|| RecordedEvent::Invoke {
+          window: self.label().to_string(),
+          command: command.clone(),
+          payload: serde_json::to_value(&payload).unwrap_or(JsonValue::Null),
+        });
         let message = InvokeMessage::new(self, command.to_string(), payload);
         if let Some(module) = &message.payload.tauri_module {
           let module = module.to_string();
           crate::endpoints::handle(module, message, manager.config(), manager.package_info());
+        } else if !manager.is_command_allowed(message.window().label(), &command) {
+          let label = message.window().label().to_string();
+          message.reject(crate::Error::WindowCommandNotAllowlisted(command, label).to_string());
         } else if command.starts_with("plugin:") {
           manager.extend_api(command, message);
         } else {
@@ -196,25 +425,50 @@ pub(crate) mod export {
       Ok(())
     }
 
+    /// Gets a handle to the application this window belongs to.
+    pub fn app_handle(&self) -> crate::AppHandle<P> {
+      crate::AppHandle::new(
+        self.window.dispatcher.runtime_handle(),
+        self.manager.clone(),
+      )
+    }
+
     /// The label of this window.
     pub fn label(&self) -> &P::Label {
       &self.window.label
     }
 
-    pub(crate) fn emit_internal<E: ToJavascript, S: Serialize>(
+    pub(crate) fn emit_internal<E: Tag, S: Serialize>(
       &self,
       event: E,
       payload: Option<S>,
+    ) -> crate::Result<()> {
+      self.emit_from_internal(None, event, payload)
+    }
+
+    /// Emits an event sent by the `source` window, exposed as the `windowLabel` of the JS event.
+    pub(crate) fn emit_from_internal<E: Tag, S: Serialize>(
+      &self,
+      source: Option<&P::Label>,
+      event: E,
+      payload: Option<S>,
     ) -> crate::Result<()> {
       let js_payload = match payload {
         Some(payload_value) => serde_json::to_value(payload_value)?,
         None => JsonValue::Null,
       };
 
+      self.manager.record(|| RecordedEvent::Emit {
+        window: self.label().to_string(),
+        event: event.to_string(),
+        payload: js_payload.clone(),
+      });
+
       self.eval(&format!(
-        "window['{}']({{event: {}, payload: {}}}, '{}')",
+        "window['{}']({{event: {}, windowLabel: {}, payload: {}}}, '{}')",
         self.manager.event_emit_function_name(),
         event.to_javascript()?,
+        serde_json::to_string(&source.map(ToString::to_string))?,
         js_payload,
         self.manager.generate_salt(),
       ))?;
@@ -255,6 +509,24 @@ pub(crate) mod export {
       self.manager.listen(event, Some(label), handler)
     }
 
+    /// Registers a handler of the events of this window, e.g. to ask for unsaved changes on [`WindowEvent::CloseRequested`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use tauri::runtime::window::WindowEvent;
+    ///
+    /// window.on_window_event(|event| {
+    ///   if let WindowEvent::CloseRequested(api) = event {
+    ///     api.prevent_close();
+    ///   }
+    /// });
+    /// ```
+    pub fn on_window_event<F: Fn(&WindowEvent) + Send + Sync + 'static>(&self, handler: F) {
+      self
+        .manager
+        .on_window_event(self.window.label.clone(), handler)
+    }
+
     /// Listen to a an event on this window a single time.
     pub fn once<F>(&self, event: P::Event, handler: F) -> EventHandler
     where
@@ -264,17 +536,82 @@ pub(crate) mod export {
       self.manager.once(event, Some(label), handler)
     }
 
+    /// Removes a listener registered with [`Window::listen`] or [`Window::once`] on this window.
+    ///
+    /// Listeners registered on other windows or globally are left untouched.
+    pub fn unlisten(&self, handler_id: EventHandler) {
+      self.manager.unlisten_window(handler_id, &self.window.label)
+    }
+
     /// Triggers an event on this window.
     pub fn trigger(&self, event: P::Event, data: Option<String>) {
       let label = self.window.label.clone();
       self.manager.trigger(event, Some(label), data)
     }
 
+    /// Posts a message to the target window, received with the `onMessage` function of the JS API.
+    ///
+    /// Unlike an event, the message only reaches the target window, and isn't seen by Rust listeners.
+    pub fn post_message<S: Serialize>(&self, target: &P::Label, data: S) -> crate::Result<()> {
+      self.manager.post_message(
+        self.label(),
+        target,
+        serde_json::to_value(data)?,
+        Vec::new(),
+      )
+    }
+
     /// Evaluates JavaScript on this window.
     pub fn eval(&self, js: &str) -> crate::Result<()> {
       self.window.dispatcher.eval_script(js)
     }
 
+    /// Evaluates JavaScript on this window and returns its value, awaited if it's a promise.
+    ///
+    /// The script is evaluated on the global scope, its value being the one of its last
+    /// statement. The value must be serializable to JSON; `undefined` is returned as `null`.
+    /// Fails with [`crate::Error::EvalScript`] if the script throws,
+    /// and with [`crate::Error::WebviewNotFound`] if the window closes before it returns.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # async fn run<P: tauri::Params>(window: tauri::Window<P>) -> tauri::Result<()> {
+    /// let title = window.eval_with_result("document.title").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn eval_with_result(&self, js: &str) -> crate::Result<JsonValue> {
+      let receiver = self.manager.eval_with_result(self, js)?;
+      match receiver.await {
+        Ok(result) => result.map_err(crate::Error::EvalScript),
+        Err(_) => Err(crate::Error::WebviewNotFound),
+      }
+    }
+
+    /// Dispatches a synthetic input event on this window's webview.
+    ///
+    /// The event is dispatched as a DOM event, so it is handled by the page but not by the
+    /// operating system. Meant to be used on integration tests.
+    #[cfg(feature = "test-automation")]
+    pub fn dispatch_input(&self, event: crate::runtime::input::InputEvent) -> crate::Result<()> {
+      self.eval(&event.to_script()?)
+    }
+
+    /// Shows this window once its page has loaded, closing the splashscreen windows.
+    ///
+    /// Meant to be called on a hidden window, while a window with the `splashscreen` config flag is shown.
+    /// The windows defined in the config are already shown this way when there's a splashscreen.
+    pub fn show_main_when_ready(&self) {
+      self.manager.show_when_ready(self.label().clone())
+    }
+
+    /// Sets the menu bar of this window, replacing the current one.
+    ///
+    /// Clicks on the menu items can be listened with [`Window::listen`] on the `tauri://menu` event.
+    pub fn set_menu(&self, menu: Vec<Menu>) -> crate::Result<()> {
+      self.window.dispatcher.set_menu(menu)
+    }
+
     /// Determines if this window should be resizable.
     pub fn set_resizable(&self, resizable: bool) -> crate::Result<()> {
       self.window.dispatcher.set_resizable(resizable)
@@ -315,11 +652,47 @@ pub(crate) mod export {
       self.window.dispatcher.hide()
     }
 
+    /// The URL of the page loaded on this window.
+    pub fn url(&self) -> String {
+      self.manager.window_url(self.label()).unwrap_or_default()
+    }
+
+    /// Navigates this window to the URL, e.g. to an identity provider for an OAuth flow.
+    ///
+    /// Fails with [`crate::Error::NavigationNotAllowed`] if the [`crate::Builder::on_navigation`] hook rejects it.
+    pub fn navigate(&self, url: &str) -> crate::Result<()> {
+      if !self.manager.is_navigation_allowed(self, url) {
+        return Err(crate::Error::NavigationNotAllowed(url.to_string()));
+      }
+      self.eval(&format!(
+        "window.location.href = {}",
+        serde_json::to_string(url)?
+      ))
+    }
+
+    /// Reloads the page, discarding its JavaScript state.
+    ///
+    /// Restarts a worker window, i.e. a hidden window used only to run JavaScript,
+    /// e.g. when it stops responding to its messages.
+    pub fn restart(&self) -> crate::Result<()> {
+      self.eval("window.location.reload()")
+    }
+
     /// Closes this window.
     pub fn close(&self) -> crate::Result<()> {
       self.window.dispatcher.close()
     }
 
+    /// Sets the label assistive technologies announce for this window's content.
+    ///
+    /// The label is set on the document loaded on the webview, and again on each page load.
+    pub fn set_accessibility_title(&self, title: &str) -> crate::Result<()> {
+      self
+        .manager
+        .set_accessibility_title(self.label(), title.to_string());
+      self.manager.apply_accessibility_title(self)
+    }
+
     /// Determines if this window should be [decorated].
     ///
     /// [decorated]: https://en.wikipedia.org/wiki/Window_(computing)#Window_decoration
@@ -332,58 +705,73 @@ pub(crate) mod export {
       self.window.dispatcher.set_always_on_top(always_on_top)
     }
 
-    /// Sets this window's width.
-    pub fn set_width(&self, width: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.set_width(width.into())
+    /// The factor that maps logical pixels to the physical pixels of the monitor this window is on.
+    pub fn scale_factor(&self) -> crate::Result<f64> {
+      self.window.dispatcher.scale_factor()
     }
 
-    /// Sets this window's height.
-    pub fn set_height(&self, height: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.set_height(height.into())
+    /// The size of this window's client area, i.e. without its decorations.
+    pub fn inner_size(&self) -> crate::Result<PhysicalSize> {
+      self.window.dispatcher.inner_size()
     }
 
-    /// Resizes this window.
-    pub fn resize(&self, width: impl Into<f64>, height: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.resize(width.into(), height.into())
+    /// The size of this whole window, including its decorations.
+    pub fn outer_size(&self) -> crate::Result<PhysicalSize> {
+      self.window.dispatcher.outer_size()
     }
 
-    /// Sets this window's minimum size.
-    pub fn set_min_size(
-      &self,
-      min_width: impl Into<f64>,
-      min_height: impl Into<f64>,
-    ) -> crate::Result<()> {
-      self
-        .window
-        .dispatcher
-        .set_min_size(min_width.into(), min_height.into())
+    /// The position of the top left corner of this window's client area on the desktop.
+    pub fn inner_position(&self) -> crate::Result<PhysicalPosition> {
+      self.window.dispatcher.inner_position()
     }
 
-    /// Sets this window's maximum size.
-    pub fn set_max_size(
-      &self,
-      max_width: impl Into<f64>,
-      max_height: impl Into<f64>,
-    ) -> crate::Result<()> {
-      self
-        .window
-        .dispatcher
-        .set_max_size(max_width.into(), max_height.into())
+    /// The position of the top left corner of this window on the desktop.
+    ///
+    /// Along with [`Self::outer_size`] and [`Self::is_maximized`], it can be saved to restore the window geometry
+    /// on the next launch.
+    pub fn outer_position(&self) -> crate::Result<PhysicalPosition> {
+      self.window.dispatcher.outer_position()
+    }
+
+    /// Whether this window is maximized.
+    pub fn is_maximized(&self) -> crate::Result<bool> {
+      self.window.dispatcher.is_maximized()
+    }
+
+    /// Whether this window is fullscreen.
+    pub fn is_fullscreen(&self) -> crate::Result<bool> {
+      self.window.dispatcher.is_fullscreen()
+    }
+
+    /// Whether this window is visible.
+    pub fn is_visible(&self) -> crate::Result<bool> {
+      self.window.dispatcher.is_visible()
     }
 
-    /// Sets this window's x position.
-    pub fn set_x(&self, x: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.set_x(x.into())
+    /// Whether this window is decorated.
+    pub fn is_decorated(&self) -> crate::Result<bool> {
+      self.window.dispatcher.is_decorated()
     }
 
-    /// Sets this window's y position.
-    pub fn set_y(&self, y: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.set_y(y.into())
+    /// Resizes this window, e.g. with a [`crate::runtime::monitor::LogicalSize`] to keep its look on any monitor.
+    pub fn set_size(&self, size: impl Into<Size>) -> crate::Result<()> {
+      self.window.dispatcher.set_size(size.into())
     }
 
-    /// Sets this window's position.
-    pub fn set_position(&self, x: impl Into<f64>, y: impl Into<f64>) -> crate::Result<()> {
-      self.window.dispatcher.set_position(x.into(), y.into())
+    /// Sets this window's minimum size.
+    pub fn set_min_size(&self, min_size: impl Into<Size>) -> crate::Result<()> {
+      self.window.dispatcher.set_min_size(min_size.into())
+    }
+
+    /// Sets this window's maximum size.
+    pub fn set_max_size(&self, max_size: impl Into<Size>) -> crate::Result<()> {
+      self.window.dispatcher.set_max_size(max_size.into())
+    }
+
+    /// Sets this window's position, e.g. with a [`crate::runtime::monitor::PhysicalPosition`]
+    /// to align it with a [`crate::runtime::monitor::Monitor`].
+    pub fn set_position(&self, position: impl Into<Position>) -> crate::Result<()> {
+      self.window.dispatcher.set_position(position.into())
     }
 
     /// Determines if this window should be fullscreen.
@@ -396,6 +784,101 @@ pub(crate) mod export {
       self.window.dispatcher.set_icon(icon.try_into()?)
     }
 
+    /// Sets the identifier used to group this window with others as native tabs.
+    ///
+    /// Windows sharing an identifier can be merged into a single tabbed window. Only supported on macOS.
+    pub fn set_tabbing_identifier(&self, identifier: Option<&str>) -> crate::Result<()> {
+      self
+        .window
+        .dispatcher
+        .set_tabbing_identifier(identifier.map(ToString::to_string))
+    }
+
+    /// Merges all windows sharing this window's tabbing identifier into its tab group.
+    pub fn merge_all_windows(&self) -> crate::Result<()> {
+      self.window.dispatcher.merge_all_windows()
+    }
+
+    /// Selects the next tab of this window's tab group.
+    pub fn select_next_tab(&self) -> crate::Result<()> {
+      self.window.dispatcher.select_next_tab()
+    }
+
+    /// Selects the previous tab of this window's tab group.
+    pub fn select_previous_tab(&self) -> crate::Result<()> {
+      self.window.dispatcher.select_previous_tab()
+    }
+
+    /// Moves this window out of its tab group into a standalone window.
+    pub fn move_tab_to_new_window(&self) -> crate::Result<()> {
+      self.window.dispatcher.move_tab_to_new_window()
+    }
+
+    /// Opens the webview dev tools, e.g. to debug a bundled app.
+    pub fn open_devtools(&self) -> crate::Result<()> {
+      self.window.dispatcher.open_devtools()
+    }
+
+    /// Closes the webview dev tools.
+    pub fn close_devtools(&self) -> crate::Result<()> {
+      self.window.dispatcher.close_devtools()
+    }
+
+    /// Shows or hides the cursor while it's over this window.
+    pub fn set_cursor_visible(&self, visible: bool) -> crate::Result<()> {
+      self.window.dispatcher.set_cursor_visible(visible)
+    }
+
+    /// Confines the cursor to this window, e.g. for the pointer lock of a game.
+    ///
+    /// Combine it with [`Window::set_cursor_visible`] to hide the locked cursor.
+    pub fn set_cursor_grab(&self, grab: bool) -> crate::Result<()> {
+      self.window.dispatcher.set_cursor_grab(grab)
+    }
+
+    /// Sets the cursor icon shown over this window.
+    pub fn set_cursor_icon(&self, icon: CursorIcon) -> crate::Result<()> {
+      self.window.dispatcher.set_cursor_icon(icon)
+    }
+
+    /// Moves the cursor to a position relative to the top left corner of this window.
+    pub fn set_cursor_position(&self, position: impl Into<Position>) -> crate::Result<()> {
+      self.window.dispatcher.set_cursor_position(position.into())
+    }
+
+    /// Assigns this window to a group of related windows, e.g. the windows of a document-based app.
+    ///
+    /// On macOS the group is also used as the tabbing identifier, so windows of the same group
+    /// can be merged as native tabs. Other platforms only keep track of the group,
+    /// see [`Window::grouped_windows`].
+    pub fn set_parent_group(&self, group: Option<&str>) -> crate::Result<()> {
+      self
+        .manager
+        .set_window_group(self.label(), group.map(ToString::to_string));
+      #[cfg(target_os = "macos")]
+      {
+        match self.set_tabbing_identifier(group) {
+          // grouping still works as a logical abstraction without native tabs
+          Err(crate::Error::UnsupportedOperation(_)) => {}
+          result => result?,
+        }
+      }
+      Ok(())
+    }
+
+    /// The group this window was assigned to with [`Window::set_parent_group`].
+    pub fn parent_group(&self) -> Option<String> {
+      self.manager.window_group(self.label())
+    }
+
+    /// All windows in the same group as this one, including itself.
+    pub fn grouped_windows(&self) -> Vec<Window<P>> {
+      match self.parent_group() {
+        Some(group) => self.manager.group_windows(&group),
+        None => vec![self.clone()],
+      }
+    }
+
     pub(crate) fn verify_salt(&self, salt: String) -> bool {
       self.manager.verify_salt(salt)
     }
@@ -0,0 +1,404 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::api::{
+  command::Command,
+  config::{Config, ShellAllowedArg, ShellAllowedArgs, ShellAllowlistConfig},
+  path::{
+    app_dir, audio_dir, cache_dir, config_dir, data_dir, desktop_dir, document_dir, download_dir,
+    home_dir, log_dir, picture_dir, resource_dir, video_dir,
+  },
+};
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+
+use std::{
+  collections::HashMap,
+  path::{Component, Path, PathBuf},
+  sync::{Arc, Mutex},
+};
+
+/// The variables a scope pattern can start with, e.g. `$HOME/Pictures/**`.
+const VARIABLES: &[(&str, fn(&Config) -> Option<PathBuf>)] = &[
+  ("$APP", app_dir),
+  ("$AUDIO", |_| audio_dir()),
+  ("$CACHE", |_| cache_dir()),
+  ("$CONFIG", |_| config_dir()),
+  ("$DATA", |_| data_dir()),
+  ("$DESKTOP", |_| desktop_dir()),
+  ("$DOCUMENT", |_| document_dir()),
+  ("$DOWNLOAD", |_| download_dir()),
+  ("$HOME", |_| home_dir()),
+  ("$LOG", log_dir),
+  ("$PICTURE", |_| picture_dir()),
+  ("$RESOURCE", |_| resource_dir()),
+  ("$VIDEO", |_| video_dir()),
+];
+
+/// The paths a frontend API can access, defined by glob patterns.
+///
+/// A pattern can start with a directory variable such as `$APP` or `$HOME`.
+/// `*` matches inside a single directory, while `**` matches any number of directories,
+/// e.g. `$HOME/Pictures/**` allows every file under the pictures directory.
+///
+/// Cloning the scope shares it, so patterns allowed at runtime apply everywhere it's used.
+#[derive(Clone, Default)]
+pub struct FsScope {
+  patterns: Arc<Mutex<Vec<Pattern>>>,
+}
+
+impl FsScope {
+  /// Creates the scope from the configured patterns.
+  /// The app directory variables are resolved with the bundle identifier of the `config`.
+  /// The invalid patterns and the ones using an unknown variable are ignored.
+  ///
+  /// The directory a pattern starts with is resolved like the checked paths,
+  /// so a pattern under a symbolic link matches the paths under its target.
+  pub(crate) fn for_patterns(config: &Config, patterns: &[String]) -> Self {
+    let patterns = patterns
+      .iter()
+      .filter_map(|pattern| {
+        let (dir, rest) = split_pattern(config, pattern)?;
+        if dir.as_os_str().is_empty() {
+          return Pattern::new(rest).ok();
+        }
+        let dir = escaped_path(&dir);
+        if rest.is_empty() {
+          Pattern::new(&dir).ok()
+        } else {
+          Pattern::new(&format!("{}/{}", dir.trim_end_matches('/'), rest)).ok()
+        }
+      })
+      .collect();
+    Self {
+      patterns: Arc::new(Mutex::new(patterns)),
+    }
+  }
+
+  /// Allows the directory and its content, e.g. a directory picked on a dialog.
+  ///
+  /// When `recursive` is `false`, only its direct children are allowed.
+  pub fn allow_directory<P: AsRef<Path>>(&self, path: P, recursive: bool) {
+    let path = escaped_path(path.as_ref());
+    let content = format!(
+      "{}/{}",
+      path.trim_end_matches('/'),
+      if recursive { "**" } else { "*" }
+    );
+    let mut patterns = self.patterns.lock().expect("poisoned scope");
+    patterns.extend(Pattern::new(&path).ok());
+    patterns.extend(Pattern::new(&content).ok());
+  }
+
+  /// Allows the file, e.g. a file picked on a dialog.
+  pub fn allow_file<P: AsRef<Path>>(&self, path: P) {
+    let path = escaped_path(path.as_ref());
+    self
+      .patterns
+      .lock()
+      .expect("poisoned scope")
+      .extend(Pattern::new(&path).ok());
+  }
+
+  /// Whether the path is in the scope.
+  ///
+  /// The symbolic links and `..` are resolved first, see [`resolve_path`].
+  /// Paths going up with `..` from a missing directory are never allowed.
+  pub fn is_allowed<P: AsRef<Path>>(&self, path: P) -> bool {
+    let path = match resolve_path(path.as_ref()) {
+      Some(path) => path,
+      None => return false,
+    };
+    let options = MatchOptions {
+      require_literal_separator: true,
+      ..Default::default()
+    };
+    self
+      .patterns
+      .lock()
+      .expect("poisoned scope")
+      .iter()
+      .any(|pattern| pattern.matches_path_with(&path, options))
+  }
+}
+
+/// The path as a pattern matching only itself, resolved like the paths checked against the scope.
+fn escaped_path(path: &Path) -> String {
+  let path = resolve_path(path).unwrap_or_else(|| path.to_path_buf());
+  Pattern::escape(&path.to_string_lossy())
+}
+
+/// Resolves the symbolic links and `..` of the path, like the file system does when accessing it.
+///
+/// The path may not exist yet, e.g. a file about to be written, so its nearest existing ancestor
+/// is resolved and the remaining components are appended.
+/// `None` if one of the remaining components goes up with `..`, since it can't be resolved.
+fn resolve_path(path: &Path) -> Option<PathBuf> {
+  for ancestor in path.ancestors() {
+    if let Ok(mut resolved) = ancestor.canonicalize() {
+      for component in path.strip_prefix(ancestor).ok()?.components() {
+        match component {
+          Component::Normal(name) => resolved.push(name),
+          Component::CurDir => {}
+          _ => return None,
+        }
+      }
+      return Some(resolved);
+    }
+  }
+  // a relative path in a missing working directory
+  if path.components().any(|c| c == Component::ParentDir) {
+    None
+  } else {
+    Some(path.to_path_buf())
+  }
+}
+
+/// The paths and URLs the shell `open` API accepts when no scope is configured.
+const DEFAULT_OPEN_SCOPE: &str = r"^((mailto:\w+)|(tel:\w+)|(https?://\w+)).+";
+
+/// An argument of a scoped command, with its compiled validator.
+enum ScopedArg {
+  Fixed(String),
+  /// The validator is `None` when its regex is invalid, rejecting every value.
+  Var(Option<Regex>),
+}
+
+/// A command the shell `execute` API can run.
+struct ScopedCommand {
+  cmd: String,
+  /// `None` when any argument is allowed.
+  args: Option<Vec<ScopedArg>>,
+  sidecar: bool,
+}
+
+/// The programs the shell APIs can run, configured on `tauri > allowlist > shell`.
+///
+/// The frontend runs a command by its name, so it can't choose the program,
+/// and each argument must match the fixed value or the validator of the configured one.
+#[derive(Clone)]
+pub struct ShellScope {
+  commands: Arc<HashMap<String, ScopedCommand>>,
+  /// `None` when the configured regex is invalid, rejecting every path.
+  open: Option<Regex>,
+  /// The programs the `open` API can use instead of the default app.
+  open_with: Arc<Vec<String>>,
+}
+
+impl ShellScope {
+  /// Creates the scope from the shell allowlist config.
+  pub(crate) fn new(config: &ShellAllowlistConfig) -> Self {
+    let commands = config
+      .scope
+      .iter()
+      .map(|command| {
+        let args = match &command.args {
+          ShellAllowedArgs::Flag(true) => None,
+          ShellAllowedArgs::Flag(false) => Some(Vec::new()),
+          ShellAllowedArgs::List(args) => Some(
+            args
+              .iter()
+              .map(|arg| match arg {
+                ShellAllowedArg::Fixed(value) => ScopedArg::Fixed(value.clone()),
+                ShellAllowedArg::Var { validator } => {
+                  ScopedArg::Var(Regex::new(&format!("^(?:{})$", validator)).ok())
+                }
+              })
+              .collect(),
+          ),
+        };
+        let scoped = ScopedCommand {
+          cmd: command.cmd.clone(),
+          args,
+          sidecar: command.sidecar,
+        };
+        (command.name.clone(), scoped)
+      })
+      .collect();
+    // the whole path must match, so the regex can't match a part of another URL
+    let open = Regex::new(&format!(
+      "^(?:{})$",
+      config.open_scope.as_deref().unwrap_or(DEFAULT_OPEN_SCOPE)
+    ))
+    .ok();
+    Self {
+      commands: Arc::new(commands),
+      open,
+      open_with: Arc::new(config.open_with.clone()),
+    }
+  }
+
+  /// Creates the command registered with the name, validating its arguments.
+  pub fn prepare(&self, name: &str, args: Vec<String>, sidecar: bool) -> crate::Result<Command> {
+    let command = match self.commands.get(name) {
+      Some(command) if command.sidecar == sidecar => command,
+      _ => return Err(crate::Error::ProgramNotAllowed(name.to_string())),
+    };
+    if let Some(allowed) = &command.args {
+      let valid = allowed.len() == args.len()
+        && allowed
+          .iter()
+          .zip(&args)
+          .all(|(allowed, arg)| match allowed {
+            ScopedArg::Fixed(value) => value == arg,
+            ScopedArg::Var(validator) => validator
+              .as_ref()
+              .map(|validator| validator.is_match(arg))
+              .unwrap_or(false),
+          });
+      if !valid {
+        return Err(crate::Error::ShellArgsNotAllowed(name.to_string()));
+      }
+    }
+    let program = if sidecar {
+      Command::new_sidecar(&command.cmd)
+    } else {
+      Command::new(&command.cmd)
+    };
+    Ok(program.args(args))
+  }
+
+  /// Whether the shell `open` API can open the path or URL.
+  pub fn is_open_allowed(&self, path: &str) -> bool {
+    self
+      .open
+      .as_ref()
+      .map(|open| open.is_match(path))
+      .unwrap_or(false)
+  }
+
+  /// Whether the shell `open` API can open the paths and URLs with the program.
+  pub fn is_open_with_allowed(&self, program: &str) -> bool {
+    self.open_with.iter().any(|allowed| allowed == program)
+  }
+}
+
+/// Splits the pattern into the directory it starts with, replacing its variable if any,
+/// and the rest of the pattern, from the first component with a glob.
+fn split_pattern<'a>(config: &Config, pattern: &'a str) -> Option<(PathBuf, &'a str)> {
+  if pattern.starts_with('$') {
+    let mut parts = pattern.splitn(2, '/');
+    let variable = parts.next().unwrap_or_default();
+    let (_, dir) = VARIABLES.iter().find(|(name, _)| *name == variable)?;
+    return Some((dir(config)?, parts.next().unwrap_or_default()));
+  }
+  let dir_end = match pattern.find(|c| matches!(c, '*' | '?' | '[')) {
+    Some(glob) => pattern[..glob]
+      .rfind('/')
+      .map(|slash| slash + 1)
+      .unwrap_or(0),
+    None => pattern.len(),
+  };
+  Some((PathBuf::from(&pattern[..dir_end]), &pattern[dir_end..]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{FsScope, ShellScope};
+  use crate::api::config::{
+    ShellAllowedArg, ShellAllowedArgs, ShellAllowedCommand, ShellAllowlistConfig,
+  };
+
+  #[test]
+  fn matches_globs() {
+    let config = crate::testing::mock_context().config;
+    let scope = FsScope::for_patterns(&config, &["/data/*.png".into(), "/media/**".into()]);
+    assert!(scope.is_allowed("/data/image.png"));
+    assert!(!scope.is_allowed("/data/nested/image.png"));
+    assert!(scope.is_allowed("/media/videos/clip.mp4"));
+    assert!(!scope.is_allowed("/media/../etc/passwd"));
+    assert!(!scope.is_allowed("/etc/passwd"));
+  }
+
+  #[test]
+  fn allows_at_runtime() {
+    let scope = FsScope::default();
+    scope.allow_file("/data/[draft].txt");
+    scope.allow_directory("/media", false);
+    scope.clone().allow_directory("/projects", true);
+    assert!(scope.is_allowed("/data/[draft].txt"));
+    assert!(!scope.is_allowed("/data/d.txt"));
+    assert!(scope.is_allowed("/media"));
+    assert!(scope.is_allowed("/media/clip.mp4"));
+    assert!(!scope.is_allowed("/media/videos/clip.mp4"));
+    assert!(scope.is_allowed("/projects/app/src/main.rs"));
+  }
+
+  #[test]
+  fn resolves_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = dir.path().join("data");
+    std::fs::create_dir(&data).unwrap();
+    let config = crate::testing::mock_context().config;
+    let scope = FsScope::for_patterns(
+      &config,
+      &[format!("{}/data/**", dir.path().to_string_lossy())],
+    );
+    assert!(scope.is_allowed(data.join("new/file.txt")));
+    assert!(scope.is_allowed(data.join("./file.txt")));
+    assert!(!scope.is_allowed(data.join("missing/../../secret.txt")));
+    assert!(!scope.is_allowed(dir.path().join("secret.txt")));
+
+    #[cfg(unix)]
+    {
+      let link = dir.path().join("link");
+      std::os::unix::fs::symlink(dir.path(), &link).unwrap();
+      assert!(scope.is_allowed(link.join("data/file.txt")));
+      assert!(!scope.is_allowed(link.join("secret.txt")));
+    }
+  }
+
+  #[test]
+  fn validates_shell_commands() {
+    let scope = ShellScope::new(&ShellAllowlistConfig {
+      scope: vec![
+        ShellAllowedCommand {
+          name: "git-log".into(),
+          cmd: "git".into(),
+          args: ShellAllowedArgs::List(vec![
+            ShellAllowedArg::Fixed("log".into()),
+            ShellAllowedArg::Var {
+              validator: "-n[0-9]+".into(),
+            },
+          ]),
+          sidecar: false,
+        },
+        ShellAllowedCommand {
+          name: "ls".into(),
+          cmd: "ls".into(),
+          args: ShellAllowedArgs::Flag(false),
+          sidecar: false,
+        },
+      ],
+      open_scope: None,
+      open_with: vec!["firefox".into()],
+    });
+    assert!(scope
+      .prepare("git-log", vec!["log".into(), "-n5".into()], false)
+      .is_ok());
+    assert!(scope
+      .prepare("git-log", vec!["log".into(), "-n5; rm".into()], false)
+      .is_err());
+    assert!(scope.prepare("git-log", vec!["log".into()], false).is_err());
+    assert!(scope.prepare("git-log", Vec::new(), true).is_err());
+    assert!(scope.prepare("ls", Vec::new(), false).is_ok());
+    assert!(scope.prepare("ls", vec!["/".into()], false).is_err());
+    assert!(scope.prepare("rm", Vec::new(), false).is_err());
+
+    assert!(scope.is_open_allowed("https://tauri.studio"));
+    assert!(scope.is_open_allowed("mailto:someone@tauri.studio"));
+    assert!(!scope.is_open_allowed("file:///etc/passwd"));
+    assert!(!scope.is_open_allowed("/usr/bin/calc"));
+    assert!(scope.is_open_with_allowed("firefox"));
+    assert!(!scope.is_open_with_allowed("/usr/bin/calc"));
+
+    let scope = ShellScope::new(&ShellAllowlistConfig {
+      scope: Vec::new(),
+      open_scope: Some("https://tauri.studio/.*".into()),
+      open_with: Vec::new(),
+    });
+    assert!(scope.is_open_allowed("https://tauri.studio/docs"));
+    assert!(!scope.is_open_allowed("evil:x?https://tauri.studio/docs"));
+  }
+}
@@ -0,0 +1,102 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  any::{Any, TypeId},
+  collections::HashMap,
+  fmt,
+  ops::Deref,
+  sync::{Arc, Mutex},
+};
+
+/// A value managed by Tauri, registered with [`crate::Manager::manage`] or [`crate::Builder::manage`].
+///
+/// Commands receive it by declaring a `State<T>` argument.
+///
+/// # Example
+/// ```rust,ignore
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct Counter(AtomicUsize);
+///
+/// #[tauri::command]
+/// fn increment(counter: tauri::State<Counter>) -> usize {
+///   counter.0.fetch_add(1, Ordering::Relaxed) + 1
+/// }
+///
+/// tauri::Builder::default()
+///   .manage(Counter(AtomicUsize::new(0)))
+///   .invoke_handler(tauri::generate_handler![increment])
+/// ```
+pub struct State<T: Send + Sync + 'static>(Arc<T>);
+
+impl<T: Send + Sync + 'static> State<T> {
+  /// The managed value.
+  pub fn inner(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: Send + Sync + 'static> Deref for State<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: Send + Sync + 'static> Clone for State<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T: Send + Sync + fmt::Debug + 'static> fmt::Debug for State<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("State").field(&self.0).finish()
+  }
+}
+
+/// The managed values, indexed by type.
+#[derive(Default)]
+pub(crate) struct StateManager {
+  map: Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl StateManager {
+  /// Manages the value. Returns `false` if a value of this type is already managed.
+  pub(crate) fn set<T: Send + Sync + 'static>(&self, state: T) -> bool {
+    let mut map = self.map.lock().expect("poisoned state manager");
+    if map.contains_key(&TypeId::of::<T>()) {
+      return false;
+    }
+    map.insert(TypeId::of::<T>(), Arc::new(state));
+    true
+  }
+
+  /// Gets the managed value of this type.
+  pub(crate) fn try_get<T: Send + Sync + 'static>(&self) -> Option<State<T>> {
+    self
+      .map
+      .lock()
+      .expect("poisoned state manager")
+      .get(&TypeId::of::<T>())
+      .cloned()
+      .map(|state| State(state.downcast().expect("state indexed by another type")))
+  }
+
+  /// Gets the managed value of this type, managing its default value first if needed.
+  ///
+  /// Used by the endpoints to keep their state with the app instead of in globals.
+  pub(crate) fn get_or_default<T: Default + Send + Sync + 'static>(&self) -> State<T> {
+    let state = self
+      .map
+      .lock()
+      .expect("poisoned state manager")
+      .entry(TypeId::of::<T>())
+      .or_insert_with(|| Arc::new(T::default()))
+      .clone();
+    State(state.downcast().expect("state indexed by another type"))
+  }
+}
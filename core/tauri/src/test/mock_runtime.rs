@@ -0,0 +1,482 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! A [`Runtime`] that doesn't create any native window, used to test Tauri applications.
+
+use crate::{
+  api::config::WindowConfig,
+  runtime::{
+    menu::{Menu, MenuItem, SystemTray, SystemTrayEvent},
+    monitor::{Monitor, PhysicalPosition, PhysicalSize, Position, Size},
+    tag::Tag,
+    webview::{Attributes, AttributesBase},
+    window::{CursorIcon, DetachedWindow, PendingWindow, WindowEvent},
+    Dispatch, Params, RunEvent, Runtime, RuntimeHandle,
+  },
+  Icon,
+};
+use serde_json::Value as JsonValue;
+use std::{
+  collections::HashMap,
+  convert::TryFrom,
+  path::PathBuf,
+  sync::{Arc, Condvar, Mutex},
+  time::{Duration, Instant},
+};
+
+/// The invoke responses captured by a [`MockDispatcher`], by success callback,
+/// and the condition notified when one is added.
+type InvokeResponses = (
+  Mutex<HashMap<String, Result<JsonValue, JsonValue>>>,
+  Condvar,
+);
+
+/// The icon type of the [`MockRuntime`]. Every [`Icon`] converts to it.
+pub struct MockIcon;
+
+impl TryFrom<Icon> for MockIcon {
+  type Error = crate::Error;
+  fn try_from(_icon: Icon) -> Result<Self, Self::Error> {
+    Ok(Self)
+  }
+}
+
+/// The webview attributes of the [`MockRuntime`].
+#[derive(Debug, Clone, Default)]
+pub struct MockAttributes {
+  has_icon: bool,
+}
+
+impl AttributesBase for MockAttributes {}
+impl Attributes for MockAttributes {
+  type Icon = MockIcon;
+
+  fn new() -> Self {
+    Default::default()
+  }
+
+  fn with_config(_config: WindowConfig) -> Self {
+    Default::default()
+  }
+
+  fn initialization_script(self, _init: &str) -> Self {
+    self
+  }
+
+  fn isolated_initialization_script(self, _init: &str) -> Self {
+    self
+  }
+
+  fn position<P: Into<Position>>(self, _position: P) -> Self {
+    self
+  }
+
+  fn size<S: Into<Size>>(self, _size: S) -> Self {
+    self
+  }
+
+  fn min_size<S: Into<Size>>(self, _min_size: S) -> Self {
+    self
+  }
+
+  fn max_size<S: Into<Size>>(self, _max_size: S) -> Self {
+    self
+  }
+
+  fn resizable(self, _resizable: bool) -> Self {
+    self
+  }
+
+  fn title<S: Into<String>>(self, _title: S) -> Self {
+    self
+  }
+
+  fn fullscreen(self, _fullscreen: bool) -> Self {
+    self
+  }
+
+  fn maximized(self, _maximized: bool) -> Self {
+    self
+  }
+
+  fn visible(self, _visible: bool) -> Self {
+    self
+  }
+
+  fn transparent(self, _transparent: bool) -> Self {
+    self
+  }
+
+  fn decorations(self, _decorations: bool) -> Self {
+    self
+  }
+
+  fn always_on_top(self, _always_on_top: bool) -> Self {
+    self
+  }
+
+  fn menu(self, _menu: Vec<Menu>) -> Self {
+    self
+  }
+
+  fn icon(mut self, _icon: Self::Icon) -> Self {
+    self.has_icon = true;
+    self
+  }
+
+  fn has_icon(&self) -> bool {
+    self.has_icon
+  }
+
+  fn user_data_path(self, _user_data_path: Option<PathBuf>) -> Self {
+    self
+  }
+
+  fn proxy_url(self, _proxy_url: Option<String>) -> Self {
+    self
+  }
+
+  fn hardware_acceleration(self, _enabled: bool) -> Self {
+    self
+  }
+
+  fn focusable(self, _focusable: bool) -> Self {
+    self
+  }
+
+  fn url(self, _url: String) -> Self {
+    self
+  }
+
+  fn build(self) -> Self {
+    self
+  }
+}
+
+/// The [`Dispatch`] of the [`MockRuntime`].
+/// It records the scripts evaluated on the window and the responses of its invokes.
+#[derive(Clone, Default)]
+pub struct MockDispatcher {
+  scripts: Arc<Mutex<Vec<String>>>,
+  invoke_responses: Arc<InvokeResponses>,
+  window_event_handler: Arc<Mutex<Option<Box<dyn Fn(WindowEvent) + Send>>>>,
+}
+
+impl MockDispatcher {
+  /// The scripts evaluated on this window, in evaluation order.
+  pub fn evaluated_scripts(&self) -> Vec<String> {
+    self.scripts.lock().expect("poisoned script list").clone()
+  }
+
+  /// Waits up to the timeout for the response of the invoke with the given success callback.
+  pub fn invoke_response(
+    &self,
+    callback: &str,
+    timeout: Duration,
+  ) -> Option<Result<JsonValue, JsonValue>> {
+    let (responses, responded) = &*self.invoke_responses;
+    let deadline = Instant::now() + timeout;
+    let mut responses = responses.lock().expect("poisoned invoke responses");
+    loop {
+      if let Some(response) = responses.remove(callback) {
+        return Some(response);
+      }
+      let now = Instant::now();
+      if now >= deadline {
+        return None;
+      }
+      responses = responded
+        .wait_timeout(responses, deadline - now)
+        .expect("poisoned invoke responses")
+        .0;
+    }
+  }
+
+  /// Reports a window event, as if it was triggered by the user or the operating system.
+  pub fn send_window_event(&self, event: WindowEvent) {
+    if let Some(handler) = &*self
+      .window_event_handler
+      .lock()
+      .expect("poisoned window event handler")
+    {
+      handler(event);
+    }
+  }
+}
+
+impl Dispatch for MockDispatcher {
+  type Runtime = MockRuntime;
+  type Icon = MockIcon;
+  type Attributes = MockAttributes;
+
+  fn runtime_handle(&self) -> MockHandle {
+    MockHandle
+  }
+
+  fn create_window<M: Params<Runtime = Self::Runtime>>(
+    &mut self,
+    pending: PendingWindow<M>,
+  ) -> crate::Result<DetachedWindow<M>> {
+    self.runtime_handle().create_window(pending)
+  }
+
+  fn set_resizable(&self, _resizable: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_menu(&self, _menu: Vec<Menu>) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_title<S: Into<String>>(&self, _title: S) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn maximize(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn unmaximize(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn minimize(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn unminimize(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn show(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn hide(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn close(&self) -> crate::Result<()> {
+    self.send_window_event(WindowEvent::Destroyed);
+    Ok(())
+  }
+
+  fn set_decorations(&self, _decorations: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_always_on_top(&self, _always_on_top: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn scale_factor(&self) -> crate::Result<f64> {
+    Ok(1.)
+  }
+
+  fn inner_size(&self) -> crate::Result<PhysicalSize> {
+    Ok(Default::default())
+  }
+
+  fn outer_size(&self) -> crate::Result<PhysicalSize> {
+    Ok(Default::default())
+  }
+
+  fn inner_position(&self) -> crate::Result<PhysicalPosition> {
+    Ok(Default::default())
+  }
+
+  fn outer_position(&self) -> crate::Result<PhysicalPosition> {
+    Ok(Default::default())
+  }
+
+  fn is_maximized(&self) -> crate::Result<bool> {
+    Ok(false)
+  }
+
+  fn is_fullscreen(&self) -> crate::Result<bool> {
+    Ok(false)
+  }
+
+  fn is_visible(&self) -> crate::Result<bool> {
+    Ok(true)
+  }
+
+  fn is_decorated(&self) -> crate::Result<bool> {
+    Ok(true)
+  }
+
+  fn set_size(&self, _size: Size) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_min_size(&self, _min_size: Size) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_max_size(&self, _max_size: Size) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_position(&self, _position: Position) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_fullscreen(&self, _fullscreen: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_icon(&self, _icon: Self::Icon) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_tabbing_identifier(&self, _identifier: Option<String>) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn merge_all_windows(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn select_next_tab(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn select_previous_tab(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn move_tab_to_new_window(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn open_devtools(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn close_devtools(&self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_cursor_visible(&self, _visible: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_cursor_grab(&self, _grab: bool) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_cursor_icon(&self, _icon: CursorIcon) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_cursor_position(&self, _position: Position) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn eval_script<S: Into<String>>(&self, script: S) -> crate::Result<()> {
+    self
+      .scripts
+      .lock()
+      .expect("poisoned script list")
+      .push(script.into());
+    Ok(())
+  }
+
+  fn captures_invoke_responses(&self) -> bool {
+    true
+  }
+
+  fn capture_invoke_response(&self, callback: String, response: Result<JsonValue, JsonValue>) {
+    let (responses, responded) = &*self.invoke_responses;
+    responses
+      .lock()
+      .expect("poisoned invoke responses")
+      .insert(callback, response);
+    responded.notify_all();
+  }
+}
+
+/// The [`RuntimeHandle`] of the [`MockRuntime`].
+///
+/// The mocked system has no monitor and the cursor is always at the origin.
+#[derive(Clone)]
+pub struct MockHandle;
+
+impl RuntimeHandle for MockHandle {
+  type Runtime = MockRuntime;
+
+  fn create_window<P: Params<Runtime = Self::Runtime>>(
+    &self,
+    pending: PendingWindow<P>,
+  ) -> crate::Result<DetachedWindow<P>> {
+    let detached = DetachedWindow {
+      label: pending.label,
+      dispatcher: MockDispatcher::default(),
+    };
+    if let Some(handler) = pending.window_event_handler {
+      let window = detached.clone();
+      detached
+        .dispatcher
+        .window_event_handler
+        .lock()
+        .expect("poisoned window event handler")
+        .replace(Box::new(move |event| handler(event, window.clone())));
+    }
+    Ok(detached)
+  }
+
+  fn cursor_position(&self) -> crate::Result<PhysicalPosition> {
+    Ok(PhysicalPosition::default())
+  }
+
+  fn available_monitors(&self) -> crate::Result<Vec<Monitor>> {
+    Ok(Vec::new())
+  }
+
+  fn primary_monitor(&self) -> crate::Result<Option<Monitor>> {
+    Ok(None)
+  }
+
+  fn set_tray_icon(&self, _icon: Icon) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn set_tray_menu(&self, _menu: Vec<MenuItem>) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+/// A [`Runtime`] that doesn't create native windows. [`Runtime::run`] returns immediately.
+pub struct MockRuntime;
+
+impl Runtime for MockRuntime {
+  type Dispatcher = MockDispatcher;
+  type Handle = MockHandle;
+
+  fn new() -> crate::Result<Self> {
+    Ok(Self)
+  }
+
+  fn handle(&self) -> Self::Handle {
+    MockHandle
+  }
+
+  fn create_window<P: Params<Runtime = Self>>(
+    &mut self,
+    pending: PendingWindow<P>,
+  ) -> crate::Result<DetachedWindow<P>> {
+    self.handle().create_window(pending)
+  }
+
+  fn system_tray(&mut self, _system_tray: SystemTray) -> crate::Result<()> {
+    Ok(())
+  }
+
+  fn on_system_tray_event<F: Fn(&SystemTrayEvent) + Send + 'static>(&mut self, _handler: F) {}
+
+  // there's no event loop to wait on
+  fn run<L: Tag, F: Fn(RunEvent<L>) + Send + Sync + 'static>(self, callback: F) {
+    callback(RunEvent::Ready);
+  }
+}
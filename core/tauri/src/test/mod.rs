@@ -0,0 +1,854 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Utilities to unit test Tauri applications without spawning a webview.
+//!
+//! Build the application with the [`MockRuntime`] and [`mock_context`], then call its
+//! commands with [`mock_invoke`]:
+//!
+//! ```rust
+//! use tauri::test::{mock_context, mock_invoke, MockRuntime, NoopAsset};
+//!
+//! let app = tauri::Builder::<String, String, NoopAsset, MockRuntime>::new()
+//!   .invoke_handler(|message| match message.command() {
+//!     "ping" => message.resolve("pong"),
+//!     _ => message.reject("unknown command"),
+//!   })
+//!   .build(mock_context())
+//!   .expect("failed to build app");
+//!
+//! assert_eq!(
+//!   mock_invoke(&app, "ping", serde_json::Value::Null),
+//!   Ok(serde_json::json!("pong"))
+//! );
+//! ```
+
+mod mock_runtime;
+
+pub use mock_runtime::{MockAttributes, MockDispatcher, MockHandle, MockIcon, MockRuntime};
+
+use crate::{
+  api::{
+    assets::{AssetKey, Assets},
+    config::Config,
+    PackageInfo,
+  },
+  hooks::InvokePayload,
+  App, Context, Manager, Params, Window,
+};
+use serde_json::Value as JsonValue;
+use std::{borrow::Cow, time::Duration};
+use uuid::Uuid;
+
+/// How long [`mock_invoke`] waits for the command to respond.
+const INVOKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An [`Assets`] implementation without any asset.
+pub struct NoopAsset;
+
+impl Assets for NoopAsset {
+  fn get<Key: Into<AssetKey>>(&self, _key: Key) -> Option<Cow<'_, [u8]>> {
+    None
+  }
+}
+
+/// Creates a [`Context`] with the default configuration, which defines a single `main` window.
+pub fn mock_context() -> Context<NoopAsset> {
+  Context {
+    config: Config {
+      tauri: Default::default(),
+      build: Default::default(),
+      plugins: Default::default(),
+    },
+    assets: NoopAsset,
+    default_window_icon: None,
+    package_info: PackageInfo {
+      name: "test",
+      version: "0.1.0",
+    },
+  }
+}
+
+/// Invokes a command on one of the application windows and waits for its response.
+///
+/// The command is handled exactly as if the webview invoked it, so the invoke handler,
+/// the plugins and the Tauri API modules are all reachable.
+/// Returns the serialized value the command resolved or rejected with.
+///
+/// # Panics
+///
+/// Panics if the application has no window or if the command doesn't respond in 10 seconds.
+pub fn mock_invoke<P: Params<Runtime = MockRuntime>>(
+  app: &App<P>,
+  command: &str,
+  payload: JsonValue,
+) -> Result<JsonValue, JsonValue> {
+  let window = app
+    .windows()
+    .into_iter()
+    .next()
+    .map(|(_, window)| window)
+    .expect("the app has no window to invoke the command on");
+  mock_window_invoke(&window, command, payload)
+}
+
+/// Invokes a command on the given window and waits for its response. See [`mock_invoke`].
+///
+/// The Tauri API modules are invoked with a `__tauriModule` payload key,
+/// like the `@tauri-apps/api` package does.
+pub fn mock_window_invoke<P: Params<Runtime = MockRuntime>>(
+  window: &Window<P>,
+  command: &str,
+  mut payload: JsonValue,
+) -> Result<JsonValue, JsonValue> {
+  let id = Uuid::new_v4();
+  let callback = format!("_{}", id.to_simple());
+  let error = format!("_{}_error", id.to_simple());
+
+  let tauri_module = payload
+    .as_object_mut()
+    .and_then(|payload| payload.remove("__tauriModule"))
+    .and_then(|module| module.as_str().map(ToString::to_string));
+  let message = InvokePayload {
+    tauri_module,
+    callback: callback.clone(),
+    error: error.clone(),
+    main_thread: false,
+    inner: payload,
+  };
+  window
+    .clone()
+    .on_message(command.to_string(), message)
+    .expect("failed to handle the command");
+
+  window
+    .dispatcher()
+    .invoke_response(&callback, INVOKE_TIMEOUT)
+    .unwrap_or_else(|| panic!("the `{}` command did not respond", command))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::{thread::sleep, time::Instant};
+
+  /// How often the tests check if the mocked window received a script.
+  const INVOKE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+  #[test]
+  fn invokes_command() {
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .invoke_handler(|message| match message.command() {
+        "echo" => {
+          let payload = message.payload();
+          message.resolve(payload)
+        }
+        "fail" => message.reject("failed"),
+        _ => {}
+      })
+      .build(mock_context())
+      .expect("failed to build app");
+
+    let payload = serde_json::json!({ "message": "hello" });
+    assert_eq!(mock_invoke(&app, "echo", payload.clone()), Ok(payload));
+    assert_eq!(
+      mock_invoke(&app, "fail", JsonValue::Null),
+      Err(JsonValue::String("failed".into()))
+    );
+  }
+
+  #[test]
+  fn prevents_window_close() {
+    use crate::{
+      runtime::window::{CloseRequestApi, WindowEvent},
+      Manager,
+    };
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    window.on_window_event(|event| {
+      if let WindowEvent::CloseRequested(api) = event {
+        api.prevent_close();
+      }
+    });
+
+    let api = CloseRequestApi::default();
+    window
+      .dispatcher()
+      .send_window_event(WindowEvent::CloseRequested(api.clone()));
+    assert!(api.is_close_prevented());
+
+    window
+      .dispatcher()
+      .send_window_event(WindowEvent::Destroyed);
+    assert!(app.get_window(&"main".into()).is_none());
+  }
+
+  #[test]
+  fn hides_window_on_close() {
+    use crate::{
+      api::config::CloseBehavior,
+      runtime::window::{CloseRequestApi, WindowEvent},
+      Manager,
+    };
+
+    let mut context = mock_context();
+    context.config.tauri.windows[0].close_behavior = CloseBehavior::Hide;
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(context)
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+
+    let api = CloseRequestApi::default();
+    window
+      .dispatcher()
+      .send_window_event(WindowEvent::CloseRequested(api.clone()));
+    assert!(api.is_close_prevented());
+    assert!(app.get_window(&"main".into()).is_some());
+  }
+
+  #[test]
+  fn emits_builtin_window_events() {
+    use crate::{
+      runtime::window::{Theme, WindowEvent},
+      Manager,
+    };
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .window_event_throttle(Duration::default())
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let dispatcher = window.dispatcher();
+    dispatcher.send_window_event(WindowEvent::Focused(false));
+    dispatcher.send_window_event(WindowEvent::Moved { x: 10, y: 20 });
+    dispatcher.send_window_event(WindowEvent::ThemeChanged(Theme::Dark));
+
+    let scripts = dispatcher.evaluated_scripts();
+    let emitted = |event: &str| scripts.iter().any(|script| script.contains(event));
+    assert!(emitted(r#"event: "tauri://blur""#));
+    assert!(!emitted(r#"event: "tauri://focus""#));
+    assert!(emitted(r#"event: "tauri://move""#));
+    assert!(emitted(r#"event: "tauri://theme-changed""#));
+  }
+
+  #[test]
+  fn saves_window_snapshot_on_close() {
+    use crate::{
+      runtime::window::{CloseRequestApi, WindowEvent},
+      window_snapshot::read_window_snapshots,
+      Manager,
+    };
+
+    let mut context = mock_context();
+    context.config.tauri.bundle.identifier = format!("studio.tauri.snapshot-{}", Uuid::new_v4());
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .window_snapshot_timeout(INVOKE_TIMEOUT)
+      .build(context)
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let api = CloseRequestApi::default();
+    window
+      .dispatcher()
+      .send_window_event(WindowEvent::CloseRequested(api.clone()));
+    // the window stays open until the page replies with its state
+    assert!(api.is_close_prevented());
+
+    let started = Instant::now();
+    let id = loop {
+      let id = window
+        .dispatcher()
+        .evaluated_scripts()
+        .iter()
+        .filter(|script| script.contains("__windowSnapshotProvider"))
+        .find_map(|script| {
+          let start = script.find("payload.id = \"")? + "payload.id = \"".len();
+          let end = start + script[start..].find('"')?;
+          Some(script[start..end].to_string())
+        });
+      if let Some(id) = id {
+        break id;
+      }
+      assert!(
+        started.elapsed() < INVOKE_TIMEOUT,
+        "the snapshot wasn't requested"
+      );
+      sleep(INVOKE_POLL_INTERVAL);
+    };
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "id": id, "result": { "snapshot": { "draft": "hello" } } }),
+    };
+    window
+      .on_message("__evalResult".into(), payload)
+      .expect("failed to send the snapshot");
+
+    let started = Instant::now();
+    while app.get_window(&"main".into()).is_some() {
+      assert!(
+        started.elapsed() < INVOKE_TIMEOUT,
+        "the window wasn't closed"
+      );
+      sleep(INVOKE_POLL_INTERVAL);
+    }
+    // the app directory is a temporary directory on the unit tests
+    let snapshots = read_window_snapshots(app.config()).expect("failed to read the snapshots");
+    assert_eq!(
+      snapshots.get("main"),
+      Some(&serde_json::json!({ "draft": "hello" }))
+    );
+    if let Some(dir) = crate::api::path::app_dir(app.config()) {
+      let _ = std::fs::remove_dir_all(dir);
+    }
+  }
+
+  #[test]
+  fn coalesces_window_events() {
+    use crate::{runtime::window::WindowEvent, Manager};
+    use std::sync::{mpsc::channel, Mutex};
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .window_event_throttle(Duration::from_millis(500))
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let (tx, rx) = channel();
+    let tx = Mutex::new(tx);
+    window.on_window_event(move |event| {
+      if let WindowEvent::Resized { width, .. } = event {
+        let _ = tx.lock().unwrap().send(*width);
+      }
+    });
+
+    for width in 1..=10 {
+      window
+        .dispatcher()
+        .send_window_event(WindowEvent::Resized { width, height: 600 });
+    }
+    // the first event is delivered right away, then the latest of each interval,
+    // however long the events took to be sent
+    let mut widths = Vec::new();
+    while widths.last() != Some(&10) {
+      let width = rx
+        .recv_timeout(INVOKE_TIMEOUT)
+        .expect("the last event wasn't delivered");
+      widths.push(width);
+    }
+    assert_eq!(widths[0], 1);
+    assert!(widths.len() < 10);
+    assert!(widths.windows(2).all(|pair| pair[0] < pair[1]));
+  }
+
+  #[test]
+  fn sends_run_events() {
+    use crate::{runtime::window::WindowEvent, Manager, RunEvent};
+    use std::sync::{Arc, Mutex};
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    app.run(move |_, event| {
+      let name = match event {
+        RunEvent::Ready => "ready".to_string(),
+        RunEvent::WindowClosed(label) => format!("closed {}", label),
+        RunEvent::ExitRequested(api) => {
+          api.prevent_exit();
+          "exit requested".to_string()
+        }
+        RunEvent::Exit => "exit".to_string(),
+      };
+      events_.lock().unwrap().push(name);
+    });
+
+    window
+      .dispatcher()
+      .send_window_event(WindowEvent::Destroyed);
+    assert_eq!(
+      *events.lock().unwrap(),
+      vec!["ready", "closed main", "exit requested"]
+    );
+  }
+
+  #[test]
+  fn tears_down_on_exit() {
+    use crate::{sealed::ManagerBase, Manager, RunEvent};
+    use std::sync::{Arc, Mutex};
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let handle = app.handle();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    app.run(move |_, event| {
+      let name = match event {
+        RunEvent::Ready => "ready".to_string(),
+        RunEvent::WindowClosed(label) => format!("closed {}", label),
+        RunEvent::ExitRequested(_) => "exit requested".to_string(),
+        RunEvent::Exit => "exit".to_string(),
+      };
+      events_.lock().unwrap().push(name);
+    });
+
+    // the part of `AppHandle::exit` before the process exits
+    handle.manager().teardown();
+    assert!(handle.windows().is_empty());
+    assert_eq!(
+      *events.lock().unwrap(),
+      vec!["ready", "closed main", "exit"]
+    );
+  }
+
+  #[test]
+  fn vetoes_navigation() {
+    use crate::Manager;
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .on_navigation(|_, url| !url.starts_with("https://evil.example"))
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let url = window.url();
+    assert!(!url.is_empty());
+
+    assert!(window.navigate("https://id.example/login").is_ok());
+    assert!(matches!(
+      window.navigate("https://evil.example/login"),
+      Err(crate::Error::NavigationNotAllowed(_))
+    ));
+    assert_eq!(window.url(), url);
+  }
+
+  #[test]
+  fn closes_splashscreen_when_main_window_loads() {
+    use crate::{api::config::WindowConfig, Manager};
+
+    let mut context = mock_context();
+    context.config.tauri.windows.push(WindowConfig {
+      label: "splashscreen".into(),
+      splashscreen: true,
+      ..Default::default()
+    });
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(context)
+      .expect("failed to build app");
+    assert!(app.get_window(&"splashscreen".into()).is_some());
+
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "url": "tauri://localhost" }),
+    };
+    window
+      .on_message("__initialized".into(), payload)
+      .expect("failed to load the page");
+    assert!(app.get_window(&"splashscreen".into()).is_none());
+  }
+
+  #[test]
+  fn closes_splashscreen_on_request() {
+    use crate::{api::config::WindowConfig, Manager};
+
+    let mut context = mock_context();
+    context.config.tauri.windows.push(WindowConfig {
+      label: "splashscreen".into(),
+      splashscreen: true,
+      splashscreen_auto_close: false,
+      ..Default::default()
+    });
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(context)
+      .expect("failed to build app");
+
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "url": "tauri://localhost" }),
+    };
+    window
+      .on_message("__initialized".into(), payload)
+      .expect("failed to load the page");
+    assert!(app.get_window(&"splashscreen".into()).is_some());
+
+    app.close_splashscreen();
+    assert!(app.get_window(&"splashscreen".into()).is_none());
+  }
+
+  #[test]
+  fn resolves_preloaded_window() {
+    use crate::{
+      api::config::{WindowConfig, WindowUrl},
+      runtime::window::PendingWindow,
+      Manager,
+    };
+
+    let mut app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let pending = PendingWindow::with_config(
+      WindowConfig::default(),
+      "preloaded".into(),
+      WindowUrl::default(),
+    );
+    let preloaded = app
+      .create_preloaded_window(pending)
+      .expect("failed to create window");
+    assert!(!preloaded.is_ready());
+
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "url": "tauri://localhost" }),
+    };
+    preloaded
+      .window()
+      .clone()
+      .on_message("__initialized".into(), payload)
+      .expect("failed to load the page");
+    let window = crate::async_runtime::block_on(preloaded.ready());
+    assert_eq!(window.label(), "preloaded");
+  }
+
+  #[test]
+  fn unlistens_window_events() {
+    use crate::Manager;
+    use std::sync::{
+      atomic::{AtomicUsize, Ordering},
+      Arc,
+    };
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_ = calls.clone();
+    let handler = window.listen("tick".into(), move |_| {
+      calls_.fetch_add(1, Ordering::Relaxed);
+    });
+    window.trigger("tick".into(), None);
+
+    window.unlisten(handler);
+    window.trigger("tick".into(), None);
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+  }
+
+  #[test]
+  fn restricts_window_api_modules() {
+    use crate::{sealed::ManagerBase, Manager};
+
+    let mut context = mock_context();
+    context.config.tauri.windows[0].allowlist = Some(vec![
+      "fs".into(),
+      "command:greet".into(),
+      "plugin:sql".into(),
+      "plugin:store|get".into(),
+    ]);
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(context)
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let manager = window.manager();
+
+    assert!(manager.is_module_allowed(window.label(), "Fs"));
+    assert!(manager.is_module_allowed(window.label(), "Event"));
+    assert!(!manager.is_module_allowed(window.label(), "Shell"));
+
+    assert!(manager.is_command_allowed(window.label(), "greet"));
+    assert!(!manager.is_command_allowed(window.label(), "delete_account"));
+    assert!(!manager.is_command_allowed(window.label(), "fs"));
+    assert!(manager.is_command_allowed(window.label(), "plugin:sql|execute"));
+    assert!(manager.is_command_allowed(window.label(), "plugin:store|get"));
+    assert!(!manager.is_command_allowed(window.label(), "plugin:store|set"));
+    assert!(!manager.is_command_allowed(window.label(), "plugin:sqlite|execute"));
+  }
+
+  #[test]
+  #[should_panic(expected = "reserved")]
+  fn rejects_tauri_uri_scheme_protocol() {
+    crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .register_uri_scheme_protocol("tauri", |_| Ok(Vec::new().into()));
+  }
+
+  #[test]
+  fn emits_data_drops() {
+    use crate::Manager;
+    use std::sync::mpsc::channel;
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let (tx, rx) = channel();
+    window.listen("tauri://drop".into(), move |event| {
+      tx.send(event.payload().map(ToString::to_string)).unwrap();
+    });
+
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({
+        "items": { "text/plain": "dropped" },
+        "position": { "x": 10.0, "y": 20.0 }
+      }),
+    };
+    window
+      .on_message("__drop".into(), payload)
+      .expect("failed to handle the drop");
+
+    let data: JsonValue = serde_json::from_str(&rx.recv().unwrap().expect("missing payload"))
+      .expect("invalid drop payload");
+    assert_eq!(data["items"]["text/plain"], "dropped");
+    assert_eq!(data["position"]["y"], 20.0);
+  }
+
+  #[test]
+  fn forwards_js_errors() {
+    use crate::{JsErrorKind, Manager};
+    use std::sync::mpsc::channel;
+
+    let (tx, rx) = channel();
+    let tx = std::sync::Mutex::new(tx);
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .on_js_error(move |window, error| {
+        tx.lock()
+          .unwrap()
+          .send((window.label().clone(), error.clone()))
+          .unwrap();
+      })
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({
+        "kind": "unhandledRejection",
+        "message": "network down",
+        "stack": "Error: network down\n    at load (app.js:3:9)"
+      }),
+    };
+    window
+      .on_message("__jsError".into(), payload)
+      .expect("failed to forward the error");
+
+    let (label, error) = rx.recv().unwrap();
+    assert_eq!(label, "main");
+    assert_eq!(error.kind(), JsErrorKind::UnhandledRejection);
+    assert_eq!(error.message(), "network down");
+    assert!(error.stack().unwrap().contains("app.js:3:9"));
+    assert_eq!(error.line(), None);
+  }
+
+  #[test]
+  fn emits_to_window() {
+    use crate::{
+      api::config::{WindowConfig, WindowUrl},
+      runtime::window::PendingWindow,
+      Manager,
+    };
+
+    let mut app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let other = app
+      .create_window(PendingWindow::with_config(
+        WindowConfig::default(),
+        "other".into(),
+        WindowUrl::default(),
+      ))
+      .expect("failed to create window");
+    let main = app.get_window(&"main".into()).expect("missing main window");
+
+    app
+      .emit_to(&"other".into(), "greeting".into(), Some("hello"))
+      .expect("failed to emit");
+    fn emitted<P: Params<Runtime = MockRuntime>>(window: &Window<P>) -> bool {
+      window
+        .dispatcher()
+        .evaluated_scripts()
+        .iter()
+        .any(|script| script.contains("greeting"))
+    }
+    assert!(emitted(&other));
+    assert!(!emitted(&main));
+
+    assert!(matches!(
+      app.emit_to(&"missing".into(), "greeting".into(), None::<()>),
+      Err(crate::Error::WebviewNotFound)
+    ));
+  }
+
+  #[test]
+  fn returns_eval_result() {
+    use crate::Manager;
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let window_ = window.clone();
+    let evaluation = std::thread::spawn(move || {
+      crate::async_runtime::block_on(window_.eval_with_result("document.title"))
+    });
+
+    // the page replies with the id embedded on the evaluated script
+    let started = Instant::now();
+    let id = loop {
+      let id = window
+        .dispatcher()
+        .evaluated_scripts()
+        .iter()
+        .find_map(|script| {
+          let start = script.find("payload.id = \"")? + "payload.id = \"".len();
+          let end = start + script[start..].find('"')?;
+          Some(script[start..end].to_string())
+        });
+      if let Some(id) = id {
+        break id;
+      }
+      assert!(
+        started.elapsed() < INVOKE_TIMEOUT,
+        "the script wasn't evaluated"
+      );
+      sleep(INVOKE_POLL_INTERVAL);
+    };
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "id": id, "result": "Tauri App" }),
+    };
+    window
+      .on_message("__evalResult".into(), payload)
+      .expect("failed to send the result");
+
+    let result = evaluation.join().expect("evaluation panicked");
+    assert_eq!(result.expect("evaluation failed"), "Tauri App");
+  }
+
+  #[test]
+  fn emits_js_events_to_other_windows() {
+    use crate::{
+      api::config::{WindowConfig, WindowUrl},
+      runtime::window::PendingWindow,
+      Manager,
+    };
+
+    let mut app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let other = app
+      .create_window(PendingWindow::with_config(
+        WindowConfig::default(),
+        "other".into(),
+        WindowUrl::default(),
+      ))
+      .expect("failed to create window");
+    let main = app.get_window(&"main".into()).expect("missing main window");
+
+    let response = mock_window_invoke(
+      &main,
+      "tauri",
+      serde_json::json!({
+        "__tauriModule": "Event",
+        "message": { "cmd": "emit", "event": "greeting", "payload": "hello" }
+      }),
+    );
+    assert_eq!(response, Ok(JsonValue::Null));
+    assert!(other
+      .dispatcher()
+      .evaluated_scripts()
+      .iter()
+      .any(|script| script.contains(r#"event: "greeting", windowLabel: "main""#)));
+  }
+
+  #[test]
+  fn reports_tampering() {
+    use crate::Manager;
+    use std::sync::mpsc::channel;
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let (tx, rx) = channel();
+    window.listen("tauri://tampering".into(), move |event| {
+      tx.send(event.payload().map(ToString::to_string)).unwrap();
+    });
+
+    let payload = InvokePayload {
+      tauri_module: None,
+      callback: String::new(),
+      error: String::new(),
+      main_thread: false,
+      inner: serde_json::json!({ "reason": "set window.__TAURI__.invoke" }),
+    };
+    window
+      .on_message("__tampering".into(), payload)
+      .expect("failed to handle the report");
+
+    let data: JsonValue = serde_json::from_str(&rx.recv().unwrap().expect("missing payload"))
+      .expect("invalid tampering payload");
+    assert_eq!(data["reason"], "set window.__TAURI__.invoke");
+  }
+
+  #[test]
+  fn skips_disabled_permission_prompts() {
+    use crate::{api::config::PermissionPrompt, sealed::ManagerBase, Manager};
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .build(mock_context())
+      .expect("failed to build app");
+    let window = app.get_window(&"main".into()).expect("missing main window");
+    let answer =
+      window
+        .manager()
+        .request_permission(window.label(), PermissionPrompt::ShellExecute, "ls");
+    assert_eq!(answer, None);
+  }
+
+  #[test]
+  fn manages_state() {
+    use crate::Manager;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counter(AtomicUsize);
+
+    let app = crate::Builder::<String, String, NoopAsset, MockRuntime>::new()
+      .manage(Counter(AtomicUsize::new(1)))
+      .build(mock_context())
+      .expect("failed to build app");
+    app.state::<Counter>().0.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(app.state::<Counter>().0.load(Ordering::Relaxed), 2);
+
+    assert!(!app.manage(Counter(AtomicUsize::new(0))));
+    assert!(app.try_state::<String>().is_none());
+    assert!(app.manage(String::from("managed")));
+    assert_eq!(app.state::<String>().as_str(), "managed");
+  }
+}
@@ -340,11 +340,7 @@ mod error;
 pub use self::error::Error;
 
 use crate::{
-  api::{
-    app::restart_application,
-    config::UpdaterConfig,
-    dialog::{ask, AskResponse},
-  },
+  api::{app::restart_application, config::UpdaterConfig, dialog::blocking::ask},
   Params, Window,
 };
 
@@ -574,34 +570,25 @@ Release Notes:
     ),
   );
 
-  match should_install {
-    AskResponse::Yes => {
-      // Launch updater download process
-      // macOS we display the `Ready to restart dialog` asking to restart
-      // Windows is closing the current App and launch the downloaded MSI when ready (the process stop here)
-      // Linux we replace the AppImage by launching a new install, it start a new AppImage instance, so we're closing the previous. (the process stop here)
-      updater.download_and_install(pubkey.clone()).await?;
-
-      // Ask user if we need to restart the application
-      let should_exit = ask(
-        "Ready to Restart",
-        "The installation was successful, do you want to restart the application now?",
-      );
-      match should_exit {
-        AskResponse::Yes => {
-          restart_application(None);
-          // safely exit even if the process
-          // should be killed
-          return Ok(());
-        }
-        AskResponse::No => {
-          // Do nothing -- maybe we can emit some event here
-        }
-      }
-    }
-    AskResponse::No => {
-      // Do nothing -- maybe we can emit some event here
+  if should_install {
+    // Launch updater download process
+    // macOS we display the `Ready to restart dialog` asking to restart
+    // Windows is closing the current App and launch the downloaded MSI when ready (the process stop here)
+    // Linux we replace the AppImage by launching a new install, it start a new AppImage instance, so we're closing the previous. (the process stop here)
+    updater.download_and_install(pubkey.clone()).await?;
+
+    // Ask user if we need to restart the application
+    let should_exit = ask(
+      "Ready to Restart",
+      "The installation was successful, do you want to restart the application now?",
+    );
+    if should_exit {
+      restart_application(None);
+      // safely exit even if the process
+      // should be killed
+      return Ok(());
     }
+    // Do nothing -- maybe we can emit some event here
   }
 
   Ok(())
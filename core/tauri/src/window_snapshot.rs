@@ -0,0 +1,59 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::api::{
+  config::Config,
+  file::read_string,
+  path::{resolve_path, BaseDirectory},
+};
+use serde_json::Value as JsonValue;
+use std::{
+  collections::HashMap,
+  fs::File,
+  io::Write,
+  path::{Path, PathBuf},
+};
+
+/// The state blobs returned by the frontend snapshot providers when the windows were closed,
+/// by window label.
+pub type WindowSnapshots = HashMap<String, JsonValue>;
+
+/// Gets the path to the window snapshot file
+fn get_window_snapshot_path(config: &Config) -> crate::api::Result<PathBuf> {
+  resolve_path(config, ".window-snapshots.json", Some(BaseDirectory::App))
+}
+
+/// Write the window snapshots to the file system.
+///
+/// The file is replaced atomically, so a crash while writing keeps the previous snapshots.
+pub(crate) fn write_window_snapshots(
+  config: &Config,
+  snapshots: &WindowSnapshots,
+) -> crate::Result<()> {
+  let snapshot_path = get_window_snapshot_path(config)?;
+  let snapshot_folder = Path::new(&snapshot_path).parent().unwrap();
+  if !snapshot_folder.exists() {
+    std::fs::create_dir_all(snapshot_folder)?;
+  }
+  let temp_path = snapshot_path.with_extension("json.tmp");
+  File::create(&temp_path)
+    .map_err(Into::into)
+    .and_then(|mut f| {
+      f.write_all(serde_json::to_string(snapshots)?.as_bytes())?;
+      f.sync_all().map_err(Into::into)
+    })
+    .and_then(|_| std::fs::rename(&temp_path, &snapshot_path).map_err(Into::into))
+}
+
+/// Reads the window snapshots saved when the windows were closed from the file system.
+pub fn read_window_snapshots(config: &Config) -> crate::Result<WindowSnapshots> {
+  let snapshot_path = get_window_snapshot_path(config)?;
+  if snapshot_path.exists() {
+    read_string(snapshot_path)
+      .and_then(|snapshots| serde_json::from_str(snapshots.as_str()).map_err(Into::into))
+      .map_err(Into::into)
+  } else {
+    Ok(Default::default())
+  }
+}
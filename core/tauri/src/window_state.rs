@@ -0,0 +1,111 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::api::{
+  config::Config,
+  file::read_string,
+  path::{resolve_path, BaseDirectory},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+/// The geometry of a window when it was last closed, in physical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct WindowState {
+  /// The outer width.
+  pub width: f64,
+  /// The outer height.
+  pub height: f64,
+  /// The outer left position.
+  pub x: f64,
+  /// The outer top position.
+  pub y: f64,
+  /// Whether the window was maximized.
+  pub maximized: bool,
+}
+
+impl WindowState {
+  /// Records the window geometry, keeping the unmaximized geometry of a maximized window.
+  pub(crate) fn update(&mut self, width: f64, height: f64, x: f64, y: f64, maximized: bool) {
+    if !maximized {
+      self.width = width;
+      self.height = height;
+      self.x = x;
+      self.y = y;
+    }
+    self.maximized = maximized;
+  }
+}
+
+/// The window states, by window label.
+pub type WindowStates = HashMap<String, WindowState>;
+
+/// A window open when the application last ran, see [`crate::App::restore_session`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WindowSession {
+  /// The window label.
+  pub label: String,
+  /// The URL of the page loaded on the window.
+  pub url: String,
+}
+
+/// What's saved on the window state file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct WindowStore {
+  /// The geometry of the closed windows.
+  #[serde(default)]
+  pub states: WindowStates,
+  /// The windows open when the application last ran, in creation order.
+  #[serde(default)]
+  pub session: Vec<WindowSession>,
+}
+
+/// Gets the path to the window state file
+fn get_window_state_path(config: &Config) -> crate::api::Result<PathBuf> {
+  resolve_path(config, ".window-state.json", Some(BaseDirectory::App))
+}
+
+/// Write the window store to the file system.
+pub(crate) fn write_window_store(config: &Config, store: &WindowStore) -> crate::Result<()> {
+  let state_path = get_window_state_path(config)?;
+  if let Some(state_folder) = state_path.parent() {
+    std::fs::create_dir_all(state_folder)?;
+  }
+  std::fs::write(state_path, serde_json::to_vec(store)?)?;
+  Ok(())
+}
+
+/// Reads the window store, saved when the windows were closed, from the file system.
+pub fn read_window_store(config: &Config) -> crate::Result<WindowStore> {
+  let state_path = get_window_state_path(config)?;
+  if state_path.exists() {
+    read_string(state_path)
+      .and_then(|store| serde_json::from_str(store.as_str()).map_err(Into::into))
+      .map_err(Into::into)
+  } else {
+    Ok(Default::default())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn keeps_unmaximized_geometry() {
+    let mut state = WindowState::default();
+    state.update(800., 600., 10., 20., false);
+    state.update(1920., 1080., 0., 0., true);
+    assert_eq!(
+      state,
+      WindowState {
+        width: 800.,
+        height: 600.,
+        x: 10.,
+        y: 20.,
+        maximized: true,
+      }
+    );
+  }
+}
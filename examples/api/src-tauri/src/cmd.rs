@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use serde::Deserialize;
-use tauri::command;
+use tauri::{command, Manager};
 
 #[derive(Debug, Deserialize)]
 pub struct RequestBody {
@@ -11,7 +11,7 @@ pub struct RequestBody {
   name: String,
 }
 
-#[command(with_window)]
+#[command]
 pub fn log_operation<M: tauri::Params>(
   _window: tauri::Window<M>,
   event: String,
@@ -25,3 +25,17 @@ pub fn perform_request(endpoint: String, body: RequestBody) -> String {
   println!("{} {:?}", endpoint, body);
   "message response".into()
 }
+
+#[command]
+pub async fn read_file_length<M: tauri::Params>(
+  window: tauri::Window<M>,
+  path: String,
+) -> Result<u64, String> {
+  // like the fs APIs, only read the paths on the `tauri > allowlist > fs > scope`
+  if !window.fs_scope().is_allowed(&path) {
+    return Err(format!("`{}` is outside of the fs scope", path));
+  }
+  std::fs::metadata(path)
+    .map(|metadata| metadata.len())
+    .map_err(|e| e.to_string())
+}
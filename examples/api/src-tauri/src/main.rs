@@ -33,7 +33,8 @@ fn main() {
     })
     .invoke_handler(tauri::generate_handler![
       cmd::log_operation,
-      cmd::perform_request
+      cmd::perform_request,
+      cmd::read_file_length
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
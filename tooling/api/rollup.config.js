@@ -27,7 +27,16 @@ export default [
       window: './src/window.ts',
       cli: './src/cli.ts',
       notification: './src/notification.ts',
-      globalShortcut: './src/globalShortcut.ts'
+      globalShortcut: './src/globalShortcut.ts',
+      clipboard: './src/clipboard.ts',
+      font: './src/font.ts',
+      geolocation: './src/geolocation.ts',
+      printer: './src/printer.ts',
+      share: './src/share.ts',
+      serial: './src/serial.ts',
+      bluetooth: './src/bluetooth.ts',
+      system: './src/system.ts',
+      license: './src/license.ts'
     },
     treeshake: true,
     perf: true,
@@ -49,6 +49,131 @@ async function getTauriVersion(): Promise<string> {
   })
 }
 
+/**
+ * Gets the random identifier of this install of the app, generated on the first call.
+ * It isn't derived from the hardware, so it changes when the app data is removed.
+ *
+ * @returns A promise resolving to the install identifier, a UUID.
+ */
+async function getInstallId(): Promise<string> {
+  return invokeTauriCommand<string>({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'getInstallId'
+    }
+  })
+}
+
+export interface AppearancePreferences {
+  /** The system accent color in the `#rrggbb` format, if available. */
+  accentColor: string | null
+  /** Whether the user asked to minimize animations. */
+  reducedMotion: boolean
+  /** Whether the user asked to reduce transparency effects. */
+  reducedTransparency: boolean
+  /** Whether the high contrast mode is active. */
+  highContrast: boolean
+}
+
+/**
+ * Gets the desktop appearance preferences.
+ * Listen to the `tauri://appearance-changed` event to be notified of changes
+ * (requires `Builder::appearance_events` on the Rust side).
+ *
+ * @returns A promise resolving to the appearance preferences.
+ */
+async function getAppearance(): Promise<AppearancePreferences> {
+  return invokeTauriCommand<AppearancePreferences>({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'getAppearance'
+    }
+  })
+}
+
+/**
+ * Gets the time since the user last used the keyboard or the mouse.
+ * Listen to the `tauri://user-idle` and `tauri://user-active` events to be notified
+ * when the user goes idle or comes back (requires `Builder::idle_events` on the Rust side).
+ *
+ * @returns A promise resolving to the idle time in milliseconds, or `null` if it can't be read.
+ */
+async function getIdleTime(): Promise<number | null> {
+  return invokeTauriCommand<number | null>({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'getIdleTime'
+    }
+  })
+}
+
+export type SystemSound =
+  | 'beep'
+  | 'error'
+  | 'warning'
+  | 'information'
+  | 'question'
+
+/**
+ * Plays a sound of the desktop theme natively, so it isn't subject to the webview autoplay policies.
+ *
+ * @param sound The sound to play
+ * @returns A promise resolving when the sound starts playing.
+ */
+async function playSound(sound: SystemSound = 'beep'): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'playSound',
+      sound
+    }
+  })
+}
+
+export type HandlerTarget =
+  | { type: 'scheme'; value: string }
+  | { type: 'extension'; value: string }
+
+/**
+ * Checks whether the app is the default handler of a URL scheme, e.g. `myapp` for `myapp://` links,
+ * or of a file extension without the dot.
+ * @example
+ * ```typescript
+ * import { isDefaultHandler } from '@tauri-apps/api/app'
+ * const isDefault = await isDefaultHandler({ type: 'scheme', value: 'myapp' })
+ * ```
+ *
+ * @param target The URL scheme or file extension
+ * @returns A promise resolving to whether the app is the default handler.
+ */
+async function isDefaultHandler(target: HandlerTarget): Promise<boolean> {
+  return invokeTauriCommand<boolean>({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'isDefaultHandler',
+      target
+    }
+  })
+}
+
+/**
+ * Asks the system to make the app the default handler of a URL scheme or file extension.
+ * The app must be registered as a handler by its bundle.
+ * On Windows, the default apps page of the Settings opens for the user to choose the app.
+ *
+ * @param target The URL scheme or file extension
+ * @returns A promise resolving once the request is made.
+ */
+async function requestDefaultHandler(target: HandlerTarget): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'App',
+    message: {
+      cmd: 'requestDefaultHandler',
+      target
+    }
+  })
+}
+
 /**
  * Exits immediately with the given `exitCode`.
  *
@@ -81,4 +206,16 @@ async function relaunch(): Promise<void> {
   })
 }
 
-export { getName, getVersion, getTauriVersion, relaunch, exit }
+export {
+  getName,
+  getVersion,
+  getTauriVersion,
+  getInstallId,
+  getAppearance,
+  getIdleTime,
+  playSound,
+  isDefaultHandler,
+  requestDefaultHandler,
+  relaunch,
+  exit
+}
@@ -0,0 +1,215 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+import { listen, UnlistenFn } from './event'
+
+export interface Device {
+  /** The device identifier, used to [[connect]] to it. */
+  id: string
+  /** The advertised device name. */
+  name: string | null
+  /** The signal strength of the last advertisement, in dBm. */
+  rssi: number | null
+}
+
+export interface Characteristic {
+  /** The characteristic UUID. */
+  uuid: string
+  /** The UUID of the service the characteristic belongs to. */
+  service: string
+  /** Whether the characteristic can be read. */
+  read: boolean
+  /** Whether the characteristic can be written. */
+  write: boolean
+  /** Whether the characteristic sends notifications. */
+  notify: boolean
+}
+
+/** A connected Bluetooth LE device. */
+export class Connection {
+  /** The device identifier. */
+  id: string
+  private readonly unlisten: UnlistenFn
+  private readonly handlers: Map<string, (value: number[]) => void>
+
+  constructor(
+    id: string,
+    unlisten: UnlistenFn,
+    handlers: Map<string, (value: number[]) => void>
+  ) {
+    this.id = id
+    this.unlisten = unlisten
+    this.handlers = handlers
+  }
+
+  /**
+   * Lists the characteristics of the device.
+   *
+   * @returns A promise resolving to the characteristics.
+   */
+  async characteristics(): Promise<Characteristic[]> {
+    return invokeTauriCommand<Characteristic[]>({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'characteristics',
+        id: this.id
+      }
+    })
+  }
+
+  /**
+   * Reads the value of a characteristic.
+   *
+   * @param characteristic The characteristic UUID
+   * @returns A promise resolving to the value bytes.
+   */
+  async read(characteristic: string): Promise<number[]> {
+    return invokeTauriCommand<number[]>({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'read',
+        id: this.id,
+        characteristic
+      }
+    })
+  }
+
+  /**
+   * Writes the value of a characteristic.
+   *
+   * @param characteristic The characteristic UUID
+   * @param value The value bytes
+   * @param withResponse Whether to wait for the device to acknowledge the write
+   * @returns
+   */
+  async write(
+    characteristic: string,
+    value: number[],
+    withResponse = true
+  ): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'write',
+        id: this.id,
+        characteristic,
+        value,
+        withResponse
+      }
+    })
+  }
+
+  /**
+   * Enables the notifications of a characteristic.
+   * @example
+   * ```typescript
+   * await connection.subscribe('00002a37-0000-1000-8000-00805f9b34fb', (value) => {
+   *   console.log(`heart rate: ${value[1]}`)
+   * })
+   * ```
+   *
+   * @param characteristic The characteristic UUID
+   * @param handler Called with each notified value
+   * @returns
+   */
+  async subscribe(
+    characteristic: string,
+    handler: (value: number[]) => void
+  ): Promise<void> {
+    this.handlers.set(characteristic, handler)
+    return invokeTauriCommand({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'subscribe',
+        id: this.id,
+        characteristic
+      }
+    })
+  }
+
+  /**
+   * Disables the notifications of a characteristic.
+   *
+   * @param characteristic The characteristic UUID
+   * @returns
+   */
+  async unsubscribe(characteristic: string): Promise<void> {
+    this.handlers.delete(characteristic)
+    return invokeTauriCommand({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'unsubscribe',
+        id: this.id,
+        characteristic
+      }
+    })
+  }
+
+  /**
+   * Disconnects from the device.
+   *
+   * @returns
+   */
+  async disconnect(): Promise<void> {
+    this.unlisten()
+    return invokeTauriCommand({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'disconnect',
+        id: this.id
+      }
+    })
+  }
+}
+
+/**
+ * Scans for Bluetooth LE devices.
+ *
+ * @param [timeout] How long to scan, in milliseconds
+ * @returns A promise resolving to the devices found.
+ */
+async function scan(timeout = 5000): Promise<Device[]> {
+  return invokeTauriCommand<Device[]>({
+    __tauriModule: 'Bluetooth',
+    message: {
+      cmd: 'scan',
+      timeout
+    }
+  })
+}
+
+/**
+ * Connects to a device found by [[scan]].
+ *
+ * @param id The device identifier
+ * @returns A promise resolving to the connection.
+ */
+async function connect(id: string): Promise<Connection> {
+  const handlers = new Map<string, (value: number[]) => void>()
+  const unlisten = await listen<{
+    id: string
+    characteristic: string
+    value: number[]
+  }>('tauri://bluetooth-notification', (event) => {
+    if (event.payload.id === id) {
+      handlers.get(event.payload.characteristic)?.(event.payload.value)
+    }
+  })
+  try {
+    await invokeTauriCommand({
+      __tauriModule: 'Bluetooth',
+      message: {
+        cmd: 'connect',
+        id
+      }
+    })
+  } catch (e) {
+    unlisten()
+    throw e
+  }
+  return new Connection(id, unlisten, handlers)
+}
+
+export { scan, connect }
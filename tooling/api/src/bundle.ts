@@ -16,6 +16,15 @@ import * as tauri from './tauri'
 import * as window from './window'
 import * as notification from './notification'
 import * as globalShortcut from './globalShortcut'
+import * as clipboard from './clipboard'
+import * as font from './font'
+import * as geolocation from './geolocation'
+import * as printer from './printer'
+import * as share from './share'
+import * as serial from './serial'
+import * as bluetooth from './bluetooth'
+import * as system from './system'
+import * as license from './license'
 
 export {
   app,
@@ -30,5 +39,14 @@ export {
   tauri,
   window,
   notification,
-  globalShortcut
+  globalShortcut,
+  clipboard,
+  font,
+  geolocation,
+  printer,
+  share,
+  serial,
+  bluetooth,
+  system,
+  license
 }
@@ -0,0 +1,37 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+/**
+ * Writes a plain text to the clipboard.
+ *
+ * @param text The text to write.
+ * @returns A promise indicating the success or failure of the operation.
+ */
+async function writeText(text: string): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Clipboard',
+    message: {
+      cmd: 'writeText',
+      data: text
+    }
+  })
+}
+
+/**
+ * Gets the clipboard content as plain text.
+ *
+ * @returns A promise resolving to the clipboard content as plain text, or `null` if it doesn't contain text.
+ */
+async function readText(): Promise<string | null> {
+  return invokeTauriCommand({
+    __tauriModule: 'Clipboard',
+    message: {
+      cmd: 'readText'
+    }
+  })
+}
+
+export { writeText, readText }
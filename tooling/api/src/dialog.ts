@@ -4,27 +4,54 @@
 
 import { invokeTauriCommand } from './helpers/tauri'
 
+/** A filter of the files shown on a dialog. */
 export interface DialogFilter {
+  /** The label of the filter, e.g. `Images`. */
   name: string
+  /** The extensions of the filter, without the dot, e.g. `['png', 'jpg']`. */
   extensions: string[]
 }
 
+/** Options for the open dialog. */
 export interface OpenDialogOptions {
+  /** The filters of the dialog. */
   filters?: DialogFilter[]
+  /** The initial path of the dialog: the starting directory, or the preselected file. */
   defaultPath?: string
+  /** Whether the dialog allows multiple selection. */
   multiple?: boolean
+  /** Whether the dialog picks directories instead of files. */
   directory?: boolean
 }
 
+/** Options for the save dialog. */
 export interface SaveDialogOptions {
+  /** The filters of the dialog. */
   filters?: DialogFilter[]
+  /** The initial path of the dialog: the starting directory, or the suggested file name with an optional directory, e.g. `report.pdf`. */
   defaultPath?: string
 }
 
+/** Options for the question dialogs. */
+export interface ConfirmDialogOptions {
+  /** The dialog title. Defaults to the app name. */
+  title?: string
+  /** The button selected when the dialog opens, answered by pressing enter. Defaults to `cancel`. */
+  defaultButton?: 'accept' | 'cancel'
+}
+
 /**
  * Open a file/directory selection dialog
+ * @example
+ * ```typescript
+ * import { open } from '@tauri-apps/api/dialog'
+ * const images = await open({
+ *   multiple: true,
+ *   filters: [{ name: 'Images', extensions: ['png', 'jpg'] }]
+ * })
+ * ```
  *
- * @returns A promise resolving to the selected path(s)
+ * @returns A promise resolving to the selected path(s), or `null` if the dialog was cancelled.
  */
 async function open(
   options: OpenDialogOptions = {}
@@ -35,7 +62,6 @@ async function open(
 
   return invokeTauriCommand<string | string[]>({
     __tauriModule: 'Dialog',
-    mainThread: true,
     message: {
       cmd: 'openDialog',
       options
@@ -46,7 +72,7 @@ async function open(
 /**
  * Open a file/directory save dialog.
  *
- * @returns A promise resolving to the selected path.
+ * @returns A promise resolving to the selected path, or `null` if the dialog was cancelled.
  */
 async function save(options: SaveDialogOptions = {}): Promise<string> {
   if (typeof options === 'object') {
@@ -55,7 +81,6 @@ async function save(options: SaveDialogOptions = {}): Promise<string> {
 
   return invokeTauriCommand<string>({
     __tauriModule: 'Dialog',
-    mainThread: true,
     message: {
       cmd: 'saveDialog',
       options
@@ -63,4 +88,84 @@ async function save(options: SaveDialogOptions = {}): Promise<string> {
   })
 }
 
-export { open, save }
+function confirmDialogOptions(
+  options?: string | ConfirmDialogOptions
+): ConfirmDialogOptions {
+  return typeof options === 'string' ? { title: options } : options ?? {}
+}
+
+/**
+ * Shows a message dialog, modal to the current window.
+ * @example
+ * ```typescript
+ * import { message } from '@tauri-apps/api/dialog'
+ * await message('The export finished.', 'Export')
+ * ```
+ *
+ * @param message The message to show
+ * @param [title] The dialog title. Defaults to the app name.
+ * @returns A promise resolving when the dialog is closed.
+ */
+async function message(message: string, title?: string): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Dialog',
+    message: {
+      cmd: 'messageDialog',
+      title,
+      message
+    }
+  })
+}
+
+/**
+ * Shows a question dialog with "Yes" and "No" buttons, modal to the current window.
+ * @example
+ * ```typescript
+ * import { ask } from '@tauri-apps/api/dialog'
+ * const yes = await ask('Save the changes?', { title: 'Editor', defaultButton: 'accept' })
+ * ```
+ *
+ * @param message The question to ask
+ * @param [options] The dialog title, or the dialog options
+ * @returns A promise resolving to whether the user answered yes.
+ */
+async function ask(
+  message: string,
+  options?: string | ConfirmDialogOptions
+): Promise<boolean> {
+  const { title, defaultButton } = confirmDialogOptions(options)
+  return invokeTauriCommand<boolean>({
+    __tauriModule: 'Dialog',
+    message: {
+      cmd: 'askDialog',
+      title,
+      message,
+      defaultButton
+    }
+  })
+}
+
+/**
+ * Shows a question dialog with "Ok" and "Cancel" buttons, modal to the current window.
+ *
+ * @param message The message to confirm
+ * @param [options] The dialog title, or the dialog options
+ * @returns A promise resolving to whether the user confirmed.
+ */
+async function confirm(
+  message: string,
+  options?: string | ConfirmDialogOptions
+): Promise<boolean> {
+  const { title, defaultButton } = confirmDialogOptions(options)
+  return invokeTauriCommand<boolean>({
+    __tauriModule: 'Dialog',
+    message: {
+      cmd: 'confirmDialog',
+      title,
+      message,
+      defaultButton
+    }
+  })
+}
+
+export { open, save, message, ask, confirm }
@@ -11,10 +11,34 @@ export interface Event<T> {
   event: string
   /** Event identifier used to unlisten */
   id: number
+  /** Label of the window that emitted the event, `null` if emitted by the backend */
+  windowLabel: string | null
   /** Event payload */
   payload: T
 }
 
+/** The built-in events emitted by Tauri to the windows. */
+export enum TauriEvent {
+  /** The window was resized, with its new physical size. */
+  WINDOW_RESIZED = 'tauri://resize',
+  /** The window was moved, with its new physical position. */
+  WINDOW_MOVED = 'tauri://move',
+  /** The user asked to close the window. */
+  WINDOW_CLOSE_REQUESTED = 'tauri://close-requested',
+  /** The window gained the focus. */
+  WINDOW_FOCUS = 'tauri://focus',
+  /** The window lost the focus. */
+  WINDOW_BLUR = 'tauri://blur',
+  /** The system theme changed, with the new `light` or `dark` theme. */
+  WINDOW_THEME_CHANGED = 'tauri://theme-changed',
+  /** Files were dropped onto the window, with their paths. */
+  WINDOW_FILE_DROP = 'tauri://file-drop',
+  /** Files are dragged over the window, with their paths. */
+  WINDOW_FILE_DROP_HOVER = 'tauri://file-drop-hover',
+  /** The files dragged over the window left it. */
+  WINDOW_FILE_DROP_CANCELLED = 'tauri://file-drop-cancelled'
+}
+
 export type EventCallback<T> = (event: Event<T>) => void
 
 export type UnlistenFn = () => void
@@ -77,7 +101,8 @@ async function once<T>(
 }
 
 /**
- * Emits an event to the backend.
+ * Emits an event to the backend and all the windows.
+ * The other windows receive it with the label of this window as `windowLabel`.
  *
  * @param event Event name
  * @param [payload] Event payload
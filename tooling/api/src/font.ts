@@ -0,0 +1,45 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+export interface FontFace {
+  /** The font family name, e.g. `DejaVu Sans`. */
+  family: string
+  /** The style of the face in its family, e.g. `Bold Oblique`, if available. */
+  style: string | null
+  /** The font file, if available. */
+  path: string | null
+}
+
+/**
+ * Lists the font families installed on the system, sorted by name.
+ *
+ * @returns A promise resolving to the family names.
+ */
+async function listFonts(): Promise<string[]> {
+  return invokeTauriCommand<string[]>({
+    __tauriModule: 'Font',
+    message: {
+      cmd: 'list'
+    }
+  })
+}
+
+/**
+ * Lists the font faces installed on the system, with their style and file.
+ * It's slower than [[listFonts]], so prefer it when only the family names are needed.
+ *
+ * @returns A promise resolving to the font faces.
+ */
+async function listFontFaces(): Promise<FontFace[]> {
+  return invokeTauriCommand<FontFace[]>({
+    __tauriModule: 'Font',
+    message: {
+      cmd: 'listFaces'
+    }
+  })
+}
+
+export { listFonts, listFontFaces }
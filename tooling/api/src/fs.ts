@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 import { invokeTauriCommand } from './helpers/tauri'
+import { listen } from './event'
 
 export enum BaseDirectory {
   Audio = 1,
@@ -23,13 +24,28 @@ export enum BaseDirectory {
   Video,
   Resource,
   App,
-  Current
+  Current,
+  Log
 }
 
 export interface FsOptions {
   dir?: BaseDirectory
 }
 
+export interface FsStreamOptions extends FsOptions {
+  /** The size of the chunks in bytes. Defaults to 512 KiB. */
+  chunkSize?: number
+}
+
+/** A chunk of a file read with `readFileStream`. */
+export interface FileChunk {
+  chunk: Uint8Array
+  /** The number of bytes read so far, including this chunk. */
+  read: number
+  /** The file size in bytes. */
+  total: number
+}
+
 export interface FsDirOptions {
   dir?: BaseDirectory
   recursive?: boolean
@@ -56,6 +72,28 @@ export interface FileEntry {
   children?: FileEntry[]
 }
 
+/** The metadata of a file or directory. */
+export interface FileMetadata {
+  /** The size in bytes. */
+  size: number
+  /** The last modification time, in milliseconds since the Unix epoch. */
+  modifiedAt: number | null
+  /** The last access time, in milliseconds since the Unix epoch. */
+  accessedAt: number | null
+  /** The creation time, in milliseconds since the Unix epoch. Not available on every file system. */
+  createdAt: number | null
+  /** Whether the path is a directory. */
+  isDir: boolean
+  /** Whether the path is a regular file. */
+  isFile: boolean
+  /** Whether the path is a symbolic link. The other fields describe the link target. */
+  isSymlink: boolean
+  /** Whether the file is read-only. */
+  readonly: boolean
+  /** The Unix permission bits, e.g. `0o644`. Always `null` on Windows. */
+  mode: number | null
+}
+
 /**
  * Reads a file as text.
  *
@@ -189,6 +227,102 @@ async function writeBinaryFile(
   })
 }
 
+/**
+ * Reads a binary file in chunks, so large files aren't transferred in a single response.
+ * @example
+ * ```typescript
+ * import { readFileStream } from '@tauri-apps/api/fs'
+ * await readFileStream('/path/to/video.mp4', ({ read, total }) => {
+ *   console.log(`read ${read} of ${total} bytes`)
+ * })
+ * ```
+ *
+ * @param filePath Path to the file
+ * @param onChunk Called with each chunk, in order
+ * @param [options] Configuration object
+ * @returns A promise resolving to the number of bytes read, once every chunk is received.
+ */
+async function readFileStream(
+  filePath: string,
+  onChunk: (chunk: FileChunk) => void,
+  options: FsStreamOptions = {}
+): Promise<number> {
+  const { chunkSize, ...fsOptions } = options
+  const streamId = Math.floor(Math.random() * 0xffffffff)
+  // the chunks are read from the `tauri-buffer` protocol concurrently, but delivered in order
+  let delivered = Promise.resolve()
+  const unlisten = await listen<{
+    streamId: number
+    buffer: string
+    read: number
+    total: number
+  }>('tauri://file-chunk', (event) => {
+    if (event.payload.streamId === streamId) {
+      const { buffer, read, total } = event.payload
+      const bytes = fetch(`tauri-buffer://${buffer}`).then(async (response) =>
+        response.arrayBuffer()
+      )
+      delivered = delivered
+        .then(async () => bytes)
+        .then((bytes) => onChunk({ chunk: new Uint8Array(bytes), read, total }))
+    }
+  })
+  try {
+    const size = await invokeTauriCommand<number>({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'readFileStream',
+        path: filePath,
+        streamId,
+        chunkSize,
+        options: fsOptions
+      }
+    })
+    await delivered
+    return size
+  } finally {
+    unlisten()
+  }
+}
+
+/**
+ * Writes a binary file in chunks, so large files aren't transferred in a single request.
+ * @example
+ * ```typescript
+ * import { writeFileStream } from '@tauri-apps/api/fs'
+ * const [file] = document.querySelector('input[type=file]').files
+ * await writeFileStream('/path/to/copy.mp4', file)
+ * ```
+ *
+ * @param filePath Path to the file
+ * @param contents The file contents, e.g. a `File` picked by the user
+ * @param [options] Configuration object
+ * @returns
+ */
+async function writeFileStream(
+  filePath: string,
+  contents: Blob,
+  options: FsStreamOptions = {}
+): Promise<void> {
+  const { chunkSize = 512 * 1024, ...fsOptions } = options
+  let offset = 0
+  // the first chunk replaces the file contents, so an empty blob still truncates it
+  do {
+    const chunk = await contents.slice(offset, offset + chunkSize).arrayBuffer()
+    await invokeTauriCommand({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'writeFileStream',
+        path: filePath,
+        contents: arrayBufferToBase64(chunk),
+        append: offset > 0,
+        options: fsOptions
+      }
+    })
+    offset += chunkSize
+  } while (offset < contents.size)
+}
+
 /**
  * List directory files.
  *
@@ -324,6 +458,75 @@ async function renameFile(
   })
 }
 
+/**
+ * Reads the metadata of a file or directory, following symbolic links.
+ * @example
+ * ```typescript
+ * import { metadata, BaseDirectory } from '@tauri-apps/api/fs'
+ * const { size, modifiedAt } = await metadata('notes.txt', { dir: BaseDirectory.App })
+ * ```
+ *
+ * @param path A path of the file or directory
+ * @param [options] Configuration object
+ * @returns A promise resolving to the metadata.
+ */
+async function metadata(
+  path: string,
+  options: FsOptions = {}
+): Promise<FileMetadata> {
+  return invokeTauriCommand<FileMetadata>({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'metadata',
+      path,
+      options
+    }
+  })
+}
+
+/**
+ * Creates a symbolic link pointing to the target.
+ * On Windows, creating links requires the developer mode or administrator privileges.
+ *
+ * @param target A path the link points to
+ * @param path A path of the link
+ * @param [options] Configuration object, the base directory applies to both paths
+ * @returns
+ */
+async function createSymlink(
+  target: string,
+  path: string,
+  options: FsOptions = {}
+): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'createSymlink',
+      target,
+      path,
+      options
+    }
+  })
+}
+
+/**
+ * Reads the target of a symbolic link.
+ *
+ * @param path A path of the link
+ * @param [options] Configuration object
+ * @returns A promise resolving to the path the link points to.
+ */
+async function readLink(path: string, options: FsOptions = {}): Promise<string> {
+  return invokeTauriCommand<string>({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'readLink',
+      path,
+      options
+    }
+  })
+}
+
 export {
   BaseDirectory as Dir,
   readTextFile,
@@ -335,5 +538,10 @@ export {
   removeDir,
   copyFile,
   removeFile,
-  renameFile
+  renameFile,
+  readFileStream,
+  writeFileStream,
+  metadata,
+  createSymlink,
+  readLink
 }
@@ -0,0 +1,68 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+export interface Coordinates {
+  /** The latitude, in decimal degrees. */
+  latitude: number
+  /** The longitude, in decimal degrees. */
+  longitude: number
+  /** The accuracy of the latitude and longitude, in meters. */
+  accuracy: number
+  /** The altitude above the sea level, in meters, if available. */
+  altitude: number | null
+}
+
+export type Permission = 'granted' | 'denied'
+
+/**
+ * Checks if the permission to read the location is granted.
+ *
+ * @returns A promise resolving to whether the permission is granted, or `null` if it wasn't requested yet.
+ */
+async function isPermissionGranted(): Promise<boolean | null> {
+  return invokeTauriCommand({
+    __tauriModule: 'Geolocation',
+    message: {
+      cmd: 'isPermissionGranted'
+    }
+  })
+}
+
+/**
+ * Requests the permission to read the location. The user is only asked once, the answer is remembered.
+ *
+ * @returns A promise resolving to whether the user granted the permission or not.
+ */
+async function requestPermission(): Promise<Permission> {
+  return invokeTauriCommand({
+    __tauriModule: 'Geolocation',
+    message: {
+      cmd: 'requestPermission'
+    }
+  })
+}
+
+/**
+ * Gets the current position of the device from the location service of the system,
+ * requesting the permission first if needed.
+ * @example
+ * ```typescript
+ * import { getCurrentPosition } from '@tauri-apps/api/geolocation'
+ * const { latitude, longitude } = await getCurrentPosition()
+ * ```
+ *
+ * @returns A promise resolving to the position.
+ */
+async function getCurrentPosition(): Promise<Coordinates> {
+  return invokeTauriCommand<Coordinates>({
+    __tauriModule: 'Geolocation',
+    message: {
+      cmd: 'getCurrentPosition'
+    }
+  })
+}
+
+export { isPermissionGranted, requestPermission, getCurrentPosition }
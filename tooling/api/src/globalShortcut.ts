@@ -3,10 +3,37 @@
 // SPDX-License-Identifier: MIT
 
 import { invokeTauriCommand } from './helpers/tauri'
-import { transformCallback } from './tauri'
+import { listen, UnlistenFn } from './event'
 
 export type ShortcutHandler = (shortcut: string) => void
 
+/** The event emitted when a registered shortcut is pressed, with the shortcut as payload. */
+const GLOBAL_SHORTCUT_EVENT = 'tauri://global-shortcut'
+
+const unlisteners: { [shortcut: string]: UnlistenFn[] } = {}
+
+async function listenShortcut(
+  shortcut: string,
+  handler: ShortcutHandler
+): Promise<void> {
+  const unlisten = await listen<string>(GLOBAL_SHORTCUT_EVENT, (event) => {
+    if (event.payload === shortcut) {
+      handler(shortcut)
+    }
+  })
+  if (!(shortcut in unlisteners)) {
+    unlisteners[shortcut] = []
+  }
+  unlisteners[shortcut].push(unlisten)
+}
+
+function unlistenShortcut(shortcut: string): void {
+  for (const unlisten of unlisteners[shortcut] ?? []) {
+    unlisten()
+  }
+  delete unlisteners[shortcut]
+}
+
 /**
  * Register a global shortcut.
  *
@@ -18,14 +45,14 @@ async function register(
   shortcut: string,
   handler: ShortcutHandler
 ): Promise<void> {
-  return invokeTauriCommand({
+  await invokeTauriCommand({
     __tauriModule: 'GlobalShortcut',
     message: {
       cmd: 'register',
-      shortcut,
-      handler: transformCallback(handler)
+      shortcut
     }
   })
+  return listenShortcut(shortcut, handler)
 }
 
 /**
@@ -39,14 +66,16 @@ async function registerAll(
   shortcuts: string[],
   handler: ShortcutHandler
 ): Promise<void> {
-  return invokeTauriCommand({
+  await invokeTauriCommand({
     __tauriModule: 'GlobalShortcut',
     message: {
       cmd: 'registerAll',
-      shortcuts,
-      handler: transformCallback(handler)
+      shortcuts
     }
   })
+  await Promise.all(
+    shortcuts.map(async (shortcut) => listenShortcut(shortcut, handler))
+  )
 }
 
 /**
@@ -72,13 +101,14 @@ async function isRegistered(shortcut: string): Promise<boolean> {
  * @returns
  */
 async function unregister(shortcut: string): Promise<void> {
-  return invokeTauriCommand({
+  await invokeTauriCommand({
     __tauriModule: 'GlobalShortcut',
     message: {
       cmd: 'unregister',
       shortcut
     }
   })
+  unlistenShortcut(shortcut)
 }
 
 /**
@@ -87,12 +117,20 @@ async function unregister(shortcut: string): Promise<void> {
  * @returns
  */
 async function unregisterAll(): Promise<void> {
-  return invokeTauriCommand({
+  await invokeTauriCommand({
     __tauriModule: 'GlobalShortcut',
     message: {
       cmd: 'unregisterAll'
     }
   })
+  Object.keys(unlisteners).forEach(unlistenShortcut)
 }
 
-export { register, registerAll, isRegistered, unregister, unregisterAll }
+export {
+  GLOBAL_SHORTCUT_EVENT,
+  register,
+  registerAll,
+  isRegistered,
+  unregister,
+  unregisterAll
+}
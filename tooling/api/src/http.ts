@@ -3,16 +3,82 @@
 // SPDX-License-Identifier: MIT
 
 import { invokeTauriCommand } from './helpers/tauri'
+import { listen } from './event'
+import { BaseDirectory } from './fs'
 
 export interface ClientOptions {
-  maxRedirections: number
-  connectTimeout: number
+  maxRedirections?: number
+  /** Whether redirections are followed. When `false`, the redirection response is returned. */
+  followRedirects?: boolean
+  /** Connect timeout in seconds. */
+  connectTimeout?: number
+  /** Timeout in seconds for the whole request. */
+  timeout?: number
+  /** The proxy URL all requests are sent through. */
+  proxy?: string
+  /** Max number of idle connections kept open for each host. */
+  poolMaxIdlePerHost?: number
+  /** Max size in bytes of the response bodies. */
+  maxResponseSize?: number
 }
 
 export enum ResponseType {
   JSON = 1,
   Text = 2,
-  Binary = 3
+  Binary = 3,
+  /** The body is delivered in chunks to the `onProgress` callback, the response `data` is `null`. */
+  Stream = 4
+}
+
+export interface DownloadProgress {
+  /** The received chunk. */
+  chunk: number[]
+  /** The number of bytes received so far. */
+  received: number
+  /** The body size, if the server sent it. */
+  total: number | null
+}
+
+export interface FileDownloadProgress {
+  /** The number of bytes written to the file so far, including the resumed part. */
+  received: number
+  /** The file size, if the server sent it. */
+  total: number | null
+}
+
+export interface DownloadOptions {
+  /** The base directory of the path. */
+  dir?: BaseDirectory
+  /** Called when a chunk is written to the file. */
+  onProgress?: (progress: FileDownloadProgress) => void
+}
+
+/** A file download in progress. */
+export class Download {
+  /** The download identifier. */
+  id: number
+  /** Resolves when the file is downloaded, rejects if it fails or is cancelled. */
+  finished: Promise<void>
+
+  constructor(id: number, finished: Promise<void>) {
+    this.id = id
+    this.finished = finished
+  }
+
+  /**
+   * Cancels the download. The partial file is kept, so downloading it again resumes it.
+   *
+   * @returns
+   */
+  async cancel(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'cancelDownload',
+        downloadId: this.id
+      }
+    })
+  }
 }
 
 export type Part = 'string' | number[]
@@ -62,6 +128,8 @@ export interface HttpOptions {
   body?: Body
   timeout?: number
   responseType?: ResponseType
+  /** Called with each chunk of a `ResponseType.Stream` response. */
+  onProgress?: (progress: DownloadProgress) => void
 }
 
 export type RequestOptions = Omit<HttpOptions, 'method' | 'url'>
@@ -102,14 +170,41 @@ export class Client {
    * @returns A promise resolving to the response.
    */
   async request<T>(options: HttpOptions): Promise<Response<T>> {
-    return invokeTauriCommand({
-      __tauriModule: 'Http',
-      message: {
-        cmd: 'httpRequest',
-        client: this.id,
-        options
+    const { onProgress, ...requestOptions } = options
+    if (options.responseType !== ResponseType.Stream || !onProgress) {
+      return invokeTauriCommand({
+        __tauriModule: 'Http',
+        message: {
+          cmd: 'httpRequest',
+          client: this.id,
+          options: requestOptions
+        }
+      })
+    }
+
+    const streamId = Math.floor(Math.random() * 0xffffffff)
+    const unlisten = await listen<DownloadProgress & { streamId: number }>(
+      'tauri://download-progress',
+      (event) => {
+        if (event.payload.streamId === streamId) {
+          const { chunk, received, total } = event.payload
+          onProgress({ chunk, received, total })
+        }
       }
-    })
+    )
+    try {
+      return await invokeTauriCommand<Response<T>>({
+        __tauriModule: 'Http',
+        message: {
+          cmd: 'httpRequest',
+          client: this.id,
+          options: requestOptions,
+          streamId
+        }
+      })
+    } finally {
+      unlisten()
+    }
   }
 
   /**
@@ -198,8 +293,60 @@ export class Client {
       ...options
     })
   }
+
+  /**
+   * Downloads a file. If the file already exists, the download is resumed from its size.
+   *
+   * @param url The file URL
+   * @param path The destination path
+   * @param [options] Download options
+   * @returns The download, resolved when it's started.
+   */
+  async download(
+    url: string,
+    path: string,
+    options: DownloadOptions = {}
+  ): Promise<Download> {
+    const { dir, onProgress } = options
+    const downloadId = Math.floor(Math.random() * 0xffffffff)
+    const unlisten = onProgress
+      ? await listen<FileDownloadProgress & { downloadId: number }>(
+          'tauri://download-progress',
+          (event) => {
+            if (event.payload.downloadId === downloadId) {
+              const { received, total } = event.payload
+              onProgress({ received, total })
+            }
+          }
+        )
+      : null
+    const finished = (async () => {
+      try {
+        await invokeTauriCommand({
+          __tauriModule: 'Http',
+          message: {
+            cmd: 'downloadFile',
+            client: this.id,
+            url,
+            path,
+            dir,
+            downloadId
+          }
+        })
+      } finally {
+        unlisten?.()
+      }
+    })()
+    return new Download(downloadId, finished)
+  }
 }
 
+/**
+ * Creates a new client. Its connections are kept open, so reuse it for requests to the same hosts.
+ *
+ * @param [options] Client options
+ * @returns A promise resolving to the client instance.
+ */
 async function getClient(options?: ClientOptions): Promise<Client> {
   return invokeTauriCommand<number>({
     __tauriModule: 'Http',
@@ -0,0 +1,79 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+export interface License {
+  /** The license identifier. */
+  id: string
+  /** The name of the license owner. */
+  licensee: string
+  /** When the license expires, in seconds since the Unix epoch. */
+  expiresAt: number | null
+  /** The app features the license unlocks. */
+  features: string[]
+}
+
+export type LicenseStatus =
+  | { status: 'licensed'; license: License }
+  | { status: 'expired'; license: License }
+  | { status: 'trial'; expiresAt: number }
+  | { status: 'trialExpired'; expiresAt: number }
+  | { status: 'unlicensed' }
+
+/**
+ * Checks the activated license,
+ * or the trial configured on `tauri > license > trialDays` if no license is activated.
+ * @example
+ * ```typescript
+ * import { check } from '@tauri-apps/api/license'
+ * const result = await check()
+ * if (result.status === 'licensed') {
+ *   console.log(`licensed to ${result.license.licensee}`)
+ * }
+ * ```
+ *
+ * @returns A promise resolving to the license status.
+ */
+async function check(): Promise<LicenseStatus> {
+  return invokeTauriCommand<LicenseStatus>({
+    __tauriModule: 'License',
+    message: {
+      cmd: 'check'
+    }
+  })
+}
+
+/**
+ * Verifies a license key with the public key configured on `tauri > license > pubkey`,
+ * and stores it in the keyring of the system.
+ *
+ * @param key The license key entered by the user
+ * @returns A promise resolving to the license, or rejecting if the key is invalid.
+ */
+async function activate(key: string): Promise<License> {
+  return invokeTauriCommand<License>({
+    __tauriModule: 'License',
+    message: {
+      cmd: 'activate',
+      key
+    }
+  })
+}
+
+/**
+ * Removes the activated license from the keyring.
+ *
+ * @returns
+ */
+async function deactivate(): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'License',
+    message: {
+      cmd: 'deactivate'
+    }
+  })
+}
+
+export { check, activate, deactivate }
@@ -3,11 +3,37 @@
 // SPDX-License-Identifier: MIT
 
 import { invokeTauriCommand } from './helpers/tauri'
+import { UnlistenFn, listen } from './event'
+
+/** An action button of a notification. */
+export interface Action {
+  /** The id reported by {@link onNotificationAction}. */
+  id: string
+  /** The button title. */
+  title: string
+}
 
 export interface Options {
+  /** The id reported by {@link onNotificationClicked} and {@link onNotificationAction}. */
+  id?: string
   title: string
   body?: string
+  /** A path to an image, or the name of an icon of the desktop theme. */
   icon?: string
+  /** The name of a system sound, e.g. `message-new-instant` on Linux, `Ping` on macOS or `Reminder` on Windows. */
+  sound?: string
+  /** The delivery time. The notification is shown right away if not set, and isn't shown if the app exits before. */
+  scheduleAt?: Date | number
+  /** The action buttons. Only shown on Linux. */
+  actions?: Action[]
+}
+
+/** The payload of the notification interaction events. */
+export interface NotificationEvent {
+  /** The id of the notification, if it has one. */
+  id: string | null
+  /** The id of the clicked action, for {@link onNotificationAction}. */
+  action: string | null
 }
 
 export type PartialOptions = Omit<Options, 'title'>
@@ -50,9 +76,47 @@ function sendNotification(options: Options | string): void {
     // eslint-disable-next-line no-new
     new window.Notification(options)
   } else {
+    const scheduleAt =
+      options.scheduleAt instanceof Date
+        ? options.scheduleAt.getTime()
+        : options.scheduleAt
     // eslint-disable-next-line no-new
-    new window.Notification(options.title, options)
+    new window.Notification(options.title, { ...options, scheduleAt })
   }
 }
 
-export { sendNotification, requestPermission, isPermissionGranted }
+/**
+ * Listens to the clicks on the notifications sent by the app. Only reported on Linux.
+ *
+ * @param handler Handler receiving the id of the clicked notification.
+ * @returns A promise resolving to a function to stop listening.
+ */
+async function onNotificationClicked(
+  handler: (event: NotificationEvent) => void
+): Promise<UnlistenFn> {
+  return listen<NotificationEvent>('tauri://notification-clicked', (event) =>
+    handler(event.payload)
+  )
+}
+
+/**
+ * Listens to the clicks on the action buttons of the notifications sent by the app. Only reported on Linux.
+ *
+ * @param handler Handler receiving the ids of the notification and of the clicked action.
+ * @returns A promise resolving to a function to stop listening.
+ */
+async function onNotificationAction(
+  handler: (event: NotificationEvent) => void
+): Promise<UnlistenFn> {
+  return listen<NotificationEvent>('tauri://notification-action', (event) =>
+    handler(event.payload)
+  )
+}
+
+export {
+  sendNotification,
+  requestPermission,
+  isPermissionGranted,
+  onNotificationClicked,
+  onNotificationAction
+}
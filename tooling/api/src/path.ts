@@ -6,13 +6,14 @@ import { invokeTauriCommand } from './helpers/tauri'
 import { BaseDirectory } from './fs'
 
 /**
- * Returns the path to the suggested directory for your app config files.
+ * Returns the path to the suggested directory for your app config files,
+ * named after the bundle identifier, e.g. `${configDir}/com.tauri.dev`.
  *
  * @returns
  */
 async function appDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -28,7 +29,7 @@ async function appDir(): Promise<string> {
  */
 async function audioDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -44,7 +45,7 @@ async function audioDir(): Promise<string> {
  */
 async function cacheDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -60,7 +61,7 @@ async function cacheDir(): Promise<string> {
  */
 async function configDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -76,7 +77,7 @@ async function configDir(): Promise<string> {
  */
 async function dataDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -92,7 +93,7 @@ async function dataDir(): Promise<string> {
  */
 async function desktopDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -108,7 +109,7 @@ async function desktopDir(): Promise<string> {
  */
 async function documentDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -124,7 +125,7 @@ async function documentDir(): Promise<string> {
  */
 async function downloadDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -140,7 +141,7 @@ async function downloadDir(): Promise<string> {
  */
 async function executableDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -156,7 +157,7 @@ async function executableDir(): Promise<string> {
  */
 async function fontDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -172,7 +173,7 @@ async function fontDir(): Promise<string> {
  */
 async function homeDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -188,7 +189,7 @@ async function homeDir(): Promise<string> {
  */
 async function localDataDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -204,7 +205,7 @@ async function localDataDir(): Promise<string> {
  */
 async function pictureDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -220,7 +221,7 @@ async function pictureDir(): Promise<string> {
  */
 async function publicDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -236,7 +237,7 @@ async function publicDir(): Promise<string> {
  */
 async function resourceDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -252,7 +253,7 @@ async function resourceDir(): Promise<string> {
  */
 async function runtimeDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -268,7 +269,7 @@ async function runtimeDir(): Promise<string> {
  */
 async function templateDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -284,7 +285,7 @@ async function templateDir(): Promise<string> {
  */
 async function videoDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -300,7 +301,7 @@ async function videoDir(): Promise<string> {
  */
 async function currentDir(): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path: '',
@@ -309,6 +310,25 @@ async function currentDir(): Promise<string> {
   })
 }
 
+/**
+ * Returns the path to the suggested directory for your app log files,
+ * named after the bundle identifier.
+ * Resolves to `~/Library/Logs/${bundleIdentifier}` on macOS
+ * and `${configDir}/${bundleIdentifier}/logs` on Linux and Windows.
+ *
+ * @returns
+ */
+async function logDir(): Promise<string> {
+  return invokeTauriCommand<string>({
+    __tauriModule: 'Path',
+    message: {
+      cmd: 'resolvePath',
+      path: '',
+      directory: BaseDirectory.Log
+    }
+  })
+}
+
 /**
  * Resolves the path with the optional base directory.
  *
@@ -321,7 +341,7 @@ async function resolve(
   directory: BaseDirectory
 ): Promise<string> {
   return invokeTauriCommand<string>({
-    __tauriModule: 'Fs',
+    __tauriModule: 'Path',
     message: {
       cmd: 'resolvePath',
       path,
@@ -350,5 +370,7 @@ export {
   templateDir,
   videoDir,
   currentDir,
-  resolve as resolvePath
+  logDir,
+  resolve as resolvePath,
+  BaseDirectory
 }
@@ -0,0 +1,56 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+export interface Printer {
+  /** The printer name. */
+  name: string
+  /** Whether it's the default printer of the system. */
+  isDefault: boolean
+}
+
+export interface PrintOptions {
+  /** The printer name. Defaults to the default printer. */
+  printer?: string
+  /** The number of copies. Defaults to 1. */
+  copies?: number
+  /** The pages to print, e.g. `1-3,5`. Unsupported on Windows. */
+  pages?: string
+}
+
+/**
+ * Lists the printers configured on the system.
+ *
+ * @returns A promise resolving to the printers.
+ */
+async function getPrinters(): Promise<Printer[]> {
+  return invokeTauriCommand<Printer[]>({
+    __tauriModule: 'Printer',
+    message: {
+      cmd: 'list'
+    }
+  })
+}
+
+/**
+ * Prints a file silently, without showing a print dialog.
+ * The file must be in the `fs` allowlist scope.
+ *
+ * @param path The file path, e.g. a PDF
+ * @param [options] Print options
+ * @returns A promise resolving when the job is queued.
+ */
+async function print(path: string, options?: PrintOptions): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Printer',
+    message: {
+      cmd: 'print',
+      path,
+      options
+    }
+  })
+}
+
+export { getPrinters, print }
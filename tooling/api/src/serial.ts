@@ -0,0 +1,154 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+import { listen, UnlistenFn } from './event'
+
+export interface PortInfo {
+  /** The port name, e.g. `/dev/ttyUSB0` or `COM3`. */
+  name: string
+  kind: 'usb' | 'pci' | 'bluetooth' | 'unknown'
+  /** The USB vendor identifier. */
+  vendorId: number | null
+  /** The USB product identifier. */
+  productId: number | null
+  /** The USB serial number. */
+  serialNumber: string | null
+  /** The USB manufacturer name. */
+  manufacturer: string | null
+  /** The USB product name. */
+  product: string | null
+}
+
+export interface OpenOptions {
+  /** Called with the bytes read from the port. */
+  onData?: (data: number[]) => void
+  /** Called when reading fails, e.g. because the device was unplugged. Reading stops after it. */
+  onError?: (error: string) => void
+}
+
+/** An open serial port. */
+export class SerialPort {
+  /** The port identifier. */
+  id: number
+  /** The port name. */
+  name: string
+  private readonly unlisten: UnlistenFn[]
+
+  constructor(id: number, name: string, unlisten: UnlistenFn[]) {
+    this.id = id
+    this.name = name
+    this.unlisten = unlisten
+  }
+
+  /**
+   * Writes to the port.
+   *
+   * @param data The bytes or the text to write
+   * @returns
+   */
+  async write(data: number[] | string): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Serial',
+      message: {
+        cmd: 'write',
+        portId: this.id,
+        data:
+          typeof data === 'string'
+            ? Array.from(new TextEncoder().encode(data))
+            : data
+      }
+    })
+  }
+
+  /**
+   * Closes the port.
+   *
+   * @returns
+   */
+  async close(): Promise<void> {
+    this.unlisten.forEach((unlisten) => unlisten())
+    return invokeTauriCommand({
+      __tauriModule: 'Serial',
+      message: {
+        cmd: 'close',
+        portId: this.id
+      }
+    })
+  }
+}
+
+/**
+ * Lists the serial ports available on the system.
+ *
+ * @returns A promise resolving to the ports.
+ */
+async function availablePorts(): Promise<PortInfo[]> {
+  return invokeTauriCommand<PortInfo[]>({
+    __tauriModule: 'Serial',
+    message: {
+      cmd: 'availablePorts'
+    }
+  })
+}
+
+/**
+ * Opens a serial port.
+ * @example
+ * ```typescript
+ * import { open } from '@tauri-apps/api/serial'
+ * const port = await open('/dev/ttyUSB0', 115200, {
+ *   onData: (data) => console.log(new TextDecoder().decode(new Uint8Array(data)))
+ * })
+ * await port.write('PING\n')
+ * ```
+ *
+ * @param name The port name, see [[availablePorts]]
+ * @param baudRate The baud rate, e.g. `115200`
+ * @param [options] The callbacks of the data read from the port
+ * @returns A promise resolving to the open port.
+ */
+async function open(
+  name: string,
+  baudRate: number,
+  options: OpenOptions = {}
+): Promise<SerialPort> {
+  const { onData, onError } = options
+  const portId = Math.floor(Math.random() * 0xffffffff)
+  const unlisten = [
+    await listen<{ portId: number; data: number[] }>(
+      'tauri://serial-data',
+      (event) => {
+        if (event.payload.portId === portId) {
+          onData?.(event.payload.data)
+        }
+      }
+    ),
+    await listen<{ portId: number; error: string }>(
+      'tauri://serial-error',
+      (event) => {
+        if (event.payload.portId === portId) {
+          onError?.(event.payload.error)
+        }
+      }
+    )
+  ]
+  try {
+    await invokeTauriCommand({
+      __tauriModule: 'Serial',
+      message: {
+        cmd: 'open',
+        name,
+        baudRate,
+        portId
+      }
+    })
+  } catch (e) {
+    unlisten.forEach((fn) => fn())
+    throw e
+  }
+  return new SerialPort(portId, name, unlisten)
+}
+
+export { availablePorts, open }
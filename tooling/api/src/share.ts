@@ -0,0 +1,38 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+
+export type ShareItem =
+  | { type: 'text'; value: string }
+  | { type: 'url'; value: string }
+  | { type: 'file'; value: string }
+
+/**
+ * Shows the share sheet of the system with the items.
+ * The files must be in the `fs` allowlist scope.
+ * On Linux, an email is composed with the texts, links and files instead.
+ * @example
+ * ```typescript
+ * import { share } from '@tauri-apps/api/share'
+ * await share([
+ *   { type: 'text', value: 'The report of this week' },
+ *   { type: 'file', value: '/tmp/report.pdf' }
+ * ])
+ * ```
+ *
+ * @param items The texts, links and file paths to share
+ * @returns A promise resolving when the share sheet is closed.
+ */
+async function share(items: ShareItem[]): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Share',
+    message: {
+      cmd: 'share',
+      items
+    }
+  })
+}
+
+export { share }
@@ -8,8 +8,8 @@ import { transformCallback } from './tauri'
 /**
  * Spawns a process.
  *
- * @param program The name of the program to execute e.g. 'mkdir' or 'node'
- * @param sidecar Whether the program is a sidecar or a system program
+ * @param program The name of the command on the `tauri > allowlist > shell > scope` config
+ * @param sidecar Whether the command is a sidecar or a system program
  * @param onEvent
  * @param [args] Command args
  * @returns A promise resolving to the process id.
@@ -112,6 +112,12 @@ class Command extends EventEmitter<'close' | 'error'> {
   stderr = new EventEmitter<'data'>()
   pid: number | null = null
 
+  /**
+   * Creates a command to execute the given program.
+   *
+   * @param program The name of the command on the `tauri > allowlist > shell > scope` config
+   * @param [args] Command args, validated against the ones on the scope
+   */
   constructor(program: string, args: string | string[] = []) {
     super()
     this.program = program
@@ -121,7 +127,7 @@ class Command extends EventEmitter<'close' | 'error'> {
   /**
    * Creates a command to execute the given sidecar binary.
    *
-   * @param program Binary name
+   * @param program The name of the sidecar command on the shell scope
    * @returns
    */
   static sidecar(program: string, args: string | string[] = []): Command {
@@ -198,6 +204,10 @@ type CommandEvent =
  * Opens a path or URL with the system's default app,
  * or the one specified with `openWith`.
  *
+ * The path must match the `tauri > allowlist > shell > openScope` regex,
+ * which only allows `mailto:`, `tel:`, `http://` and `https://` URLs by default.
+ * The `openWith` app must be listed on `tauri > allowlist > shell > openWith`.
+ *
  * @param path the path or URL to open
  * @param [openWith] the app to open the file or URL with
  * @returns
@@ -0,0 +1,153 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invokeTauriCommand } from './helpers/tauri'
+import { listen, UnlistenFn } from './event'
+
+export interface ProcessMetrics {
+  /** The process identifier. */
+  pid: number
+  /** The process name. */
+  name: string
+  /**
+   * The CPU usage, in percent of one core,
+   * so it exceeds 100 when the process uses several cores.
+   */
+  cpuUsage: number
+  /** The resident memory, in bytes. */
+  memory: number
+}
+
+export interface LoadAverage {
+  /** The average over the last minute. */
+  one: number
+  /** The average over the last five minutes. */
+  five: number
+  /** The average over the last fifteen minutes. */
+  fifteen: number
+}
+
+export interface SystemMetrics {
+  /** The CPU usage, in percent of all the cores. */
+  cpuUsage: number
+  /** The CPU usage of each core, in percent. */
+  cores: number[]
+  /** The total memory, in bytes. */
+  totalMemory: number
+  /** The used memory, in bytes. */
+  usedMemory: number
+  /** The total swap space, in bytes. */
+  totalSwap: number
+  /** The used swap space, in bytes. */
+  usedSwap: number
+  /** The load average, which is always zero on Windows. */
+  loadAverage: LoadAverage
+}
+
+export interface Metrics {
+  system: SystemMetrics
+  /** The metrics of the app process. */
+  process: ProcessMetrics | null
+}
+
+/**
+ * Gets the CPU and memory usage of the system.
+ * The CPU usage is measured since the previous call, so the first call reports a rough value.
+ *
+ * @returns A promise resolving to the system metrics.
+ */
+async function systemMetrics(): Promise<SystemMetrics> {
+  return invokeTauriCommand<SystemMetrics>({
+    __tauriModule: 'System',
+    message: {
+      cmd: 'systemMetrics'
+    }
+  })
+}
+
+/**
+ * Gets the CPU and memory usage of the app process.
+ * The CPU usage is measured since the previous call, so the first call reports a rough value.
+ *
+ * @returns A promise resolving to the process metrics.
+ */
+async function processMetrics(): Promise<ProcessMetrics | null> {
+  return invokeTauriCommand<ProcessMetrics | null>({
+    __tauriModule: 'System',
+    message: {
+      cmd: 'processMetrics'
+    }
+  })
+}
+
+/**
+ * Lists the processes of the system with their CPU and memory usage.
+ *
+ * @returns A promise resolving to the processes, sorted by identifier.
+ */
+async function processes(): Promise<ProcessMetrics[]> {
+  return invokeTauriCommand<ProcessMetrics[]>({
+    __tauriModule: 'System',
+    message: {
+      cmd: 'processes'
+    }
+  })
+}
+
+/**
+ * Polls the system and app process metrics.
+ * @example
+ * ```typescript
+ * import { subscribe } from '@tauri-apps/api/system'
+ * const unsubscribe = await subscribe(1000, ({ system }) => {
+ *   console.log(`CPU ${system.cpuUsage.toFixed(1)}%`)
+ * })
+ * ```
+ *
+ * @param interval The polling interval, in milliseconds
+ * @param handler Called with the metrics on every poll
+ * @returns A promise resolving to a function to stop polling.
+ */
+async function subscribe(
+  interval: number,
+  handler: (metrics: Metrics) => void
+): Promise<UnlistenFn> {
+  const subscriptionId = Math.floor(Math.random() * 0xffffffff)
+  const unlisten = await listen<Metrics & { subscriptionId: number }>(
+    'tauri://system-metrics',
+    (event) => {
+      if (event.payload.subscriptionId === subscriptionId) {
+        handler({
+          system: event.payload.system,
+          process: event.payload.process
+        })
+      }
+    }
+  )
+  try {
+    await invokeTauriCommand({
+      __tauriModule: 'System',
+      message: {
+        cmd: 'subscribe',
+        interval,
+        subscriptionId
+      }
+    })
+  } catch (e) {
+    unlisten()
+    throw e
+  }
+  return () => {
+    unlisten()
+    invokeTauriCommand({
+      __tauriModule: 'System',
+      message: {
+        cmd: 'unsubscribe',
+        subscriptionId
+      }
+    }).catch(() => {})
+  }
+}
+
+export { systemMetrics, processMetrics, processes, subscribe }
@@ -45,6 +45,53 @@ export interface InvokeArgs {
   [key: string]: unknown
 }
 
+interface BufferRef {
+  parent: Record<string, unknown>
+  key: string
+  id: string
+}
+
+/**
+ * Finds the `{ __tauriBuffer: id }` values of an invoke response, sent by `tauri::Binary`.
+ */
+function collectBuffers(
+  value: unknown,
+  parent: Record<string, unknown>,
+  key: string,
+  found: BufferRef[]
+): void {
+  if (value === null || typeof value !== 'object') {
+    return
+  }
+  const object = value as Record<string, unknown>
+  if (typeof object.__tauriBuffer === 'string') {
+    found.push({ parent, key, id: object.__tauriBuffer })
+    return
+  }
+  for (const k of Object.keys(object)) {
+    collectBuffers(object[k], object, k, found)
+  }
+}
+
+/**
+ * Replaces the buffer ids of an invoke response with the bytes read from the `tauri-buffer` protocol.
+ */
+async function resolveBuffers(response: unknown): Promise<unknown> {
+  const root: Record<string, unknown> = { value: response }
+  const found: BufferRef[] = []
+  collectBuffers(response, root, 'value', found)
+  await Promise.all(
+    found.map(async ({ parent, key, id }) =>
+      fetch(`tauri-buffer://${id}`)
+        .then(async (response) => response.arrayBuffer())
+        .then((buffer) => {
+          parent[key] = new Uint8Array(buffer)
+        })
+    )
+  )
+  return root.value
+}
+
 /**
  * Sends a message to the backend.
  *
@@ -55,7 +102,7 @@ export interface InvokeArgs {
 async function invoke<T>(cmd: string, args: InvokeArgs = {}): Promise<T> {
   return new Promise((resolve, reject) => {
     const callback = transformCallback((e) => {
-      resolve(e)
+      resolveBuffers(e).then((value) => resolve(value as T), reject)
       Reflect.deleteProperty(window, error)
     }, true)
     const error = transformCallback((e) => {
@@ -71,4 +118,16 @@ async function invoke<T>(cmd: string, args: InvokeArgs = {}): Promise<T> {
   })
 }
 
-export { transformCallback, invoke }
+/**
+ * Converts a file path to an URL loaded by the webview with the `asset` protocol,
+ * e.g. as an `<img>` or `<video>` source.
+ * The file must be in the `tauri > allowlist > protocol > assetScope` config.
+ *
+ * @param filePath The absolute file path
+ * @return The URL of the file.
+ */
+function convertFileSrc(filePath: string): string {
+  return `asset://localhost/${encodeURIComponent(filePath)}`
+}
+
+export { transformCallback, invoke, convertFileSrc }
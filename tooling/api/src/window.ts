@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 import { invokeTauriCommand } from './helpers/tauri'
-import { EventCallback, UnlistenFn, listen, once } from './event'
+import { EventCallback, TauriEvent, UnlistenFn, listen, once } from './event'
 import { emit } from './helpers/event'
 
 interface WindowDef {
@@ -15,18 +15,121 @@ declare global {
     __TAURI__: {
       __windows: WindowDef[]
       __currentWindow: WindowDef
+      __windowSnapshot?: unknown
+      __windowSnapshotProvider?: () => unknown
     }
   }
 }
 
-function getCurrent(): WebviewWindowHandle {
-  return new WebviewWindowHandle(window.__TAURI__.__currentWindow.label)
+function getCurrent(): WindowManager {
+  return new WindowManager(window.__TAURI__.__currentWindow.label)
 }
 
 function getAll(): WindowDef[] {
   return window.__TAURI__.__windows
 }
 
+/** A size in logical pixels, which are scaled by the monitor scale factor. */
+class LogicalSize {
+  type = 'Logical'
+  width: number
+  height: number
+
+  constructor(width: number, height: number) {
+    this.width = width
+    this.height = height
+  }
+}
+
+/** A size in the physical pixels of the monitor. */
+class PhysicalSize {
+  type = 'Physical'
+  width: number
+  height: number
+
+  constructor(width: number, height: number) {
+    this.width = width
+    this.height = height
+  }
+
+  /**
+   * Converts the size to logical pixels.
+   *
+   * @param scaleFactor The monitor scale factor, see [[WindowManager.scaleFactor]]
+   * @returns The logical size.
+   */
+  toLogical(scaleFactor: number): LogicalSize {
+    return new LogicalSize(this.width / scaleFactor, this.height / scaleFactor)
+  }
+}
+
+/** A position in logical pixels, which are scaled by the monitor scale factor. */
+class LogicalPosition {
+  type = 'Logical'
+  x: number
+  y: number
+
+  constructor(x: number, y: number) {
+    this.x = x
+    this.y = y
+  }
+}
+
+/** A position in the physical pixels of the monitor. */
+class PhysicalPosition {
+  type = 'Physical'
+  x: number
+  y: number
+
+  constructor(x: number, y: number) {
+    this.x = x
+    this.y = y
+  }
+
+  /**
+   * Converts the position to logical pixels.
+   *
+   * @param scaleFactor The monitor scale factor, see [[WindowManager.scaleFactor]]
+   * @returns The logical position.
+   */
+  toLogical(scaleFactor: number): LogicalPosition {
+    return new LogicalPosition(this.x / scaleFactor, this.y / scaleFactor)
+  }
+}
+
+/** Reads the deprecated `(width, height)` and `(x, y)` arguments as logical pixels. */
+function legacySize(
+  size: LogicalSize | PhysicalSize | number,
+  height?: number
+): LogicalSize | PhysicalSize {
+  return typeof size === 'number' ? new LogicalSize(size, height ?? 0) : size
+}
+
+function legacyPosition(
+  position: LogicalPosition | PhysicalPosition | number,
+  y?: number
+): LogicalPosition | PhysicalPosition {
+  return typeof position === 'number'
+    ? new LogicalPosition(position, y ?? 0)
+    : position
+}
+
+function sizeDto(size: LogicalSize | PhysicalSize): Record<string, unknown> {
+  return {
+    type: size.type,
+    data: { width: size.width, height: size.height }
+  }
+}
+
+function positionDto(
+  position: LogicalPosition | PhysicalPosition
+): Record<string, unknown> {
+  return {
+    type: position.type,
+    data: { x: position.x, y: position.y }
+  }
+}
+
 // events that are emitted right here instead of by the created webview
 const localTauriEvents = ['tauri://created', 'tauri://error']
 
@@ -81,6 +184,7 @@ class WebviewWindowHandle {
 
   /**
    * Emits an event to the webview.
+   * It's received with the label of the current window as `windowLabel`.
    *
    * @param event Event name
    * @param [payload] Event payload
@@ -89,7 +193,12 @@ class WebviewWindowHandle {
     if (localTauriEvents.includes(event)) {
       // eslint-disable-next-line
       for (const handler of this.listeners[event] || []) {
-        handler({ event, id: -1, payload })
+        handler({
+          event,
+          id: -1,
+          windowLabel: window.__TAURI__.__currentWindow.label,
+          payload
+        })
       }
       return Promise.resolve()
     }
@@ -111,38 +220,10 @@ class WebviewWindowHandle {
   }
 }
 
-class WebviewWindow extends WebviewWindowHandle {
-  constructor(label: string, options: WindowOptions = {}) {
-    super(label)
-    invokeTauriCommand({
-      __tauriModule: 'Window',
-      message: {
-        cmd: 'createWebview',
-        options: {
-          label,
-          ...options
-        }
-      }
-    })
-      .then(async () => this.emit('tauri://created'))
-      .catch(async (e) => this.emit('tauri://error', e))
-  }
-
-  /**
-   * Gets the WebviewWindow handle for the webview associated with the given label.
-   *
-   * @param label The webview window label.
-   * @returns The handle to communicate with the webview or null if the webview doesn't exist.
-   */
-  static getByLabel(label: string): WebviewWindowHandle | null {
-    if (getAll().some((w) => w.label === label)) {
-      return new WebviewWindowHandle(label)
-    }
-    return null
-  }
-}
-
-export class WindowManager {
+/**
+ * Manages a window. The methods are applied to the window with the given label.
+ */
+class WindowManager extends WebviewWindowHandle {
   /**
    * Updates the window resizable flag.
    *
@@ -153,6 +234,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setResizable',
         resizable
       }
@@ -169,12 +251,30 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setTitle',
         title
       }
     })
   }
 
+  /**
+   * Sets the label assistive technologies announce for the window content.
+   *
+   * @param title The accessibility label
+   * @returns
+   */
+  async setAccessibilityTitle(title: string): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setAccessibilityTitle',
+        title
+      }
+    })
+  }
+
   /**
    * Maximizes the window.
    *
@@ -184,6 +284,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'maximize'
       }
     })
@@ -198,6 +299,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'unmaximize'
       }
     })
@@ -212,6 +314,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'minimize'
       }
     })
@@ -226,6 +329,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'unminimize'
       }
     })
@@ -240,6 +344,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'show'
       }
     })
@@ -254,11 +359,47 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'hide'
       }
     })
   }
 
+  /**
+   * Posts a message to the window, received with `onMessage`.
+   * The message only reaches this window and isn't seen by the Rust event listeners.
+   *
+   * @param data The message, serialized as JSON
+   * @param [transfer] Binary buffers sent along the message; the window reads them without JSON serialization
+   * @returns
+   */
+  async postMessage(data: unknown, transfer: Uint8Array[] = []): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Event',
+      message: {
+        cmd: 'postMessage',
+        target: this.label,
+        data,
+        buffers: transfer.map((buffer) => Array.from(buffer))
+      }
+    })
+  }
+
+  /**
+   * Reloads the page, discarding its JavaScript state. Used to restart worker windows.
+   *
+   * @returns
+   */
+  async restart(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'restart'
+      }
+    })
+  }
+
   /**
    * Closes the window.
    *
@@ -268,6 +409,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'close'
       }
     })
@@ -283,6 +425,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setDecorations',
         decorations
       }
@@ -299,6 +442,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setAlwaysOnTop',
         alwaysOnTop
       }
@@ -306,141 +450,297 @@ export class WindowManager {
   }
 
   /**
-   * Sets the window width.
+   * Gets the factor that maps logical pixels to the physical pixels of the window's monitor.
    *
-   * @param width The new window width
-   * @returns
+   * @returns A promise resolving to the scale factor.
    */
-  async setWidth(width: number): Promise<void> {
+  async scaleFactor(): Promise<number> {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setWidth',
-        width
+        label: this.label,
+        cmd: 'scaleFactor'
       }
     })
   }
 
   /**
-   * Sets the window height.
+   * Gets the size of the window's client area, i.e. without its decorations.
    *
-   * @param height The new window height
-   * @returns
+   * @returns A promise resolving to the size.
    */
-  async setHeight(height: number): Promise<void> {
-    return invokeTauriCommand({
+  async innerSize(): Promise<PhysicalSize> {
+    return invokeTauriCommand<{ width: number; height: number }>({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'innerSize'
+      }
+    }).then(({ width, height }) => new PhysicalSize(width, height))
+  }
+
+  /**
+   * Gets the size of the whole window, including its decorations.
+   *
+   * @returns A promise resolving to the size.
+   */
+  async outerSize(): Promise<PhysicalSize> {
+    return invokeTauriCommand<{ width: number; height: number }>({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setHeight',
-        height
+        label: this.label,
+        cmd: 'outerSize'
+      }
+    }).then(({ width, height }) => new PhysicalSize(width, height))
+  }
+
+  /**
+   * Gets the position of the top left corner of the window's client area on the desktop.
+   *
+   * @returns A promise resolving to the position.
+   */
+  async innerPosition(): Promise<PhysicalPosition> {
+    return invokeTauriCommand<{ x: number; y: number }>({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'innerPosition'
+      }
+    }).then(({ x, y }) => new PhysicalPosition(x, y))
+  }
+
+  /**
+   * Gets the position of the top left corner of the window on the desktop.
+   *
+   * @returns A promise resolving to the position.
+   */
+  async outerPosition(): Promise<PhysicalPosition> {
+    return invokeTauriCommand<{ x: number; y: number }>({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'outerPosition'
+      }
+    }).then(({ x, y }) => new PhysicalPosition(x, y))
+  }
+
+  /**
+   * Gets whether the window is maximized.
+   *
+   * @returns A promise resolving to whether the window is maximized.
+   */
+  async isMaximized(): Promise<boolean> {
+    return invokeTauriCommand<boolean>({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'isMaximized'
       }
     })
   }
 
   /**
-   * Resizes the window.
+   * Gets whether the window is fullscreen.
    *
-   * @param width The new window width
-   * @param height The new window height
-   * @returns
+   * @returns A promise resolving to whether the window is fullscreen.
    */
-  async resize(width: number, height: number): Promise<void> {
-    return invokeTauriCommand({
+  async isFullscreen(): Promise<boolean> {
+    return invokeTauriCommand<boolean>({
       __tauriModule: 'Window',
       message: {
-        cmd: 'resize',
-        width,
-        height
+        label: this.label,
+        cmd: 'isFullscreen'
       }
     })
   }
 
   /**
-   * Sets the window min size.
+   * Gets whether the window is visible.
    *
-   * @param minWidth The new window min width
-   * @param minHeight The new window min height
-   * @returns
+   * @returns A promise resolving to whether the window is visible.
    */
-  async setMinSize(minWidth: number, minHeight: number): Promise<void> {
-    return invokeTauriCommand({
+  async isVisible(): Promise<boolean> {
+    return invokeTauriCommand<boolean>({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setMinSize',
-        minWidth,
-        minHeight
+        label: this.label,
+        cmd: 'isVisible'
       }
     })
   }
 
   /**
-   * Sets the window max size.
+   * Gets whether the window is decorated.
+   *
+   * @returns A promise resolving to whether the window is decorated.
+   */
+  async isDecorated(): Promise<boolean> {
+    return invokeTauriCommand<boolean>({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'isDecorated'
+      }
+    })
+  }
+
+  /**
+   * Resizes the window.
+   * @example
+   * ```typescript
+   * import { appWindow, LogicalSize } from '@tauri-apps/api/window'
+   * await appWindow.setSize(new LogicalSize(600, 500))
+   * ```
    *
-   * @param maxWidth The new window max width
-   * @param maxHeight The new window max height
+   * @param size The new window size
    * @returns
    */
-  async setMaxSize(maxWidth: number, maxHeight: number): Promise<void> {
+  async setSize(size: LogicalSize | PhysicalSize): Promise<void> {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setMaxSize',
-        maxWidth,
-        maxHeight
+        label: this.label,
+        cmd: 'setSize',
+        size: sizeDto(size)
       }
     })
   }
 
   /**
-   * Sets the window x position.
+   * Resizes the window.
    *
-   * @param x The new window x position
+   * @deprecated Use [[WindowManager.setSize]] with a `LogicalSize` instead.
+   * @param width The new window width, in logical pixels
+   * @param height The new window height, in logical pixels
    * @returns
    */
-  async setX(x: number): Promise<void> {
+  async resize(width: number, height: number): Promise<void> {
+    return this.setSize(new LogicalSize(width, height))
+  }
+
+  /**
+   * Sets the window width, keeping its height.
+   *
+   * @deprecated Use [[WindowManager.setSize]] with a `LogicalSize` instead.
+   * @param width The new window width, in logical pixels
+   * @returns
+   */
+  async setWidth(width: number): Promise<void> {
+    const size = (await this.innerSize()).toLogical(await this.scaleFactor())
+    return this.setSize(new LogicalSize(width, size.height))
+  }
+
+  /**
+   * Sets the window height, keeping its width.
+   *
+   * @deprecated Use [[WindowManager.setSize]] with a `LogicalSize` instead.
+   * @param height The new window height, in logical pixels
+   * @returns
+   */
+  async setHeight(height: number): Promise<void> {
+    const size = (await this.innerSize()).toLogical(await this.scaleFactor())
+    return this.setSize(new LogicalSize(size.width, height))
+  }
+
+  /**
+   * Sets the window min size.
+   *
+   * @param size The new window min size. Passing the width and height in logical pixels
+   * is deprecated.
+   * @param minHeight The new window min height, when `size` is the deprecated width
+   * @returns
+   */
+  async setMinSize(
+    size: LogicalSize | PhysicalSize | number,
+    minHeight?: number
+  ): Promise<void> {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setX',
-        x
+        label: this.label,
+        cmd: 'setMinSize',
+        size: sizeDto(legacySize(size, minHeight))
       }
     })
   }
 
   /**
-   * Sets the window y position.
+   * Sets the window max size.
    *
-   * @param y The new window y position
+   * @param size The new window max size. Passing the width and height in logical pixels
+   * is deprecated.
+   * @param maxHeight The new window max height, when `size` is the deprecated width
    * @returns
    */
-  async setY(y: number): Promise<void> {
+  async setMaxSize(
+    size: LogicalSize | PhysicalSize | number,
+    maxHeight?: number
+  ): Promise<void> {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
-        cmd: 'setY',
-        y
+        label: this.label,
+        cmd: 'setMaxSize',
+        size: sizeDto(legacySize(size, maxHeight))
       }
     })
   }
 
   /**
    * Sets the window position.
+   * @example
+   * ```typescript
+   * import { appWindow, LogicalPosition } from '@tauri-apps/api/window'
+   * await appWindow.setPosition(new LogicalPosition(600, 500))
+   * ```
    *
-   * @param x The new window x position
-   * @param y The new window y position
+   * @param position The new window position. Passing the x and y coordinates in logical pixels
+   * is deprecated.
+   * @param y The new window y position, when `position` is the deprecated x position
    * @returns
    */
-  async setPosition(x: number, y: number): Promise<void> {
+  async setPosition(
+    position: LogicalPosition | PhysicalPosition | number,
+    y?: number
+  ): Promise<void> {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setPosition',
-        x,
-        y
+        position: positionDto(legacyPosition(position, y))
       }
     })
   }
 
+  /**
+   * Sets the window x position, keeping its y position.
+   *
+   * @deprecated Use [[WindowManager.setPosition]] with a `LogicalPosition` instead.
+   * @param x The new window x position, in logical pixels
+   * @returns
+   */
+  async setX(x: number): Promise<void> {
+    const position = (await this.outerPosition()).toLogical(
+      await this.scaleFactor()
+    )
+    return this.setPosition(new LogicalPosition(x, position.y))
+  }
+
+  /**
+   * Sets the window y position, keeping its x position.
+   *
+   * @deprecated Use [[WindowManager.setPosition]] with a `LogicalPosition` instead.
+   * @param y The new window y position, in logical pixels
+   * @returns
+   */
+  async setY(y: number): Promise<void> {
+    const position = (await this.outerPosition()).toLogical(
+      await this.scaleFactor()
+    )
+    return this.setPosition(new LogicalPosition(position.x, y))
+  }
+
   /**
    * Sets the window fullscreen state.
    *
@@ -451,6 +751,7 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setFullscreen',
         fullscreen
       }
@@ -467,14 +768,525 @@ export class WindowManager {
     return invokeTauriCommand({
       __tauriModule: 'Window',
       message: {
+        label: this.label,
         cmd: 'setIcon',
         icon
       }
     })
   }
+
+  /**
+   * Assigns the window to a group of related windows.
+   * On macOS, windows of the same group can be merged as native tabs.
+   *
+   * @param group The group name, or `null` to remove the window from its group
+   * @returns
+   */
+  async setParentGroup(group: string | null): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setParentGroup',
+        group
+      }
+    })
+  }
+
+  /**
+   * Merges all windows of the window's tab group into a single tabbed window. macOS only.
+   *
+   * @returns
+   */
+  async mergeAllWindows(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'mergeAllWindows'
+      }
+    })
+  }
+
+  /**
+   * Selects the next tab of the window's tab group. macOS only.
+   *
+   * @returns
+   */
+  async selectNextTab(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'selectNextTab'
+      }
+    })
+  }
+
+  /**
+   * Selects the previous tab of the window's tab group. macOS only.
+   *
+   * @returns
+   */
+  async selectPreviousTab(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'selectPreviousTab'
+      }
+    })
+  }
+
+  /**
+   * Moves the window out of its tab group into a standalone window. macOS only.
+   *
+   * @returns
+   */
+  async moveTabToNewWindow(): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'moveTabToNewWindow'
+      }
+    })
+  }
+
+  /**
+   * Shows or hides the cursor while it's over the window.
+   *
+   * @param visible Whether the cursor should be visible or not
+   * @returns
+   */
+  async setCursorVisible(visible: boolean): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setCursorVisible',
+        visible
+      }
+    })
+  }
+
+  /**
+   * Confines the cursor to the window, e.g. for the pointer lock of a game.
+   *
+   * @param grab Whether the cursor should be confined or not
+   * @returns
+   */
+  async setCursorGrab(grab: boolean): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setCursorGrab',
+        grab
+      }
+    })
+  }
+
+  /**
+   * Sets the cursor icon shown over the window.
+   *
+   * @param icon The icon, named after the CSS `cursor` values
+   * @returns
+   */
+  async setCursorIcon(icon: CursorIcon): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setCursorIcon',
+        icon
+      }
+    })
+  }
+
+  /**
+   * Moves the cursor to a position relative to the top left corner of the window.
+   *
+   * @param position The new cursor position. Passing the x and y coordinates in logical pixels
+   * is deprecated.
+   * @param y The new cursor y position, when `position` is the deprecated x position
+   * @returns
+   */
+  async setCursorPosition(
+    position: LogicalPosition | PhysicalPosition | number,
+    y?: number
+  ): Promise<void> {
+    return invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        label: this.label,
+        cmd: 'setCursorPosition',
+        position: positionDto(legacyPosition(position, y))
+      }
+    })
+  }
+}
+
+/**
+ * Creates a new webview window.
+ *
+ * The `tauri://created` event is emitted on the instance once the window is created,
+ * and `tauri://error` if the creation fails.
+ */
+class WebviewWindow extends WindowManager {
+  constructor(label: string, options: WindowOptions = {}) {
+    super(label)
+    invokeTauriCommand({
+      __tauriModule: 'Window',
+      message: {
+        cmd: 'createWebview',
+        options: {
+          label,
+          ...options
+        }
+      }
+    })
+      .then(async () => this.emit('tauri://created'))
+      .catch(async (e) => this.emit('tauri://error', e))
+  }
+
+  /**
+   * Creates a new webview window. Requires the `window > create` allowlist entry.
+   *
+   * @param label The unique webview window label.
+   * @param options The window configuration.
+   * @returns The handle to manage the window and communicate with its webview.
+   */
+  static create(label: string, options: WindowOptions = {}): WebviewWindow {
+    return new WebviewWindow(label, options)
+  }
+
+  /**
+   * Gets the WebviewWindow handle for the webview associated with the given label.
+   *
+   * @param label The webview window label.
+   * @returns The handle to manage the window and communicate with its webview or null if the webview doesn't exist.
+   */
+  static getByLabel(label: string): WindowManager | null {
+    if (getAll().some((w) => w.label === label)) {
+      return new WindowManager(label)
+    }
+    return null
+  }
 }
 
-const appWindow = new WindowManager()
+const appWindow = new WindowManager(window.__TAURI__.__currentWindow.label)
+
+/** Data other than files dropped onto the current window. */
+export interface DataDropEvent {
+  /** The dropped data indexed by MIME type, e.g. `text/plain`, `text/uri-list` or `text/html`. */
+  items: Record<string, string>
+  /** Where the data was dropped, in CSS pixels relative to the webview. */
+  position: { x: number; y: number }
+}
+
+/**
+ * Listens to the text, URLs, HTML and custom MIME types dropped onto the current window.
+ * The drops handled by the page, i.e. calling `event.preventDefault()`, and the drops on editable elements aren't reported.
+ *
+ * @param handler Drop handler callback
+ * @returns A promise resolving to a function to stop listening to the drops.
+ */
+async function onDrop(
+  handler: (event: DataDropEvent) => void
+): Promise<UnlistenFn> {
+  return listen<DataDropEvent>('tauri://drop', (event) =>
+    handler(event.payload)
+  )
+}
+
+/**
+ * Listens to the resizes of the current window.
+ * The resizes are coalesced while the user drags the window, see `Builder::window_event_throttle` on the Rust side,
+ * but the final size is always reported.
+ *
+ * @param handler Resize handler callback, receiving the new size.
+ * @returns A promise resolving to a function to stop listening to the resizes.
+ */
+async function onResized(
+  handler: (size: PhysicalSize) => void
+): Promise<UnlistenFn> {
+  return listen<{ width: number; height: number }>(
+    TauriEvent.WINDOW_RESIZED,
+    (event) =>
+      handler(new PhysicalSize(event.payload.width, event.payload.height))
+  )
+}
+
+/**
+ * Listens to the moves of the current window.
+ * The moves are coalesced while the user drags the window, see `Builder::window_event_throttle` on the Rust side,
+ * but the final position is always reported.
+ *
+ * @param handler Move handler callback, receiving the new position.
+ * @returns A promise resolving to a function to stop listening to the moves.
+ */
+async function onMoved(
+  handler: (position: PhysicalPosition) => void
+): Promise<UnlistenFn> {
+  return listen<{ x: number; y: number }>(TauriEvent.WINDOW_MOVED, (event) =>
+    handler(new PhysicalPosition(event.payload.x, event.payload.y))
+  )
+}
+
+/**
+ * Listens to the focus changes of the current window.
+ *
+ * @param handler Handler receiving whether the window gained the focus.
+ * @returns A promise resolving to a function to stop listening to the focus changes.
+ */
+async function onFocusChanged(
+  handler: (focused: boolean) => void
+): Promise<UnlistenFn> {
+  const unlistenFocus = await listen(TauriEvent.WINDOW_FOCUS, () =>
+    handler(true)
+  )
+  const unlistenBlur = await listen(TauriEvent.WINDOW_BLUR, () =>
+    handler(false)
+  )
+  return () => {
+    unlistenFocus()
+    unlistenBlur()
+  }
+}
+
+/** The light or dark theme of a window. */
+export type Theme = 'light' | 'dark'
+
+/**
+ * Listens to the system theme changes of the current window.
+ *
+ * @param handler Handler receiving the new theme.
+ * @returns A promise resolving to a function to stop listening to the theme changes.
+ */
+async function onThemeChanged(
+  handler: (theme: Theme) => void
+): Promise<UnlistenFn> {
+  return listen<Theme>(TauriEvent.WINDOW_THEME_CHANGED, (event) =>
+    handler(event.payload)
+  )
+}
+
+/**
+ * Listens to the close requests of the current window, e.g. to save the page state.
+ * The page can't prevent the close, use `Window::on_window_event` on the Rust side instead.
+ *
+ * @param handler Close request handler callback.
+ * @returns A promise resolving to a function to stop listening to the close requests.
+ */
+async function onCloseRequested(handler: () => void): Promise<UnlistenFn> {
+  return listen(TauriEvent.WINDOW_CLOSE_REQUESTED, () => handler())
+}
+
+/** The files dragged over or dropped onto a window. */
+export type FileDropEvent =
+  | { type: 'hover'; paths: string[] }
+  | { type: 'drop'; paths: string[] }
+  | { type: 'cancel' }
+
+/**
+ * Listens to the files dragged over, dropped onto or leaving the current window.
+ *
+ * @param handler File drop handler callback.
+ * @returns A promise resolving to a function to stop listening to the file drops.
+ */
+async function onFileDrop(
+  handler: (event: FileDropEvent) => void
+): Promise<UnlistenFn> {
+  const unlistenHover = await listen<string[]>(
+    TauriEvent.WINDOW_FILE_DROP_HOVER,
+    (event) => handler({ type: 'hover', paths: event.payload })
+  )
+  const unlistenDrop = await listen<string[]>(
+    TauriEvent.WINDOW_FILE_DROP,
+    (event) => handler({ type: 'drop', paths: event.payload })
+  )
+  const unlistenCancel = await listen(TauriEvent.WINDOW_FILE_DROP_CANCELLED, () =>
+    handler({ type: 'cancel' })
+  )
+  return () => {
+    unlistenHover()
+    unlistenDrop()
+    unlistenCancel()
+  }
+}
+
+/** A message posted to the current window with `WindowManager.postMessage`. */
+export interface WindowMessage<T> {
+  /** The label of the window that posted the message. */
+  source: string
+  data: T
+  buffers: Uint8Array[]
+}
+
+/**
+ * Listens to the messages posted to the current window.
+ *
+ * @param handler Message handler callback
+ * @returns A promise resolving to a function to stop listening to the messages.
+ */
+async function onMessage<T>(
+  handler: (message: WindowMessage<T>) => void
+): Promise<UnlistenFn> {
+  return listen<{ source: string; data: T; buffers: string[] }>(
+    'tauri://message',
+    (event) => {
+      const { source, data, buffers } = event.payload
+      void Promise.all(
+        buffers.map(async (id) =>
+          fetch(`tauri-buffer://${id}`)
+            .then(async (response) => response.arrayBuffer())
+            .then((buffer) => new Uint8Array(buffer))
+        )
+      ).then((buffers) => handler({ source, data, buffers }))
+    }
+  )
+}
+
+export type CursorIcon =
+  | 'default'
+  | 'crosshair'
+  | 'hand'
+  | 'arrow'
+  | 'move'
+  | 'text'
+  | 'wait'
+  | 'help'
+  | 'progress'
+  | 'not-allowed'
+  | 'context-menu'
+  | 'cell'
+  | 'vertical-text'
+  | 'alias'
+  | 'copy'
+  | 'no-drop'
+  | 'grab'
+  | 'grabbing'
+  | 'all-scroll'
+  | 'zoom-in'
+  | 'zoom-out'
+  | 'e-resize'
+  | 'n-resize'
+  | 'ne-resize'
+  | 'nw-resize'
+  | 's-resize'
+  | 'se-resize'
+  | 'sw-resize'
+  | 'w-resize'
+  | 'ew-resize'
+  | 'ns-resize'
+  | 'nesw-resize'
+  | 'nwse-resize'
+  | 'col-resize'
+  | 'row-resize'
+
+/** A screen or a window that can be shared. */
+export interface CaptureSource {
+  /** The source identifier, e.g. `screen:HDMI-1` or `window:0x04000007`. */
+  id: string
+  kind: 'screen' | 'window'
+  /** The screen name or the window title. */
+  title: string
+  /** A PNG preview of the source, as a `data:` URL, if requested and available. */
+  thumbnail: string | null
+}
+
+/**
+ * Lists the screens and windows that can be shared, e.g. to build a screen sharing picker.
+ *
+ * @param thumbnails Whether to take a preview of each source, which is slow.
+ * @returns A promise resolving to the sources.
+ */
+async function getCaptureSources(
+  thumbnails: boolean = false
+): Promise<CaptureSource[]> {
+  return invokeTauriCommand<CaptureSource[]>({
+    __tauriModule: 'Window',
+    message: {
+      cmd: 'getCaptureSources',
+      thumbnails
+    }
+  })
+}
+
+export interface MediaDevice {
+  /** The device identifier of the system, e.g. `/dev/video0` or a PulseAudio source name. */
+  id: string
+  kind: 'videoInput' | 'audioInput'
+  /** The device name, available before the user allows the capture. */
+  label: string
+  /** Whether it's the default device of its kind. */
+  isDefault: boolean
+}
+
+/**
+ * Lists the cameras and the microphones connected to the system, with their names,
+ * which `navigator.mediaDevices.enumerateDevices` hides until the user allows the capture.
+ *
+ * @returns A promise resolving to the devices.
+ */
+async function getMediaDevices(): Promise<MediaDevice[]> {
+  return invokeTauriCommand<MediaDevice[]>({
+    __tauriModule: 'Window',
+    message: {
+      cmd: 'getMediaDevices'
+    }
+  })
+}
+
+/**
+ * Shows the windows created hidden while the splashscreen shows and closes the splashscreen.
+ * Meant for splashscreens with the `splashscreenAutoClose` config disabled,
+ * e.g. called once the app finished loading its data.
+ * @example
+ * ```typescript
+ * import { closeSplashscreen } from '@tauri-apps/api/window'
+ * await loadSettings()
+ * await closeSplashscreen()
+ * ```
+ *
+ * @returns A promise resolving when the windows are swapped.
+ */
+async function closeSplashscreen(): Promise<void> {
+  return invokeTauriCommand({
+    __tauriModule: 'Window',
+    message: {
+      cmd: 'closeSplashscreen'
+    }
+  })
+}
+
+/**
+ * Registers the function returning the state of the page, saved when the current window closes
+ * and restored on the window with the same label when the app launches again.
+ * Requires the `window-snapshot` feature of the `tauri` crate.
+ * @example
+ * ```typescript
+ * import { setWindowSnapshotProvider, getWindowSnapshot } from '@tauri-apps/api/window'
+ * const draft = getWindowSnapshot<string>() ?? ''
+ * setWindowSnapshotProvider(() => editor.value)
+ * ```
+ *
+ * @param provider Returns the JSON-serializable state, or a promise resolving to it. Returning `null` clears the saved state.
+ */
+function setWindowSnapshotProvider(provider: () => unknown): void {
+  window.__TAURI__.__windowSnapshotProvider = provider
+}
+
+/**
+ * Gets the state returned by the snapshot provider when the current window was last closed.
+ *
+ * @returns The state, or `null` if none was saved.
+ */
+function getWindowSnapshot<T>(): T | null {
+  const snapshot = window.__TAURI__.__windowSnapshot
+  return snapshot === undefined ? null : (snapshot as T)
+}
 
 export interface WindowOptions {
   url?: string
@@ -493,6 +1305,40 @@ export interface WindowOptions {
   visible?: boolean
   decorations?: boolean
   alwaysOnTop?: boolean
+  /** The proxy the webview sends its network requests through, e.g. `http://proxy.local:3128`. */
+  proxyUrl?: string
+  /** Whether the webview renders with the GPU. Disabling it forces software rendering. */
+  hardwareAcceleration?: boolean
+  /**
+   * Whether the window can receive focus.
+   * Combined with `visible: false`, the window is a worker: a background page used only to run JavaScript.
+   */
+  focusable?: boolean
+  /** Whether the window is a splashscreen, shown while the other windows load. */
+  splashscreen?: boolean
 }
 
-export { WebviewWindow, getCurrent, getAll, appWindow }
+export {
+  WebviewWindow,
+  WindowManager,
+  LogicalSize,
+  PhysicalSize,
+  LogicalPosition,
+  PhysicalPosition,
+  getCurrent,
+  getAll,
+  appWindow,
+  onMessage,
+  onDrop,
+  onResized,
+  onMoved,
+  onFocusChanged,
+  onThemeChanged,
+  onCloseRequested,
+  onFileDrop,
+  getCaptureSources,
+  getMediaDevices,
+  closeSplashscreen,
+  setWindowSnapshotProvider,
+  getWindowSnapshot
+}
@@ -22,6 +22,7 @@ mod wix;
 pub use self::{
   category::AppCategory,
   common::{print_error, print_info},
+  platform::target_triple,
   settings::{
     BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings, PackageType,
     Settings, SettingsBuilder, UpdaterSettings,
@@ -59,6 +59,16 @@ fn symlink_file(src: &Path, dst: &Path) -> io::Result<()> {
   std::os::windows::fs::symlink_file(src, dst)
 }
 
+/// The file name of a bundled external binary: its name without the target triple suffix,
+/// e.g. `my-bin-x86_64-pc-windows-msvc.exe` is bundled as `my-bin.exe`.
+pub fn external_binary_name(path: &Path) -> crate::Result<String> {
+  let file_name = path
+    .file_name()
+    .and_then(OsStr::to_str)
+    .ok_or_else(|| crate::Error::GenericError(format!("invalid external binary {:?}", path)))?;
+  Ok(file_name.replacen(&format!("-{}", super::platform::target_triple()?), "", 1))
+}
+
 /// Copies a regular file from one path to another, creating any parent
 /// directories of the destination path as necessary.  Fails if the source path
 /// is a directory or doesn't exist.
@@ -474,15 +474,11 @@ impl Settings {
     }
   }
 
-  /// Copies external binaries to a path.
+  /// Copies external binaries to a path, without their target triple suffix.
   pub fn copy_binaries(&self, path: &Path) -> crate::Result<()> {
     for src in self.external_binaries() {
       let src = src?;
-      let dest = path.join(
-        src
-          .file_name()
-          .expect("failed to extract external binary filename"),
-      );
+      let dest = path.join(common::external_binary_name(&src)?);
       common::copy_file(&src, &dest)?;
     }
     Ok(())
@@ -82,6 +82,8 @@ struct Binary {
   id: String,
   /// the binary path.
   path: String,
+  /// the installed file name, without the target triple suffix of the external binaries.
+  name: String,
 }
 
 /// A Resource file to bundle with WIX.
@@ -676,9 +678,11 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
       .expect("failed to convert external binary filename to string");
 
     let guid = generate_guid(filename.as_bytes()).to_string();
+    let name = common::external_binary_name(&src)?;
 
     binaries.push(Binary {
       guid,
+      name,
       path: cwd
         .join(src)
         .into_os_string()
@@ -692,10 +696,15 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
     let filename = bin.name();
     let guid = generate_guid(filename.as_bytes()).to_string();
     if !bin.main() {
+      let path = settings.binary_path(bin);
       binaries.push(Binary {
         guid,
-        path: settings
-          .binary_path(bin)
+        name: path
+          .file_name()
+          .expect("failed to extract binary filename")
+          .to_string_lossy()
+          .into_owned(),
+        path: path
           .into_os_string()
           .into_string()
           .expect("failed to read binary path"),
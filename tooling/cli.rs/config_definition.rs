@@ -84,6 +84,9 @@ pub struct BundleConfig {
   pub deb: DebConfig,
   #[serde(rename = "macOS", default)]
   pub macos: MacConfig,
+  /// The sidecar binaries bundled with the app, e.g. `binaries/my-bin`, run with `Command::new_sidecar`.
+  /// The file of each platform is suffixed with its target triple, e.g. `binaries/my-bin-x86_64-pc-windows-msvc.exe`,
+  /// and the suffix is removed when it's copied next to the app executable.
   pub external_bin: Option<Vec<String>>,
   #[serde(default)]
   pub windows: WindowsConfig,
@@ -260,6 +263,60 @@ pub struct WindowConfig {
   /// Whether the window should always be on top of other windows.
   #[serde(default)]
   pub always_on_top: bool,
+  /// The proxy the webview sends its network requests through, e.g. `http://proxy.local:3128`.
+  pub proxy_url: Option<String>,
+  /// Whether the webview renders with the GPU. Disabling it forces software rendering,
+  /// working around graphics driver bugs, e.g. on Linux virtual machines.
+  #[serde(default = "default_hardware_acceleration")]
+  pub hardware_acceleration: bool,
+  /// Whether the window can receive focus. Combined with `visible: false`, the window is a worker:
+  /// a background page used only to run JavaScript.
+  #[serde(default = "default_focusable")]
+  pub focusable: bool,
+  /// Whether the window is a splashscreen, shown while the other windows load.
+  ///
+  /// The other windows are created hidden, then shown when their page has loaded,
+  /// closing the splashscreen.
+  #[serde(default)]
+  pub splashscreen: bool,
+  /// Whether the splashscreen closes once the other windows load. Disable it to close the
+  /// splashscreen with the `close_splashscreen` API instead, e.g. once the app setup finished.
+  #[serde(default = "default_splashscreen_auto_close")]
+  pub splashscreen_auto_close: bool,
+  /// The API modules and the commands the window can use, on top of the `event` module,
+  /// e.g. `["fs", "dialog", "command:greet", "plugin:sql"]`.
+  ///
+  /// Restricts the modules enabled on the app allowlist and the app and plugin commands;
+  /// all of them are available by default. The app commands are listed as `command:<name>`,
+  /// and the plugins as `plugin:<name>`, or `plugin:<name>|<command>` for a single command.
+  pub allowlist: Option<Vec<String>>,
+  /// JavaScript sources evaluated on each page load, after the Tauri API is injected.
+  #[serde(default)]
+  pub initialization_scripts: Vec<String>,
+  /// What happens when the user closes the window, unless a `CloseRequested` handler prevents it.
+  #[serde(default)]
+  pub close_behavior: CloseBehavior,
+  /// Whether the webview dev tools are opened when the window is created. Ignored on release builds.
+  #[serde(default)]
+  pub devtools: bool,
+}
+
+/// What happens when the user closes a window.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseBehavior {
+  /// The window is destroyed.
+  Destroy,
+  /// The window is hidden, e.g. for an app living in the system tray.
+  Hide,
+  /// The app exits, like `AppHandle::exit`: the other windows are closed first.
+  Quit,
+}
+
+impl Default for CloseBehavior {
+  fn default() -> Self {
+    Self::Destroy
+  }
 }
 
 fn default_visible() -> bool {
@@ -270,11 +327,53 @@ fn default_decorations() -> bool {
   true
 }
 
+fn default_hardware_acceleration() -> bool {
+  true
+}
+
+fn default_focusable() -> bool {
+  true
+}
+
+fn default_splashscreen_auto_close() -> bool {
+  true
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SecurityConfig {
   pub csp: Option<String>,
+  /// The commands asking the user for consent with a native dialog before running.
+  /// The answer is remembered for the window until the app exits.
+  #[serde(default)]
+  pub permission_prompts: Vec<PermissionPrompt>,
+  /// The scheme of the protocol serving the app assets, e.g. `app` for `app://myapp`.
+  /// The CSP and the OAuth or service worker allowlists must use the resulting origin.
+  #[serde(default = "default_scheme")]
+  pub scheme: String,
+  /// The host of the app URL, e.g. `myapp` for `app://myapp`. Defaults to the bundle identifier.
+  pub host: Option<String>,
+  /// Whether the app pages can register service workers and use the Cache API, for offline support.
+  /// The webview may still disable them, e.g. WebKitGTK only allows them on `https`.
+  #[serde(default)]
+  pub service_workers: bool,
+}
+
+fn default_scheme() -> String {
+  "tauri".to_string()
+}
+
+/// A command asking the user for consent before running.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionPrompt {
+  /// The file system APIs reading a path outside of the `tauri > allowlist > fs > scope`.
+  FsReadOutsideScope,
+  /// The file system APIs writing a path outside of the `tauri > allowlist > fs > scope`.
+  FsWriteOutsideScope,
+  /// The shell `execute` API.
+  ShellExecute,
 }
 
 trait Allowlist {
@@ -316,6 +415,14 @@ struct FsAllowlistConfig {
   rename_file: bool,
   #[serde(default)]
   path: bool,
+  #[serde(default)]
+  metadata: bool,
+  /// Allows creating and reading symbolic links.
+  #[serde(default)]
+  symlink: bool,
+  /// The glob patterns of the paths the file system APIs can access, e.g. `$APP/**`.
+  #[serde(default)]
+  scope: Vec<String>,
 }
 
 impl Allowlist for FsAllowlistConfig {
@@ -335,6 +442,8 @@ impl Allowlist for FsAllowlistConfig {
       check_feature!(self, features, remove_file, "fs-remove-file");
       check_feature!(self, features, rename_file, "fs-rename-file");
       check_feature!(self, features, path, "fs-path");
+      check_feature!(self, features, metadata, "fs-metadata");
+      check_feature!(self, features, symlink, "fs-symlink");
       features
     }
   }
@@ -361,6 +470,52 @@ impl Allowlist for WindowAllowlistConfig {
   }
 }
 
+/// An argument of a command on the shell scope.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum ShellAllowedArg {
+  /// An argument that can't be changed.
+  Fixed(String),
+  /// An argument set by the frontend.
+  Var {
+    /// The regex the whole argument must match.
+    validator: String,
+  },
+}
+
+/// The arguments the frontend can pass to a command on the shell scope.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum ShellAllowedArgs {
+  /// Whether any argument is allowed.
+  Flag(bool),
+  /// The exact list of arguments.
+  List(Vec<ShellAllowedArg>),
+}
+
+impl Default for ShellAllowedArgs {
+  fn default() -> Self {
+    Self::Flag(false)
+  }
+}
+
+/// A command the shell `execute` API can run.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ShellAllowedCommand {
+  /// The name the frontend uses to run the command.
+  name: String,
+  /// The program to run, or the sidecar name when `sidecar` is `true`.
+  cmd: String,
+  /// The arguments the frontend can pass. No arguments are allowed by default.
+  #[serde(default)]
+  args: ShellAllowedArgs,
+  /// Whether the command is a sidecar.
+  #[serde(default)]
+  sidecar: bool,
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct ShellAllowlistConfig {
@@ -370,6 +525,16 @@ struct ShellAllowlistConfig {
   execute: bool,
   #[serde(default)]
   open: bool,
+  /// The commands the `execute` API can run.
+  #[serde(default)]
+  scope: Vec<ShellAllowedCommand>,
+  /// The regex the paths and URLs opened with the `open` API must match.
+  /// Only `mailto:`, `tel:`, `http://` and `https://` URLs are allowed by default.
+  open_scope: Option<String>,
+  /// The programs the `open` API can open the paths and URLs with, e.g. `firefox`.
+  /// The paths and URLs can only be opened with the default app when it's empty.
+  #[serde(default)]
+  open_with: Vec<String>,
 }
 
 impl Allowlist for ShellAllowlistConfig {
@@ -394,6 +559,12 @@ struct DialogAllowlistConfig {
   open: bool,
   #[serde(default)]
   save: bool,
+  #[serde(default)]
+  message: bool,
+  #[serde(default)]
+  ask: bool,
+  #[serde(default)]
+  confirm: bool,
 }
 
 impl Allowlist for DialogAllowlistConfig {
@@ -404,6 +575,9 @@ impl Allowlist for DialogAllowlistConfig {
       let mut features = Vec::new();
       check_feature!(self, features, open, "dialog-open");
       check_feature!(self, features, save, "dialog-save");
+      check_feature!(self, features, message, "dialog-message");
+      check_feature!(self, features, ask, "dialog-ask");
+      check_feature!(self, features, confirm, "dialog-confirm");
       features
     }
   }
@@ -416,6 +590,8 @@ struct HttpAllowlistConfig {
   all: bool,
   #[serde(default)]
   request: bool,
+  #[serde(default)]
+  download: bool,
 }
 
 impl Allowlist for HttpAllowlistConfig {
@@ -425,6 +601,7 @@ impl Allowlist for HttpAllowlistConfig {
     } else {
       let mut features = Vec::new();
       check_feature!(self, features, request, "http-request");
+      check_feature!(self, features, download, "http-download");
       features
     }
   }
@@ -464,6 +641,207 @@ impl Allowlist for GlobalShortcutAllowlistConfig {
   }
 }
 
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ClipboardAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+  #[serde(default)]
+  write_text: bool,
+  #[serde(default)]
+  read_text: bool,
+}
+
+impl Allowlist for ClipboardAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["clipboard-all"]
+    } else {
+      let mut features = Vec::new();
+      check_feature!(self, features, write_text, "clipboard-write-text");
+      check_feature!(self, features, read_text, "clipboard-read-text");
+      features
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct FontAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for FontAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["font-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GeolocationAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for GeolocationAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["geolocation-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct PathAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for PathAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["path-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct PrinterAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for PrinterAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["printer-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ShareAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for ShareAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["share-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SystemAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for SystemAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["system-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SerialAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for SerialAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["serial"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct BluetoothAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for BluetoothAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["bluetooth"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct LicenseAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for LicenseAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["license"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ProtocolAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+  #[serde(default)]
+  asset: bool,
+  /// The glob patterns of the files served by the `asset` protocol, e.g. `$HOME/Pictures/**`.
+  #[serde(default)]
+  asset_scope: Vec<String>,
+}
+
+impl Allowlist for ProtocolAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["protocol-all"]
+    } else {
+      let mut features = Vec::new();
+      check_feature!(self, features, asset, "protocol-asset");
+      features
+    }
+  }
+}
+
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct AllowlistConfig {
@@ -483,14 +861,44 @@ struct AllowlistConfig {
   notification: NotificationAllowlistConfig,
   #[serde(default)]
   global_shortcut: GlobalShortcutAllowlistConfig,
+  #[serde(default)]
+  clipboard: ClipboardAllowlistConfig,
+  #[serde(default)]
+  font: FontAllowlistConfig,
+  #[serde(default)]
+  geolocation: GeolocationAllowlistConfig,
+  #[serde(default)]
+  path: PathAllowlistConfig,
+  #[serde(default)]
+  printer: PrinterAllowlistConfig,
+  #[serde(default)]
+  share: ShareAllowlistConfig,
+  /// The system metrics API. It isn't enabled by `all`, since it adds the `sysinfo` dependency.
+  #[serde(default)]
+  system: SystemAllowlistConfig,
+  /// The serial port API. It isn't enabled by `all`, since it depends on `libudev` on Linux.
+  #[serde(default)]
+  serial: SerialAllowlistConfig,
+  /// The Bluetooth LE API. It isn't enabled by `all`, since it depends on D-Bus on Linux.
+  #[serde(default)]
+  bluetooth: BluetoothAllowlistConfig,
+  /// The license API. It isn't enabled by `all`, since it depends on the Secret Service on Linux.
+  #[serde(default)]
+  license: LicenseAllowlistConfig,
+  #[serde(default)]
+  protocol: ProtocolAllowlistConfig,
 }
 
 impl Allowlist for AllowlistConfig {
   fn to_features(&self) -> Vec<&str> {
+    let mut features = self.system.to_features();
+    features.extend(self.serial.to_features());
+    features.extend(self.bluetooth.to_features());
+    features.extend(self.license.to_features());
+    features.extend(self.clipboard.to_features());
     if self.all {
-      vec!["api-all"]
+      features.push("api-all");
     } else {
-      let mut features = Vec::new();
       features.extend(self.fs.to_features());
       features.extend(self.window.to_features());
       features.extend(self.shell.to_features());
@@ -498,8 +906,14 @@ impl Allowlist for AllowlistConfig {
       features.extend(self.http.to_features());
       features.extend(self.notification.to_features());
       features.extend(self.global_shortcut.to_features());
-      features
+      features.extend(self.font.to_features());
+      features.extend(self.geolocation.to_features());
+      features.extend(self.path.to_features());
+      features.extend(self.printer.to_features());
+      features.extend(self.share.to_features());
+      features.extend(self.protocol.to_features());
     }
+    features
   }
 }
 
@@ -522,6 +936,8 @@ pub struct TauriConfig {
   /// The updater configuration.
   #[serde(default = "default_updater")]
   pub updater: UpdaterConfig,
+  /// The license configuration.
+  pub license: Option<LicenseConfig>,
 }
 
 impl TauriConfig {
@@ -547,6 +963,17 @@ pub struct UpdaterConfig {
   pub pubkey: Option<String>,
 }
 
+/// The license configuration.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LicenseConfig {
+  /// The public key verifying the license keys, as generated by `tauri signer generate`.
+  pub pubkey: Option<String>,
+  /// The trial duration, in days, starting on the first license check.
+  pub trial_days: Option<u32>,
+}
+
 // We enable the unnecessary_wraps because we need
 // to use an Option for dialog otherwise the CLI schema will mark
 // the dialog as a required field which is not as we default it to true.
@@ -573,12 +1000,20 @@ pub struct BuildConfig {
   /// Whether we should inject the Tauri API on `window.__TAURI__` or not.
   #[serde(default)]
   pub with_global_tauri: bool,
+  /// Whether the `console` messages of the windows are printed to the terminal
+  /// during `tauri dev`, prefixed with the window label.
+  #[serde(default = "default_dev_console")]
+  pub dev_console: bool,
 }
 
 fn default_dev_path() -> String {
   "".to_string()
 }
 
+fn default_dev_console() -> bool {
+  true
+}
+
 fn default_dist_dir() -> String {
   "../dist".to_string()
 }
@@ -611,6 +1046,7 @@ fn default_build() -> BuildConfig {
     before_dev_command: None,
     before_build_command: None,
     with_global_tauri: false,
+    dev_console: default_dev_console(),
   }
 }
 
@@ -8,21 +8,32 @@ use tauri_bundler::bundle::{
 
 use crate::helpers::{
   app_paths::{app_dir, tauri_dir},
-  config::get as get_config,
+  config::{get as get_config, reload as reload_config},
   execute_with_output,
+  failure::Failure,
   manifest::rewrite_manifest,
   updater_signature::sign_file_from_env_variables,
   Logger,
 };
 
-use std::{env::set_current_dir, fs::rename, path::PathBuf, process::Command};
+use anyhow::Context;
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
 
-mod rust;
+use std::{
+  env::set_current_dir, ffi::OsStr, fs::rename, path::PathBuf, process::Command,
+  sync::mpsc::channel, time::Duration,
+};
+
+/// The delay used to debounce the file system events in watch mode.
+const WATCH_DEBOUNCE: Duration = Duration::from_secs(1);
+
+pub mod rust;
 
 #[derive(Default)]
 pub struct Build {
   debug: bool,
   verbose: bool,
+  watch: bool,
   targets: Option<Vec<String>>,
   config: Option<String>,
 }
@@ -42,6 +53,12 @@ impl Build {
     self
   }
 
+  /// Rebuilds and bundles the app again when its sources or configuration change.
+  pub fn watch(mut self) -> Self {
+    self.watch = true;
+    self
+  }
+
   pub fn targets(mut self, targets: Vec<String>) -> Self {
     self.targets = Some(targets);
     self
@@ -53,12 +70,64 @@ impl Build {
   }
 
   pub fn run(self) -> crate::Result<()> {
-    let logger = Logger::new("tauri:build");
-    let config = get_config(self.config.as_deref())?;
-
     let tauri_path = tauri_dir();
     set_current_dir(&tauri_path)?;
 
+    if !self.watch {
+      return self.build();
+    }
+
+    let logger = Logger::new("tauri:build");
+    if let Err(e) = self.build() {
+      logger.error(e.to_string());
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = watcher(tx, WATCH_DEBOUNCE).unwrap();
+    watcher.watch(tauri_path.join("src"), RecursiveMode::Recursive)?;
+    watcher.watch(tauri_path.join("Cargo.toml"), RecursiveMode::Recursive)?;
+    watcher.watch(tauri_path.join("tauri.conf.json"), RecursiveMode::Recursive)?;
+    let dist_dir = get_config(self.config.as_deref())?
+      .lock()
+      .unwrap()
+      .as_ref()
+      .unwrap()
+      .build
+      .dist_dir
+      .to_string();
+    if !dist_dir.starts_with("http") {
+      watcher.watch(&dist_dir, RecursiveMode::Recursive)?;
+    }
+    logger.log("Watching for changes...");
+
+    loop {
+      let event_path = match rx.recv() {
+        Ok(DebouncedEvent::Create(path))
+        | Ok(DebouncedEvent::Remove(path))
+        | Ok(DebouncedEvent::Rename(_, path))
+        | Ok(DebouncedEvent::Write(path)) => path,
+        Ok(_) => continue,
+        Err(_) => return Ok(()),
+      };
+
+      logger.log(format!("{} changed, rebuilding", event_path.display()));
+      if event_path.file_name() == Some(OsStr::new("tauri.conf.json")) {
+        reload_config(self.config.as_deref())?;
+      }
+      if let Err(e) = self.build() {
+        logger.error(e.to_string());
+      }
+      // the build itself touches the watched files (manifest rewrite, beforeBuildCommand output)
+      // so we discard the events it triggered
+      while rx.recv_timeout(WATCH_DEBOUNCE * 2).is_ok() {}
+      logger.log("Watching for changes...");
+    }
+  }
+
+  fn build(&self) -> crate::Result<()> {
+    let logger = Logger::new("tauri:build");
+    let config = get_config(self.config.as_deref())?;
+
     rewrite_manifest(config.clone())?;
 
     let config_guard = config.lock().unwrap();
@@ -81,18 +150,21 @@ impl Build {
             .arg("/C")
             .arg(before_build)
             .current_dir(app_dir()),
-        )?;
+        )
+        .context(Failure::FrontendBuild)?;
         #[cfg(not(target_os = "windows"))]
         execute_with_output(
           &mut Command::new("sh")
             .arg("-c")
             .arg(before_build)
             .current_dir(app_dir()),
-        )?;
+        )
+        .context(Failure::FrontendBuild)?;
       }
     }
 
-    rust::build_project(self.debug)?;
+    rust::build_project(self.debug).context(Failure::CargoBuild)?;
+    rust::copy_external_binaries(&config_, self.debug)?;
 
     let app_settings = rust::AppSettings::new(&config_)?;
 
@@ -137,7 +209,7 @@ impl Build {
         settings_builder = settings_builder.verbose();
       }
 
-      if let Some(names) = self.targets {
+      if let Some(names) = self.targets.clone() {
         let mut types = vec![];
         for name in names {
           if name == "none" {
@@ -160,9 +232,9 @@ impl Build {
       }
 
       // Bundle the project
-      let settings = settings_builder.build()?;
+      let settings = settings_builder.build().context(Failure::Bundle)?;
 
-      let bundles = bundle_project(settings)?;
+      let bundles = bundle_project(settings).context(Failure::Bundle)?;
 
       // If updater is active and pubkey is available
       if config_.tauri.updater.active && config_.tauri.updater.pubkey.is_some() {
@@ -176,7 +248,8 @@ impl Build {
           // another type of updater package who require multiple file signature
           for path in elem.bundle_paths.iter() {
             // sign our path from environment variables
-            let (signature_path, _signature) = sign_file_from_env_variables(path)?;
+            let (signature_path, _signature) =
+              sign_file_from_env_variables(path).context(Failure::Signing)?;
             signed_paths.append(&mut vec![signature_path]);
           }
         }
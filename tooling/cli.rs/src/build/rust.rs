@@ -16,6 +16,10 @@ use crate::helpers::{app_paths::tauri_dir, config::Config};
 #[cfg(windows)]
 use tauri_bundler::WindowsSettings;
 use tauri_bundler::{
+  bundle::{
+    common::{copy_file, external_binary_name},
+    target_triple,
+  },
   AppCategory, BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings,
   UpdaterSettings,
 };
@@ -105,6 +109,24 @@ pub fn build_project(debug: bool) -> crate::Result<()> {
   Ok(())
 }
 
+/// Copies the `tauri > bundle > externalBin` binaries of the current platform next to the app
+/// executable, without their target triple suffix, so `Command::new_sidecar` finds them.
+pub fn copy_external_binaries(config: &Config, debug: bool) -> crate::Result<()> {
+  let tauri_dir = tauri_dir();
+  let out_dir = get_target_dir(&get_workspace_dir(&tauri_dir), None, !debug)?;
+  let target_triple = target_triple()?;
+  for bin in config.tauri.bundle.external_bin.iter().flatten() {
+    let src = tauri_dir.join(format!(
+      "{}-{}{}",
+      bin,
+      target_triple,
+      if cfg!(windows) { ".exe" } else { "" }
+    ));
+    copy_file(&src, out_dir.join(external_binary_name(&src)?))?;
+  }
+  Ok(())
+}
+
 pub struct AppSettings {
   cargo_settings: CargoSettings,
   cargo_package_settings: CargoPackageSettings,
@@ -2,13 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::helpers::{
-  app_paths::{app_dir, tauri_dir},
-  config::{get as get_config, reload as reload_config},
-  manifest::rewrite_manifest,
-  Logger,
+use crate::{
+  build::rust::copy_external_binaries,
+  helpers::{
+    app_paths::{app_dir, tauri_dir},
+    config::{get as get_config, reload as reload_config},
+    dev_proxy,
+    failure::Failure,
+    manifest::rewrite_manifest,
+    Logger,
+  },
 };
 
+use anyhow::Context;
 use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
 use once_cell::sync::OnceCell;
 use shared_child::SharedChild;
@@ -35,6 +41,7 @@ fn kill_before_dev_process() {
 #[derive(Default)]
 pub struct Dev {
   exit_on_panic: bool,
+  proxy: bool,
   config: Option<String>,
   args: Vec<String>,
 }
@@ -54,6 +61,12 @@ impl Dev {
     self
   }
 
+  /// Serves the `devPath` URL through a local proxy, see [`dev_proxy`].
+  pub fn proxy(mut self, proxy: bool) -> Self {
+    self.proxy = proxy;
+    self
+  }
+
   pub fn args(mut self, args: Vec<String>) -> Self {
     self.args = args;
     self
@@ -63,7 +76,7 @@ impl Dev {
     let logger = Logger::new("tauri:dev");
     let tauri_path = tauri_dir();
     set_current_dir(&tauri_path)?;
-    let merge_config = self.config.clone();
+    let mut merge_config = self.config.clone();
     let config = get_config(merge_config.as_deref())?;
     let mut process: Arc<SharedChild>;
 
@@ -82,13 +95,15 @@ impl Dev {
           .arg("/C")
           .arg(before_dev)
           .current_dir(app_dir())
-          .spawn()?;
+          .spawn()
+          .context(Failure::FrontendBuild)?;
         #[cfg(not(target_os = "windows"))]
         let child = Command::new("sh")
           .arg("-c")
           .arg(before_dev)
           .current_dir(app_dir())
-          .spawn()?;
+          .spawn()
+          .context(Failure::FrontendBuild)?;
         BEFORE_DEV.set(Mutex::new(child)).unwrap();
       }
     }
@@ -102,7 +117,23 @@ impl Dev {
       .dev_path
       .to_string();
 
+    if self.proxy && dev_path.starts_with("http") {
+      let proxy_url = dev_proxy::start(&dev_path)?;
+      logger.log(format!("Proxying {} on {}", dev_path, proxy_url));
+      let mut proxy_config: serde_json::Value = match &merge_config {
+        Some(merge_config) => serde_json::from_str(merge_config)?,
+        None => serde_json::json!({}),
+      };
+      if !proxy_config["build"].is_object() {
+        proxy_config["build"] = serde_json::json!({});
+      }
+      proxy_config["build"]["devPath"] = proxy_url.into();
+      merge_config.replace(serde_json::to_string(&proxy_config)?);
+      reload_config(merge_config.as_deref())?;
+    }
+
     rewrite_manifest(config.clone())?;
+    copy_external_binaries(config.lock().unwrap().as_ref().unwrap(), true)?;
 
     let (child_wait_tx, child_wait_rx) = channel();
     let child_wait_rx = Arc::new(Mutex::new(child_wait_rx));
@@ -143,6 +174,7 @@ impl Dev {
           if event_path.file_name() == Some(OsStr::new("tauri.conf.json")) {
             reload_config(merge_config.as_deref())?;
             rewrite_manifest(config.clone())?;
+            copy_external_binaries(config.lock().unwrap().as_ref().unwrap(), true)?;
           } else {
             // When tauri.conf.json is changed, rewrite_manifest will be called
             // which will trigger the watcher again
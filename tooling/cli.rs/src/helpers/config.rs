@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use anyhow::Context;
 #[cfg(target_os = "linux")]
 use heck::KebabCase;
 use json_patch::merge;
@@ -12,11 +13,12 @@ use serde_json::Value as JsonValue;
 mod config_definition;
 pub use config_definition::*;
 
+use super::failure::Failure;
+
 use std::{
   env::set_var,
   fs::File,
   io::BufReader,
-  process::exit,
   sync::{Arc, Mutex},
 };
 
@@ -43,19 +45,23 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
   let schema = scope.compile_and_return(schema, false).unwrap();
   let state = schema.validate(&config);
   if !state.errors.is_empty() {
-    for error in state.errors {
-      eprintln!(
-        "`tauri.conf.json` error on `{}`: {}",
-        error
-          .get_path()
-          .chars()
-          .skip(1)
-          .collect::<String>()
-          .replace("/", " > "),
-        error.get_detail().unwrap_or_else(|| error.get_title()),
-      );
-    }
-    exit(1);
+    let errors = state
+      .errors
+      .iter()
+      .map(|error| {
+        format!(
+          "`tauri.conf.json` error on `{}`: {}",
+          error
+            .get_path()
+            .chars()
+            .skip(1)
+            .collect::<String>()
+            .replace("/", " > "),
+          error.get_detail().unwrap_or_else(|| error.get_title()),
+        )
+      })
+      .collect::<Vec<_>>();
+    return Err(anyhow::anyhow!(errors.join("\n")));
   }
 
   if let Some(merge_config) = merge_config {
@@ -76,10 +82,10 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
 }
 
 pub fn get(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
-  get_internal(merge_config, false)
+  get_internal(merge_config, false).context(Failure::InvalidConfig)
 }
 
 pub fn reload(merge_config: Option<&str>) -> crate::Result<()> {
-  get_internal(merge_config, true)?;
+  get_internal(merge_config, true).context(Failure::InvalidConfig)?;
   Ok(())
 }
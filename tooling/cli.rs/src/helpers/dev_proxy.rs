@@ -0,0 +1,386 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! A local proxy for the frontend dev server, used by `tauri dev --proxy`.
+//!
+//! The webview loads the app from the proxy over plain HTTP, while the proxy talks to the
+//! dev server over HTTP or HTTPS (accepting self-signed certificates), rewriting the `Host`
+//! and `Origin` headers so the dev server sees its own origin. WebSocket connections,
+//! used by the frameworks live reload (HMR), are forwarded as-is.
+
+use native_tls::{TlsConnector, TlsStream};
+
+use std::{
+  io::{self, Read, Write},
+  net::{Ipv4Addr, TcpListener, TcpStream},
+  thread,
+  time::Duration,
+};
+
+/// How long a WebSocket connection waits for data on one side before checking the other.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Maximum size of a request head.
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// Redirects the WebSockets the page opens to the dev server through the proxy,
+/// for the HMR clients configured with the dev server address.
+const INIT_SCRIPT: &str = r#"(function () {
+  var upstream = __UPSTREAM__;
+  var proxy = (window.location.protocol === 'https:' ? 'wss://' : 'ws://') + window.location.host;
+  var NativeWebSocket = window.WebSocket;
+  function ProxiedWebSocket(url, protocols) {
+    if (typeof url === 'string' && url.indexOf(upstream) === 0) {
+      url = proxy + url.slice(upstream.length);
+    }
+    return protocols === undefined ? new NativeWebSocket(url) : new NativeWebSocket(url, protocols);
+  }
+  ProxiedWebSocket.prototype = NativeWebSocket.prototype;
+  ['CONNECTING', 'OPEN', 'CLOSING', 'CLOSED'].forEach(function (state) {
+    ProxiedWebSocket[state] = NativeWebSocket[state];
+  });
+  window.WebSocket = ProxiedWebSocket;
+  window.__TAURI_DEV_PROXY__ = { upstream: upstream };
+})();"#;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Upstream {
+  tls: bool,
+  host: String,
+  port: u16,
+  path: String,
+}
+
+impl Upstream {
+  fn parse(url: &str) -> Option<Self> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+      (true, rest)
+    } else {
+      (false, url.strip_prefix("http://")?)
+    };
+    let (authority, path) = match rest.find('/') {
+      Some(index) => (&rest[..index], &rest[index..]),
+      None => (rest, ""),
+    };
+    let mut parts = authority.rsplitn(2, ':');
+    let (host, port) = match (parts.next(), parts.next()) {
+      (Some(port), Some(host)) => (host, port.parse().ok()?),
+      (Some(host), None) => (host, if tls { 443 } else { 80 }),
+      _ => return None,
+    };
+    Some(Self {
+      tls,
+      host: host.to_string(),
+      port,
+      path: path.to_string(),
+    })
+  }
+
+  fn authority(&self) -> String {
+    format!("{}:{}", self.host, self.port)
+  }
+
+  fn origin(&self) -> String {
+    format!(
+      "{}://{}",
+      if self.tls { "https" } else { "http" },
+      self.authority()
+    )
+  }
+
+  fn websocket_origin(&self) -> String {
+    format!(
+      "{}://{}",
+      if self.tls { "wss" } else { "ws" },
+      self.authority()
+    )
+  }
+
+  fn connect(&self) -> io::Result<Stream> {
+    let stream = TcpStream::connect(self.authority())?;
+    if self.tls {
+      let connector = TlsConnector::builder()
+        // dev servers usually serve self-signed certificates
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+      let stream = connector
+        .connect(&self.host, stream)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+      Ok(Stream::Tls(stream))
+    } else {
+      Ok(Stream::Plain(stream))
+    }
+  }
+}
+
+enum Stream {
+  Plain(TcpStream),
+  Tls(TlsStream<TcpStream>),
+}
+
+impl Stream {
+  fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+    match self {
+      Self::Plain(stream) => stream.set_read_timeout(timeout),
+      Self::Tls(stream) => stream.get_ref().set_read_timeout(timeout),
+    }
+  }
+}
+
+impl Read for Stream {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    match self {
+      Self::Plain(stream) => stream.read(buf),
+      Self::Tls(stream) => stream.read(buf),
+    }
+  }
+}
+
+impl Write for Stream {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    match self {
+      Self::Plain(stream) => stream.write(buf),
+      Self::Tls(stream) => stream.write(buf),
+    }
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    match self {
+      Self::Plain(stream) => stream.flush(),
+      Self::Tls(stream) => stream.flush(),
+    }
+  }
+}
+
+/// Starts the proxy for the given dev server URL on a background thread.
+///
+/// Returns the URL the webview must load.
+pub fn start(dev_path: &str) -> crate::Result<String> {
+  let upstream = Upstream::parse(dev_path)
+    .ok_or_else(|| anyhow::anyhow!("invalid dev server URL `{}`", dev_path))?;
+  let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+  let url = format!(
+    "http://{}{}",
+    listener.local_addr()?,
+    upstream.path.as_str()
+  );
+
+  thread::spawn(move || {
+    for client in listener.incoming().flatten() {
+      let upstream = upstream.clone();
+      thread::spawn(move || {
+        if let Err(e) = handle(client, &upstream) {
+          eprintln!("[tauri:dev] proxy error: {}", e);
+        }
+      });
+    }
+  });
+
+  Ok(url)
+}
+
+fn handle(mut client: TcpStream, upstream: &Upstream) -> io::Result<()> {
+  let (head, body_start) = read_head(&mut client)?;
+  let mut lines = head.split("\r\n");
+  let request_line = lines.next().unwrap_or_default();
+  let headers = lines
+    .filter_map(|line| {
+      let mut parts = line.splitn(2, ':');
+      match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) => Some((name.trim().to_string(), value.trim().to_string())),
+        _ => None,
+      }
+    })
+    .collect::<Vec<_>>();
+  let header = |name: &str| {
+    headers
+      .iter()
+      .find(|(n, _)| n.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  };
+  let websocket = header("upgrade")
+    .map(|value| value.eq_ignore_ascii_case("websocket"))
+    .unwrap_or(false);
+
+  let mut request = if websocket {
+    request_line.to_string()
+  } else {
+    // HTTP/1.0 responses aren't chunked, so the HTML can be rewritten as it is
+    request_line.replace("HTTP/1.1", "HTTP/1.0")
+  };
+  request.push_str("\r\n");
+  for (name, value) in &headers {
+    let rewritten: &[&str] = if websocket {
+      &["host", "origin", "referer"]
+    } else {
+      &["host", "origin", "referer", "accept-encoding", "connection"]
+    };
+    if rewritten.iter().any(|h| name.eq_ignore_ascii_case(h)) {
+      continue;
+    }
+    request.push_str(&format!("{}: {}\r\n", name, value));
+  }
+  request.push_str(&format!("Host: {}\r\n", upstream.authority()));
+  if header("origin").is_some() {
+    request.push_str(&format!("Origin: {}\r\n", upstream.origin()));
+  }
+  if !websocket {
+    request.push_str("Accept-Encoding: identity\r\nConnection: close\r\n");
+  }
+  request.push_str("\r\n");
+
+  let mut server = upstream.connect()?;
+  server.write_all(request.as_bytes())?;
+  server.write_all(&body_start)?;
+
+  if websocket {
+    return pipe(client, server);
+  }
+
+  let content_length = header("content-length")
+    .and_then(|length| length.parse::<usize>().ok())
+    .unwrap_or(0);
+  if content_length > body_start.len() {
+    let remaining = (content_length - body_start.len()) as u64;
+    io::copy(&mut (&mut client).take(remaining), &mut server)?;
+  }
+
+  let mut response = Vec::new();
+  server.read_to_end(&mut response)?;
+  client.write_all(&inject_init_script(response, upstream))?;
+  client.flush()
+}
+
+/// Reads the request head, returning it with the body bytes read after it.
+fn read_head(stream: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+  let mut data = Vec::new();
+  let mut buf = [0; 4096];
+  loop {
+    let read = stream.read(&mut buf)?;
+    if read == 0 {
+      return Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "connection closed before the request head was received",
+      ));
+    }
+    data.extend_from_slice(&buf[..read]);
+    if let Some(index) = find(&data, b"\r\n\r\n") {
+      let body = data.split_off(index + 4);
+      data.truncate(index);
+      return Ok((String::from_utf8_lossy(&data).into_owned(), body));
+    }
+    if data.len() > MAX_HEAD_SIZE {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "request head too large",
+      ));
+    }
+  }
+}
+
+/// Injects the proxy init script on HTML responses.
+fn inject_init_script(response: Vec<u8>, upstream: &Upstream) -> Vec<u8> {
+  let head_end = match find(&response, b"\r\n\r\n") {
+    Some(index) => index,
+    None => return response,
+  };
+  let head = String::from_utf8_lossy(&response[..head_end]).into_owned();
+  let is_html = head.lines().any(|line| {
+    let line = line.to_lowercase();
+    line.starts_with("content-type:") && line.contains("text/html")
+  });
+  if !is_html {
+    return response;
+  }
+
+  let html = String::from_utf8_lossy(&response[head_end + 4..]).into_owned();
+  let script = format!(
+    "<script>{}</script>",
+    INIT_SCRIPT.replace(
+      "__UPSTREAM__",
+      &serde_json::to_string(&upstream.websocket_origin()).unwrap()
+    )
+  );
+  let html = match html.to_ascii_lowercase().find("<head>") {
+    Some(index) => format!("{}{}{}", &html[..index + 6], script, &html[index + 6..]),
+    None => format!("{}{}", script, html),
+  };
+
+  let mut head = head
+    .split("\r\n")
+    .filter(|line| !line.to_lowercase().starts_with("content-length:"))
+    .collect::<Vec<_>>()
+    .join("\r\n");
+  head.push_str(&format!("\r\nContent-Length: {}\r\n\r\n", html.len()));
+  let mut response = head.into_bytes();
+  response.extend_from_slice(html.as_bytes());
+  response
+}
+
+/// Forwards the data between the client and the dev server until one of them closes the connection.
+fn pipe(mut client: TcpStream, mut server: Stream) -> io::Result<()> {
+  client.set_read_timeout(Some(POLL_INTERVAL))?;
+  server.set_read_timeout(Some(POLL_INTERVAL))?;
+  let mut buf = [0; 16 * 1024];
+  loop {
+    if !transfer(&mut client, &mut server, &mut buf)?
+      || !transfer(&mut server, &mut client, &mut buf)?
+    {
+      return Ok(());
+    }
+  }
+}
+
+/// Forwards the available data, returning `false` if the connection was closed.
+fn transfer(from: &mut impl Read, to: &mut impl Write, buf: &mut [u8]) -> io::Result<bool> {
+  match from.read(buf) {
+    Ok(0) => Ok(false),
+    Ok(read) => {
+      to.write_all(&buf[..read])?;
+      to.flush()?;
+      Ok(true)
+    }
+    Err(e) if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {
+      Ok(true)
+    }
+    Err(e) => Err(e),
+  }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{inject_init_script, Upstream};
+
+  #[test]
+  fn parses_upstream() {
+    let upstream = Upstream::parse("https://localhost:5173/app").unwrap();
+    assert!(upstream.tls);
+    assert_eq!(upstream.authority(), "localhost:5173");
+    assert_eq!(upstream.path, "/app");
+    assert_eq!(upstream.websocket_origin(), "wss://localhost:5173");
+    assert_eq!(Upstream::parse("http://localhost").unwrap().port, 80);
+    assert!(Upstream::parse("../dist").is_none());
+  }
+
+  #[test]
+  fn injects_script_on_html() {
+    let upstream = Upstream::parse("http://localhost:3000").unwrap();
+    let response = b"HTTP/1.0 200 OK\r\nContent-Type: text/html\r\nContent-Length: 26\r\n\r\n<html><head></head></html>".to_vec();
+    let response = String::from_utf8(inject_init_script(response, &upstream)).unwrap();
+    assert!(response.contains("<head><script>"));
+    assert!(response.contains("\"ws://localhost:3000\""));
+    let body_length = response.split("\r\n\r\n").nth(1).unwrap().len();
+    assert!(response.contains(&format!("Content-Length: {}\r\n", body_length)));
+
+    let css = b"HTTP/1.0 200 OK\r\nContent-Type: text/css\r\n\r\nbody {}".to_vec();
+    assert_eq!(inject_init_script(css.clone(), &upstream), css);
+  }
+}
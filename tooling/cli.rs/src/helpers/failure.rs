@@ -0,0 +1,71 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+/// The exit code of failures that aren't classified by [`Failure`].
+pub const DEFAULT_EXIT_CODE: i32 = 1;
+
+/// The classes of failures reported with a dedicated exit code, so CI scripts can branch on them.
+///
+/// The failure is attached to the error as context, e.g. `.context(Failure::CargoBuild)`,
+/// and read back with `error.downcast_ref::<Failure>()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Failure {
+  /// `tauri.conf.json` is missing, malformed or doesn't match the schema.
+  InvalidConfig,
+  /// The `beforeBuildCommand` or `beforeDevCommand` failed.
+  FrontendBuild,
+  /// `cargo build` failed.
+  CargoBuild,
+  /// The bundler failed.
+  Bundle,
+  /// Signing the updater artifacts or a file failed.
+  Signing,
+}
+
+impl Failure {
+  /// The process exit code. These values are stable.
+  pub fn exit_code(self) -> i32 {
+    match self {
+      Self::InvalidConfig => 2,
+      Self::FrontendBuild => 3,
+      Self::CargoBuild => 4,
+      Self::Bundle => 5,
+      Self::Signing => 6,
+    }
+  }
+
+  /// The identifier used on the `--json` output.
+  pub fn id(self) -> &'static str {
+    match self {
+      Self::InvalidConfig => "invalid-config",
+      Self::FrontendBuild => "frontend-build",
+      Self::CargoBuild => "cargo-build",
+      Self::Bundle => "bundle",
+      Self::Signing => "signing",
+    }
+  }
+}
+
+impl fmt::Display for Failure {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let message = match self {
+      Self::InvalidConfig => "invalid configuration",
+      Self::FrontendBuild => "frontend build failed",
+      Self::CargoBuild => "cargo build failed",
+      Self::Bundle => "bundling failed",
+      Self::Signing => "signing failed",
+    };
+    f.write_str(message)
+  }
+}
+
+/// Gets the failure class and exit code of the given error.
+pub fn classify(error: &anyhow::Error) -> (Option<Failure>, i32) {
+  match error.downcast_ref::<Failure>() {
+    Some(failure) => (Some(*failure), failure.exit_code()),
+    None => (None, DEFAULT_EXIT_CODE),
+  }
+}
@@ -3,6 +3,21 @@
 // SPDX-License-Identifier: MIT
 
 use colored::Colorize;
+use serde_json::json;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Prints the log messages as JSON lines instead of colored text, see `--json`.
+pub fn set_json_output(enabled: bool) {
+  JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the `--json` output is enabled.
+pub fn json_output() -> bool {
+  JSON_OUTPUT.load(Ordering::Relaxed)
+}
 
 pub struct Logger<'a> {
   context: &'a str,
@@ -13,7 +28,22 @@ impl<'a> Logger<'a> {
     Self { context }
   }
 
+  fn print_json(&self, level: &str, message: &str) {
+    println!(
+      "{}",
+      json!({
+        "type": "log",
+        "context": self.context,
+        "level": level,
+        "message": message,
+      })
+    );
+  }
+
   pub fn log(&self, message: impl AsRef<str>) {
+    if json_output() {
+      return self.print_json("info", message.as_ref());
+    }
     println!(
       "{} {}",
       format!("[{}]", self.context).green().bold(),
@@ -22,6 +52,9 @@ impl<'a> Logger<'a> {
   }
 
   pub fn warn(&self, message: impl AsRef<str>) {
+    if json_output() {
+      return self.print_json("warn", message.as_ref());
+    }
     println!(
       "{} {}",
       format!("[{}]", self.context).yellow().bold(),
@@ -30,6 +63,9 @@ impl<'a> Logger<'a> {
   }
 
   pub fn error(&self, message: impl AsRef<str>) {
+    if json_output() {
+      return self.print_json("error", message.as_ref());
+    }
     println!(
       "{} {}",
       format!("[{}]", self.context).red().bold(),
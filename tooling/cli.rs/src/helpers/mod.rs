@@ -4,11 +4,13 @@
 
 pub mod app_paths;
 pub mod config;
+pub mod dev_proxy;
+pub mod failure;
 mod logger;
 pub mod manifest;
 pub mod updater_signature;
 
-pub use logger::Logger;
+pub use logger::{json_output, set_json_output, Logger};
 
 use std::{
   io::{BufRead, BufReader},
@@ -25,7 +27,13 @@ pub fn execute_with_output(cmd: &mut Command) -> crate::Result<()> {
     let reader = BufReader::new(stdout);
 
     for line in reader.lines() {
-      println!("{}", line.expect("Failed to get line"));
+      let line = line.expect("Failed to get line");
+      // keep stdout reserved to the JSON lines
+      if json_output() {
+        eprintln!("{}", line);
+      } else {
+        println!("{}", line);
+      }
     }
   }
 
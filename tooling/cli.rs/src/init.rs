@@ -14,6 +14,9 @@ use handlebars::{to_json, Handlebars};
 use include_dir::{include_dir, Dir};
 use serde::Deserialize;
 
+mod frontend;
+pub use frontend::detect as detect_frontend;
+
 const TEMPLATE_DIR: Dir = include_dir!("templates");
 
 #[derive(Deserialize)]
@@ -31,6 +34,8 @@ pub struct Init {
   window_title: Option<String>,
   dist_dir: Option<String>,
   dev_path: Option<String>,
+  before_dev_command: Option<String>,
+  before_build_command: Option<String>,
 }
 
 impl Default for Init {
@@ -43,6 +48,8 @@ impl Default for Init {
       window_title: None,
       dist_dir: None,
       dev_path: None,
+      before_dev_command: None,
+      before_build_command: None,
     }
   }
 }
@@ -87,6 +94,16 @@ impl Init {
     self
   }
 
+  pub fn before_dev_command(mut self, before_dev_command: impl Into<String>) -> Self {
+    self.before_dev_command = Some(before_dev_command.into());
+    self
+  }
+
+  pub fn before_build_command(mut self, before_build_command: impl Into<String>) -> Self {
+    self.before_build_command = Some(before_build_command.into());
+    self
+  }
+
   pub fn run(self) -> crate::Result<()> {
     let logger = Logger::new("tauri:init");
     let template_target_path = self.directory.join("src-tauri");
@@ -133,6 +150,14 @@ impl Init {
             .unwrap_or_else(|| "http://localhost:4000".to_string()),
         ),
       );
+      data.insert(
+        "before_dev_command",
+        to_json(self.before_dev_command.unwrap_or_default()),
+      );
+      data.insert(
+        "before_build_command",
+        to_json(self.before_build_command.unwrap_or_default()),
+      );
       data.insert(
         "app_name",
         to_json(self.app_name.unwrap_or_else(|| "Tauri App".to_string())),
@@ -0,0 +1,135 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detects the frontend tooling of an existing project, so `tauri init` can fill
+//! the `build` configuration following the framework conventions.
+
+use serde::Deserialize;
+
+use std::{collections::HashMap, fs::read_to_string, path::Path};
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+  #[serde(default)]
+  scripts: HashMap<String, String>,
+  #[serde(default)]
+  dependencies: HashMap<String, String>,
+  #[serde(default)]
+  dev_dependencies: HashMap<String, String>,
+}
+
+impl PackageJson {
+  fn has_dependency(&self, name: &str) -> bool {
+    self.dependencies.contains_key(name) || self.dev_dependencies.contains_key(name)
+  }
+}
+
+/// A frontend framework and its conventions.
+struct Framework {
+  name: &'static str,
+  /// The package identifying the framework.
+  package: &'static str,
+  /// The config files identifying the framework, when the package isn't a direct dependency.
+  config_files: &'static [&'static str],
+  dev_port: u16,
+  /// The build output, relative to the project directory.
+  dist_dir: &'static str,
+  dev_script: &'static str,
+  build_script: &'static str,
+}
+
+// checked in order, the first match wins
+const FRAMEWORKS: &[Framework] = &[
+  Framework {
+    name: "Next.js",
+    package: "next",
+    config_files: &["next.config.js"],
+    dev_port: 3000,
+    dist_dir: "out",
+    dev_script: "dev",
+    build_script: "build",
+  },
+  Framework {
+    name: "Create React App",
+    package: "react-scripts",
+    config_files: &[],
+    dev_port: 3000,
+    dist_dir: "build",
+    dev_script: "start",
+    build_script: "build",
+  },
+  Framework {
+    name: "Vue CLI",
+    package: "@vue/cli-service",
+    config_files: &["vue.config.js"],
+    dev_port: 8080,
+    dist_dir: "dist",
+    dev_script: "serve",
+    build_script: "build",
+  },
+  Framework {
+    name: "Vite",
+    package: "vite",
+    config_files: &["vite.config.js", "vite.config.ts"],
+    dev_port: 3000,
+    dist_dir: "dist",
+    dev_script: "dev",
+    build_script: "build",
+  },
+];
+
+/// The `build` configuration detected for a project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frontend {
+  /// The detected framework name.
+  pub framework: &'static str,
+  pub dev_path: String,
+  /// Relative to the `src-tauri` folder.
+  pub dist_dir: String,
+  pub before_dev_command: String,
+  pub before_build_command: String,
+}
+
+/// Detects the frontend framework used by the project in the given directory.
+pub fn detect(directory: &Path) -> Option<Frontend> {
+  let package_json = read_to_string(directory.join("package.json")).ok()?;
+  let package_json: PackageJson = serde_json::from_str(&package_json).ok()?;
+
+  let framework = FRAMEWORKS.iter().find(|framework| {
+    package_json.has_dependency(framework.package)
+      || framework
+        .config_files
+        .iter()
+        .any(|file| directory.join(file).exists())
+  })?;
+
+  let run_script = |script: &str| {
+    // prefer the project script, which may set additional flags
+    if !package_json.scripts.contains_key(script) {
+      return String::new();
+    }
+    if directory.join("yarn.lock").exists() {
+      format!("yarn {}", script)
+    } else if directory.join("pnpm-lock.yaml").exists() {
+      format!("pnpm run {}", script)
+    } else {
+      format!("npm run {}", script)
+    }
+  };
+
+  let mut before_build_command = run_script(framework.build_script);
+  // Next.js needs `next export` to generate the static files
+  if framework.package == "next" && package_json.scripts.contains_key("export") {
+    before_build_command = format!("{} && {}", before_build_command, run_script("export"));
+  }
+
+  Some(Frontend {
+    framework: framework.name,
+    dev_path: format!("http://localhost:{}", framework.dev_port),
+    dist_dir: format!("../{}", framework.dist_dir),
+    before_dev_command: run_script(framework.dev_script),
+    before_build_command,
+  })
+}
@@ -4,13 +4,17 @@
 
 pub use anyhow::Result;
 use clap::{crate_version, load_yaml, App, AppSettings, ArgMatches};
-use dialoguer::Input;
+use dialoguer::{Confirm, Input};
+
+use helpers::failure;
+use std::process::exit;
 
 mod build;
 mod dev;
 mod helpers;
 mod info;
 mod init;
+mod migrate;
 mod sign;
 
 pub use helpers::Logger;
@@ -36,11 +40,40 @@ fn init_command(matches: &ArgMatches) -> Result<()> {
   let tauri_path = matches.value_of("tauri-path");
   let app_name = matches.value_of("app-name");
   let window_title = matches.value_of("window-title");
-  let dist_dir = matches.value_of("dist-dir");
-  let dev_path = matches.value_of("dev-path");
+  let mut dist_dir = matches.value_of("dist-dir").map(ToString::to_string);
+  let mut dev_path = matches.value_of("dev-path").map(ToString::to_string);
   let ci = matches.is_present("ci") || std::env::var("CI").is_ok();
 
   let mut init_runner = init::Init::new();
+
+  if dist_dir.is_none() && dev_path.is_none() {
+    let project_dir = directory
+      .map(std::path::PathBuf::from)
+      .unwrap_or_else(|| std::env::current_dir().expect("failed to read cwd"));
+    if let Some(frontend) = init::detect_frontend(&project_dir) {
+      let use_frontend = ci
+        || Confirm::new()
+          .with_prompt(format!(
+            "Detected {}: use `{}` as devPath, `{}` as distDir, `{}` as beforeDevCommand and `{}` as beforeBuildCommand?",
+            frontend.framework,
+            frontend.dev_path,
+            frontend.dist_dir,
+            frontend.before_dev_command,
+            frontend.before_build_command
+          ))
+          .default(true)
+          .interact()?;
+      if use_frontend {
+        Logger::new("tauri:init").log(format!("Using the {} conventions", frontend.framework));
+        dist_dir.replace(frontend.dist_dir);
+        dev_path.replace(frontend.dev_path);
+        init_runner = init_runner
+          .before_dev_command(frontend.before_dev_command)
+          .before_build_command(frontend.before_build_command);
+      }
+    }
+  }
+
   if force {
     init_runner = init_runner.force();
   }
@@ -84,13 +117,17 @@ fn init_command(matches: &ArgMatches) -> Result<()> {
 
 fn dev_command(matches: &ArgMatches) -> Result<()> {
   let exit_on_panic = matches.is_present("exit-on-panic");
+  let proxy = matches.is_present("proxy");
   let config = matches.value_of("config");
   let args: Vec<String> = matches
     .values_of("args")
     .map(|a| a.into_iter().map(|v| v.to_string()).collect())
     .unwrap_or_default();
 
-  let mut dev_runner = dev::Dev::new().exit_on_panic(exit_on_panic).args(args);
+  let mut dev_runner = dev::Dev::new()
+    .exit_on_panic(exit_on_panic)
+    .proxy(proxy)
+    .args(args);
 
   if let Some(config) = config {
     dev_runner = dev_runner.config(config.to_string());
@@ -102,6 +139,7 @@ fn dev_command(matches: &ArgMatches) -> Result<()> {
 fn build_command(matches: &ArgMatches) -> Result<()> {
   let debug = matches.is_present("debug");
   let verbose = matches.is_present("verbose");
+  let watch = matches.is_present("watch");
   let targets = matches.values_of_lossy("target");
   let config = matches.value_of("config");
 
@@ -112,6 +150,9 @@ fn build_command(matches: &ArgMatches) -> Result<()> {
   if verbose {
     build_runner = build_runner.verbose();
   }
+  if watch {
+    build_runner = build_runner.watch();
+  }
   if let Some(targets) = targets {
     build_runner = build_runner.targets(targets);
   }
@@ -122,6 +163,17 @@ fn build_command(matches: &ArgMatches) -> Result<()> {
   build_runner.run()
 }
 
+fn migrate_command(matches: &ArgMatches) -> Result<()> {
+  let mut migrate_runner = migrate::Migrate::new();
+  if matches.is_present("dry-run") {
+    migrate_runner = migrate_runner.dry_run();
+  }
+  if matches.is_present("yes") {
+    migrate_runner = migrate_runner.yes();
+  }
+  migrate_runner.run()
+}
+
 fn info_command() -> Result<()> {
   info::Info::new().run()
 }
@@ -183,7 +235,7 @@ fn sign_command(matches: &ArgMatches) -> Result<()> {
   sign_runner.run()
 }
 
-fn main() -> Result<()> {
+fn main() {
   let yaml = load_yaml!("cli.yml");
   let app = App::from(yaml)
     .version(crate_version!())
@@ -192,18 +244,58 @@ fn main() -> Result<()> {
     .setting(AppSettings::SubcommandRequired);
   let app_matches = app.get_matches();
   let matches = app_matches.subcommand_matches("tauri").unwrap();
+  // `--json` is global, so it can be passed after the subcommand too
+  helpers::set_json_output(
+    matches.is_present("json")
+      || matches
+        .subcommand_name()
+        .and_then(|name| matches.subcommand_matches(name))
+        .map(|matches| matches.is_present("json"))
+        .unwrap_or(false),
+  );
 
-  if let Some(matches) = matches.subcommand_matches("init") {
-    init_command(&matches)?;
+  let result = if let Some(matches) = matches.subcommand_matches("init") {
+    init_command(&matches)
   } else if let Some(matches) = matches.subcommand_matches("dev") {
-    dev_command(&matches)?;
+    dev_command(&matches)
   } else if let Some(matches) = matches.subcommand_matches("build") {
-    build_command(&matches)?;
+    build_command(&matches)
+  } else if let Some(matches) = matches.subcommand_matches("migrate") {
+    migrate_command(&matches)
   } else if matches.subcommand_matches("info").is_some() {
-    info_command()?;
+    info_command()
   } else if let Some(matches) = matches.subcommand_matches("sign") {
-    sign_command(&matches)?;
+    sign_command(&matches)
+  } else {
+    Ok(())
+  };
+
+  let exit_code = match &result {
+    Ok(()) => 0,
+    Err(error) => failure::classify(error).1,
+  };
+
+  if helpers::json_output() {
+    let (failure, message) = match &result {
+      Ok(()) => (None, None),
+      Err(error) => (
+        failure::classify(error).0.map(|f| f.id()),
+        Some(format!("{:#}", error)),
+      ),
+    };
+    println!(
+      "{}",
+      serde_json::json!({
+        "type": "result",
+        "success": result.is_ok(),
+        "exitCode": exit_code,
+        "failure": failure,
+        "message": message,
+      })
+    );
+  } else if let Err(error) = &result {
+    eprintln!("Error: {:?}", error);
   }
 
-  Ok(())
+  exit(exit_code);
 }
@@ -0,0 +1,251 @@
+// Copyright 2019-2021 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::helpers::{app_paths::tauri_dir, config::get as get_config, Logger};
+
+use colored::Colorize;
+use dialoguer::Confirm;
+use serde_json::{Map, Value as JsonValue};
+
+use std::fs::{read_to_string, write};
+
+/// The allowlist flags of the old flat layout and their current location.
+const ALLOWLIST_ENTRIES: &[(&str, &str, &str)] = &[
+  ("readTextFile", "fs", "readTextFile"),
+  ("readBinaryFile", "fs", "readBinaryFile"),
+  ("writeFile", "fs", "writeFile"),
+  ("writeBinaryFile", "fs", "writeBinaryFile"),
+  ("readDir", "fs", "readDir"),
+  ("copyFile", "fs", "copyFile"),
+  ("createDir", "fs", "createDir"),
+  ("removeDir", "fs", "removeDir"),
+  ("removeFile", "fs", "removeFile"),
+  ("renameFile", "fs", "renameFile"),
+  ("createWindow", "window", "create"),
+  ("setTitle", "window", "all"),
+  ("execute", "shell", "execute"),
+  ("open", "shell", "open"),
+  ("openDialog", "dialog", "open"),
+  ("saveDialog", "dialog", "save"),
+  ("httpRequest", "http", "request"),
+  ("notification", "notification", "all"),
+];
+
+/// The keys that don't exist anymore, with the reason shown to the user.
+const REMOVED_KEYS: &[(&str, &str, &str)] = &[
+  (
+    "tauri",
+    "embeddedServer",
+    "the assets are served with the custom protocol",
+  ),
+  (
+    "tauri",
+    "inliner",
+    "the assets are served with the custom protocol",
+  ),
+  ("tauri", "edge", "WebView2 is always used on Windows"),
+];
+
+/// A change applied to the configuration.
+type Migration = fn(&mut Map<String, JsonValue>) -> Vec<String>;
+
+const MIGRATIONS: &[Migration] = &[
+  migrate_window,
+  migrate_macos_bundle,
+  migrate_allowlist,
+  remove_keys,
+];
+
+#[derive(Default)]
+pub struct Migrate {
+  dry_run: bool,
+  yes: bool,
+}
+
+impl Migrate {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Only prints the changes, without writing `tauri.conf.json`.
+  pub fn dry_run(mut self) -> Self {
+    self.dry_run = true;
+    self
+  }
+
+  /// Writes the changes without asking for confirmation.
+  pub fn yes(mut self) -> Self {
+    self.yes = true;
+    self
+  }
+
+  pub fn run(self) -> crate::Result<()> {
+    let logger = Logger::new("tauri:migrate");
+    let config_path = tauri_dir().join("tauri.conf.json");
+    let original: JsonValue = serde_json::from_str(&read_to_string(&config_path)?)?;
+
+    let mut config = original.clone();
+    let changes = match config.as_object_mut() {
+      Some(config) => MIGRATIONS
+        .iter()
+        .flat_map(|migration| migration(config))
+        .collect::<Vec<_>>(),
+      None => return Err(anyhow::anyhow!("`tauri.conf.json` must be a JSON object")),
+    };
+
+    if changes.is_empty() {
+      logger.log("`tauri.conf.json` is up to date");
+      return Ok(());
+    }
+
+    for change in &changes {
+      logger.log(change);
+    }
+    print_diff(
+      &serde_json::to_string_pretty(&original)?,
+      &serde_json::to_string_pretty(&config)?,
+    );
+
+    if self.dry_run {
+      return Ok(());
+    }
+    if !self.yes
+      && !Confirm::new()
+        .with_prompt("Write the changes to `tauri.conf.json`?")
+        .default(true)
+        .interact()?
+    {
+      return Ok(());
+    }
+
+    write(&config_path, serde_json::to_string_pretty(&config)?)?;
+    // validates the migrated configuration against the current schema
+    get_config(None)?;
+    logger.log("`tauri.conf.json` migrated");
+    Ok(())
+  }
+}
+
+fn object<'a>(
+  parent: &'a mut Map<String, JsonValue>,
+  key: &str,
+) -> Option<&'a mut Map<String, JsonValue>> {
+  parent.get_mut(key).and_then(|value| value.as_object_mut())
+}
+
+/// `tauri > window` was replaced by the `tauri > windows` list.
+fn migrate_window(config: &mut Map<String, JsonValue>) -> Vec<String> {
+  let tauri = match object(config, "tauri") {
+    Some(tauri) => tauri,
+    None => return Vec::new(),
+  };
+  match tauri.remove("window") {
+    Some(window) if !tauri.contains_key("windows") => {
+      tauri.insert("windows".into(), JsonValue::Array(vec![window]));
+      vec!["moved `tauri > window` to the `tauri > windows` list".into()]
+    }
+    Some(window) => {
+      tauri.insert("window".into(), window);
+      vec![]
+    }
+    None => vec![],
+  }
+}
+
+/// `tauri > bundle > osx` was renamed to `macOS`.
+fn migrate_macos_bundle(config: &mut Map<String, JsonValue>) -> Vec<String> {
+  let bundle = match object(config, "tauri").and_then(|tauri| object(tauri, "bundle")) {
+    Some(bundle) => bundle,
+    None => return Vec::new(),
+  };
+  if bundle.contains_key("macOS") {
+    return Vec::new();
+  }
+  match bundle.remove("osx") {
+    Some(osx) => {
+      bundle.insert("macOS".into(), osx);
+      vec!["renamed `tauri > bundle > osx` to `tauri > bundle > macOS`".into()]
+    }
+    None => vec![],
+  }
+}
+
+/// The allowlist flags were grouped by API module.
+fn migrate_allowlist(config: &mut Map<String, JsonValue>) -> Vec<String> {
+  let allowlist = match object(config, "tauri").and_then(|tauri| object(tauri, "allowlist")) {
+    Some(allowlist) => allowlist,
+    None => return Vec::new(),
+  };
+  let mut changes = Vec::new();
+  for (old, module, entry) in ALLOWLIST_ENTRIES {
+    let enabled = match allowlist.get(*old) {
+      Some(JsonValue::Bool(enabled)) => *enabled,
+      _ => continue,
+    };
+    allowlist.remove(*old);
+    if !allowlist
+      .get(*module)
+      .map(|m| m.is_object())
+      .unwrap_or(false)
+    {
+      allowlist.insert(module.to_string(), JsonValue::Object(Map::new()));
+    }
+    if enabled {
+      allowlist[*module][*entry] = JsonValue::Bool(true);
+    }
+    changes.push(format!(
+      "moved `tauri > allowlist > {}` to `tauri > allowlist > {} > {}`",
+      old, module, entry
+    ));
+  }
+  if allowlist.remove("event").is_some() {
+    changes.push("removed `tauri > allowlist > event`: events are always enabled".into());
+  }
+  changes
+}
+
+fn remove_keys(config: &mut Map<String, JsonValue>) -> Vec<String> {
+  let mut changes = Vec::new();
+  for (parent, key, reason) in REMOVED_KEYS {
+    if let Some(parent_object) = object(config, parent) {
+      if parent_object.remove(*key).is_some() {
+        changes.push(format!("removed `{} > {}`: {}", parent, key, reason));
+      }
+    }
+  }
+  changes
+}
+
+/// Prints a line diff of the configuration.
+fn print_diff(old: &str, new: &str) {
+  let old = old.lines().collect::<Vec<_>>();
+  let new = new.lines().collect::<Vec<_>>();
+
+  // longest common subsequence table, the config files are small
+  let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i][j] = if old[i] == new[j] {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
+    }
+  }
+
+  let (mut i, mut j) = (0, 0);
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      println!("  {}", old[i]);
+      i += 1;
+      j += 1;
+    } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+      println!("{}", format!("+ {}", new[j]).green());
+      j += 1;
+    } else {
+      println!("{}", format!("- {}", old[i]).red());
+      i += 1;
+    }
+  }
+}
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::helpers::updater_signature::{
-  generate_key, read_key_from_file, save_keypair, sign_file,
+use crate::helpers::{
+  failure::Failure,
+  updater_signature::{generate_key, read_key_from_file, save_keypair, sign_file},
 };
+use anyhow::Context;
 use std::path::{Path, PathBuf};
 
 #[derive(Default)]
@@ -63,7 +65,8 @@ impl Signer {
       self.password.unwrap(),
       self.file.unwrap(),
       false,
-    )?;
+    )
+    .context(Failure::Signing)?;
 
     println!(
          "\nYour file was signed successfully, You can find the signature here:\n{}\n\nPublic signature:\n{}\n\nMake sure to include this into the signature field of your update server.",